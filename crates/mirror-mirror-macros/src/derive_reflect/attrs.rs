@@ -4,6 +4,7 @@ use proc_macro2::Ident;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
 use syn::Attribute;
 use syn::Expr;
 use syn::Field;
@@ -13,17 +14,28 @@ use syn::Lit;
 use syn::LitStr;
 use syn::Meta;
 use syn::Token;
+use syn::Type;
 use syn::UseTree;
 
 mod kw {
     syn::custom_keyword!(Debug);
+    syn::custom_keyword!(Default);
+    syn::custom_keyword!(Display);
     syn::custom_keyword!(Clone);
     syn::custom_keyword!(FromReflect);
+    syn::custom_keyword!(Hash);
+    syn::custom_keyword!(Methods);
+    syn::custom_keyword!(PartialEq);
+    syn::custom_keyword!(PartialOrd);
     syn::custom_keyword!(skip);
+    syn::custom_keyword!(default);
     syn::custom_keyword!(meta);
     syn::custom_keyword!(opt_out);
+    syn::custom_keyword!(opt_in);
     syn::custom_keyword!(crate_name);
     syn::custom_keyword!(from_reflect_with);
+    syn::custom_keyword!(opaque);
+    syn::custom_keyword!(inner);
 }
 
 #[derive(Clone)]
@@ -31,32 +43,51 @@ pub(super) struct ItemAttrs {
     pub(super) debug_opt_out: bool,
     pub(super) clone_opt_out: bool,
     pub(super) from_reflect_opt_out: bool,
+    pub(super) hash_opt_in: bool,
+    pub(super) partial_eq_opt_in: bool,
+    pub(super) partial_ord_opt_in: bool,
+    pub(super) default_opt_in: bool,
+    pub(super) display_opt_in: bool,
+    pub(super) methods_opt_in: bool,
+    pub(super) opaque: bool,
+    pub(super) opaque_inner_type: Option<Type>,
     pub(super) crate_name: UseTree,
+    pub(super) repr: Option<Ident>,
     meta: BTreeMap<Ident, Expr>,
     docs: Vec<LitStr>,
 }
 
 impl ItemAttrs {
-    fn new(docs: Vec<LitStr>) -> Self {
+    fn new(docs: Vec<LitStr>, repr: Option<Ident>) -> Self {
         Self {
             debug_opt_out: Default::default(),
             clone_opt_out: Default::default(),
             from_reflect_opt_out: Default::default(),
+            hash_opt_in: Default::default(),
+            partial_eq_opt_in: Default::default(),
+            partial_ord_opt_in: Default::default(),
+            default_opt_in: Default::default(),
+            display_opt_in: Default::default(),
+            methods_opt_in: Default::default(),
+            opaque: Default::default(),
+            opaque_inner_type: Default::default(),
             meta: Default::default(),
             docs,
+            repr,
             crate_name: syn::parse_quote!(mirror_mirror),
         }
     }
 
     pub(super) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
         let docs = parse_docs(attrs);
+        let repr = parse_repr(attrs);
 
         let mut reflect_attrs = attrs
             .iter()
             .filter(|attr| attr.path.is_ident("reflect"))
             .peekable();
 
-        let Some(attr) = reflect_attrs.next() else { return Ok(Self::new(docs)) };
+        let Some(attr) = reflect_attrs.next() else { return Ok(Self::new(docs, repr)) };
 
         if let Some(next) = reflect_attrs.peek() {
             return Err(syn::Error::new_spanned(
@@ -66,7 +97,7 @@ impl ItemAttrs {
         }
 
         attr.parse_args_with(|input: ParseStream<'_>| {
-            let mut item_attrs = Self::new(docs);
+            let mut item_attrs = Self::new(docs, repr);
 
             while !input.is_empty() {
                 let lh = input.lookahead1();
@@ -90,6 +121,36 @@ impl ItemAttrs {
                             return Err(lh.error());
                         }
 
+                        let _ = content.parse::<Token![,]>();
+                    }
+                } else if lh.peek(kw::opt_in) {
+                    input.parse::<kw::opt_in>()?;
+                    let content;
+                    syn::parenthesized!(content in input);
+                    while !content.is_empty() {
+                        let lh = content.lookahead1();
+                        if lh.peek(kw::Hash) {
+                            content.parse::<kw::Hash>()?;
+                            item_attrs.hash_opt_in = true;
+                        } else if lh.peek(kw::PartialEq) {
+                            content.parse::<kw::PartialEq>()?;
+                            item_attrs.partial_eq_opt_in = true;
+                        } else if lh.peek(kw::PartialOrd) {
+                            content.parse::<kw::PartialOrd>()?;
+                            item_attrs.partial_ord_opt_in = true;
+                        } else if lh.peek(kw::Default) {
+                            content.parse::<kw::Default>()?;
+                            item_attrs.default_opt_in = true;
+                        } else if lh.peek(kw::Display) {
+                            content.parse::<kw::Display>()?;
+                            item_attrs.display_opt_in = true;
+                        } else if lh.peek(kw::Methods) {
+                            content.parse::<kw::Methods>()?;
+                            item_attrs.methods_opt_in = true;
+                        } else {
+                            return Err(lh.error());
+                        }
+
                         let _ = content.parse::<Token![,]>();
                     }
                 } else if lh.peek(kw::meta) {
@@ -109,6 +170,16 @@ impl ItemAttrs {
 
                         let _ = content.parse::<Token![,]>();
                     }
+                } else if lh.peek(kw::opaque) {
+                    input.parse::<kw::opaque>()?;
+                    item_attrs.opaque = true;
+                    if input.peek(syn::token::Paren) {
+                        let content;
+                        syn::parenthesized!(content in input);
+                        content.parse::<kw::inner>()?;
+                        content.parse::<Token![=]>()?;
+                        item_attrs.opaque_inner_type = Some(content.parse()?);
+                    }
                 } else if lh.peek(kw::crate_name) {
                     input.parse::<kw::crate_name>()?;
                     let content;
@@ -162,6 +233,75 @@ impl ItemAttrs {
         }
     }
 
+    pub(super) fn fn_reflect_hash_tokens(&self) -> TokenStream {
+        if self.hash_opt_in {
+            quote! {
+                fn reflect_hash(&self) -> Option<u64> {
+                    Some(hash_with_default_hasher(self))
+                }
+            }
+        } else {
+            quote! {}
+        }
+    }
+
+    pub(super) fn fn_reflect_partial_eq_tokens(&self) -> TokenStream {
+        if self.partial_eq_opt_in {
+            quote! {
+                fn reflect_partial_eq(&self, other: &dyn Reflect) -> Option<bool> {
+                    if let Some(other) = other.downcast_ref::<Self>() {
+                        Some(self == other)
+                    } else {
+                        reflect_partial_eq(self, other)
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        }
+    }
+
+    pub(super) fn fn_reflect_partial_cmp_tokens(&self) -> TokenStream {
+        if self.partial_ord_opt_in {
+            quote! {
+                fn reflect_partial_cmp(&self, other: &dyn Reflect) -> Option<core::cmp::Ordering> {
+                    if let Some(other) = other.downcast_ref::<Self>() {
+                        PartialOrd::partial_cmp(self, other)
+                    } else {
+                        reflect_partial_cmp(self, other)
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        }
+    }
+
+    pub(super) fn fn_reflect_display_tokens(&self) -> TokenStream {
+        if self.display_opt_in {
+            quote! {
+                fn reflect_display(&self) -> Option<&dyn fmt::Display> {
+                    Some(self)
+                }
+            }
+        } else {
+            quote! {}
+        }
+    }
+
+    pub(super) fn fn_methods_tokens(&self) -> TokenStream {
+        if self.methods_opt_in {
+            let crate_name = &self.crate_name;
+            quote! {
+                fn methods(&self) -> &'static [#crate_name::method::Method] {
+                    <Self as #crate_name::method::ReflectMethods>::METHODS
+                }
+            }
+        } else {
+            quote! {}
+        }
+    }
+
     pub(super) fn meta(&self) -> TokenStream {
         tokenize_meta(&self.meta)
     }
@@ -172,7 +312,7 @@ impl ItemAttrs {
     }
 }
 
-fn parse_docs(attrs: &[Attribute]) -> Vec<LitStr> {
+pub(crate) fn parse_docs(attrs: &[Attribute]) -> Vec<LitStr> {
     attrs
         .iter()
         .filter(|attr| attr.path.is_ident("doc"))
@@ -185,6 +325,24 @@ fn parse_docs(attrs: &[Attribute]) -> Vec<LitStr> {
         .collect::<Vec<_>>()
 }
 
+/// Finds the integer type in `#[repr(...)]`, if any. Other representations such as `C` are
+/// ignored.
+fn parse_repr(attrs: &[Attribute]) -> Option<Ident> {
+    const INTEGER_REPRS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .find(|ident| INTEGER_REPRS.iter().any(|repr| ident == repr))
+}
+
 fn tokenize_meta(meta: &BTreeMap<Ident, Expr>) -> TokenStream {
     let pairs = meta.iter().map(|(ident, expr)| {
         quote! {
@@ -270,6 +428,20 @@ where
     pub(super) fn from_reflect_with(&self, key: &T) -> Option<&Ident> {
         self.map.get(key)?.from_reflect_with.as_ref()
     }
+
+    pub(super) fn default(&self, key: &T) -> Option<&Expr> {
+        self.map.get(key)?.default.as_ref()
+    }
+
+    /// With `type_default` fields without a `default` attribute record the default value of their
+    /// type, which is what `opt_in(Default)` uses for them.
+    pub(super) fn with_default_value(&self, key: &T, ty: &Type, type_default: bool) -> TokenStream {
+        with_default_value_tokens(self.default(key), ty, type_default)
+    }
+
+    pub(super) fn default_field_value(&self, key: &T) -> TokenStream {
+        default_field_value_tokens(self.default(key))
+    }
 }
 
 pub(super) struct InnerAttrs {
@@ -277,6 +449,7 @@ pub(super) struct InnerAttrs {
     pub(super) meta: BTreeMap<Ident, Expr>,
     pub(super) docs: Vec<LitStr>,
     pub(super) from_reflect_with: Option<Ident>,
+    pub(super) default: Option<Expr>,
 }
 
 impl InnerAttrs {
@@ -285,6 +458,7 @@ impl InnerAttrs {
             skip: Default::default(),
             meta: Default::default(),
             from_reflect_with: Default::default(),
+            default: Default::default(),
             docs,
         }
     }
@@ -337,6 +511,13 @@ impl InnerAttrs {
                     syn::parenthesized!(content in input);
                     field_attrs.from_reflect_with = Some(content.parse()?);
                     let _ = content.parse::<Token![,]>();
+                } else if lh.peek(kw::default) {
+                    input.parse::<kw::default>()?;
+                    field_attrs.default = if input.parse::<Option<Token![=]>>()?.is_some() {
+                        Some(input.parse()?)
+                    } else {
+                        Some(syn::parse_quote! { ::core::default::Default::default() })
+                    };
                 } else {
                     return Err(lh.error());
                 }
@@ -356,4 +537,35 @@ impl InnerAttrs {
         let docs = &self.docs;
         quote! { &[#(#docs,)*] }
     }
+
+    pub(super) fn with_default_value(&self, ty: &Type) -> TokenStream {
+        with_default_value_tokens(self.default.as_ref(), ty, false)
+    }
+
+    pub(super) fn default_field_value(&self) -> TokenStream {
+        default_field_value_tokens(self.default.as_ref())
+    }
+}
+
+/// The expression used to initialize a field that isn't read from another value, such as skipped
+/// fields.
+fn default_field_value_tokens(default: Option<&Expr>) -> TokenStream {
+    match default {
+        Some(default) => quote! { #default },
+        None => quote! { ::core::default::Default::default() },
+    }
+}
+
+fn with_default_value_tokens(default: Option<&Expr>, ty: &Type, type_default: bool) -> TokenStream {
+    let default = match default {
+        Some(default) => quote! { #default },
+        None if type_default => quote! { ::core::default::Default::default() },
+        None => return quote! {},
+    };
+    quote! {
+        .with_default_value({
+            let value: #ty = #default;
+            value.to_value()
+        })
+    }
 }
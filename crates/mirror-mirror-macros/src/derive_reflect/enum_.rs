@@ -1,8 +1,10 @@
 use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
 
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::DataEnum;
+use syn::Expr;
 use syn::Fields;
 use syn::Ident;
 use syn::Type;
@@ -18,6 +20,13 @@ pub(super) fn expand(
     attrs: ItemAttrs,
     generics: &Generics<'_>,
 ) -> syn::Result<TokenStream> {
+    if attrs.default_opt_in {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`opt_in(Default)` is only supported on structs",
+        ));
+    }
+
     let variants = VariantData::try_from_enum(&enum_)?;
 
     let reflect = expand_reflect(ident, &variants, &attrs, generics)?;
@@ -194,11 +203,16 @@ fn expand_reflect(
         }
     };
 
-    let fn_type_info = {
+    let fn_type_info = cfg!(feature = "type_info").then(|| {
+        let discriminants = discriminants(variants, attrs);
+
         let code_for_variants = variants.iter().filter(filter_out_skipped).map(|variant| {
             let variant_ident_string = stringify(&variant.ident);
             let meta = variant.attrs.meta();
             let docs = variant.attrs.docs();
+            let with_discriminant = discriminants.get(variant.ident).map(|discriminant| {
+                quote! { .with_discriminant(#discriminant) }
+            });
 
             match &variant.fields {
                 FieldsData::Named(fields) => {
@@ -208,8 +222,9 @@ fn expand_reflect(
                         let field_ty = &field.ty;
                         let meta = field.attrs.meta();
                         let docs = field.attrs.docs();
+                        let with_default_value = field.attrs.with_default_value(field_ty);
                         quote! {
-                            NamedFieldNode::new::<#field_ty>(#field_name, #meta, #docs, graph)
+                            NamedFieldNode::new::<#field_ty>(#field_name, #meta, #docs, graph)#with_default_value
                         }
                     });
 
@@ -220,7 +235,7 @@ fn expand_reflect(
                                 &[#(#fields),*],
                                 #meta,
                                 #docs,
-                            )
+                            )#with_discriminant
                         )
                     }
                 }
@@ -229,8 +244,9 @@ fn expand_reflect(
                         let field_ty = &field.ty;
                         let meta = field.attrs.meta();
                         let docs = field.attrs.docs();
+                        let with_default_value = field.attrs.with_default_value(field_ty);
                         quote! {
-                            UnnamedFieldNode::new::<#field_ty>(#meta, #docs, graph)
+                            UnnamedFieldNode::new::<#field_ty>(#meta, #docs, graph)#with_default_value
                         }
                     });
 
@@ -241,7 +257,7 @@ fn expand_reflect(
                                 &[#(#fields),*],
                                 #meta,
                                 #docs,
-                            )
+                            )#with_discriminant
                         )
                     }
                 }
@@ -250,13 +266,17 @@ fn expand_reflect(
                         #variant_ident_string,
                         #meta,
                         #docs,
-                    ))
+                    )#with_discriminant)
                 },
             }
         });
 
         let meta = attrs.meta();
         let docs = attrs.docs();
+        let with_repr = attrs.repr.as_ref().map(|repr| {
+            let repr = stringify(repr);
+            quote! { .with_repr(#repr) }
+        });
 
         let Generics {
             impl_generics,
@@ -270,7 +290,7 @@ fn expand_reflect(
                     fn build(graph: &mut TypeGraph) -> NodeId {
                         let variants = &[#(#code_for_variants),*];
                         graph.get_or_build_node_with::<Self, _>(|graph| {
-                            EnumNode::new::<Self>(variants, #meta, #docs)
+                            EnumNode::new::<Self>(variants, #meta, #docs)#with_repr
                         })
                     }
                 }
@@ -278,10 +298,16 @@ fn expand_reflect(
                 <Self as Typed>::type_info()
             }
         }
-    };
+    })
+    .unwrap_or_else(super::placeholder_fn_type_info);
 
     let fn_debug = attrs.fn_debug_tokens();
     let fn_clone_reflect = attrs.fn_clone_reflect_tokens();
+    let fn_reflect_hash = attrs.fn_reflect_hash_tokens();
+    let fn_reflect_partial_eq = attrs.fn_reflect_partial_eq_tokens();
+    let fn_reflect_partial_cmp = attrs.fn_reflect_partial_cmp_tokens();
+    let fn_reflect_display = attrs.fn_reflect_display_tokens();
+    let fn_methods = attrs.fn_methods_tokens();
 
     let Generics {
         impl_generics,
@@ -320,6 +346,11 @@ fn expand_reflect(
             #fn_to_value
             #fn_clone_reflect
             #fn_debug
+            #fn_reflect_hash
+            #fn_reflect_partial_eq
+            #fn_reflect_partial_cmp
+            #fn_reflect_display
+            #fn_methods
 
             fn reflect_owned(self: Box<Self>) -> ReflectOwned {
                 ReflectOwned::Enum(self)
@@ -352,8 +383,9 @@ fn expand_from_reflect(
                     let ident = &field.ident;
 
                     if field.skip() {
+                        let default = field.attrs.default_field_value();
                         quote! {
-                            #ident: ::core::default::Default::default(),
+                            #ident: #default,
                         }
                     } else {
                         let ident_string = stringify(ident);
@@ -396,8 +428,9 @@ fn expand_from_reflect(
             FieldsData::Unnamed(fields) => {
                 let set_fields = fields.iter().enumerate().map(|(idx, field)| {
                     if field.skip() {
+                        let default = field.attrs.default_field_value();
                         quote! {
-                            ::core::default::Default::default(),
+                            #default,
                         }
                     } else {
                         let ty = &field.ty;
@@ -526,15 +559,16 @@ fn expand_enum(
                             let ident = &field.ident;
                             let ident_string = stringify(ident);
                             quote! {
-                                if name == #ident_string {
-                                    return Some(#ident);
-                                }
+                                #ident_string => return Some(#ident),
                             }
                         });
 
                     quote! {
                         Self::#variant_ident { #(#field_names,)* } => {
-                            #(#return_if_name_matches)*
+                            match name {
+                                #(#return_if_name_matches)*
+                                _ => {}
+                            }
                         }
                     }
                 }
@@ -574,15 +608,16 @@ fn expand_enum(
                             let ident = &field.ident;
                             let ident_string = stringify(ident);
                             quote! {
-                                if name == #ident_string {
-                                    return Some(#ident);
-                                }
+                                #ident_string => return Some(#ident),
                             }
                         });
 
                     quote! {
                         Self::#variant_ident { #(#field_names,)* } => {
-                            #(#return_if_name_matches)*
+                            match name {
+                                #(#return_if_name_matches)*
+                                _ => {}
+                            }
                         },
                     }
                 }
@@ -622,16 +657,17 @@ fn expand_enum(
                                 |(idx, field)| {
                                     let field_name = &field.ident;
                                     quote! {
-                                        if #idx == index {
-                                            return Some(#field_name.as_reflect());
-                                        }
+                                        #idx => return Some(#field_name.as_reflect()),
                                     }
                                 },
                             );
 
                         quote! {
                             Self::#variant_ident { #(#field_names,)* } => {
-                                #(#return_if_index_matches)*
+                                match index {
+                                    #(#return_if_index_matches)*
+                                    _ => {}
+                                }
                             },
                         }
                     }
@@ -641,16 +677,17 @@ fn expand_enum(
                                 |(idx, field)| {
                                     let field_name = &field.fake_ident;
                                     quote! {
-                                        if #idx == index {
-                                            return Some(#field_name.as_reflect());
-                                        }
+                                        #idx => return Some(#field_name.as_reflect()),
                                     }
                                 },
                             );
 
                         quote! {
                             Self::#variant_ident(#(#field_names,)*) => {
-                                #(#return_if_index_matches)*
+                                match index {
+                                    #(#return_if_index_matches)*
+                                    _ => {}
+                                }
                             },
                         }
                     }
@@ -686,16 +723,17 @@ fn expand_enum(
                                 |(idx, field)| {
                                     let field_name = &field.ident;
                                     quote! {
-                                        if #idx == index {
-                                            return Some(#field_name.as_reflect_mut());
-                                        }
+                                        #idx => return Some(#field_name.as_reflect_mut()),
                                     }
                                 },
                             );
 
                         quote! {
                             Self::#variant_ident { #(#field_names,)* } => {
-                                #(#return_if_index_matches)*
+                                match index {
+                                    #(#return_if_index_matches)*
+                                    _ => {}
+                                }
                             },
                         }
                     }
@@ -705,16 +743,17 @@ fn expand_enum(
                                 |(idx, field)| {
                                     let field_name = &field.fake_ident;
                                     quote! {
-                                        if #idx == index {
-                                            return Some(#field_name.as_reflect_mut());
-                                        }
+                                        #idx => return Some(#field_name.as_reflect_mut()),
                                     }
                                 },
                             );
 
                         quote! {
                             Self::#variant_ident(#(#field_names,)*) => {
-                                #(#return_if_index_matches)*
+                                match index {
+                                    #(#return_if_index_matches)*
+                                    _ => {}
+                                }
                             },
                         }
                     }
@@ -862,16 +901,17 @@ fn expand_enum(
                                 |(idx, field)| {
                                     let field_name = &field.ident;
                                     quote! {
-                                        if #idx == index {
-                                            return Some(::core::stringify!(#field_name));
-                                        }
+                                        #idx => return Some(::core::stringify!(#field_name)),
                                     }
                                 },
                             );
 
                         quote! {
                             Self::#variant_ident { #(#field_names,)* } => {
-                                #(#return_if_index_matches)*
+                                match index {
+                                    #(#return_if_index_matches)*
+                                    _ => {}
+                                }
                             },
                         }
                     }
@@ -881,16 +921,17 @@ fn expand_enum(
                                 |(idx, field)| {
                                     let field_name = &field.fake_ident;
                                     quote! {
-                                        if #idx == index {
-                                            return Some(::core::stringify!(#field_name));
-                                        }
+                                        #idx => return Some(::core::stringify!(#field_name)),
                                     }
                                 },
                             );
 
                         quote! {
                             Self::#variant_ident(#(#field_names,)*) => {
-                                #(#return_if_index_matches)*
+                                match index {
+                                    #(#return_if_index_matches)*
+                                    _ => {}
+                                }
                             },
                         }
                     }
@@ -934,10 +975,52 @@ fn expand_enum(
     }
 }
 
+/// The discriminant of each variant, as an `i128` expression, following the same rules as the
+/// compiler: variants without an explicit discriminant are one greater than the previous one.
+///
+/// Discriminants are only captured if the enum has an integer `#[repr]` or at least one explicit
+/// discriminant.
+fn discriminants<'a>(
+    variants: &[VariantData<'a>],
+    attrs: &ItemAttrs,
+) -> BTreeMap<&'a Ident, TokenStream> {
+    let has_discriminants = attrs.repr.is_some()
+        || variants
+            .iter()
+            .any(|variant| variant.discriminant.is_some());
+
+    if !has_discriminants {
+        return Default::default();
+    }
+
+    let mut base = None::<&Expr>;
+    let mut offset = 0_i128;
+    variants
+        .iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            if let Some(expr) = variant.discriminant {
+                base = Some(expr);
+                offset = 0;
+            } else if index != 0 {
+                offset += 1;
+            }
+
+            let discriminant = match (base, offset) {
+                (Some(expr), 0) => quote! { (#expr) as i128 },
+                (Some(expr), offset) => quote! { (#expr) as i128 + #offset },
+                (None, offset) => quote! { #offset },
+            };
+            (variant.ident, discriminant)
+        })
+        .collect()
+}
+
 struct VariantData<'a> {
     ident: &'a Ident,
     attrs: InnerAttrs,
     fields: FieldsData<'a>,
+    discriminant: Option<&'a Expr>,
 }
 
 impl<'a> VariantData<'a> {
@@ -991,6 +1074,7 @@ impl<'a> VariantData<'a> {
                     ident: &variant.ident,
                     fields,
                     attrs,
+                    discriminant: variant.discriminant.as_ref().map(|(_, expr)| expr),
                 })
             })
             .collect::<syn::Result<Vec<_>>>()
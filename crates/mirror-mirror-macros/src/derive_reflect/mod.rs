@@ -1,4 +1,5 @@
 use proc_macro2::TokenStream;
+use quote::quote;
 use quote::quote_spanned;
 use syn::spanned::Spanned;
 use syn::DeriveInput;
@@ -8,15 +9,28 @@ use syn::WhereClause;
 
 mod attrs;
 mod enum_;
+mod opaque;
 mod struct_named;
 mod tuple_struct;
 
+pub(crate) use self::attrs::parse_docs;
+
 struct Generics<'a> {
     impl_generics: ImplGenerics<'a>,
     type_generics: TypeGenerics<'a>,
     where_clause: Option<&'a WhereClause>,
 }
 
+/// Without the `type_info` feature only the value level traits are implemented, and
+/// `Reflect::type_info` returns the placeholder from the blanket `Typed` impl.
+fn placeholder_fn_type_info() -> TokenStream {
+    quote! {
+        fn type_info(&self) -> TypeRoot {
+            <Self as Typed>::type_info()
+        }
+    }
+}
+
 pub(crate) fn expand(item: DeriveInput) -> syn::Result<TokenStream> {
     let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
     let generics = Generics {
@@ -30,9 +44,10 @@ pub(crate) fn expand(item: DeriveInput) -> syn::Result<TokenStream> {
     let attrs = attrs::ItemAttrs::parse(&item.attrs)?;
     let crate_name = attrs.crate_name.clone();
 
-    check_for_known_unsupported_types(&item)?;
-
     let tokens = match item.data {
+        syn::Data::Struct(_) | syn::Data::Enum(_) if attrs.opaque => {
+            opaque::expand(ident, attrs, &generics)
+        }
         syn::Data::Struct(data) => match data.fields {
             syn::Fields::Named(named) => struct_named::expand(ident, named, attrs, &generics)?,
             syn::Fields::Unnamed(unnamed) => {
@@ -89,27 +104,3 @@ pub(crate) fn expand(item: DeriveInput) -> syn::Result<TokenStream> {
         };
     })
 }
-
-fn check_for_known_unsupported_types(item: &DeriveInput) -> syn::Result<()> {
-    #[derive(Default)]
-    struct Visitor(Option<syn::Error>);
-
-    impl<'ast> syn::visit::Visit<'ast> for Visitor {
-        fn visit_ident(&mut self, i: &'ast proc_macro2::Ident) {
-            if i == "HashMap" && self.0.is_none() {
-                self.0 = Some(syn::Error::new_spanned(
-                    i,
-                    "`#[derive(Reflect)]` doesn't support `HashMap`. Use a `BTreeMap` instead.",
-                ));
-            }
-        }
-    }
-
-    let mut visitor = Visitor::default();
-    syn::visit::visit_derive_input(&mut visitor, item);
-
-    match visitor.0 {
-        Some(err) => Err(err),
-        None => Ok(()),
-    }
-}
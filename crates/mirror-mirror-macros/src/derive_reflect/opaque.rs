@@ -0,0 +1,142 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use super::attrs::ItemAttrs;
+use super::Generics;
+
+// Types with `#[reflect(opaque)]` are reflected as opaque values, like function pointers. Their
+// `Value` is their type name since there is no way to turn a value back into the type.
+pub(super) fn expand(ident: &Ident, attrs: ItemAttrs, generics: &Generics<'_>) -> TokenStream {
+    let reflect = expand_reflect(ident, &attrs, generics);
+    let from_reflect = (!attrs.from_reflect_opt_out).then(|| expand_from_reflect(ident, generics));
+
+    quote! {
+        #reflect
+        #from_reflect
+    }
+}
+
+fn expand_reflect(ident: &Ident, attrs: &ItemAttrs, generics: &Generics<'_>) -> TokenStream {
+    let fn_type_info = cfg!(feature = "type_info")
+        .then(|| {
+            let meta = attrs.meta();
+            let with_inner_type = attrs.opaque_inner_type.as_ref().map(|inner_type| {
+                quote! {
+                    .with_inner_type::<#inner_type>(graph)
+                }
+            });
+            let Generics {
+                impl_generics,
+                type_generics,
+                where_clause,
+            } = generics;
+
+            quote! {
+                fn type_info(&self) -> TypeRoot {
+                    impl #impl_generics Typed for #ident #type_generics #where_clause {
+                        fn build(graph: &mut TypeGraph) -> NodeId {
+                            graph.get_or_build_node_with::<Self, _>(|graph| {
+                                OpaqueNode::new::<Self>(#meta, graph)#with_inner_type
+                            })
+                        }
+                    }
+
+                    <Self as Typed>::type_info()
+                }
+            }
+        })
+        .unwrap_or_else(super::placeholder_fn_type_info);
+
+    let fn_debug = attrs.fn_debug_tokens();
+    let fn_reflect_hash = attrs.fn_reflect_hash_tokens();
+    let fn_reflect_partial_eq = attrs.fn_reflect_partial_eq_tokens();
+    let fn_reflect_partial_cmp = attrs.fn_reflect_partial_cmp_tokens();
+    let fn_reflect_display = attrs.fn_reflect_display_tokens();
+    let fn_methods = attrs.fn_methods_tokens();
+
+    let Generics {
+        impl_generics,
+        type_generics,
+        where_clause,
+    } = generics;
+
+    quote! {
+        impl #impl_generics Reflect for #ident #type_generics #where_clause {
+            fn into_any(self: Box<Self>) -> Box<dyn Any> {
+                self
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+
+            fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            #fn_type_info
+
+            fn patch(&mut self, value: &dyn Reflect) {
+                if let Some(value) = value.downcast_ref::<Self>() {
+                    *self = value.clone();
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::from(::core::any::type_name::<Self>())
+            }
+
+            fn clone_reflect(&self) -> Box<dyn Reflect> {
+                Box::new(self.clone())
+            }
+
+            #fn_debug
+            #fn_reflect_hash
+            #fn_reflect_partial_eq
+            #fn_reflect_partial_cmp
+            #fn_reflect_display
+            #fn_methods
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Opaque(self)
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Opaque(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Opaque(self)
+            }
+        }
+    }
+}
+
+fn expand_from_reflect(ident: &Ident, generics: &Generics<'_>) -> TokenStream {
+    let Generics {
+        impl_generics,
+        type_generics,
+        where_clause,
+    } = generics;
+
+    quote! {
+        impl #impl_generics FromReflect for #ident #type_generics #where_clause {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                reflect.downcast_ref::<Self>().cloned()
+            }
+        }
+    }
+}
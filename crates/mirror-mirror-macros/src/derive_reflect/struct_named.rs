@@ -29,11 +29,17 @@ pub(super) fn expand(
     let from_reflect = (!attrs.from_reflect_opt_out)
         .then(|| expand_from_reflect(ident, &attrs, &fields, &field_attrs, generics));
     let struct_ = expand_struct(ident, &fields, &attrs, &field_attrs, generics);
+    let reflected_fields = expand_reflected_fields(ident, &fields, &field_attrs, generics);
+    let default = attrs
+        .default_opt_in
+        .then(|| expand_default(ident, &fields, &field_attrs, generics));
 
     Ok(quote! {
         #reflect
         #from_reflect
         #struct_
+        #reflected_fields
+        #default
     })
 }
 
@@ -87,47 +93,56 @@ fn expand_reflect(
         }
     };
 
-    let fn_type_info = {
-        let code_for_fields = fields
-            .iter()
-            .filter(field_attrs.filter_out_skipped_named())
-            .map(|field| {
-                let name = stringify(&field.ident);
-                let field_ty = &field.ty;
-                let ident = field.ident.as_ref().unwrap();
-                let meta = field_attrs.meta(ident);
-                let docs = field_attrs.docs(ident);
-                quote! {
-                    NamedFieldNode::new::<#field_ty>(#name, #meta, #docs, graph)
-                }
-            });
-
-        let meta = attrs.meta();
-        let docs = attrs.docs();
-        let Generics {
-            impl_generics,
-            type_generics,
-            where_clause,
-        } = generics;
-
-        quote! {
-            fn type_info(&self) -> TypeRoot {
-                impl #impl_generics Typed for #ident #type_generics #where_clause {
-                    fn build(graph: &mut TypeGraph) -> NodeId {
-                        graph.get_or_build_node_with::<Self, _>(|graph| {
-                            let fields = &[#(#code_for_fields),*];
-                            StructNode::new::<Self>(fields, #meta, #docs)
-                        })
+    let fn_type_info = cfg!(feature = "type_info")
+        .then(|| {
+            let code_for_fields = fields
+                .iter()
+                .filter(field_attrs.filter_out_skipped_named())
+                .map(|field| {
+                    let name = stringify(&field.ident);
+                    let field_ty = &field.ty;
+                    let ident = field.ident.as_ref().unwrap();
+                    let meta = field_attrs.meta(ident);
+                    let docs = field_attrs.docs(ident);
+                    let with_default_value =
+                        field_attrs.with_default_value(ident, field_ty, attrs.default_opt_in);
+                    quote! {
+                        NamedFieldNode::new::<#field_ty>(#name, #meta, #docs, graph)#with_default_value
+                    }
+                });
+
+            let meta = attrs.meta();
+            let docs = attrs.docs();
+            let Generics {
+                impl_generics,
+                type_generics,
+                where_clause,
+            } = generics;
+
+            quote! {
+                fn type_info(&self) -> TypeRoot {
+                    impl #impl_generics Typed for #ident #type_generics #where_clause {
+                        fn build(graph: &mut TypeGraph) -> NodeId {
+                            graph.get_or_build_node_with::<Self, _>(|graph| {
+                                let fields = &[#(#code_for_fields),*];
+                                StructNode::new::<Self>(fields, #meta, #docs)
+                            })
+                        }
                     }
-                }
 
-                <Self as Typed>::type_info()
+                    <Self as Typed>::type_info()
+                }
             }
-        }
-    };
+        })
+        .unwrap_or_else(super::placeholder_fn_type_info);
 
     let fn_debug = attrs.fn_debug_tokens();
     let fn_clone_reflect = attrs.fn_clone_reflect_tokens();
+    let fn_reflect_hash = attrs.fn_reflect_hash_tokens();
+    let fn_reflect_partial_eq = attrs.fn_reflect_partial_eq_tokens();
+    let fn_reflect_partial_cmp = attrs.fn_reflect_partial_cmp_tokens();
+    let fn_reflect_display = attrs.fn_reflect_display_tokens();
+    let fn_methods = attrs.fn_methods_tokens();
 
     let Generics {
         impl_generics,
@@ -166,6 +181,11 @@ fn expand_reflect(
             #fn_to_value
             #fn_clone_reflect
             #fn_debug
+            #fn_reflect_hash
+            #fn_reflect_partial_eq
+            #fn_reflect_partial_cmp
+            #fn_reflect_display
+            #fn_methods
 
             fn reflect_owned(self: Box<Self>) -> ReflectOwned {
                 ReflectOwned::Struct(self)
@@ -196,8 +216,9 @@ fn expand_from_reflect(
             let span = field.ty.span();
 
             if skip {
+                let default = field_attrs.default_field_value(ident);
                 quote_spanned! {span=>
-                    #ident: ::core::default::Default::default(),
+                    #ident: #default,
                 }
             } else {
                 let ty = &field.ty;
@@ -269,16 +290,16 @@ fn expand_struct(
                 let ident = &field.ident;
                 let field = stringify(ident);
                 quote! {
-                    if name == #field {
-                        return Some(&self.#ident);
-                    }
+                    #field => Some(&self.#ident),
                 }
             });
 
         quote! {
             fn field(&self, name: &str) -> Option<&dyn Reflect> {
-                #(#code_for_fields)*
-                None
+                match name {
+                    #(#code_for_fields)*
+                    _ => None,
+                }
             }
         }
     };
@@ -291,16 +312,16 @@ fn expand_struct(
                 let ident = &field.ident;
                 let field = stringify(ident);
                 quote! {
-                    if name == #field {
-                        return Some(&mut self.#ident);
-                    }
+                    #field => Some(&mut self.#ident),
                 }
             });
 
         quote! {
             fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
-                #(#code_for_fields)*
-                None
+                match name {
+                    #(#code_for_fields)*
+                    _ => None,
+                }
             }
         }
     };
@@ -313,16 +334,16 @@ fn expand_struct(
             .map(|(index, field)| {
                 let ident = &field.ident;
                 quote! {
-                    if index == #index {
-                        return Some(&self.#ident);
-                    }
+                    #index => Some(&self.#ident),
                 }
             });
 
         quote! {
             fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
-                #(#code_for_fields)*
-                None
+                match index {
+                    #(#code_for_fields)*
+                    _ => None,
+                }
             }
         }
     };
@@ -335,16 +356,16 @@ fn expand_struct(
             .map(|(index, field)| {
                 let ident = &field.ident;
                 quote! {
-                    if index == #index {
-                        return Some(&mut self.#ident);
-                    }
+                    #index => Some(&mut self.#ident),
                 }
             });
 
         quote! {
             fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
-                #(#code_for_fields)*
-                None
+                match index {
+                    #(#code_for_fields)*
+                    _ => None,
+                }
             }
         }
     };
@@ -357,16 +378,16 @@ fn expand_struct(
             .map(|(index, field)| {
                 let ident = &field.ident;
                 quote! {
-                    if index == #index {
-                        return Some(::core::stringify!(#ident));
-                    }
+                    #index => Some(::core::stringify!(#ident)),
                 }
             });
 
         quote! {
             fn name_at(&self, index: usize) -> Option<&str> {
-                #(#code_for_fields)*
-                None
+                match index {
+                    #(#code_for_fields)*
+                    _ => None,
+                }
             }
         }
     };
@@ -433,3 +454,72 @@ fn expand_struct(
         }
     }
 }
+
+// a struct with only the reflected fields, so `typed_key_path!` can't reach skipped ones
+fn expand_reflected_fields(
+    ident: &Ident,
+    fields: &Fields,
+    field_attrs: &AttrsDatabase<Ident>,
+    generics: &Generics<'_>,
+) -> TokenStream {
+    let fields = fields
+        .iter()
+        .filter(field_attrs.filter_out_skipped_named())
+        .collect::<Vec<_>>();
+    let idents = fields.iter().map(|field| &field.ident);
+    let params = (0..fields.len())
+        .map(|idx| quote::format_ident!("F{idx}"))
+        .collect::<Vec<_>>();
+    let field_tys = fields.iter().map(|field| &field.ty);
+
+    let Generics {
+        impl_generics,
+        type_generics,
+        where_clause,
+    } = generics;
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub struct __ReflectedFields<#(#params),*> {
+            #(pub #idents: #params,)*
+        }
+
+        struct __ReflectedFieldsMarker;
+
+        impl #impl_generics ReflectedFields<__ReflectedFieldsMarker> for #ident #type_generics #where_clause {
+            type Fields = __ReflectedFields<#(#field_tys),*>;
+        }
+    }
+}
+
+fn expand_default(
+    ident: &Ident,
+    fields: &Fields,
+    field_attrs: &AttrsDatabase<Ident>,
+    generics: &Generics<'_>,
+) -> TokenStream {
+    let code_for_fields = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let default = field_attrs.default_field_value(ident);
+        quote_spanned! {field.ty.span()=>
+            #ident: #default,
+        }
+    });
+
+    let Generics {
+        impl_generics,
+        type_generics,
+        where_clause,
+    } = generics;
+
+    quote! {
+        impl #impl_generics ::core::default::Default for #ident #type_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#code_for_fields)*
+                }
+            }
+        }
+    }
+}
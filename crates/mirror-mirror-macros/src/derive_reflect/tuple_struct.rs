@@ -29,11 +29,17 @@ pub(super) fn expand(
     let from_reflect = (!attrs.from_reflect_opt_out)
         .then(|| expand_from_reflect(ident, &attrs, &fields, &field_attrs, generics));
     let tuple_struct = expand_tuple_struct(ident, &fields, &attrs, &field_attrs, generics);
+    let reflected_fields = expand_reflected_fields(ident, &fields, &field_attrs, generics);
+    let default = attrs
+        .default_opt_in
+        .then(|| expand_default(ident, &fields, &field_attrs, generics));
 
     Ok(quote! {
         #reflect
         #from_reflect
         #tuple_struct
+        #reflected_fields
+        #default
     })
 }
 
@@ -90,46 +96,55 @@ fn expand_reflect(
         }
     };
 
-    let fn_type_info = {
-        let code_for_fields = fields
-            .iter()
-            .enumerate()
-            .filter(field_attrs.filter_out_skipped_unnamed())
-            .map(|(idx, field)| {
-                let field_ty = &field.ty;
-                let meta = field_attrs.meta(&idx);
-                let docs = field_attrs.docs(&idx);
-                quote! {
-                    UnnamedFieldNode::new::<#field_ty>(#meta, #docs, graph)
-                }
-            });
-
-        let meta = attrs.meta();
-        let docs = attrs.docs();
-        let Generics {
-            impl_generics,
-            type_generics,
-            where_clause,
-        } = generics;
-
-        quote! {
-            fn type_info(&self) -> TypeRoot {
-                impl #impl_generics Typed for #ident #type_generics #where_clause {
-                    fn build(graph: &mut TypeGraph) -> NodeId {
-                        let fields = &[#(#code_for_fields),*];
-                        graph.get_or_build_node_with::<Self, _>(|graph| {
-                            TupleStructNode::new::<Self>(fields, #meta, #docs)
-                        })
+    let fn_type_info = cfg!(feature = "type_info")
+        .then(|| {
+            let code_for_fields = fields
+                .iter()
+                .enumerate()
+                .filter(field_attrs.filter_out_skipped_unnamed())
+                .map(|(idx, field)| {
+                    let field_ty = &field.ty;
+                    let meta = field_attrs.meta(&idx);
+                    let docs = field_attrs.docs(&idx);
+                    let with_default_value =
+                        field_attrs.with_default_value(&idx, field_ty, attrs.default_opt_in);
+                    quote! {
+                        UnnamedFieldNode::new::<#field_ty>(#meta, #docs, graph)#with_default_value
+                    }
+                });
+
+            let meta = attrs.meta();
+            let docs = attrs.docs();
+            let Generics {
+                impl_generics,
+                type_generics,
+                where_clause,
+            } = generics;
+
+            quote! {
+                fn type_info(&self) -> TypeRoot {
+                    impl #impl_generics Typed for #ident #type_generics #where_clause {
+                        fn build(graph: &mut TypeGraph) -> NodeId {
+                            let fields = &[#(#code_for_fields),*];
+                            graph.get_or_build_node_with::<Self, _>(|graph| {
+                                TupleStructNode::new::<Self>(fields, #meta, #docs)
+                            })
+                        }
                     }
-                }
 
-                <Self as Typed>::type_info()
+                    <Self as Typed>::type_info()
+                }
             }
-        }
-    };
+        })
+        .unwrap_or_else(super::placeholder_fn_type_info);
 
     let fn_debug = attrs.fn_debug_tokens();
     let fn_clone_reflect = attrs.fn_clone_reflect_tokens();
+    let fn_reflect_hash = attrs.fn_reflect_hash_tokens();
+    let fn_reflect_partial_eq = attrs.fn_reflect_partial_eq_tokens();
+    let fn_reflect_partial_cmp = attrs.fn_reflect_partial_cmp_tokens();
+    let fn_reflect_display = attrs.fn_reflect_display_tokens();
+    let fn_methods = attrs.fn_methods_tokens();
     let Generics {
         impl_generics,
         type_generics,
@@ -167,6 +182,11 @@ fn expand_reflect(
             #fn_to_value
             #fn_clone_reflect
             #fn_debug
+            #fn_reflect_hash
+            #fn_reflect_partial_eq
+            #fn_reflect_partial_cmp
+            #fn_reflect_display
+            #fn_methods
 
             fn reflect_owned(self: Box<Self>) -> ReflectOwned {
                 ReflectOwned::TupleStruct(self)
@@ -199,8 +219,9 @@ fn expand_from_reflect(
             let ty = &field.ty;
             let span = ty.span();
             if field_attrs.skip(&idx) {
+                let default = field_attrs.default_field_value(&idx);
                 quote_spanned! {span=>
-                    #field_index: ::core::default::Default::default(),
+                    #field_index: #default,
                 }
             } else if let Some(from_reflect_with) = field_attrs.from_reflect_with(&idx) {
                 quote_spanned! {span=>
@@ -373,3 +394,62 @@ fn expand_tuple_struct(
         }
     }
 }
+
+// skipped fields are replaced with `Skipped` so the reflected fields keep their indices
+fn expand_reflected_fields(
+    ident: &Ident,
+    fields: &Fields,
+    field_attrs: &AttrsDatabase<usize>,
+    generics: &Generics<'_>,
+) -> TokenStream {
+    let field_tys = fields.iter().enumerate().map(|(idx, field)| {
+        if field_attrs.skip(&idx) {
+            quote! { Skipped }
+        } else {
+            let ty = &field.ty;
+            quote! { #ty }
+        }
+    });
+
+    let Generics {
+        impl_generics,
+        type_generics,
+        where_clause,
+    } = generics;
+
+    quote! {
+        struct __ReflectedFieldsMarker;
+
+        impl #impl_generics ReflectedFields<__ReflectedFieldsMarker> for #ident #type_generics #where_clause {
+            type Fields = (#(#field_tys,)*);
+        }
+    }
+}
+
+fn expand_default(
+    ident: &Ident,
+    fields: &Fields,
+    field_attrs: &AttrsDatabase<usize>,
+    generics: &Generics<'_>,
+) -> TokenStream {
+    let code_for_fields = fields.iter().enumerate().map(|(idx, field)| {
+        let default = field_attrs.default_field_value(&idx);
+        quote_spanned! {field.ty.span()=>
+            #default,
+        }
+    });
+
+    let Generics {
+        impl_generics,
+        type_generics,
+        where_clause,
+    } = generics;
+
+    quote! {
+        impl #impl_generics ::core::default::Default for #ident #type_generics #where_clause {
+            fn default() -> Self {
+                Self(#(#code_for_fields)*)
+            }
+        }
+    }
+}
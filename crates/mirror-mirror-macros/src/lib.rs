@@ -45,14 +45,27 @@ use proc_macro::TokenStream;
 use quote::quote;
 use quote::ToTokens;
 use syn::parse::Parse;
+use syn::spanned::Spanned;
 
 mod derive_reflect;
+mod reflect_methods;
 
 #[proc_macro_derive(Reflect, attributes(reflect))]
 pub fn derive_reflect(item: TokenStream) -> TokenStream {
     expand_with(item, derive_reflect::expand)
 }
 
+/// Make the methods in an `impl` block marked with `#[reflect(func)]` callable through
+/// reflection.
+///
+/// The type must also have `#[reflect(opt_in(Methods))]`. See the `method` module in
+/// `mirror-mirror` for more details.
+#[proc_macro_attribute]
+pub fn reflect_methods(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    expand_with(item, |item| reflect_methods::expand(attr, item))
+}
+
 /// Private API: Do not use!
 #[proc_macro]
 #[doc(hidden)]
@@ -90,9 +103,9 @@ where
     T: ToTokens,
 {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let token = &self.0;
-        tokens.extend(quote::quote! {
-            ::core::stringify!(#token)
-        })
+        // raw identifiers such as `r#type` are reflected without their `r#` prefix
+        let string = self.0.to_token_stream().to_string();
+        let string = string.strip_prefix("r#").unwrap_or(&string);
+        syn::LitStr::new(string, self.0.span()).to_tokens(tokens)
     }
 }
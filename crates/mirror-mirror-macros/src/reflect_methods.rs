@@ -0,0 +1,210 @@
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use quote::quote_spanned;
+use quote::ToTokens;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::Attribute;
+use syn::FnArg;
+use syn::ImplItem;
+use syn::ImplItemMethod;
+use syn::ItemImpl;
+use syn::Pat;
+use syn::ReturnType;
+use syn::Type;
+use syn::UseTree;
+
+use crate::derive_reflect::parse_docs;
+use crate::stringify;
+
+mod kw {
+    syn::custom_keyword!(func);
+    syn::custom_keyword!(crate_name);
+}
+
+pub(crate) fn expand(attr: TokenStream, mut item: ItemImpl) -> syn::Result<TokenStream> {
+    let crate_name = parse_crate_name(attr)?;
+
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(syn::Error::new_spanned(
+            path,
+            "`#[reflect_methods]` only supports inherent `impl` blocks",
+        ));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "`#[reflect_methods]` doesn't support generic `impl` blocks",
+        ));
+    }
+
+    let self_ty = &*item.self_ty;
+    let mut methods = Vec::new();
+    for impl_item in &mut item.items {
+        let ImplItem::Method(method) = impl_item else { continue };
+        if take_func_attr(&mut method.attrs)? {
+            methods.push(expand_method(&crate_name, self_ty, method)?);
+        }
+    }
+
+    Ok(quote! {
+        #item
+
+        impl #crate_name::method::ReflectMethods for #self_ty {
+            const METHODS: &'static [#crate_name::method::Method] = &[#(#methods,)*];
+        }
+    })
+}
+
+fn parse_crate_name(attr: TokenStream) -> syn::Result<UseTree> {
+    if attr.is_empty() {
+        return Ok(syn::parse_quote!(mirror_mirror));
+    }
+
+    syn::parse::Parser::parse2(
+        |input: ParseStream<'_>| {
+            input.parse::<kw::crate_name>()?;
+            let content;
+            syn::parenthesized!(content in input);
+            content.parse()
+        },
+        attr,
+    )
+}
+
+/// Removes `#[reflect(func)]` from the attributes, since `reflect` isn't a known attribute
+/// outside of `#[derive(Reflect)]`.
+fn take_func_attr(attrs: &mut Vec<Attribute>) -> syn::Result<bool> {
+    let mut found = false;
+    let mut result = Ok(());
+    attrs.retain(|attr| {
+        if !attr.path.is_ident("reflect") {
+            return true;
+        }
+        if let Err(err) = attr.parse_args::<kw::func>() {
+            result = Err(err);
+        }
+        found = true;
+        false
+    });
+    result.map(|_| found)
+}
+
+fn expand_method(
+    crate_name: &UseTree,
+    self_ty: &Type,
+    method: &ImplItemMethod,
+) -> syn::Result<TokenStream> {
+    let sig = &method.sig;
+    let ident = &sig.ident;
+    let name = stringify(ident);
+
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "`#[reflect(func)]` methods cannot be generic",
+        ));
+    }
+    if let Some(unsafety) = &sig.unsafety {
+        return Err(syn::Error::new_spanned(
+            unsafety,
+            "`#[reflect(func)]` methods cannot be `unsafe`",
+        ));
+    }
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "`#[reflect(func)]` methods cannot be `async`",
+        ));
+    }
+
+    let mut inputs = sig.inputs.iter();
+    let receiver = match inputs.next() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {
+            if receiver.mutability.is_some() {
+                quote! { #crate_name::method::Receiver::Mut }
+            } else {
+                quote! { #crate_name::method::Receiver::Ref }
+            }
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "`#[reflect(func)]` methods must take `&self` or `&mut self`",
+            ))
+        }
+    };
+
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    let mut arg_idents = Vec::new();
+    for (index, input) in inputs.enumerate() {
+        let FnArg::Typed(pat_type) = input else {
+            unreachable!("only the first argument can be `self`")
+        };
+        let ty = &*pat_type.ty;
+        if let Type::Reference(_) = ty {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`#[reflect(func)]` methods cannot take references, since arguments are converted \
+                 with `FromReflect`",
+            ));
+        }
+
+        let param_name = match &*pat_type.pat {
+            Pat::Ident(pat) => stringify(&pat.ident).into_token_stream(),
+            pat => stringify(pat).into_token_stream(),
+        };
+        let type_name = stringify(ty);
+        let arg_ident = format_ident!("arg_{}", index);
+
+        params.push(quote! {
+            #crate_name::method::Param::__new(#param_name, #type_name)
+        });
+        args.push(quote_spanned! {ty.span()=>
+            let #arg_ident = <#ty as #crate_name::FromReflect>::from_reflect(args[#index])
+                .ok_or(#crate_name::method::CallError::ArgumentType {
+                    method: #name,
+                    param: #param_name,
+                    expected: #type_name,
+                })?;
+        });
+        arg_idents.push(arg_ident);
+    }
+
+    let return_type = match &sig.output {
+        ReturnType::Default => quote! { "()" },
+        ReturnType::Type(_, ty) => stringify(ty).into_token_stream(),
+    };
+
+    let docs = parse_docs(&method.attrs);
+
+    Ok(quote! {
+        {
+            fn call(
+                receiver: &mut dyn #crate_name::Reflect,
+                args: &[&dyn #crate_name::Reflect],
+            ) -> ::core::result::Result<
+                #crate_name::__private::Box<dyn #crate_name::Reflect>,
+                #crate_name::method::CallError,
+            > {
+                let receiver = receiver
+                    .downcast_mut::<#self_ty>()
+                    .ok_or(#crate_name::method::CallError::Receiver { method: #name })?;
+                #(#args)*
+                let output = <#self_ty>::#ident(receiver, #(#arg_idents,)*);
+                ::core::result::Result::Ok(#crate_name::__private::Box::new(output))
+            }
+
+            #crate_name::method::Method::__new(
+                #name,
+                #receiver,
+                &[#(#params,)*],
+                #return_type,
+                &[#(#docs,)*],
+                call,
+            )
+        }
+    })
+}
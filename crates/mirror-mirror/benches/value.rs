@@ -0,0 +1,63 @@
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use mirror_mirror::FromReflect;
+use mirror_mirror::Reflect;
+
+#[derive(Reflect, Clone, Debug)]
+struct Particle {
+    id: u64,
+    name: String,
+    position: [f32; 3],
+    tags: Vec<u8>,
+}
+
+fn particles(n: u64) -> Vec<Particle> {
+    (0..n)
+        .map(|id| Particle {
+            id,
+            name: format!("particle {id}"),
+            position: [id as f32, 0.0, 1.0],
+            tags: Vec::from([1, 2, 3]),
+        })
+        .collect()
+}
+
+fn to_value(c: &mut Criterion) {
+    let particles = particles(1_000);
+    c.bench_function("to_value", |b| b.iter(|| black_box(&particles).to_value()));
+}
+
+fn from_reflect(c: &mut Criterion) {
+    let value = particles(1_000).to_value();
+    c.bench_function("from_reflect", |b| {
+        b.iter(|| Vec::<Particle>::from_reflect(black_box(&value)).unwrap())
+    });
+}
+
+fn clone(c: &mut Criterion) {
+    let value = particles(1_000).to_value();
+    c.bench_function("clone", |b| b.iter(|| black_box(&value).clone()));
+}
+
+fn scalar_list(c: &mut Criterion) {
+    let numbers = (0..10_000).collect::<Vec<u32>>();
+    c.bench_function("scalar_list", |b| {
+        b.iter(|| {
+            let value = black_box(&numbers).to_value();
+            Vec::<u32>::from_reflect(&value).unwrap()
+        })
+    });
+}
+
+fn compare(c: &mut Criterion) {
+    let a = particles(1_000).to_value();
+    let b = a.clone();
+    c.bench_function("compare", |bench| {
+        bench.iter(|| black_box(&a) == black_box(&b))
+    });
+}
+
+criterion_group!(benches, to_value, from_reflect, clone, scalar_list, compare);
+criterion_main!(benches);
@@ -0,0 +1,645 @@
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::enum_::EnumValue;
+use crate::key_path::KeyPath;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+use crate::type_info::ScalarType;
+use crate::type_info::Type;
+use crate::type_info::TypeKind;
+use crate::type_info::Variant;
+use crate::FromReflect;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Typed;
+use crate::Value;
+
+/// Convert a value into another type with a similar shape.
+///
+/// The structure of `Dst` drives the conversion:
+///
+/// - Struct fields and struct variant fields are matched by name. Fields that only exist on
+///   `Src` are ignored and fields that only exist on `Dst` use their default value, if they have
+///   one.
+/// - Tuple fields are matched by position.
+/// - Enum variants are matched by name.
+/// - Integers are converted to other integer types if the value fits, and to floats.
+///
+/// This is useful for moving data between nearly identical versions of a type, such as a DTO and
+/// a domain struct, without writing the glue by hand.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct PlayerDto {
+///     name: String,
+///     score: u32,
+///     is_admin: bool,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Player {
+///     name: String,
+///     score: u64,
+/// }
+///
+/// let dto = PlayerDto {
+///     name: "Alice".to_owned(),
+///     score: 42,
+///     is_admin: false,
+/// };
+///
+/// let player = mirror_mirror::convert::<_, Player>(&dto).unwrap();
+///
+/// assert_eq!(
+///     player,
+///     Player {
+///         name: "Alice".to_owned(),
+///         score: 42,
+///     }
+/// );
+/// ```
+pub fn convert<Src, Dst>(src: &Src) -> Result<Dst, ConvertError>
+where
+    Src: Reflect + ?Sized,
+    Dst: FromReflect + Typed,
+{
+    convert_with(src, &CoercionPolicy::default())
+}
+
+/// Convert a value into a [`Value`] with the shape of `ty`.
+///
+/// See [`convert`] for the rules used.
+pub fn convert_to_value(src: &dyn Reflect, ty: Type<'_>) -> Result<Value, ConvertError> {
+    convert_to_value_with(src, ty, &CoercionPolicy::default())
+}
+
+/// Like [`convert`] but with the conversions of scalars controlled by `policy`.
+///
+/// Unlike [`FromReflect::from_reflect`], this reports where and why a value didn't match the
+/// type, which makes it a good fit for loading hand-written data.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::convert::convert_with;
+/// use mirror_mirror::convert::CoercionPolicy;
+/// use mirror_mirror::convert::NumberCoercion;
+/// use mirror_mirror::struct_::StructValue;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Settings {
+///     volume: u32,
+///     key: char,
+///     quality: Quality,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// enum Quality {
+///     Low,
+///     High,
+/// }
+///
+/// let policy = CoercionPolicy::new()
+///     .numbers(NumberCoercion::Widening)
+///     .strings_to_chars(true)
+///     .strings_to_unit_variants(true);
+///
+/// let settings = StructValue::new()
+///     .with_field("volume", 80_u8)
+///     .with_field("key", "x")
+///     .with_field("quality", "High");
+///
+/// assert_eq!(
+///     convert_with::<_, Settings>(&settings, &policy).unwrap(),
+///     Settings {
+///         volume: 80,
+///         key: 'x',
+///         quality: Quality::High,
+///     },
+/// );
+///
+/// // `u64` doesn't always fit in a `u32`
+/// let error = convert_with::<_, Settings>(&settings.with_field("volume", 80_u64), &policy)
+///     .unwrap_err();
+/// assert_eq!(error.to_string(), "cannot convert `.volume` to `u32`");
+/// ```
+pub fn convert_with<Src, Dst>(src: &Src, policy: &CoercionPolicy) -> Result<Dst, ConvertError>
+where
+    Src: Reflect + ?Sized,
+    Dst: FromReflect + Typed,
+{
+    let type_info = <Dst as Typed>::type_info();
+    let value = convert_to_value_with(src.as_reflect(), type_info.get_type(), policy)?;
+    Dst::from_reflect(&value).ok_or(ConvertError::FromReflect)
+}
+
+/// Like [`convert_to_value`] but with the conversions of scalars controlled by `policy`.
+pub fn convert_to_value_with(
+    src: &dyn Reflect,
+    ty: Type<'_>,
+    policy: &CoercionPolicy,
+) -> Result<Value, ConvertError> {
+    convert_value(src, ty, policy, &mut KeyPath::default())
+}
+
+/// Patch `target` with `src`, after converting `src` to the type of `target` according to
+/// `policy`.
+///
+/// Like [`Reflect::patch`], fields missing from `src` are left unchanged.
+pub fn patch_with(
+    target: &mut dyn Reflect,
+    src: &dyn Reflect,
+    policy: &CoercionPolicy,
+) -> Result<(), ConvertError> {
+    let policy = CoercionPolicy {
+        skip_missing_fields: true,
+        ..*policy
+    };
+    let type_info = target.type_info();
+    let value = convert_to_value_with(src, type_info.get_type(), &policy)?;
+    target.patch(&value);
+    Ok(())
+}
+
+/// The conversions between scalars done by [`convert_with`], [`convert_to_value_with`], and
+/// [`patch_with`].
+///
+/// The default policy is the one used by [`convert`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoercionPolicy {
+    numbers: NumberCoercion,
+    strings_to_chars: bool,
+    strings_to_unit_variants: bool,
+    skip_missing_fields: bool,
+}
+
+impl Default for CoercionPolicy {
+    fn default() -> Self {
+        Self {
+            numbers: NumberCoercion::InRange,
+            strings_to_chars: false,
+            strings_to_unit_variants: false,
+            skip_missing_fields: false,
+        }
+    }
+}
+
+impl CoercionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Which conversions between numbers are allowed. Defaults to [`NumberCoercion::InRange`].
+    pub fn numbers(mut self, numbers: NumberCoercion) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Whether strings with exactly one character can be converted to `char`s. Defaults to
+    /// `false`.
+    pub fn strings_to_chars(mut self, enabled: bool) -> Self {
+        self.strings_to_chars = enabled;
+        self
+    }
+
+    /// Whether strings can be converted to the unit variant of an enum with the same name.
+    /// Defaults to `false`.
+    pub fn strings_to_unit_variants(mut self, enabled: bool) -> Self {
+        self.strings_to_unit_variants = enabled;
+        self
+    }
+}
+
+/// Which conversions between numbers a [`CoercionPolicy`] allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberCoercion {
+    /// Integers can be converted to any integer type the value fits in, and integers and floats
+    /// can be converted to floats even if that loses precision.
+    InRange,
+    /// Only conversions to types that can hold every value of the source type are allowed, such
+    /// as `u8` to `u32`, `i32` to `i64`, `u16` to `f32`, or `f32` to `f64`.
+    Widening,
+}
+
+/// The error returned by [`convert`] and [`convert_to_value`].
+#[derive(Debug, Clone)]
+pub enum ConvertError {
+    /// A field that exists on the target type didn't exist in the source and doesn't have a
+    /// default value.
+    MissingField { path: KeyPath },
+    /// The source value has a different kind than the target type, for example a struct was
+    /// found where a list was expected.
+    KindMismatch { path: KeyPath, expected: TypeKind },
+    /// The source scalar cannot be converted into the target scalar type.
+    ScalarMismatch { path: KeyPath, expected: ScalarType },
+    /// The source number doesn't fit in the target scalar type.
+    OutOfRange { path: KeyPath, expected: ScalarType },
+    /// The source enum is a variant that doesn't exist on the target type.
+    UnknownVariant { path: KeyPath, variant: String },
+    /// The converted value was rejected by [`FromReflect`].
+    FromReflect,
+}
+
+impl ConvertError {
+    /// The location of the error, if known.
+    pub fn path(&self) -> Option<&KeyPath> {
+        match self {
+            ConvertError::MissingField { path }
+            | ConvertError::KindMismatch { path, .. }
+            | ConvertError::ScalarMismatch { path, .. }
+            | ConvertError::OutOfRange { path, .. }
+            | ConvertError::UnknownVariant { path, .. } => Some(path),
+            ConvertError::FromReflect => None,
+        }
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::MissingField { path } => {
+                write!(f, "missing field `{path}` without a default value")
+            }
+            ConvertError::KindMismatch { path, expected } => {
+                write!(f, "expected {expected:?} at `{path}`")
+            }
+            ConvertError::ScalarMismatch { path, expected } => {
+                write!(f, "cannot convert `{path}` to `{}`", expected.type_name())
+            }
+            ConvertError::OutOfRange { path, expected } => {
+                write!(f, "`{path}` is out of range for `{}`", expected.type_name())
+            }
+            ConvertError::UnknownVariant { path, variant } => {
+                write!(f, "unknown variant `{variant}` at `{path}`")
+            }
+            ConvertError::FromReflect => write!(f, "`FromReflect` rejected the converted value"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConvertError {}
+
+fn convert_value(
+    src: &dyn Reflect,
+    ty: Type<'_>,
+    policy: &CoercionPolicy,
+    path: &mut KeyPath,
+) -> Result<Value, ConvertError> {
+    match (ty, src.reflect_ref()) {
+        (Type::Struct(ty), ReflectRef::Struct(src)) => {
+            let mut value = StructValue::new();
+            for field in ty.field_types() {
+                path.push_field(field.name());
+                let field_value = convert_field(
+                    src.field(field.name()),
+                    field.get_type(),
+                    || field.default_value(),
+                    policy,
+                    path,
+                )?;
+                path.pop();
+                if let Some(field_value) = field_value {
+                    value.set_field(field.name(), field_value);
+                }
+            }
+            Ok(value.into())
+        }
+        (Type::TupleStruct(ty), ReflectRef::TupleStruct(src)) => {
+            let mut value = TupleStructValue::new();
+            for (index, field) in ty.field_types().enumerate() {
+                path.push_field(index);
+                let field_value = convert_field(
+                    src.field_at(index),
+                    field.get_type(),
+                    || field.default_value(),
+                    policy,
+                    path,
+                )?;
+                path.pop();
+                if let Some(field_value) = field_value {
+                    value.push_field(field_value);
+                }
+            }
+            Ok(value.into())
+        }
+        (Type::Tuple(ty), ReflectRef::Tuple(src)) => {
+            let mut value = TupleValue::new();
+            for (index, field) in ty.field_types().enumerate() {
+                path.push_field(index);
+                let field_value = convert_field(
+                    src.field_at(index),
+                    field.get_type(),
+                    || field.default_value(),
+                    policy,
+                    path,
+                )?;
+                path.pop();
+                if let Some(field_value) = field_value {
+                    value.push_field(field_value);
+                }
+            }
+            Ok(value.into())
+        }
+        (Type::Enum(ty), ReflectRef::Enum(src)) => {
+            let name = src.variant_name();
+            let Some(variant) = ty.variant(name) else {
+                return Err(ConvertError::UnknownVariant {
+                    path: path.clone(),
+                    variant: name.to_owned(),
+                });
+            };
+            path.push_variant(name);
+            let value = match variant {
+                Variant::Struct(variant) => {
+                    let mut value = EnumValue::new_struct_variant(name);
+                    for field in variant.field_types() {
+                        path.push_field(field.name());
+                        let field_value = convert_field(
+                            src.field(field.name()),
+                            field.get_type(),
+                            || field.default_value(),
+                            policy,
+                            path,
+                        )?;
+                        path.pop();
+                        if let Some(field_value) = field_value {
+                            value.set_struct_field(field.name(), field_value);
+                        }
+                    }
+                    value.finish()
+                }
+                Variant::Tuple(variant) => {
+                    let mut value = EnumValue::new_tuple_variant(name);
+                    for (index, field) in variant.field_types().enumerate() {
+                        path.push_field(index);
+                        let field_value = convert_field(
+                            src.field_at(index),
+                            field.get_type(),
+                            || field.default_value(),
+                            policy,
+                            path,
+                        )?;
+                        path.pop();
+                        if let Some(field_value) = field_value {
+                            value.push_tuple_field(field_value);
+                        }
+                    }
+                    value.finish()
+                }
+                Variant::Unit(_) => EnumValue::new_unit_variant(name),
+            };
+            path.pop();
+            Ok(value.into())
+        }
+        (Type::List(ty), ReflectRef::List(src)) => {
+            convert_elements(src.iter(), ty.element_type(), policy, path)
+        }
+        (Type::List(ty), ReflectRef::Array(src)) => {
+            convert_elements(src.iter(), ty.element_type(), policy, path)
+        }
+        (Type::Array(ty), ReflectRef::List(src)) => {
+            convert_elements(src.iter(), ty.element_type(), policy, path)
+        }
+        (Type::Array(ty), ReflectRef::Array(src)) => {
+            convert_elements(src.iter(), ty.element_type(), policy, path)
+        }
+        (Type::Map(ty), ReflectRef::Map(src)) => {
+            let mut map = BTreeMap::new();
+            for (key, value) in src.iter() {
+                let key = convert_value(key, ty.key_type(), policy, path)?;
+                path.push_get(key.clone());
+                let value = convert_value(value, ty.value_type(), policy, path)?;
+                path.pop();
+                map.insert(key, value);
+            }
+            Ok(Value::Map(Box::new(map)))
+        }
+        (Type::Enum(ty), ReflectRef::Scalar(ScalarRef::String(name)))
+            if policy.strings_to_unit_variants =>
+        {
+            match ty.variant(name) {
+                Some(Variant::Unit(_)) => Ok(EnumValue::new_unit_variant(name).into()),
+                Some(_) => Err(ConvertError::KindMismatch {
+                    path: path.clone(),
+                    expected: TypeKind::Enum,
+                }),
+                None => Err(ConvertError::UnknownVariant {
+                    path: path.clone(),
+                    variant: name.to_owned(),
+                }),
+            }
+        }
+        (Type::Scalar(ty), ReflectRef::Scalar(src)) => convert_scalar(src, ty, policy, path),
+        // opaque types cannot be inspected so the best we can do is hand the value to
+        // `FromReflect` as is
+        (Type::Opaque(_), _) => Ok(src.to_value()),
+        (ty, _) => Err(ConvertError::KindMismatch {
+            path: path.clone(),
+            expected: ty.kind(),
+        }),
+    }
+}
+
+fn convert_field(
+    src: Option<&dyn Reflect>,
+    ty: Type<'_>,
+    default_value: impl FnOnce() -> Option<Value>,
+    policy: &CoercionPolicy,
+    path: &mut KeyPath,
+) -> Result<Option<Value>, ConvertError> {
+    match src {
+        Some(src) => convert_value(src, ty, policy, path).map(Some),
+        None if policy.skip_missing_fields => Ok(None),
+        None => default_value()
+            .map(Some)
+            .ok_or_else(|| ConvertError::MissingField { path: path.clone() }),
+    }
+}
+
+fn convert_elements<'a>(
+    src: impl Iterator<Item = &'a dyn Reflect>,
+    element_type: Type<'_>,
+    policy: &CoercionPolicy,
+    path: &mut KeyPath,
+) -> Result<Value, ConvertError> {
+    let mut elements = Vec::new();
+    for (index, element) in src.enumerate() {
+        path.push_get(index);
+        elements.push(convert_value(element, element_type, policy, path)?);
+        path.pop();
+    }
+    Ok(Value::List(Box::new(elements)))
+}
+
+fn convert_scalar(
+    src: ScalarRef<'_>,
+    ty: ScalarType,
+    policy: &CoercionPolicy,
+    path: &KeyPath,
+) -> Result<Value, ConvertError> {
+    enum Number {
+        Unsigned(u128),
+        Signed(i128),
+        Float(f64),
+    }
+
+    let mismatch = || ConvertError::ScalarMismatch {
+        path: path.clone(),
+        expected: ty,
+    };
+
+    let number = match src {
+        ScalarRef::usize(n) => Number::Unsigned(n as u128),
+        ScalarRef::u8(n) => Number::Unsigned(n as u128),
+        ScalarRef::u16(n) => Number::Unsigned(n as u128),
+        ScalarRef::u32(n) => Number::Unsigned(n as u128),
+        ScalarRef::u64(n) => Number::Unsigned(n as u128),
+        ScalarRef::u128(n) => Number::Unsigned(n),
+        ScalarRef::i8(n) => Number::Signed(n as i128),
+        ScalarRef::i16(n) => Number::Signed(n as i128),
+        ScalarRef::i32(n) => Number::Signed(n as i128),
+        ScalarRef::i64(n) => Number::Signed(n as i128),
+        ScalarRef::i128(n) => Number::Signed(n),
+        ScalarRef::f32(n) => Number::Float(n as f64),
+        ScalarRef::f64(n) => Number::Float(n),
+        ScalarRef::bool(b) => {
+            let ScalarType::bool = ty else { return Err(mismatch()) };
+            return Ok(Value::bool(b));
+        }
+        ScalarRef::char(c) => {
+            let ScalarType::char = ty else { return Err(mismatch()) };
+            return Ok(Value::char(c));
+        }
+        ScalarRef::String(s) => {
+            return match ty {
+                ScalarType::String => Ok(Value::from(s)),
+                ScalarType::char if policy.strings_to_chars => {
+                    let mut chars = s.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Ok(Value::char(c)),
+                        _ => Err(mismatch()),
+                    }
+                }
+                _ => Err(mismatch()),
+            };
+        }
+    };
+
+    if policy.numbers == NumberCoercion::Widening && !is_widening(src, ty) {
+        return Err(mismatch());
+    }
+
+    macro_rules! integer {
+        ($ty:ident) => {{
+            let n = match number {
+                Number::Unsigned(n) => $ty::try_from(n).ok(),
+                Number::Signed(n) => $ty::try_from(n).ok(),
+                Number::Float(_) => return Err(mismatch()),
+            };
+            let n = n.ok_or_else(|| ConvertError::OutOfRange {
+                path: path.clone(),
+                expected: ty,
+            })?;
+            Value::from(n)
+        }};
+    }
+
+    macro_rules! float {
+        ($ty:ident) => {
+            match number {
+                Number::Unsigned(n) => Value::$ty(n as $ty),
+                Number::Signed(n) => Value::$ty(n as $ty),
+                Number::Float(n) => Value::$ty(n as $ty),
+            }
+        };
+    }
+
+    let value = match ty {
+        ScalarType::usize => integer!(usize),
+        ScalarType::u8 => integer!(u8),
+        ScalarType::u16 => integer!(u16),
+        ScalarType::u32 => integer!(u32),
+        ScalarType::u64 => integer!(u64),
+        ScalarType::u128 => integer!(u128),
+        ScalarType::i8 => integer!(i8),
+        ScalarType::i16 => integer!(i16),
+        ScalarType::i32 => integer!(i32),
+        ScalarType::i64 => integer!(i64),
+        ScalarType::i128 => integer!(i128),
+        ScalarType::f32 => float!(f32),
+        ScalarType::f64 => float!(f64),
+        ScalarType::bool | ScalarType::char | ScalarType::String => return Err(mismatch()),
+    };
+
+    Ok(value)
+}
+
+/// Whether every value of the type of `src` can be represented exactly by `ty`.
+fn is_widening(src: ScalarRef<'_>, ty: ScalarType) -> bool {
+    // the number of bits of integers, and the number of bits of the mantissa of floats
+    enum Kind {
+        Unsigned(u32),
+        Signed(u32),
+        Float(u32),
+    }
+
+    let from = match src {
+        ScalarRef::usize(_) => Kind::Unsigned(usize::BITS),
+        ScalarRef::u8(_) => Kind::Unsigned(u8::BITS),
+        ScalarRef::u16(_) => Kind::Unsigned(u16::BITS),
+        ScalarRef::u32(_) => Kind::Unsigned(u32::BITS),
+        ScalarRef::u64(_) => Kind::Unsigned(u64::BITS),
+        ScalarRef::u128(_) => Kind::Unsigned(u128::BITS),
+        ScalarRef::i8(_) => Kind::Signed(i8::BITS),
+        ScalarRef::i16(_) => Kind::Signed(i16::BITS),
+        ScalarRef::i32(_) => Kind::Signed(i32::BITS),
+        ScalarRef::i64(_) => Kind::Signed(i64::BITS),
+        ScalarRef::i128(_) => Kind::Signed(i128::BITS),
+        ScalarRef::f32(_) => Kind::Float(f32::MANTISSA_DIGITS),
+        ScalarRef::f64(_) => Kind::Float(f64::MANTISSA_DIGITS),
+        ScalarRef::bool(_) | ScalarRef::char(_) | ScalarRef::String(_) => return false,
+    };
+
+    let to = match ty {
+        ScalarType::usize => Kind::Unsigned(usize::BITS),
+        ScalarType::u8 => Kind::Unsigned(u8::BITS),
+        ScalarType::u16 => Kind::Unsigned(u16::BITS),
+        ScalarType::u32 => Kind::Unsigned(u32::BITS),
+        ScalarType::u64 => Kind::Unsigned(u64::BITS),
+        ScalarType::u128 => Kind::Unsigned(u128::BITS),
+        ScalarType::i8 => Kind::Signed(i8::BITS),
+        ScalarType::i16 => Kind::Signed(i16::BITS),
+        ScalarType::i32 => Kind::Signed(i32::BITS),
+        ScalarType::i64 => Kind::Signed(i64::BITS),
+        ScalarType::i128 => Kind::Signed(i128::BITS),
+        ScalarType::f32 => Kind::Float(f32::MANTISSA_DIGITS),
+        ScalarType::f64 => Kind::Float(f64::MANTISSA_DIGITS),
+        ScalarType::bool | ScalarType::char | ScalarType::String => return false,
+    };
+
+    match (from, to) {
+        (Kind::Unsigned(from), Kind::Unsigned(to)) | (Kind::Signed(from), Kind::Signed(to)) => {
+            from <= to
+        }
+        (Kind::Unsigned(from), Kind::Signed(to)) => from < to,
+        (Kind::Signed(_), Kind::Unsigned(_)) => false,
+        (Kind::Unsigned(from), Kind::Float(to)) => from <= to,
+        // the sign bit doesn't need to fit in the mantissa
+        (Kind::Signed(from), Kind::Float(to)) => from - 1 <= to,
+        (Kind::Float(from), Kind::Float(to)) => from <= to,
+        (Kind::Float(_), Kind::Unsigned(_) | Kind::Signed(_)) => false,
+    }
+}
@@ -0,0 +1,482 @@
+//! Compute, apply, and undo the changes between two values.
+//!
+//! [`diff`] compares two values and returns a [`Diff`] that contains only the parts that differ,
+//! each stored along with the value it replaced. That makes every [`Diff`] invertible, so undo
+//! and redo can be implemented by storing diffs rather than full snapshots, which is what
+//! [`History`] does.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::diff::History;
+//! use mirror_mirror::Reflect;
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Player {
+//!     name: String,
+//!     scores: Vec<u32>,
+//! }
+//!
+//! let mut player = Player {
+//!     name: "Alice".to_owned(),
+//!     scores: vec![1],
+//! };
+//!
+//! let mut history = History::new();
+//! history.edit(&mut player, |player| {
+//!     let player = player.downcast_mut::<Player>().unwrap();
+//!     player.scores.push(2);
+//! });
+//! assert_eq!(player.scores, [1, 2]);
+//!
+//! history.undo(&mut player).unwrap();
+//! assert_eq!(player.scores, [1]);
+//!
+//! history.redo(&mut player).unwrap();
+//! assert_eq!(player.scores, [1, 2]);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "type_info")]
+use crate::convert::ConvertError;
+use crate::enum_::VariantField;
+use crate::key_path::GetPath;
+use crate::key_path::KeyPath;
+use crate::key_path::SetError;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::Value;
+
+/// Compute the changes needed to turn `old` into `new`.
+///
+/// Values are compared recursively so only the parts that actually differ are recorded. Values of
+/// different kinds, structs with different fields, enums with different variants, and tuples or
+/// arrays with different lengths are replaced as a whole. Lists only record the elements that were
+/// pushed or popped at the end, and maps the entries that were inserted or removed.
+pub fn diff(old: &dyn Reflect, new: &dyn Reflect) -> Diff {
+    let mut diff = Diff::default();
+    diff_at(old, new, &mut KeyPath::default(), &mut diff.changes);
+    diff
+}
+
+fn diff_at(old: &dyn Reflect, new: &dyn Reflect, path: &mut KeyPath, changes: &mut Vec<Change>) {
+    match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Struct(old_inner), ReflectRef::Struct(new_inner))
+            if old_inner.fields_len() == new_inner.fields_len()
+                && old_inner
+                    .fields()
+                    .all(|(name, _)| new_inner.field(name).is_some()) =>
+        {
+            for (name, old) in old_inner.fields() {
+                let new = new_inner.field(name).expect("checked above");
+                path.push_field(name);
+                diff_at(old, new, path, changes);
+                path.pop();
+            }
+        }
+        (ReflectRef::TupleStruct(old_inner), ReflectRef::TupleStruct(new_inner))
+            if old_inner.fields_len() == new_inner.fields_len() =>
+        {
+            for (index, (old, new)) in old_inner.fields().zip(new_inner.fields()).enumerate() {
+                path.push_field(index);
+                diff_at(old, new, path, changes);
+                path.pop();
+            }
+        }
+        (ReflectRef::Tuple(old_inner), ReflectRef::Tuple(new_inner))
+            if old_inner.fields_len() == new_inner.fields_len() =>
+        {
+            for (index, (old, new)) in old_inner.fields().zip(new_inner.fields()).enumerate() {
+                path.push_field(index);
+                diff_at(old, new, path, changes);
+                path.pop();
+            }
+        }
+        (ReflectRef::Enum(old_inner), ReflectRef::Enum(new_inner))
+            if old_inner.variant_name() == new_inner.variant_name()
+                && old_inner.fields_len() == new_inner.fields_len() =>
+        {
+            path.push_variant(old_inner.variant_name());
+            for (index, (old, new)) in old_inner.fields().zip(new_inner.fields()).enumerate() {
+                match (old, new) {
+                    (VariantField::Struct(name, old), VariantField::Struct(_, new)) => {
+                        path.push_field(name);
+                        diff_at(old, new, path, changes);
+                    }
+                    (VariantField::Tuple(old), VariantField::Tuple(new)) => {
+                        path.push_field(index);
+                        diff_at(old, new, path, changes);
+                    }
+                    (VariantField::Struct(_, old), VariantField::Tuple(new))
+                    | (VariantField::Tuple(old), VariantField::Struct(_, new)) => {
+                        // can't happen for variants with the same name but replace the entire
+                        // field just in case
+                        path.push_field(index);
+                        replace(old, new, path, changes);
+                    }
+                }
+                path.pop();
+            }
+            path.pop();
+        }
+        (ReflectRef::Array(old_inner), ReflectRef::Array(new_inner))
+            if old_inner.len() == new_inner.len() =>
+        {
+            for (index, (old, new)) in old_inner.iter().zip(new_inner.iter()).enumerate() {
+                path.push_get(index);
+                diff_at(old, new, path, changes);
+                path.pop();
+            }
+        }
+        (ReflectRef::List(old_inner), ReflectRef::List(new_inner)) => {
+            for (index, (old, new)) in old_inner.iter().zip(new_inner.iter()).enumerate() {
+                path.push_get(index);
+                diff_at(old, new, path, changes);
+                path.pop();
+            }
+            // pop from the end so undoing pushes the elements back in the right order
+            for index in (new_inner.len()..old_inner.len()).rev() {
+                if let Some(value) = old_inner.get(index) {
+                    changes.push(Change::Pop {
+                        path: path.clone(),
+                        value: value.to_value(),
+                    });
+                }
+            }
+            for value in new_inner.iter().skip(old_inner.len()) {
+                changes.push(Change::Push {
+                    path: path.clone(),
+                    value: value.to_value(),
+                });
+            }
+        }
+        (ReflectRef::Map(old_inner), ReflectRef::Map(new_inner)) => {
+            for (key, old) in old_inner.iter() {
+                match new_inner.get(key) {
+                    Some(new) => {
+                        path.push_get(key.to_value());
+                        diff_at(old, new, path, changes);
+                        path.pop();
+                    }
+                    None => changes.push(Change::Remove {
+                        path: path.clone(),
+                        key: key.to_value(),
+                        value: old.to_value(),
+                    }),
+                }
+            }
+            for (key, new) in new_inner.iter() {
+                if old_inner.get(key).is_none() {
+                    changes.push(Change::Insert {
+                        path: path.clone(),
+                        key: key.to_value(),
+                        value: new.to_value(),
+                    });
+                }
+            }
+        }
+        _ => {
+            // opaque values can't be compared so they're always replaced
+            if old.reflect_partial_eq(new) != Some(true) {
+                replace(old, new, path, changes);
+            }
+        }
+    }
+}
+
+fn replace(old: &dyn Reflect, new: &dyn Reflect, path: &KeyPath, changes: &mut Vec<Change>) {
+    changes.push(Change::Replace {
+        path: path.clone(),
+        old: old.to_value(),
+        new: new.to_value(),
+    });
+}
+
+/// The changes between two values, as computed by [`diff`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Whether the values that were compared are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// The changes, in the order they are applied.
+    pub fn changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter()
+    }
+
+    /// Apply the changes to `target`, turning the old value into the new one.
+    ///
+    /// Applying stops at the first change that fails, in which case the changes before it remain
+    /// applied.
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<(), DiffError> {
+        self.changes
+            .iter()
+            .try_for_each(|change| change.apply(target))
+    }
+
+    /// The diff that undoes this one, turning the new value back into the old one.
+    pub fn invert(&self) -> Diff {
+        Diff {
+            changes: self.changes.iter().rev().map(Change::invert).collect(),
+        }
+    }
+}
+
+/// A single change in a [`Diff`].
+///
+/// Every change stores the value it replaces so it can be inverted.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Change {
+    /// The value at `path` was replaced.
+    Replace {
+        path: KeyPath,
+        old: Value,
+        new: Value,
+    },
+    /// `value` was pushed onto the list at `path`.
+    Push { path: KeyPath, value: Value },
+    /// `value` was popped off the list at `path`.
+    Pop { path: KeyPath, value: Value },
+    /// An entry was inserted into the map at `path`.
+    Insert {
+        path: KeyPath,
+        key: Value,
+        value: Value,
+    },
+    /// An entry was removed from the map at `path`.
+    Remove {
+        path: KeyPath,
+        key: Value,
+        value: Value,
+    },
+}
+
+impl Change {
+    /// The path, relative to the value that was diffed, of the value this change applies to.
+    pub fn path(&self) -> &KeyPath {
+        match self {
+            Change::Replace { path, .. }
+            | Change::Push { path, .. }
+            | Change::Pop { path, .. }
+            | Change::Insert { path, .. }
+            | Change::Remove { path, .. } => path,
+        }
+    }
+
+    /// The change that undoes this one.
+    pub fn invert(&self) -> Change {
+        match self.clone() {
+            Change::Replace { path, old, new } => Change::Replace {
+                path,
+                old: new,
+                new: old,
+            },
+            Change::Push { path, value } => Change::Pop { path, value },
+            Change::Pop { path, value } => Change::Push { path, value },
+            Change::Insert { path, key, value } => Change::Remove { path, key, value },
+            Change::Remove { path, key, value } => Change::Insert { path, key, value },
+        }
+    }
+
+    fn apply(&self, target: &mut dyn Reflect) -> Result<(), DiffError> {
+        match self {
+            Change::Replace { path, new, .. } => {
+                target
+                    .set_at(path, new.clone())
+                    .map_err(|error| match error {
+                        SetError::NotFound { path } => DiffError::NotFound { path },
+                        #[cfg(feature = "type_info")]
+                        SetError::Convert { path, error } => DiffError::Convert { path, error },
+                    })
+            }
+            Change::Push { path, value } => {
+                let list =
+                    at_mut(target, path)?
+                        .as_list_mut()
+                        .ok_or_else(|| DiffError::WrongKind {
+                            path: path.clone(),
+                            expected: "list",
+                        })?;
+                list.push(value);
+                Ok(())
+            }
+            Change::Pop { path, .. } => {
+                let list =
+                    at_mut(target, path)?
+                        .as_list_mut()
+                        .ok_or_else(|| DiffError::WrongKind {
+                            path: path.clone(),
+                            expected: "list",
+                        })?;
+                list.pop();
+                Ok(())
+            }
+            Change::Insert { path, key, value } => {
+                let map =
+                    at_mut(target, path)?
+                        .as_map_mut()
+                        .ok_or_else(|| DiffError::WrongKind {
+                            path: path.clone(),
+                            expected: "map",
+                        })?;
+                map.insert(key, value);
+                Ok(())
+            }
+            Change::Remove { path, key, .. } => {
+                let map =
+                    at_mut(target, path)?
+                        .as_map_mut()
+                        .ok_or_else(|| DiffError::WrongKind {
+                            path: path.clone(),
+                            expected: "map",
+                        })?;
+                map.remove(key);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn at_mut<'a>(
+    target: &'a mut dyn Reflect,
+    path: &KeyPath,
+) -> Result<&'a mut dyn Reflect, DiffError> {
+    target
+        .at_mut(path)
+        .ok_or_else(|| DiffError::NotFound { path: path.clone() })
+}
+
+/// The error returned when applying a [`Diff`] fails.
+#[derive(Debug, Clone)]
+pub enum DiffError {
+    /// There is no value at the key path.
+    NotFound { path: KeyPath },
+    /// The value at the key path isn't of the kind the change applies to, for example a push to
+    /// something that isn't a list.
+    WrongKind {
+        path: KeyPath,
+        expected: &'static str,
+    },
+    /// The new value couldn't be converted into the type at the key path.
+    ///
+    /// The path in `error` is relative to `path`.
+    #[cfg(feature = "type_info")]
+    Convert { path: KeyPath, error: ConvertError },
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffError::NotFound { path } => write!(f, "no value at `{path}`"),
+            DiffError::WrongKind { path, expected } => {
+                write!(f, "expected a {expected} at `{path}`")
+            }
+            #[cfg(feature = "type_info")]
+            DiffError::Convert { path, error } => write!(f, "cannot set `{path}`: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DiffError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DiffError::NotFound { .. } | DiffError::WrongKind { .. } => None,
+            #[cfg(feature = "type_info")]
+            DiffError::Convert { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Undo and redo stacks of [`Diff`]s.
+///
+/// Each edit is stored as the diff between the value before and after it, so memory use grows
+/// with the size of the edits rather than the size of the value being edited.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo: Vec<Diff>,
+    redo: Vec<Diff>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an edit that has already been applied.
+    ///
+    /// Clears the redo stack. Empty diffs are ignored.
+    pub fn record(&mut self, diff: Diff) {
+        if diff.is_empty() {
+            return;
+        }
+        self.undo.push(diff);
+        self.redo.clear();
+    }
+
+    /// Run `f` to edit `target` and record the changes it made.
+    ///
+    /// This takes a snapshot of `target` while `f` runs to compute the diff. Use
+    /// [`record`](Self::record) to avoid that if the diff is known ahead of time.
+    pub fn edit<R>(
+        &mut self,
+        target: &mut dyn Reflect,
+        f: impl FnOnce(&mut dyn Reflect) -> R,
+    ) -> R {
+        let before = target.to_value();
+        let out = f(target);
+        self.record(diff(&before, target));
+        out
+    }
+
+    /// Undo the most recent edit.
+    ///
+    /// Returns `false` if there was nothing to undo. If applying the inverted diff fails the edit
+    /// stays on the undo stack.
+    pub fn undo(&mut self, target: &mut dyn Reflect) -> Result<bool, DiffError> {
+        let Some(diff) = self.undo.last() else { return Ok(false) };
+        diff.invert().apply(target)?;
+        self.redo.extend(self.undo.pop());
+        Ok(true)
+    }
+
+    /// Redo the most recently undone edit.
+    ///
+    /// Returns `false` if there was nothing to redo. If applying the diff fails the edit stays on
+    /// the redo stack.
+    pub fn redo(&mut self, target: &mut dyn Reflect) -> Result<bool, DiffError> {
+        let Some(diff) = self.redo.last() else { return Ok(false) };
+        diff.apply(target)?;
+        self.undo.extend(self.redo.pop());
+        Ok(true)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all edits.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
@@ -0,0 +1,564 @@
+//! Inspect and edit reflected values with [egui].
+//!
+//! [`inspect`] renders an editable UI for any reflected value, using its type info to pick
+//! widgets:
+//!
+//! - Numbers are edited with a [`DragValue`]. A field's `range` metadata, such as `0..10` or
+//!   `[0.0, 1.0]`, clamps the value and its `step` metadata sets how much it changes per pixel
+//!   dragged. Like in Rust `[start, end]` includes the end and `start..end` doesn't, except for
+//!   floats where there's no largest number below the end to clamp to, so it's included too.
+//!   Values outside the range are clamped as soon as they're shown, which counts as a change.
+//! - `bool`s get a checkbox and `String`s and `char`s a text field.
+//! - Structs, tuples, and other compound values are shown in collapsing headers. The header
+//!   includes the value's [`Reflect::reflect_display`], if any.
+//! - Enums get a combo box to switch variant. Switching resets the fields to their default values,
+//!   so it only works for variants where all fields have one.
+//! - Lists and maps have buttons to remove entries and to add entries with default values.
+//!
+//! Metadata set on a list, array, or map field applies to its elements.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::Reflect;
+//!
+//! #[derive(Reflect, Clone, Debug)]
+//! struct Player {
+//!     name: String,
+//!     #[reflect(meta(range = 0..100))]
+//!     health: u8,
+//! }
+//!
+//! let mut player = Player {
+//!     name: "Alice".to_owned(),
+//!     health: 10,
+//! };
+//!
+//! let ctx = egui::Context::default();
+//! let _ = ctx.run(Default::default(), |ctx| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         if mirror_mirror::egui::inspect(ui, &mut player) {
+//!             println!("player changed: {player:?}");
+//!         }
+//!     });
+//! });
+//! ```
+//!
+//! [egui]: https://github.com/emilk/egui
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use core::hash::Hash;
+use core::str::FromStr;
+
+use ::egui::emath::Numeric;
+use ::egui::Button;
+use ::egui::CollapsingHeader;
+use ::egui::ComboBox;
+use ::egui::DragValue;
+use ::egui::Ui;
+
+use crate::enum_::VariantFieldMut;
+use crate::type_info::EnumType;
+use crate::type_info::GetMeta;
+use crate::type_info::Type;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectRef;
+use crate::ScalarMut;
+use crate::ScalarRef;
+use crate::Value;
+
+/// Render an editable UI for `value`.
+///
+/// Returns whether `value` was changed.
+pub fn inspect(ui: &mut Ui, value: &mut dyn Reflect) -> bool {
+    let type_info = value.type_info();
+    inspect_with_type(ui, value, type_info.get_type())
+}
+
+/// Like [`inspect`] but with the type info passed explicitly.
+///
+/// This is needed to get the most out of [`Value`]s, whose type info is opaque.
+pub fn inspect_with_type(ui: &mut Ui, value: &mut dyn Reflect, ty: Type<'_>) -> bool {
+    value_ui(ui, value, Some(ty), &Hints::default())
+}
+
+/// Hints read from a field's metadata.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Hints {
+    range: Option<NumberRange>,
+    step: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct NumberRange {
+    start: f64,
+    end: f64,
+    /// `false` for half-open ranges such as `0..100`.
+    end_inclusive: bool,
+}
+
+impl Hints {
+    fn new<'a>(field: impl GetMeta<'a> + Copy) -> Self {
+        Self {
+            range: field.meta("range").and_then(range),
+            step: field.meta("step").and_then(as_f64),
+        }
+    }
+}
+
+/// Read a range either from something with `start` and `end` fields, such as `Range`, which
+/// excludes the end, or from a list, array, or tuple with two elements, which includes it.
+fn range(value: &dyn Reflect) -> Option<NumberRange> {
+    let (start, end, end_inclusive) = match value.reflect_ref() {
+        ReflectRef::Struct(inner) => (inner.field("start")?, inner.field("end")?, false),
+        ReflectRef::Tuple(inner) if inner.fields_len() == 2 => {
+            (inner.field_at(0)?, inner.field_at(1)?, true)
+        }
+        ReflectRef::List(inner) if inner.len() == 2 => (inner.get(0)?, inner.get(1)?, true),
+        ReflectRef::Array(inner) if inner.len() == 2 => (inner.get(0)?, inner.get(1)?, true),
+        _ => return None,
+    };
+    Some(NumberRange {
+        start: as_f64(start)?,
+        end: as_f64(end)?,
+        end_inclusive,
+    })
+}
+
+fn as_f64(value: &dyn Reflect) -> Option<f64> {
+    let n = match value.as_scalar()? {
+        ScalarRef::usize(n) => n as f64,
+        ScalarRef::u8(n) => n as f64,
+        ScalarRef::u16(n) => n as f64,
+        ScalarRef::u32(n) => n as f64,
+        ScalarRef::u64(n) => n as f64,
+        ScalarRef::u128(n) => n as f64,
+        ScalarRef::i8(n) => n as f64,
+        ScalarRef::i16(n) => n as f64,
+        ScalarRef::i32(n) => n as f64,
+        ScalarRef::i64(n) => n as f64,
+        ScalarRef::i128(n) => n as f64,
+        ScalarRef::f32(n) => n as f64,
+        ScalarRef::f64(n) => n,
+        ScalarRef::bool(_) | ScalarRef::char(_) | ScalarRef::String(_) => return None,
+    };
+    Some(n)
+}
+
+fn value_ui(ui: &mut Ui, value: &mut dyn Reflect, ty: Option<Type<'_>>, hints: &Hints) -> bool {
+    let mut changed = false;
+
+    if value.as_enum().is_some() {
+        return enum_ui(ui, value, ty.and_then(Type::as_enum));
+    }
+
+    match value.reflect_mut() {
+        ReflectMut::Struct(inner) => {
+            let struct_type = ty.and_then(Type::as_struct);
+            for (name, value) in inner.fields_mut() {
+                let field = struct_type.and_then(|ty| ty.field_type(name));
+                let hints = field.map(Hints::new).unwrap_or_default();
+                changed |= field_ui(ui, name, name, value, field.map(|f| f.get_type()), &hints);
+            }
+        }
+        ReflectMut::TupleStruct(inner) => {
+            let tuple_struct_type = ty.and_then(Type::as_tuple_struct);
+            for (index, value) in inner.fields_mut().enumerate() {
+                let field = tuple_struct_type.and_then(|ty| ty.field_type_at(index));
+                let hints = field.map(Hints::new).unwrap_or_default();
+                let label = index.to_string();
+                changed |= field_ui(
+                    ui,
+                    &label,
+                    index,
+                    value,
+                    field.map(|f| f.get_type()),
+                    &hints,
+                );
+            }
+        }
+        ReflectMut::Tuple(inner) => {
+            let tuple_type = ty.and_then(Type::as_tuple);
+            for (index, value) in inner.fields_mut().enumerate() {
+                let field = tuple_type.and_then(|ty| ty.field_type_at(index));
+                let hints = field.map(Hints::new).unwrap_or_default();
+                let label = index.to_string();
+                changed |= field_ui(
+                    ui,
+                    &label,
+                    index,
+                    value,
+                    field.map(|f| f.get_type()),
+                    &hints,
+                );
+            }
+        }
+        // handled above
+        ReflectMut::Enum(_) => {}
+        ReflectMut::List(inner) => {
+            let element_type = ty.and_then(Type::as_list).map(|ty| ty.element_type());
+
+            let mut remove = None;
+            for (index, value) in inner.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.small_button("-").clicked() {
+                        remove = Some(index);
+                    }
+                    let label = format!("[{index}]");
+                    changed |= field_ui(ui, &label, index, value, element_type, hints);
+                });
+            }
+            if let Some(index) = remove {
+                inner.try_remove(index);
+                changed = true;
+            }
+
+            if let Some(element_type) = element_type {
+                if ui.small_button("+").clicked() {
+                    if let Some(value) = element_type.default_value() {
+                        inner.push(&value);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        ReflectMut::Array(inner) => {
+            let element_type = ty.and_then(Type::as_array).map(|ty| ty.element_type());
+            for (index, value) in inner.iter_mut().enumerate() {
+                let label = format!("[{index}]");
+                changed |= field_ui(ui, &label, index, value, element_type, hints);
+            }
+        }
+        ReflectMut::Map(inner) => {
+            let map_type = ty.and_then(Type::as_map);
+            let value_type = map_type.map(|ty| ty.value_type());
+
+            let mut remove = None;
+            for (key, value) in inner.iter_mut() {
+                ui.horizontal(|ui| {
+                    if ui.small_button("-").clicked() {
+                        remove = Some(key.to_value());
+                    }
+                    let label = format!("{key:?}");
+                    changed |= field_ui(ui, &label, &label, value, value_type, hints);
+                });
+            }
+            if let Some(key) = remove {
+                inner.remove(&key);
+                changed = true;
+            }
+
+            // the key of the next entry to add is kept in egui's memory while it's being edited
+            if let Some(map_type) = map_type {
+                let id = ui.id().with("new_key");
+                let key = ui
+                    .data(|data| data.get_temp::<Value>(id))
+                    .or_else(|| map_type.key_type().default_value());
+                if let Some(mut key) = key {
+                    ui.horizontal(|ui| {
+                        let exists = inner.get(&key).is_some();
+                        if ui.add_enabled(!exists, Button::new("+").small()).clicked() {
+                            if let Some(value) = map_type.value_type().default_value() {
+                                inner.insert(&key, &value);
+                                changed = true;
+                            }
+                        }
+                        value_ui(ui, &mut key, Some(map_type.key_type()), &Hints::default());
+                    });
+                    ui.data_mut(|data| data.insert_temp(id, key));
+                }
+            }
+        }
+        ReflectMut::Scalar(inner) => changed = scalar_ui(ui, inner, hints),
+        ReflectMut::Opaque(inner) => match inner.reflect_display() {
+            Some(display) => {
+                ui.label(display.to_string());
+            }
+            None => {
+                ui.label(format!("{inner:?}"));
+            }
+        },
+    }
+
+    changed
+}
+
+/// Render a field with a label. Compound values get a collapsing header, others are shown on a
+/// single line.
+fn field_ui(
+    ui: &mut Ui,
+    label: &str,
+    id: impl Hash,
+    value: &mut dyn Reflect,
+    ty: Option<Type<'_>>,
+    hints: &Hints,
+) -> bool {
+    ui.push_id(id, |ui| {
+        if matches!(
+            value.reflect_ref(),
+            ReflectRef::Scalar(_) | ReflectRef::Opaque(_)
+        ) {
+            ui.horizontal(|ui| {
+                ui.label(label);
+                value_ui(ui, value, ty, hints)
+            })
+            .inner
+        } else {
+            let mut changed = false;
+            let header = match value.reflect_display() {
+                Some(display) => format!("{label}: {display}"),
+                None => label.to_owned(),
+            };
+            CollapsingHeader::new(header)
+                .id_source(label)
+                .show(ui, |ui| {
+                    changed = value_ui(ui, value, ty, hints);
+                });
+            changed
+        }
+    })
+    .inner
+}
+
+fn enum_ui(ui: &mut Ui, value: &mut dyn Reflect, enum_type: Option<EnumType<'_>>) -> bool {
+    let mut changed = false;
+
+    let Some(current) = value.as_enum().map(|inner| inner.variant_name().to_owned()) else { return false };
+
+    match enum_type {
+        Some(enum_type) => {
+            let mut selected = None;
+            ComboBox::from_id_source("variant")
+                .selected_text(&current)
+                .show_ui(ui, |ui| {
+                    for variant in enum_type.variants() {
+                        let is_current = variant.name() == current;
+                        if ui.selectable_label(is_current, variant.name()).clicked() && !is_current
+                        {
+                            selected = Some(variant);
+                        }
+                    }
+                });
+            if let Some(new) = selected.and_then(|variant| variant.default_value()) {
+                value.patch(&new);
+                changed = true;
+            }
+        }
+        None => {
+            ui.label(&current);
+        }
+    }
+
+    let Some(inner) = value.as_enum_mut() else { return changed };
+    let variant = enum_type.and_then(|ty| ty.variant(inner.variant_name()));
+    for (index, field) in inner.fields_mut().enumerate() {
+        match field {
+            VariantFieldMut::Struct(name, value) => {
+                let field = variant.and_then(|variant| variant.field_type(name));
+                let hints = field.map(Hints::new).unwrap_or_default();
+                changed |= field_ui(ui, name, name, value, field.map(|f| f.get_type()), &hints);
+            }
+            VariantFieldMut::Tuple(value) => {
+                let field = variant.and_then(|variant| variant.field_type_at(index));
+                let hints = field.map(Hints::new).unwrap_or_default();
+                let label = index.to_string();
+                changed |= field_ui(
+                    ui,
+                    &label,
+                    index,
+                    value,
+                    field.map(|f| f.get_type()),
+                    &hints,
+                );
+            }
+        }
+    }
+
+    changed
+}
+
+fn scalar_ui(ui: &mut Ui, scalar: ScalarMut<'_>, hints: &Hints) -> bool {
+    match scalar {
+        ScalarMut::usize(n) => drag_ui(ui, n, hints),
+        ScalarMut::u8(n) => drag_ui(ui, n, hints),
+        ScalarMut::u16(n) => drag_ui(ui, n, hints),
+        ScalarMut::u32(n) => drag_ui(ui, n, hints),
+        ScalarMut::u64(n) => drag_ui(ui, n, hints),
+        ScalarMut::i8(n) => drag_ui(ui, n, hints),
+        ScalarMut::i16(n) => drag_ui(ui, n, hints),
+        ScalarMut::i32(n) => drag_ui(ui, n, hints),
+        ScalarMut::i64(n) => drag_ui(ui, n, hints),
+        ScalarMut::f32(n) => drag_ui(ui, n, hints),
+        ScalarMut::f64(n) => drag_ui(ui, n, hints),
+        // `DragValue` goes through `f64` which can't represent all 128 bit integers
+        ScalarMut::u128(n) => parse_ui(ui, n),
+        ScalarMut::i128(n) => parse_ui(ui, n),
+        ScalarMut::bool(b) => ui.checkbox(b, "").changed(),
+        ScalarMut::char(c) => {
+            let mut text = c.to_string();
+            if !ui.text_edit_singleline(&mut text).changed() {
+                return false;
+            }
+            match text.chars().last() {
+                Some(new) if new != *c => {
+                    *c = new;
+                    true
+                }
+                _ => false,
+            }
+        }
+        ScalarMut::String(s) => ui.text_edit_singleline(s).changed(),
+    }
+}
+
+fn drag_ui<N>(ui: &mut Ui, n: &mut N, hints: &Hints) -> bool
+where
+    N: Numeric,
+{
+    let before = n.to_f64();
+    let mut drag = DragValue::new(n);
+    if let Some(range) = hints.range {
+        let end = if range.end_inclusive || !N::INTEGRAL {
+            range.end
+        } else {
+            range.end - 1.0
+        };
+        drag = drag.clamp_range(range.start..=end);
+    }
+    if let Some(step) = hints.step {
+        drag = drag.speed(step);
+    }
+    let changed = ui.add(drag).changed();
+    // `DragValue` silently clamps values that start out of range
+    changed || n.to_f64().to_bits() != before.to_bits()
+}
+
+/// Edit a number as text. The number is only updated when the text parses.
+fn parse_ui<N>(ui: &mut Ui, n: &mut N) -> bool
+where
+    N: FromStr + ToString,
+{
+    let mut text: String = n.to_string();
+    if !ui.text_edit_singleline(&mut text).changed() {
+        return false;
+    }
+    match text.parse() {
+        Ok(new) => {
+            *n = new;
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::ToOwned;
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::Typed;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Player {
+        name: String,
+        #[reflect(meta(range = 0..100, step = 0.5))]
+        health: u8,
+        #[reflect(meta(range = [-1.0, 1.0]))]
+        position: [f32; 2],
+        inventory: Vec<Item>,
+        stats: BTreeMap<String, i128>,
+        state: State,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Item(String, u32);
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum State {
+        Idle,
+        Walking { speed: f32 },
+        Dead(char),
+    }
+
+    fn player() -> Player {
+        Player {
+            name: "Alice".to_owned(),
+            health: 10,
+            position: [0.5, -0.5],
+            inventory: Vec::from([Item("sword".to_owned(), 1)]),
+            stats: BTreeMap::from([("strength".to_owned(), 3)]),
+            state: State::Walking { speed: 1.0 },
+        }
+    }
+
+    fn run(mut f: impl FnMut(&mut Ui)) {
+        let ctx = ::egui::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| {
+            ::egui::CentralPanel::default().show(ctx, |ui| f(ui));
+        });
+    }
+
+    #[test]
+    fn renders_without_changes() {
+        let mut player = player();
+        run(|ui| assert!(!inspect(ui, &mut player)));
+        assert_eq!(player, self::player());
+
+        let mut value = player.to_value();
+        let type_info = <Player as Typed>::type_info();
+        run(|ui| assert!(!inspect_with_type(ui, &mut value, type_info.get_type())));
+        assert_eq!(value, player.to_value());
+    }
+
+    #[test]
+    fn half_open_ranges_exclude_the_end() {
+        let mut player = player();
+        player.health = 100;
+        run(|ui| assert!(inspect(ui, &mut player)));
+        assert_eq!(player.health, 99);
+        run(|ui| assert!(!inspect(ui, &mut player)));
+    }
+
+    #[test]
+    fn reads_hints_from_metadata() {
+        let type_info = <Player as Typed>::type_info();
+        let struct_ = type_info.as_struct().unwrap();
+
+        assert_eq!(
+            Hints::new(struct_.field_type("health").unwrap()),
+            Hints {
+                range: Some(NumberRange {
+                    start: 0.0,
+                    end: 100.0,
+                    end_inclusive: false,
+                }),
+                step: Some(0.5),
+            }
+        );
+        assert_eq!(
+            Hints::new(struct_.field_type("position").unwrap()),
+            Hints {
+                range: Some(NumberRange {
+                    start: -1.0,
+                    end: 1.0,
+                    end_inclusive: true,
+                }),
+                step: None,
+            }
+        );
+        assert_eq!(
+            Hints::new(struct_.field_type("name").unwrap()),
+            Hints::default()
+        );
+    }
+}
@@ -8,8 +8,11 @@ use crate::iter::PairIterMut;
 use crate::iter::ValueIterMut;
 use crate::struct_::StructValue;
 use crate::tuple::TupleValue;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::OpaqueNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::Reflect;
@@ -70,7 +73,7 @@ pub struct EnumValue {
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 #[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-enum EnumValueKind {
+pub(crate) enum EnumValueKind {
     Struct(StructValue),
     Tuple(TupleValue),
     Unit,
@@ -137,6 +140,32 @@ impl EnumValue {
             EnumValueKind::Unit => panic!("Cannot set fields on unit variants"),
         }
     }
+
+    pub(crate) fn kind(&self) -> &EnumValueKind {
+        &self.kind
+    }
+
+    pub(crate) fn kind_mut(&mut self) -> &mut EnumValueKind {
+        &mut self.kind
+    }
+
+    #[cfg(any(feature = "serde", feature = "speedy"))]
+    pub(crate) fn from_parts(name: String, kind: EnumValueKind) -> Self {
+        Self { name, kind }
+    }
+
+    /// Clone the enum, creating the values of the fields with `f`.
+    pub(crate) fn map_values(&self, f: impl FnMut(&Value) -> Value) -> Self {
+        let kind = match &self.kind {
+            EnumValueKind::Struct(fields) => EnumValueKind::Struct(fields.map_values(f)),
+            EnumValueKind::Tuple(fields) => EnumValueKind::Tuple(fields.map_values(f)),
+            EnumValueKind::Unit => EnumValueKind::Unit,
+        };
+        Self {
+            name: self.name.clone(),
+            kind,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -181,6 +210,7 @@ impl TupleVariantBuilder {
 
 impl Reflect for EnumValue {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl Typed for EnumValue {
             fn build(graph: &mut TypeGraph) -> NodeId {
                 graph.get_or_build_node_with::<Self, _>(|graph| {
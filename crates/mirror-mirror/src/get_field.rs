@@ -1,5 +1,7 @@
 use alloc::borrow::ToOwned;
 use alloc::string::String;
+use core::any::type_name;
+use core::fmt;
 
 use crate::Array;
 use crate::Enum;
@@ -16,12 +18,36 @@ pub trait GetField<'a, K, M> {
     fn get_field<T>(self, key: K) -> Option<&'a T>
     where
         T: Reflect;
+
+    /// Like [`GetField::get_field`] but with an error that says why the field couldn't be
+    /// accessed.
+    ///
+    /// The default implementation can't tell why, so it returns [`FieldError::Unavailable`].
+    fn try_get_field<T>(self, key: K) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+        Self: Sized,
+    {
+        self.get_field(key).ok_or(FieldError::Unavailable)
+    }
 }
 
 pub trait GetFieldMut<'a, K, M> {
     fn get_field_mut<T>(self, key: K) -> Option<&'a mut T>
     where
         T: Reflect;
+
+    /// Like [`GetFieldMut::get_field_mut`] but with an error that says why the field couldn't be
+    /// accessed.
+    ///
+    /// The default implementation can't tell why, so it returns [`FieldError::Unavailable`].
+    fn try_get_field_mut<T>(self, key: K) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+        Self: Sized,
+    {
+        self.get_field_mut(key).ok_or(FieldError::Unavailable)
+    }
 }
 
 impl<'a, R, K, M> GetField<'a, K, M> for &'a mut R
@@ -35,42 +61,63 @@ where
     {
         <&R as GetField<_, _>>::get_field(self, key)
     }
+
+    fn try_get_field<T>(self, key: K) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        <&R as GetField<_, _>>::try_get_field(self, key)
+    }
 }
 
 impl<'a> GetField<'a, &str, private::Value> for &'a Value {
     fn get_field<T>(self, key: &str) -> Option<&'a T>
+    where
+        T: Reflect,
+    {
+        <Self as GetField<'a, &str, private::Value>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: &str) -> Result<&'a T, FieldError>
     where
         T: Reflect,
     {
         match self.reflect_ref() {
-            ReflectRef::Struct(inner) => inner.get_field(key),
-            ReflectRef::Enum(inner) => inner.get_field(key),
-            ReflectRef::Map(inner) => inner.get_field(key),
+            ReflectRef::Struct(inner) => inner.try_get_field(key),
+            ReflectRef::Enum(inner) => inner.try_get_field(key),
+            ReflectRef::Map(inner) => inner.try_get_field(key),
             ReflectRef::TupleStruct(_)
             | ReflectRef::Tuple(_)
             | ReflectRef::List(_)
             | ReflectRef::Array(_)
             | ReflectRef::Opaque(_)
-            | ReflectRef::Scalar(_) => None,
+            | ReflectRef::Scalar(_) => Err(FieldError::missing(key)),
         }
     }
 }
 
 impl<'a> GetFieldMut<'a, &str, private::Value> for &'a mut Value {
     fn get_field_mut<T>(self, key: &str) -> Option<&'a mut T>
+    where
+        T: Reflect,
+    {
+        <Self as GetFieldMut<'a, &str, private::Value>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: &str) -> Result<&'a mut T, FieldError>
     where
         T: Reflect,
     {
         match self.reflect_mut() {
-            ReflectMut::Struct(inner) => inner.get_field_mut(key),
-            ReflectMut::Enum(inner) => inner.get_field_mut(key),
-            ReflectMut::Map(inner) => inner.get_field_mut(key),
+            ReflectMut::Struct(inner) => inner.try_get_field_mut(key),
+            ReflectMut::Enum(inner) => inner.try_get_field_mut(key),
+            ReflectMut::Map(inner) => inner.try_get_field_mut(key),
             ReflectMut::TupleStruct(_)
             | ReflectMut::Tuple(_)
             | ReflectMut::List(_)
             | ReflectMut::Array(_)
             | ReflectMut::Opaque(_)
-            | ReflectMut::Scalar(_) => None,
+            | ReflectMut::Scalar(_) => Err(FieldError::missing(key)),
         }
     }
 }
@@ -80,34 +127,43 @@ where
     K: Reflect,
 {
     fn get_field<T>(self, key: K) -> Option<&'a T>
+    where
+        T: Reflect,
+    {
+        <Self as GetField<'a, K, private::Value>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: K) -> Result<&'a T, FieldError>
     where
         T: Reflect,
     {
         if let Some(&key) = key.as_any().downcast_ref::<usize>() {
             match self.reflect_ref() {
-                ReflectRef::TupleStruct(inner) => inner.get_field(key),
-                ReflectRef::Tuple(inner) => inner.get_field(key),
-                ReflectRef::Enum(inner) => inner.get_field(key),
-                ReflectRef::Array(inner) => inner.get_field(key),
-                ReflectRef::List(inner) => inner.get_field(key),
-                ReflectRef::Map(inner) => inner.get_field(key),
-                ReflectRef::Struct(_) | ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => None,
+                ReflectRef::TupleStruct(inner) => inner.try_get_field(key),
+                ReflectRef::Tuple(inner) => inner.try_get_field(key),
+                ReflectRef::Enum(inner) => inner.try_get_field(key),
+                ReflectRef::Array(inner) => inner.try_get_field(key),
+                ReflectRef::List(inner) => inner.try_get_field(key),
+                ReflectRef::Map(inner) => inner.try_get_field(key),
+                ReflectRef::Struct(_) | ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => {
+                    Err(FieldError::missing(key))
+                }
             }
         } else if let Some(key) = key.as_any().downcast_ref::<String>() {
             match self.reflect_ref() {
-                ReflectRef::Map(inner) => inner.get_field(key.to_owned()),
-                ReflectRef::Struct(inner) => inner.get_field(key),
+                ReflectRef::Map(inner) => inner.try_get_field(key.to_owned()),
+                ReflectRef::Struct(inner) => inner.try_get_field(key),
                 ReflectRef::TupleStruct(_)
                 | ReflectRef::Tuple(_)
                 | ReflectRef::Enum(_)
                 | ReflectRef::List(_)
                 | ReflectRef::Array(_)
                 | ReflectRef::Opaque(_)
-                | ReflectRef::Scalar(_) => None,
+                | ReflectRef::Scalar(_) => Err(FieldError::missing(key.as_str())),
             }
         } else {
             match self.reflect_ref() {
-                ReflectRef::Map(inner) => inner.get_field(key),
+                ReflectRef::Map(inner) => inner.try_get_field(key),
                 ReflectRef::TupleStruct(_)
                 | ReflectRef::Tuple(_)
                 | ReflectRef::Enum(_)
@@ -115,7 +171,7 @@ where
                 | ReflectRef::List(_)
                 | ReflectRef::Struct(_)
                 | ReflectRef::Opaque(_)
-                | ReflectRef::Scalar(_) => None,
+                | ReflectRef::Scalar(_) => Err(FieldError::missing(key.as_reflect())),
             }
         }
     }
@@ -126,34 +182,43 @@ where
     K: Reflect,
 {
     fn get_field_mut<T>(self, key: K) -> Option<&'a mut T>
+    where
+        T: Reflect,
+    {
+        <Self as GetFieldMut<'a, K, private::Value>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: K) -> Result<&'a mut T, FieldError>
     where
         T: Reflect,
     {
         if let Some(&key) = key.as_any().downcast_ref::<usize>() {
             match self.reflect_mut() {
-                ReflectMut::TupleStruct(inner) => inner.get_field_mut(key),
-                ReflectMut::Tuple(inner) => inner.get_field_mut(key),
-                ReflectMut::Enum(inner) => inner.get_field_mut(key),
-                ReflectMut::List(inner) => inner.get_field_mut(key),
-                ReflectMut::Array(inner) => inner.get_field_mut(key),
-                ReflectMut::Map(inner) => inner.get_field_mut(key),
-                ReflectMut::Struct(_) | ReflectMut::Scalar(_) | ReflectMut::Opaque(_) => None,
+                ReflectMut::TupleStruct(inner) => inner.try_get_field_mut(key),
+                ReflectMut::Tuple(inner) => inner.try_get_field_mut(key),
+                ReflectMut::Enum(inner) => inner.try_get_field_mut(key),
+                ReflectMut::List(inner) => inner.try_get_field_mut(key),
+                ReflectMut::Array(inner) => inner.try_get_field_mut(key),
+                ReflectMut::Map(inner) => inner.try_get_field_mut(key),
+                ReflectMut::Struct(_) | ReflectMut::Scalar(_) | ReflectMut::Opaque(_) => {
+                    Err(FieldError::missing(key))
+                }
             }
         } else if let Some(key) = key.as_any().downcast_ref::<String>() {
             match self.reflect_mut() {
-                ReflectMut::Map(inner) => inner.get_field_mut(key.to_owned()),
-                ReflectMut::Struct(inner) => inner.get_field_mut(key),
+                ReflectMut::Map(inner) => inner.try_get_field_mut(key.to_owned()),
+                ReflectMut::Struct(inner) => inner.try_get_field_mut(key),
                 ReflectMut::TupleStruct(_)
                 | ReflectMut::Tuple(_)
                 | ReflectMut::Enum(_)
                 | ReflectMut::List(_)
                 | ReflectMut::Array(_)
                 | ReflectMut::Opaque(_)
-                | ReflectMut::Scalar(_) => None,
+                | ReflectMut::Scalar(_) => Err(FieldError::missing(key.as_str())),
             }
         } else {
             match self.reflect_mut() {
-                ReflectMut::Map(inner) => inner.get_field_mut(key),
+                ReflectMut::Map(inner) => inner.try_get_field_mut(key),
                 ReflectMut::TupleStruct(_)
                 | ReflectMut::Tuple(_)
                 | ReflectMut::Enum(_)
@@ -161,7 +226,7 @@ where
                 | ReflectMut::Array(_)
                 | ReflectMut::Struct(_)
                 | ReflectMut::Opaque(_)
-                | ReflectMut::Scalar(_) => None,
+                | ReflectMut::Scalar(_) => Err(FieldError::missing(key.as_reflect())),
             }
         }
     }
@@ -175,7 +240,15 @@ where
     where
         T: Reflect,
     {
-        self.field(key)?.downcast_ref()
+        <Self as GetField<'a, &str, private::Struct>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: &str) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self.field(key).ok_or_else(|| FieldError::missing(key))?;
+        downcast_ref(value, || key.into())
     }
 }
 
@@ -187,7 +260,17 @@ where
     where
         T: Reflect,
     {
-        self.field_mut(key)?.downcast_mut()
+        <Self as GetFieldMut<'a, &str, private::Struct>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: &str) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .field_mut(key)
+            .ok_or_else(|| FieldError::missing(key))?;
+        downcast_mut(value, || key.into())
     }
 }
 
@@ -199,7 +282,15 @@ where
     where
         T: Reflect,
     {
-        self.field_at(key)?.downcast_ref()
+        <Self as GetField<'a, usize, private::TupleStruct>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: usize) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self.field_at(key).ok_or_else(|| FieldError::missing(key))?;
+        downcast_ref(value, || key.into())
     }
 }
 
@@ -211,7 +302,17 @@ where
     where
         T: Reflect,
     {
-        self.field_at_mut(key)?.downcast_mut()
+        <Self as GetFieldMut<'a, usize, private::TupleStruct>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: usize) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .field_at_mut(key)
+            .ok_or_else(|| FieldError::missing(key))?;
+        downcast_mut(value, || key.into())
     }
 }
 
@@ -223,7 +324,17 @@ where
     where
         T: Reflect,
     {
-        self.field(key)?.downcast_ref()
+        <Self as GetField<'a, &str, private::Enum>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: &str) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .field(key)
+            .ok_or_else(|| FieldError::missing_in_variant(key, self.variant_name()))?;
+        downcast_ref(value, || key.into())
     }
 }
 
@@ -235,7 +346,20 @@ where
     where
         T: Reflect,
     {
-        self.field_mut(key)?.downcast_mut()
+        <Self as GetFieldMut<'a, &str, private::Enum>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: &str) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        if self.field(key).is_none() {
+            return Err(FieldError::missing_in_variant(key, self.variant_name()));
+        }
+        let value = self
+            .field_mut(key)
+            .ok_or_else(|| FieldError::missing(key))?;
+        downcast_mut(value, || key.into())
     }
 }
 
@@ -247,7 +371,17 @@ where
     where
         T: Reflect,
     {
-        self.field_at(key)?.downcast_ref()
+        <Self as GetField<'a, usize, private::Enum>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: usize) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .field_at(key)
+            .ok_or_else(|| FieldError::missing_in_variant(key, self.variant_name()))?;
+        downcast_ref(value, || key.into())
     }
 }
 
@@ -259,7 +393,20 @@ where
     where
         T: Reflect,
     {
-        self.field_at_mut(key)?.downcast_mut()
+        <Self as GetFieldMut<'a, usize, private::Enum>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: usize) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        if self.field_at(key).is_none() {
+            return Err(FieldError::missing_in_variant(key, self.variant_name()));
+        }
+        let value = self
+            .field_at_mut(key)
+            .ok_or_else(|| FieldError::missing(key))?;
+        downcast_mut(value, || key.into())
     }
 }
 
@@ -271,7 +418,15 @@ where
     where
         T: Reflect,
     {
-        self.field_at(key)?.downcast_ref()
+        <Self as GetField<'a, usize, private::Tuple>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: usize) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self.field_at(key).ok_or_else(|| FieldError::missing(key))?;
+        downcast_ref(value, || key.into())
     }
 }
 
@@ -283,7 +438,17 @@ where
     where
         T: Reflect,
     {
-        self.field_at_mut(key)?.downcast_mut()
+        <Self as GetFieldMut<'a, usize, private::Tuple>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: usize) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .field_at_mut(key)
+            .ok_or_else(|| FieldError::missing(key))?;
+        downcast_mut(value, || key.into())
     }
 }
 
@@ -296,7 +461,15 @@ where
     where
         T: Reflect,
     {
-        self.get(key)?.downcast_ref()
+        <Self as GetField<'a, usize, private::Array>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: usize) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self.get(key).ok_or_else(|| FieldError::missing(key))?;
+        downcast_ref(value, || key.into())
     }
 }
 
@@ -308,7 +481,15 @@ where
     where
         T: Reflect,
     {
-        self.get_mut(key)?.downcast_mut()
+        <Self as GetFieldMut<'a, usize, private::Array>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: usize) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self.get_mut(key).ok_or_else(|| FieldError::missing(key))?;
+        downcast_mut(value, || key.into())
     }
 }
 
@@ -321,7 +502,17 @@ where
     where
         T: Reflect,
     {
-        self.get(&key)?.downcast_ref()
+        <Self as GetField<'a, K, private::Map>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: K) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .get(&key)
+            .ok_or_else(|| FieldError::missing(key.as_reflect()))?;
+        downcast_ref(value, || key.as_reflect().into())
     }
 }
 
@@ -334,7 +525,17 @@ where
     where
         T: Reflect,
     {
-        self.get_mut(&key)?.downcast_mut()
+        <Self as GetFieldMut<'a, K, private::Map>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: K) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .get_mut(&key)
+            .ok_or_else(|| FieldError::missing(key.as_reflect()))?;
+        downcast_mut(value, || key.as_reflect().into())
     }
 }
 
@@ -346,7 +547,17 @@ where
     where
         T: Reflect,
     {
-        self.get(&key.to_owned())?.downcast_ref()
+        <Self as GetField<'a, &str, private::Map>>::try_get_field(self, key).ok()
+    }
+
+    fn try_get_field<T>(self, key: &str) -> Result<&'a T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .get_str(key)
+            .ok_or_else(|| FieldError::missing(Value::from(key)))?;
+        downcast_ref(value, || Value::from(key).into())
     }
 }
 
@@ -358,8 +569,154 @@ where
     where
         T: Reflect,
     {
-        self.get_mut(&key.to_owned())?.downcast_mut()
+        <Self as GetFieldMut<'a, &str, private::Map>>::try_get_field_mut(self, key).ok()
+    }
+
+    fn try_get_field_mut<T>(self, key: &str) -> Result<&'a mut T, FieldError>
+    where
+        T: Reflect,
+    {
+        let value = self
+            .get_str_mut(key)
+            .ok_or_else(|| FieldError::missing(Value::from(key)))?;
+        downcast_mut(value, || Value::from(key).into())
+    }
+}
+
+/// The error returned by [`GetField::try_get_field`] and [`GetFieldMut::try_get_field_mut`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    /// The value doesn't have the field.
+    Missing { field: FieldKey },
+    /// The active variant of an enum doesn't have the field.
+    MissingInVariant { field: FieldKey, variant: String },
+    /// The field exists but doesn't have the requested type.
+    WrongType {
+        field: FieldKey,
+        expected: &'static str,
+        actual: String,
+    },
+    /// The field couldn't be accessed, for an unknown reason. Returned by the default
+    /// implementations of [`GetField::try_get_field`] and [`GetFieldMut::try_get_field_mut`].
+    Unavailable,
+}
+
+impl FieldError {
+    fn missing(field: impl Into<FieldKey>) -> Self {
+        Self::Missing {
+            field: field.into(),
+        }
+    }
+
+    fn missing_in_variant(field: impl Into<FieldKey>, variant: &str) -> Self {
+        Self::MissingInVariant {
+            field: field.into(),
+            variant: variant.to_owned(),
+        }
+    }
+
+    /// The field that couldn't be accessed, if it's known.
+    pub fn field(&self) -> Option<&FieldKey> {
+        match self {
+            FieldError::Missing { field }
+            | FieldError::MissingInVariant { field, .. }
+            | FieldError::WrongType { field, .. } => Some(field),
+            FieldError::Unavailable => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::Missing { field } => write!(f, "no field `{field}`"),
+            FieldError::MissingInVariant { field, variant } => {
+                write!(f, "variant `{variant}` has no field `{field}`")
+            }
+            FieldError::WrongType {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field `{field}` has type `{actual}` but `{expected}` was expected"
+            ),
+            FieldError::Unavailable => write!(f, "field couldn't be accessed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldError {}
+
+/// The name, index, or map key of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKey {
+    Name(String),
+    Index(usize),
+    Key(Value),
+}
+
+impl fmt::Display for FieldKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldKey::Name(name) => write!(f, "{name}"),
+            FieldKey::Index(index) => write!(f, "{index}"),
+            FieldKey::Key(key) => write!(f, "{:?}", key.as_reflect()),
+        }
+    }
+}
+
+impl From<&str> for FieldKey {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_owned())
+    }
+}
+
+impl From<usize> for FieldKey {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl From<Value> for FieldKey {
+    fn from(key: Value) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<&dyn Reflect> for FieldKey {
+    fn from(key: &dyn Reflect) -> Self {
+        Self::Key(key.to_value())
+    }
+}
+
+fn downcast_ref<T>(value: &dyn Reflect, field: impl FnOnce() -> FieldKey) -> Result<&T, FieldError>
+where
+    T: Reflect,
+{
+    value.downcast_ref().ok_or_else(|| FieldError::WrongType {
+        field: field(),
+        expected: type_name::<T>(),
+        actual: value.type_name().to_owned(),
+    })
+}
+
+fn downcast_mut<T>(
+    value: &mut dyn Reflect,
+    field: impl FnOnce() -> FieldKey,
+) -> Result<&mut T, FieldError>
+where
+    T: Reflect,
+{
+    if !value.is::<T>() {
+        return Err(FieldError::WrongType {
+            field: field(),
+            expected: type_name::<T>(),
+            actual: value.type_name().to_owned(),
+        });
     }
+    Ok(value.downcast_mut().expect("type was checked above"))
 }
 
 mod private {
@@ -0,0 +1,42 @@
+use core::hash::Hasher;
+
+/// 64-bit FNV-1a.
+///
+/// Unlike `ahash` its output is fully specified, so it is the same on every platform and with
+/// every version of our dependencies, and it doesn't need a source of randomness.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(all(feature = "ahash", not(feature = "type_info")), allow(dead_code))]
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(Self::PRIME)
+        });
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The hasher used for [`Reflect::reflect_hash`](crate::Reflect::reflect_hash).
+///
+/// `ahash` is faster but can be disabled, with the `ahash` feature, for targets it doesn't
+/// support.
+#[cfg(feature = "ahash")]
+pub(crate) type DefaultHasher = ahash::AHasher;
+
+#[cfg(not(feature = "ahash"))]
+pub(crate) type DefaultHasher = StableHasher;
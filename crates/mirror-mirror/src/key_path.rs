@@ -1,10 +1,21 @@
 use alloc::borrow::ToOwned;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::any::Any;
+use core::any::TypeId;
 use core::fmt;
 use core::iter::Peekable;
+use core::marker::PhantomData;
 
+#[cfg(feature = "type_info")]
+use crate::convert::convert_to_value;
+#[cfg(feature = "type_info")]
+use crate::convert::ConvertError;
+use crate::enum_::EnumValue;
+use crate::enum_::VariantField;
 use crate::enum_::VariantKind;
+use crate::struct_::StructValue;
+#[cfg(feature = "type_info")]
 use crate::type_info::TypeAtPath;
 use crate::Reflect;
 use crate::ReflectMut;
@@ -29,8 +40,40 @@ pub trait GetPath {
     {
         self.at_mut(key_path)?.downcast_mut()
     }
+
+    /// Assign `value` to the value at `key_path`.
+    ///
+    /// `value` is first converted into the type found at `key_path`, using the same rules as
+    /// [`convert`](crate::convert::convert), so for example a `u32` can be set from any integer
+    /// that fits.
+    ///
+    /// Unlike [`Reflect::patch`], lists and maps are replaced entirely rather than only updating
+    /// the elements that already exist.
+    ///
+    /// Without the `type_info` feature `value` isn't converted, so it must already have the shape
+    /// of the value at `key_path`.
+    fn set_at(&mut self, key_path: &KeyPath, value: impl Into<Value>) -> Result<(), SetError> {
+        let target = self.at_mut(key_path).ok_or_else(|| SetError::NotFound {
+            path: key_path.clone(),
+        })?;
+        #[cfg(feature = "type_info")]
+        let value = {
+            let type_info = target.type_info();
+            convert_to_value(&value.into(), type_info.get_type()).map_err(|error| {
+                SetError::Convert {
+                    path: key_path.clone(),
+                    error,
+                }
+            })?
+        };
+        #[cfg(not(feature = "type_info"))]
+        let value = value.into();
+        assign(target, &value);
+        Ok(())
+    }
 }
 
+#[cfg(feature = "type_info")]
 pub trait GetTypePath<'a> {
     fn type_at(self, key_path: &KeyPath) -> Option<TypeAtPath<'a>>;
 }
@@ -40,181 +83,199 @@ where
     R: Reflect + ?Sized,
 {
     fn at(&self, key_path: &KeyPath) -> Option<&dyn Reflect> {
-        fn go<'a, 'b, R>(
-            value: &'a R,
-            mut stack: Peekable<impl Iterator<Item = &'b Key>>,
-        ) -> Option<&'a dyn Reflect>
-        where
-            R: Reflect + ?Sized,
-        {
-            let head = stack.next()?;
-
-            let value_at_key = match head {
-                // .foo
-                Key::Field(private::KeyOrIndex::Key(key)) => match value.reflect_ref() {
-                    ReflectRef::Struct(inner) => inner.field(key)?,
-                    ReflectRef::Enum(inner) => match inner.variant_kind() {
-                        VariantKind::Struct => inner.field(key)?,
-                        VariantKind::Tuple | VariantKind::Unit => return None,
-                    },
-                    ReflectRef::TupleStruct(_)
-                    | ReflectRef::Tuple(_)
-                    | ReflectRef::Array(_)
-                    | ReflectRef::List(_)
-                    | ReflectRef::Map(_)
-                    | ReflectRef::Scalar(_)
-                    | ReflectRef::Opaque(_) => return None,
-                },
-                // .0
-                Key::Field(private::KeyOrIndex::Index(index)) => match value.reflect_ref() {
-                    ReflectRef::TupleStruct(inner) => inner.field_at(*index)?,
-                    ReflectRef::Tuple(inner) => inner.field_at(*index)?,
-                    ReflectRef::Enum(inner) => match inner.variant_kind() {
-                        VariantKind::Tuple => inner.field_at(*index)?,
-                        VariantKind::Struct | VariantKind::Unit => return None,
-                    },
-                    ReflectRef::Map(_)
-                    | ReflectRef::Struct(_)
-                    | ReflectRef::Array(_)
-                    | ReflectRef::List(_)
-                    | ReflectRef::Scalar(_)
-                    | ReflectRef::Opaque(_) => return None,
-                },
-                // ["foo"] or [0]
-                Key::FieldAt(key) => match value.reflect_ref() {
-                    ReflectRef::Map(inner) => inner.get(key)?,
-                    ReflectRef::Array(inner) => inner.get(value_to_usize(key)?)?,
-                    ReflectRef::List(inner) => inner.get(value_to_usize(key)?)?,
-                    ReflectRef::Struct(_)
-                    | ReflectRef::TupleStruct(_)
-                    | ReflectRef::Tuple(_)
-                    | ReflectRef::Enum(_)
-                    | ReflectRef::Scalar(_)
-                    | ReflectRef::Opaque(_) => return None,
-                },
-                // ::Some
-                Key::Variant(variant) => match value.reflect_ref() {
-                    ReflectRef::Enum(enum_) => {
-                        if enum_.variant_name() == variant {
-                            enum_.as_reflect()
-                        } else {
-                            return None;
-                        }
-                    }
-                    ReflectRef::Struct(_)
-                    | ReflectRef::TupleStruct(_)
-                    | ReflectRef::Tuple(_)
-                    | ReflectRef::List(_)
-                    | ReflectRef::Array(_)
-                    | ReflectRef::Map(_)
-                    | ReflectRef::Opaque(_)
-                    | ReflectRef::Scalar(_) => return None,
-                },
-            };
-
-            if stack.peek().is_none() {
-                Some(value_at_key)
-            } else {
-                go(value_at_key, stack)
-            }
-        }
-
         if key_path.is_empty() {
             return Some(self.as_reflect());
         }
 
-        go(self, key_path.path.iter().peekable())
+        value_at(self, key_path.path.iter().map(|key| (key, None)).peekable())
     }
 
     fn at_mut(&mut self, key_path: &KeyPath) -> Option<&mut dyn Reflect> {
-        fn go<'a, 'b, R>(
-            value: &'a mut R,
-            mut stack: Peekable<impl Iterator<Item = &'b Key>>,
-        ) -> Option<&'a mut dyn Reflect>
-        where
-            R: Reflect + ?Sized,
-        {
-            let head = stack.next()?;
-
-            let value_at_key = match head {
-                // .foo
-                Key::Field(private::KeyOrIndex::Key(key)) => match value.reflect_mut() {
-                    ReflectMut::Struct(inner) => inner.field_mut(key)?,
-                    ReflectMut::Enum(inner) => match inner.variant_kind() {
-                        VariantKind::Struct => inner.field_mut(key)?,
-                        VariantKind::Tuple | VariantKind::Unit => return None,
-                    },
-                    ReflectMut::TupleStruct(_)
-                    | ReflectMut::Tuple(_)
-                    | ReflectMut::Array(_)
-                    | ReflectMut::List(_)
-                    | ReflectMut::Map(_)
-                    | ReflectMut::Scalar(_)
-                    | ReflectMut::Opaque(_) => return None,
-                },
-                // .0
-                Key::Field(private::KeyOrIndex::Index(index)) => match value.reflect_mut() {
-                    ReflectMut::TupleStruct(inner) => inner.field_at_mut(*index)?,
-                    ReflectMut::Tuple(inner) => inner.field_at_mut(*index)?,
-                    ReflectMut::Enum(inner) => match inner.variant_kind() {
-                        VariantKind::Tuple => inner.field_at_mut(*index)?,
-                        VariantKind::Struct | VariantKind::Unit => return None,
-                    },
-                    ReflectMut::Map(_)
-                    | ReflectMut::Struct(_)
-                    | ReflectMut::Array(_)
-                    | ReflectMut::List(_)
-                    | ReflectMut::Scalar(_)
-                    | ReflectMut::Opaque(_) => return None,
-                },
-                // ["foo"] or [0]
-                Key::FieldAt(key) => match value.reflect_mut() {
-                    ReflectMut::Array(inner) => inner.get_mut(value_to_usize(key)?)?,
-                    ReflectMut::List(inner) => inner.get_mut(value_to_usize(key)?)?,
-                    ReflectMut::Map(inner) => inner.get_mut(key)?,
-                    ReflectMut::Struct(_)
-                    | ReflectMut::TupleStruct(_)
-                    | ReflectMut::Tuple(_)
-                    | ReflectMut::Enum(_)
-                    | ReflectMut::Scalar(_)
-                    | ReflectMut::Opaque(_) => return None,
+        if key_path.is_empty() {
+            return Some(self.as_reflect_mut());
+        }
+
+        value_at_mut(self, key_path.path.iter().map(|key| (key, None)).peekable())
+    }
+}
+
+/// Get the value at the keys in `stack`.
+///
+/// Each key comes with the index of the field it names, if that's known, which avoids looking up
+/// the field by name. The index is only used if the value has the type it was resolved in.
+fn value_at<'a, 'b, R>(
+    value: &'a R,
+    mut stack: Peekable<impl Iterator<Item = (&'b Key, Option<ResolvedField>)>>,
+) -> Option<&'a dyn Reflect>
+where
+    R: Reflect + ?Sized,
+{
+    let (head, field_index) = stack.next()?;
+
+    let value_at_key = match head {
+        // .foo
+        Key::Field(private::KeyOrIndex::Key(key)) => match value.reflect_ref() {
+            ReflectRef::Struct(inner) => match field_index {
+                Some(field) if field.is_in(inner.as_any()) => inner.field_at(field.index)?,
+                _ => inner.field(key)?,
+            },
+            ReflectRef::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Struct => match field_index {
+                    Some(field) if field.is_in(inner.as_any()) => inner.field_at(field.index)?,
+                    _ => inner.field(key)?,
                 },
-                // ::Some
-                Key::Variant(variant) => match value.reflect_mut() {
-                    ReflectMut::Enum(enum_) => {
-                        if enum_.variant_name() == variant {
-                            enum_.as_reflect_mut()
-                        } else {
-                            return None;
-                        }
+                VariantKind::Tuple | VariantKind::Unit => return None,
+            },
+            ReflectRef::TupleStruct(_)
+            | ReflectRef::Tuple(_)
+            | ReflectRef::Array(_)
+            | ReflectRef::List(_)
+            | ReflectRef::Map(_)
+            | ReflectRef::Scalar(_)
+            | ReflectRef::Opaque(_) => return None,
+        },
+        // .0
+        Key::Field(private::KeyOrIndex::Index(index)) => match value.reflect_ref() {
+            ReflectRef::TupleStruct(inner) => inner.field_at(*index)?,
+            ReflectRef::Tuple(inner) => inner.field_at(*index)?,
+            ReflectRef::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Tuple => inner.field_at(*index)?,
+                VariantKind::Struct | VariantKind::Unit => return None,
+            },
+            ReflectRef::Map(_)
+            | ReflectRef::Struct(_)
+            | ReflectRef::Array(_)
+            | ReflectRef::List(_)
+            | ReflectRef::Scalar(_)
+            | ReflectRef::Opaque(_) => return None,
+        },
+        // ["foo"], [0], or [-1]
+        Key::FieldAt(key) => match value.reflect_ref() {
+            ReflectRef::Map(inner) => inner.get(key)?,
+            ReflectRef::Array(inner) => inner.get(value_to_index(key, inner.len())?)?,
+            ReflectRef::List(inner) => inner.get(value_to_index(key, inner.len())?)?,
+            ReflectRef::Struct(_)
+            | ReflectRef::TupleStruct(_)
+            | ReflectRef::Tuple(_)
+            | ReflectRef::Enum(_)
+            | ReflectRef::Scalar(_)
+            | ReflectRef::Opaque(_) => return None,
+        },
+        // ::Some
+        Key::Variant(variant) => match value.reflect_ref() {
+            ReflectRef::Enum(enum_) => {
+                if enum_.variant_name() == variant {
+                    enum_.as_reflect()
+                } else {
+                    return None;
+                }
+            }
+            ReflectRef::Struct(_)
+            | ReflectRef::TupleStruct(_)
+            | ReflectRef::Tuple(_)
+            | ReflectRef::List(_)
+            | ReflectRef::Array(_)
+            | ReflectRef::Map(_)
+            | ReflectRef::Opaque(_)
+            | ReflectRef::Scalar(_) => return None,
+        },
+    };
+
+    if stack.peek().is_none() {
+        Some(value_at_key)
+    } else {
+        value_at(value_at_key, stack)
+    }
+}
+
+fn value_at_mut<'a, 'b, R>(
+    value: &'a mut R,
+    mut stack: Peekable<impl Iterator<Item = (&'b Key, Option<ResolvedField>)>>,
+) -> Option<&'a mut dyn Reflect>
+where
+    R: Reflect + ?Sized,
+{
+    let (head, field_index) = stack.next()?;
+
+    let value_at_key = match head {
+        // .foo
+        Key::Field(private::KeyOrIndex::Key(key)) => match value.reflect_mut() {
+            ReflectMut::Struct(inner) => match field_index {
+                Some(field) if field.is_in(inner.as_any()) => inner.field_at_mut(field.index)?,
+                _ => inner.field_mut(key)?,
+            },
+            ReflectMut::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Struct => match field_index {
+                    Some(field) if field.is_in(inner.as_any()) => {
+                        inner.field_at_mut(field.index)?
                     }
-                    ReflectMut::Struct(_)
-                    | ReflectMut::TupleStruct(_)
-                    | ReflectMut::Tuple(_)
-                    | ReflectMut::List(_)
-                    | ReflectMut::Array(_)
-                    | ReflectMut::Map(_)
-                    | ReflectMut::Opaque(_)
-                    | ReflectMut::Scalar(_) => return None,
+                    _ => inner.field_mut(key)?,
                 },
-            };
-
-            if stack.peek().is_none() {
-                Some(value_at_key)
-            } else {
-                go(value_at_key, stack)
+                VariantKind::Tuple | VariantKind::Unit => return None,
+            },
+            ReflectMut::TupleStruct(_)
+            | ReflectMut::Tuple(_)
+            | ReflectMut::Array(_)
+            | ReflectMut::List(_)
+            | ReflectMut::Map(_)
+            | ReflectMut::Scalar(_)
+            | ReflectMut::Opaque(_) => return None,
+        },
+        // .0
+        Key::Field(private::KeyOrIndex::Index(index)) => match value.reflect_mut() {
+            ReflectMut::TupleStruct(inner) => inner.field_at_mut(*index)?,
+            ReflectMut::Tuple(inner) => inner.field_at_mut(*index)?,
+            ReflectMut::Enum(inner) => match inner.variant_kind() {
+                VariantKind::Tuple => inner.field_at_mut(*index)?,
+                VariantKind::Struct | VariantKind::Unit => return None,
+            },
+            ReflectMut::Map(_)
+            | ReflectMut::Struct(_)
+            | ReflectMut::Array(_)
+            | ReflectMut::List(_)
+            | ReflectMut::Scalar(_)
+            | ReflectMut::Opaque(_) => return None,
+        },
+        // ["foo"], [0], or [-1]
+        Key::FieldAt(key) => match value.reflect_mut() {
+            ReflectMut::Array(inner) => inner.get_mut(value_to_index(key, inner.len())?)?,
+            ReflectMut::List(inner) => inner.get_mut(value_to_index(key, inner.len())?)?,
+            ReflectMut::Map(inner) => inner.get_mut(key)?,
+            ReflectMut::Struct(_)
+            | ReflectMut::TupleStruct(_)
+            | ReflectMut::Tuple(_)
+            | ReflectMut::Enum(_)
+            | ReflectMut::Scalar(_)
+            | ReflectMut::Opaque(_) => return None,
+        },
+        // ::Some
+        Key::Variant(variant) => match value.reflect_mut() {
+            ReflectMut::Enum(enum_) => {
+                if enum_.variant_name() == variant {
+                    enum_.as_reflect_mut()
+                } else {
+                    return None;
+                }
             }
-        }
-
-        if key_path.is_empty() {
-            return Some(self.as_reflect_mut());
-        }
+            ReflectMut::Struct(_)
+            | ReflectMut::TupleStruct(_)
+            | ReflectMut::Tuple(_)
+            | ReflectMut::List(_)
+            | ReflectMut::Array(_)
+            | ReflectMut::Map(_)
+            | ReflectMut::Opaque(_)
+            | ReflectMut::Scalar(_) => return None,
+        },
+    };
 
-        go(self, key_path.path.iter().peekable())
+    if stack.peek().is_none() {
+        Some(value_at_key)
+    } else {
+        value_at_mut(value_at_key, stack)
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyPath {
@@ -240,6 +301,18 @@ impl KeyPath {
         self.path.push(Key::FieldAt(field.into()))
     }
 
+    /// Select the last element of a list or array. The same as `.get(-1)`.
+    ///
+    /// Negative indices count from the end of lists and arrays, so `[-1]` is the last element,
+    /// `[-2]` the one before it, and so on.
+    pub fn last(self) -> Self {
+        self.get(-1_i32)
+    }
+
+    pub fn push_last(&mut self) {
+        self.push_get(-1_i32);
+    }
+
     pub fn variant<S>(mut self, variant: S) -> Self
     where
         S: Into<String>,
@@ -266,6 +339,85 @@ impl KeyPath {
     pub fn pop(&mut self) {
         self.path.pop();
     }
+
+    /// Append the segments of `other`.
+    pub fn push(&mut self, other: &KeyPath) {
+        self.path.extend(other.path.iter().cloned());
+    }
+
+    /// Create a new key path with the segments of `other` appended.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::key_path;
+    ///
+    /// let volume = key_path!(.settings.audio.volume);
+    /// let enabled = volume.parent().unwrap().join(&key_path!(.enabled));
+    /// assert_eq!(enabled, key_path!(.settings.audio.enabled));
+    /// ```
+    pub fn join(&self, other: &KeyPath) -> KeyPath {
+        let mut path = self.clone();
+        path.push(other);
+        path
+    }
+
+    /// The key path without its last segment, or `None` if it's empty.
+    pub fn parent(&self) -> Option<KeyPath> {
+        let (_, parent) = self.path.split_last()?;
+        Some(KeyPath {
+            path: parent.to_vec(),
+        })
+    }
+
+    /// Whether the first segments of the key path are those of `prefix`.
+    pub fn starts_with(&self, prefix: &KeyPath) -> bool {
+        self.path.starts_with(&prefix.path)
+    }
+
+    /// Whether the last segments of the key path are those of `suffix`.
+    pub fn ends_with(&self, suffix: &KeyPath) -> bool {
+        self.path.ends_with(&suffix.path)
+    }
+
+    /// The key path relative to `prefix`, or `None` if it doesn't start with `prefix`.
+    pub fn strip_prefix(&self, prefix: &KeyPath) -> Option<KeyPath> {
+        let path = self.path.strip_prefix(&*prefix.path)?;
+        Some(KeyPath {
+            path: path.to_vec(),
+        })
+    }
+
+    /// Resolve the names of fields in the key path to indices, by looking them up in `root`.
+    ///
+    /// See [`ResolvedKeyPath`].
+    pub fn resolve(&self, root: &dyn Reflect) -> ResolvedKeyPath {
+        let mut value = Some(root);
+        let mut after_variant = false;
+        let field_indices = self
+            .path
+            .iter()
+            .map(|key| {
+                let current = value?;
+                let index = match key {
+                    Key::Field(private::KeyOrIndex::Key(name)) => {
+                        resolve_field(current, name, after_variant)
+                    }
+                    Key::Field(private::KeyOrIndex::Index(_))
+                    | Key::FieldAt(_)
+                    | Key::Variant(_) => None,
+                };
+                after_variant = matches!(key, Key::Variant(_));
+                value = value_at(current, core::iter::once((key, index)).peekable());
+                index
+            })
+            .collect();
+
+        ResolvedKeyPath {
+            key_path: self.clone(),
+            field_indices,
+        }
+    }
 }
 
 mod private {
@@ -276,7 +428,7 @@ mod private {
     impl Sealed for String {}
     impl Sealed for usize {}
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
     #[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
     #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[allow(unreachable_pub)]
@@ -286,7 +438,7 @@ mod private {
         Variant(String),
     }
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
     #[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
     #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum KeyOrIndex {
@@ -296,6 +448,7 @@ mod private {
 }
 
 pub(crate) use private::Key;
+#[cfg(feature = "type_info")]
 pub(crate) use private::KeyOrIndex;
 
 pub trait IntoKeyOrIndex: private::Sealed {
@@ -328,6 +481,10 @@ pub fn get(field: impl Into<Value>) -> KeyPath {
     KeyPath::default().get(field)
 }
 
+pub fn last() -> KeyPath {
+    KeyPath::default().last()
+}
+
 pub fn variant<S>(variant: S) -> KeyPath
 where
     S: Into<String>,
@@ -354,7 +511,7 @@ macro_rules! key_path {
     ) => {
         $crate::key_path!(
             @go:
-            $path.field(stringify!($field)),
+            $path.field($crate::__private::strip_raw_prefix(stringify!($field))),
             [$($tt)*],
         )
     };
@@ -393,7 +550,20 @@ macro_rules! key_path {
     ) => {
         $crate::key_path!(
             @go:
-            $path.variant(stringify!($variant)),
+            $path.variant($crate::__private::strip_raw_prefix(stringify!($variant))),
+            [$($tt)*],
+        )
+    };
+
+    // recursive case (variant)
+    (
+        @go:
+        $path:expr,
+        [ :: $variant:literal $($tt:tt)*],
+    ) => {
+        $crate::key_path!(
+            @go:
+            $path.variant($variant),
             [$($tt)*],
         )
     };
@@ -417,6 +587,436 @@ macro_rules! key_path {
     };
 }
 
+/// Create a [`TypedKeyPath`] whose path is checked against the fields of the root type at compile
+/// time.
+///
+/// The syntax is the same as [`key_path!`], after the root type and `=>`, except that enum variants
+/// aren't supported.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::typed_key_path;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Person {
+///     employer: Company,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Company {
+///     countries: Vec<Country>,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Country {
+///     name: String,
+/// }
+///
+/// let path = typed_key_path!(Person => .employer.countries[0].name);
+///
+/// let person = Person {
+///     employer: Company {
+///         countries: vec![Country {
+///             name: "Denmark".to_owned(),
+///         }],
+///     },
+/// };
+/// let name: &String = path.get(&person).unwrap();
+/// assert_eq!(name, "Denmark");
+/// ```
+///
+/// Paths that don't exist don't compile:
+///
+/// ```compile_fail
+/// use mirror_mirror::typed_key_path;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Person {
+///     name: String,
+/// }
+///
+/// let path = typed_key_path!(Person => .employer);
+/// ```
+///
+/// Neither do paths to fields that aren't reflected:
+///
+/// ```compile_fail
+/// use mirror_mirror::typed_key_path;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Person {
+///     name: String,
+///     #[reflect(skip)]
+///     cache: Vec<u8>,
+/// }
+///
+/// let path = typed_key_path!(Person => .cache);
+/// ```
+///
+/// Or keys of the wrong type:
+///
+/// ```compile_fail
+/// use std::collections::HashMap;
+///
+/// use mirror_mirror::typed_key_path;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Person {
+///     scores: HashMap<String, u32>,
+/// }
+///
+/// let path = typed_key_path!(Person => .scores[0]);
+/// ```
+#[macro_export]
+macro_rules! typed_key_path {
+    // base case
+    (
+        @witness:
+        $value:expr,
+        [],
+    ) => {
+        $value
+    };
+
+    // recursive case (field)
+    (
+        @witness:
+        $value:expr,
+        [ . $field:ident $($tt:tt)*],
+    ) => {
+        $crate::typed_key_path!(
+            @witness:
+            (*$crate::__private::reflected_fields(&$value)).$field,
+            [$($tt)*],
+        )
+    };
+
+    // recursive case (tuple field)
+    (
+        @witness:
+        $value:expr,
+        [ . $field:tt $($tt:tt)*],
+    ) => {
+        $crate::typed_key_path!(
+            @witness:
+            (*$crate::__private::reflected_fields(&$value)).$field,
+            [$($tt)*],
+        )
+    };
+
+    // recursive case (element)
+    (
+        @witness:
+        $value:expr,
+        [ [$field:expr] $($tt:tt)*],
+    ) => {
+        $crate::typed_key_path!(
+            @witness:
+            (*$crate::__private::element(&$value)),
+            [$($tt)*],
+        )
+    };
+
+    // on variants
+    (
+        @witness:
+        $value:expr,
+        [ :: $($tt:tt)*],
+    ) => {
+        compile_error!("`typed_key_path!` doesn't support enum variants")
+    };
+
+    // on invalid syntax
+    (
+        @witness:
+        $value:expr,
+        [$($tt:tt)*],
+    ) => {
+        compile_error!(concat!("Unexpected tokens ", stringify!($($tt)*)))
+    };
+
+    // base case (build)
+    (
+        @build:
+        $root:ty,
+        [$($done:tt)*],
+        [$($path:tt)*],
+        [],
+    ) => {
+        $crate::key_path::TypedKeyPath::<$root, _>::__new(
+            $crate::key_path!($($path)*),
+            |root: &$root| &$crate::typed_key_path!(@witness: (*root), [$($done)*],),
+        )
+    };
+
+    // recursive case (build, element)
+    //
+    // the key is checked against the container that the path so far leads to
+    (
+        @build:
+        $root:ty,
+        [$($done:tt)*],
+        [$($path:tt)*],
+        [ [$field:expr] $($tt:tt)*],
+    ) => {
+        $crate::typed_key_path!(
+            @build:
+            $root,
+            [$($done)* [$field]],
+            [$($path)* [$crate::__private::checked_key(
+                |root: &$root| &$crate::typed_key_path!(@witness: (*root), [$($done)*],),
+                $field,
+            )]],
+            [$($tt)*],
+        )
+    };
+
+    // recursive case (build)
+    (
+        @build:
+        $root:ty,
+        [$($done:tt)*],
+        [$($path:tt)*],
+        [$next:tt $($tt:tt)*],
+    ) => {
+        $crate::typed_key_path!(
+            @build:
+            $root,
+            [$($done)* $next],
+            [$($path)* $next],
+            [$($tt)*],
+        )
+    };
+
+    // entry point
+    ( $root:ty => $($tt:tt)* ) => {
+        $crate::typed_key_path!(@build: $root, [], [], [$($tt)*],)
+    };
+}
+
+/// A [`KeyPath`] that is known to lead from an `R` to a `T`.
+///
+/// Created with [`typed_key_path!`].
+pub struct TypedKeyPath<R, T> {
+    key_path: KeyPath,
+    _marker: PhantomData<fn(&R) -> &T>,
+}
+
+impl<R, T> TypedKeyPath<R, T>
+where
+    R: Reflect,
+    T: Reflect,
+{
+    // `_witness` is never called, it's only there so the compiler checks the path exists and
+    // infers `T`
+    #[doc(hidden)]
+    pub fn __new(key_path: KeyPath, _witness: fn(&R) -> &T) -> Self {
+        Self {
+            key_path,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn key_path(&self) -> &KeyPath {
+        &self.key_path
+    }
+
+    pub fn into_key_path(self) -> KeyPath {
+        self.key_path
+    }
+
+    /// Get the value at the path.
+    ///
+    /// Returns `None` if an index or key in the path doesn't exist in `root`. A [`Value`] reflects
+    /// as the type it contains, so paths leading to a `Value`, like the entries of an
+    /// [`OrderedMap`](crate::map::OrderedMap), always return `None`. Use [`GetPath::get_at`] with
+    /// the [`key_path`](Self::key_path) instead.
+    pub fn get<'a>(&self, root: &'a R) -> Option<&'a T> {
+        root.get_at(&self.key_path)
+    }
+
+    /// Get the value at the path mutably.
+    ///
+    /// Returns `None` if an index or key in the path doesn't exist in `root`.
+    pub fn get_mut<'a>(&self, root: &'a mut R) -> Option<&'a mut T> {
+        root.get_at_mut(&self.key_path)
+    }
+}
+
+impl<R, T> Clone for TypedKeyPath<R, T> {
+    fn clone(&self) -> Self {
+        Self {
+            key_path: self.key_path.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R, T> fmt::Debug for TypedKeyPath<R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedKeyPath").field(&self.key_path).finish()
+    }
+}
+
+impl<R, T> From<TypedKeyPath<R, T>> for KeyPath {
+    fn from(typed: TypedKeyPath<R, T>) -> Self {
+        typed.key_path
+    }
+}
+
+/// A [`KeyPath`] with the names of fields resolved to indices.
+///
+/// Getting a field by index avoids comparing its name to the names of the other fields, which adds
+/// up when the same path is evaluated over and over, for example every frame. Each index is
+/// stored with the type of the struct or enum it was resolved in and is only used on values of
+/// that type, so evaluating the path on a value of another type falls back to looking up fields by
+/// name.
+///
+/// Fields of enums are only resolved right after a variant in the path, such as `::Mesh.path`,
+/// since the index of a field depends on the variant. Fields of [`StructValue`] and
+/// [`EnumValue`] are never resolved since each value can have different fields.
+///
+/// Created with [`KeyPath::resolve`].
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::key_path;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Player {
+///     name: String,
+///     stats: Stats,
+/// }
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Stats {
+///     health: u32,
+///     mana: u32,
+/// }
+///
+/// let mut players = vec![
+///     Player {
+///         name: "Alice".to_owned(),
+///         stats: Stats { health: 10, mana: 5 },
+///     };
+///     3
+/// ];
+///
+/// let mana = key_path!(.stats.mana).resolve(&players[0]);
+///
+/// for player in &mut players {
+///     *mana.get_at_mut::<u32>(player).unwrap() += 1;
+/// }
+/// assert!(players.iter().all(|player| player.stats.mana == 6));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResolvedKeyPath {
+    key_path: KeyPath,
+    field_indices: Vec<Option<ResolvedField>>,
+}
+
+impl ResolvedKeyPath {
+    pub fn key_path(&self) -> &KeyPath {
+        &self.key_path
+    }
+
+    pub fn into_key_path(self) -> KeyPath {
+        self.key_path
+    }
+
+    /// Like [`GetPath::at`].
+    pub fn at<'a>(&self, root: &'a dyn Reflect) -> Option<&'a dyn Reflect> {
+        if self.key_path.is_empty() {
+            return Some(root);
+        }
+        value_at(root, self.keys().peekable())
+    }
+
+    /// Like [`GetPath::at_mut`].
+    pub fn at_mut<'a>(&self, root: &'a mut dyn Reflect) -> Option<&'a mut dyn Reflect> {
+        if self.key_path.is_empty() {
+            return Some(root);
+        }
+        value_at_mut(root, self.keys().peekable())
+    }
+
+    /// Like [`GetPath::get_at`].
+    pub fn get_at<'a, T>(&self, root: &'a dyn Reflect) -> Option<&'a T>
+    where
+        T: Reflect,
+    {
+        self.at(root)?.downcast_ref()
+    }
+
+    /// Like [`GetPath::get_at_mut`].
+    pub fn get_at_mut<'a, T>(&self, root: &'a mut dyn Reflect) -> Option<&'a mut T>
+    where
+        T: Reflect,
+    {
+        self.at_mut(root)?.downcast_mut()
+    }
+
+    fn keys(&self) -> impl Iterator<Item = (&Key, Option<ResolvedField>)> {
+        self.key_path
+            .path
+            .iter()
+            .zip(self.field_indices.iter().copied())
+    }
+}
+
+/// The index of a field, and the type of the struct or enum it was found in.
+///
+/// Every value of a type has its fields at the same indices, so checking the type is enough to
+/// know the index is right. That isn't true of [`StructValue`] and [`EnumValue`], whose fields
+/// depend on the value, so their fields aren't resolved. Neither are fields of enums, whose
+/// fields depend on the variant, unless the path checks the variant right before the field.
+#[derive(Debug, Clone, Copy)]
+struct ResolvedField {
+    index: usize,
+    type_id: TypeId,
+}
+
+impl ResolvedField {
+    fn is_in(&self, value: &dyn Any) -> bool {
+        value.type_id() == self.type_id
+    }
+}
+
+/// Resolve the field called `name`, if `value` is a struct or, `after_variant`, a struct variant.
+fn resolve_field(value: &dyn Reflect, name: &str, after_variant: bool) -> Option<ResolvedField> {
+    let (index, any) = match value.reflect_ref() {
+        ReflectRef::Struct(inner) => (
+            (0..inner.fields_len()).find(|index| inner.name_at(*index) == Some(name))?,
+            inner.as_any(),
+        ),
+        ReflectRef::Enum(inner) if after_variant => (
+            (0..inner.fields_len()).find(|index| inner.name_at(*index) == Some(name))?,
+            inner.as_any(),
+        ),
+        ReflectRef::Enum(_)
+        | ReflectRef::TupleStruct(_)
+        | ReflectRef::Tuple(_)
+        | ReflectRef::List(_)
+        | ReflectRef::Array(_)
+        | ReflectRef::Map(_)
+        | ReflectRef::Scalar(_)
+        | ReflectRef::Opaque(_) => return None,
+    };
+
+    let type_id = any.type_id();
+    if type_id == TypeId::of::<StructValue>() || type_id == TypeId::of::<EnumValue>() {
+        return None;
+    }
+    Some(ResolvedField { index, type_id })
+}
+
 impl fmt::Display for KeyPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for key in &self.path {
@@ -431,6 +1031,143 @@ impl fmt::Display for KeyPath {
     }
 }
 
+/// The error returned by [`GetPath::set_at`].
+#[derive(Debug, Clone)]
+pub enum SetError {
+    /// There is no value at the key path.
+    NotFound { path: KeyPath },
+    /// The new value couldn't be converted into the type at the key path.
+    ///
+    /// The path in `error` is relative to `path`.
+    #[cfg(feature = "type_info")]
+    Convert { path: KeyPath, error: ConvertError },
+}
+
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetError::NotFound { path } => write!(f, "no value at `{path}`"),
+            #[cfg(feature = "type_info")]
+            SetError::Convert { path, error } => write!(f, "cannot set `{path}`: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SetError::NotFound { .. } => None,
+            #[cfg(feature = "type_info")]
+            SetError::Convert { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Overwrite `target` with `value`, which must already have the same shape.
+fn assign(target: &mut dyn Reflect, value: &dyn Reflect) {
+    match (target.reflect_mut(), value.reflect_ref()) {
+        (ReflectMut::Struct(target), ReflectRef::Struct(value)) => {
+            for (name, value) in value.fields() {
+                if let Some(target) = target.field_mut(name) {
+                    assign(target, value);
+                }
+            }
+        }
+        (ReflectMut::TupleStruct(target), ReflectRef::TupleStruct(value)) => {
+            for (index, value) in value.fields().enumerate() {
+                if let Some(target) = target.field_at_mut(index) {
+                    assign(target, value);
+                }
+            }
+        }
+        (ReflectMut::Tuple(target), ReflectRef::Tuple(value)) => {
+            for (index, value) in value.fields().enumerate() {
+                if let Some(target) = target.field_at_mut(index) {
+                    assign(target, value);
+                }
+            }
+        }
+        (ReflectMut::Enum(target), ReflectRef::Enum(value))
+            if target.variant_name() == value.variant_name() =>
+        {
+            for (index, field) in value.fields().enumerate() {
+                let (target, value) = match field {
+                    VariantField::Struct(name, value) => (target.field_mut(name), value),
+                    VariantField::Tuple(value) => (target.field_at_mut(index), value),
+                };
+                if let Some(target) = target {
+                    assign(target, value);
+                }
+            }
+        }
+        (ReflectMut::Array(target), ReflectRef::Array(value)) => {
+            for (index, value) in value.iter().enumerate() {
+                if let Some(target) = target.get_mut(index) {
+                    assign(target, value);
+                }
+            }
+        }
+        (ReflectMut::Array(target), ReflectRef::List(value)) => {
+            for (index, value) in value.iter().enumerate() {
+                if let Some(target) = target.get_mut(index) {
+                    assign(target, value);
+                }
+            }
+        }
+        (ReflectMut::List(target), ReflectRef::List(value)) => {
+            while target.pop().is_some() {}
+            for value in value.iter() {
+                target.push(value);
+            }
+        }
+        (ReflectMut::List(target), ReflectRef::Array(value)) => {
+            while target.pop().is_some() {}
+            for value in value.iter() {
+                target.push(value);
+            }
+        }
+        (ReflectMut::Map(target), ReflectRef::Map(value)) => {
+            let stale_keys = target
+                .iter()
+                .map(|(key, _)| key.to_value())
+                .filter(|key| value.get(key).is_none())
+                .collect::<Vec<_>>();
+            for key in stale_keys {
+                target.remove(&key);
+            }
+            for (key, value) in value.iter() {
+                if let Some(target) = target.get_mut(key) {
+                    assign(target, value);
+                } else {
+                    target.insert(key, value);
+                }
+            }
+        }
+        // scalars, opaque values, and enums changing variant are replaced wholesale
+        _ => target.patch(value),
+    }
+}
+
+/// Convert an index into a list of length `len`. Negative indices count from the end.
+fn value_to_index(value: &Value, len: usize) -> Option<usize> {
+    let from_end = |n: i128| -> Option<usize> {
+        if n < 0 {
+            len.checked_sub(usize::try_from(n.unsigned_abs()).ok()?)
+        } else {
+            usize::try_from(n).ok()
+        }
+    };
+    match value {
+        Value::i8(n) => from_end(*n as i128),
+        Value::i16(n) => from_end(*n as i128),
+        Value::i32(n) => from_end(*n as i128),
+        Value::i64(n) => from_end(*n as i128),
+        Value::i128(n) => from_end(**n),
+        _ => value_to_usize(value),
+    }
+}
+
 pub(crate) fn value_to_usize(value: &Value) -> Option<usize> {
     match value {
         Value::usize(n) => Some(*n),
@@ -438,12 +1175,12 @@ pub(crate) fn value_to_usize(value: &Value) -> Option<usize> {
         Value::u16(n) => Some(*n as usize),
         Value::u32(n) => Some(*n as usize),
         Value::u64(n) => Some(*n as usize),
-        Value::u128(n) => Some(*n as usize),
+        Value::u128(n) => Some(**n as usize),
         Value::i8(n) => Some(*n as usize),
         Value::i16(n) => Some(*n as usize),
         Value::i32(n) => Some(*n as usize),
         Value::i64(n) => Some(*n as usize),
-        Value::i128(n) => Some(*n as usize),
+        Value::i128(n) => Some(**n as usize),
         Value::bool(_)
         | Value::char(_)
         | Value::f32(_)
@@ -454,6 +1191,7 @@ pub(crate) fn value_to_usize(value: &Value) -> Option<usize> {
         | Value::TupleStructValue(_)
         | Value::TupleValue(_)
         | Value::List(_)
-        | Value::Map(_) => None,
+        | Value::Map(_)
+        | Value::OrderedMap(_) => None,
     }
 }
@@ -52,10 +52,14 @@ use alloc::boxed::Box;
 use alloc::string::String;
 use core::any::Any;
 use core::any::TypeId;
+use core::cmp::Ordering;
 use core::fmt;
 
 use crate::enum_::VariantField;
 use crate::enum_::VariantKind;
+use crate::key_path::KeyPath;
+use crate::method::CallError;
+use crate::method::Method;
 
 macro_rules! trivial_reflect_methods {
     () => {
@@ -86,19 +90,39 @@ macro_rules! trivial_reflect_methods {
 }
 
 pub mod array;
+#[cfg(feature = "type_info")]
+pub mod convert;
+pub mod diff;
+#[cfg(feature = "egui")]
+pub mod egui;
 pub mod enum_;
 pub mod get_field;
 pub mod iter;
 pub mod key_path;
 pub mod list;
 pub mod map;
+pub mod merge;
+pub mod method;
+#[cfg(feature = "type_info")]
+pub mod pretty;
+#[cfg(feature = "erased-serde")]
+pub mod registry;
+#[cfg(feature = "rhai")]
+pub mod rhai;
 pub mod struct_;
+pub mod testing;
+pub mod tracked;
 pub mod tuple;
 pub mod tuple_struct;
+#[cfg(feature = "type_info")]
 pub mod type_info;
 pub mod value;
+pub mod visit;
 
+mod hash;
 mod std_impls;
+#[cfg(feature = "speedy")]
+mod versioned;
 
 #[cfg(feature = "std")]
 #[cfg(test)]
@@ -109,6 +133,9 @@ pub use mirror_mirror_macros::*;
 
 #[doc(inline)]
 pub use self::array::Array;
+#[cfg(feature = "type_info")]
+#[doc(inline)]
+pub use self::convert::convert;
 #[doc(inline)]
 pub use self::enum_::Enum;
 #[doc(inline)]
@@ -125,14 +152,54 @@ pub use self::struct_::Struct;
 pub use self::tuple::Tuple;
 #[doc(inline)]
 pub use self::tuple_struct::TupleStruct;
+#[cfg(feature = "type_info")]
 #[doc(inline)]
 pub use self::type_info::TypeRoot;
+#[cfg(feature = "type_info")]
 #[doc(inline)]
 pub use self::type_info::Typed;
+
+/// Without the `type_info` feature `Typed` is implemented for all types and
+/// [`Typed::type_info`] returns a placeholder, so bounds on it and calls to it keep compiling.
+#[cfg(not(feature = "type_info"))]
+pub trait Typed: 'static {
+    fn type_info() -> TypeRoot {
+        TypeRoot { _private: () }
+    }
+}
+
+#[cfg(not(feature = "type_info"))]
+impl<T> Typed for T where T: ?Sized + 'static {}
+
+/// Stands in for the type information that only exists with the `type_info` feature, so
+/// [`Reflect::type_info`] has the same signature with or without it.
+#[cfg(not(feature = "type_info"))]
+#[derive(Debug, Clone, Copy)]
+pub struct TypeRoot {
+    _private: (),
+}
+
 #[doc(inline)]
 pub use self::value::Value;
 
-pub trait Reflect: Any + Send + 'static {
+/// With the `sync` feature this requires `Sync`, and since it's a supertrait of [`Reflect`] that
+/// makes `dyn Reflect` `Sync` as well, so reflected values can be shared between threads. Without
+/// the feature it is implemented for all types.
+#[cfg(feature = "sync")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "sync")]
+impl<T> MaybeSync for T where T: ?Sized + Sync {}
+
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "sync"))]
+impl<T> MaybeSync for T where T: ?Sized {}
+
+pub trait Reflect: Any + Send + MaybeSync + 'static {
+    /// Without the `type_info` feature this returns a placeholder, but it's still part of the trait
+    /// so implementations compile whether or not the feature is enabled.
     fn type_info(&self) -> TypeRoot;
 
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
@@ -157,10 +224,77 @@ pub trait Reflect: Any + Send + 'static {
 
     fn to_value(&self) -> Value;
 
+    /// Update `value` to be equal to [`Reflect::to_value`], while keeping the parts that haven't
+    /// changed.
+    ///
+    /// Struct and enum fields, and list elements, are updated in place when the structure
+    /// matches, so calling this repeatedly with the same `value`, for example to snapshot a
+    /// component every frame, mostly avoids allocating and cloning.
+    fn update_value(&self, value: &mut Value) {
+        value::update_value(value, self.as_reflect())
+    }
+
     fn clone_reflect(&self) -> Box<dyn Reflect>;
 
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
 
+    /// Hash the value.
+    ///
+    /// By default this hashes the structure of the value, as given by [`Reflect::reflect_ref`],
+    /// so a type and its [`Value`] hash the same. Returns `None` if the value contains an opaque
+    /// type.
+    ///
+    /// `#[derive(Reflect)]` can use the type's own [`Hash`](core::hash::Hash) implementation
+    /// instead with `#[reflect(opt_in(Hash))]`.
+    fn reflect_hash(&self) -> Option<u64> {
+        reflect_hash(self.as_reflect())
+    }
+
+    /// Compare the value with another, possibly differently typed, value.
+    ///
+    /// By default this compares the structure of the two values, as given by
+    /// [`Reflect::reflect_ref`], so a type compares equal to its [`Value`]. Floats compare equal
+    /// if their [`Value`]s would, meaning `NaN` is equal to itself. Returns `None` if the
+    /// comparison reaches an opaque type.
+    ///
+    /// `#[derive(Reflect)]` can use the type's own [`PartialEq`] implementation, when `other`
+    /// has the same type, with `#[reflect(opt_in(PartialEq))]`.
+    fn reflect_partial_eq(&self, other: &dyn Reflect) -> Option<bool> {
+        reflect_partial_eq(self.as_reflect(), other)
+    }
+
+    /// Order the value relative to another, possibly differently typed, value.
+    ///
+    /// By default this follows the same rules as the [`Ord`] implementation of [`Value`], without
+    /// converting either side to a [`Value`]. Returns `None` if the comparison reaches an opaque
+    /// type.
+    ///
+    /// `#[derive(Reflect)]` can use the type's own [`PartialOrd`] implementation, when `other`
+    /// has the same type, with `#[reflect(opt_in(PartialOrd))]`.
+    fn reflect_partial_cmp(&self, other: &dyn Reflect) -> Option<Ordering> {
+        reflect_partial_cmp(self.as_reflect(), other)
+    }
+
+    /// A user facing representation of the value, such as `#ff0080` for a color.
+    ///
+    /// When this returns `Some`, [`pretty`](crate::pretty) and the egui inspector show it instead
+    /// of, or alongside, the value's fields. Returns `None` by default. [`Value`]s always return
+    /// `None` since they don't know which type they were created from.
+    ///
+    /// `#[derive(Reflect)]` can use the type's own [`Display`](core::fmt::Display) implementation
+    /// with `#[reflect(opt_in(Display))]`.
+    fn reflect_display(&self) -> Option<&dyn fmt::Display> {
+        None
+    }
+
+    /// The methods that can be called on the value. Empty by default.
+    ///
+    /// `#[derive(Reflect)]` returns the methods marked with `#[reflect(func)]` with
+    /// `#[reflect(opt_in(Methods))]`. See [`method`] for more details.
+    fn methods(&self) -> &'static [Method] {
+        &[]
+    }
+
     fn type_id(&self) -> TypeId {
         TypeId::of::<Self>()
     }
@@ -280,6 +414,105 @@ impl dyn Reflect {
     {
         self.as_any_mut().downcast_mut::<T>()
     }
+
+    pub fn is<T>(&self) -> bool
+    where
+        T: Reflect,
+    {
+        self.as_any().is::<T>()
+    }
+
+    /// Find one of the value's [`methods`](Reflect::methods) by name.
+    pub fn method(&self, name: &str) -> Option<&'static Method> {
+        self.methods().iter().find(|method| method.name() == name)
+    }
+
+    /// Call one of the value's [`methods`](Reflect::methods) by name.
+    pub fn call_method(
+        &mut self,
+        name: &str,
+        args: &[&dyn Reflect],
+    ) -> Result<Box<dyn Reflect>, CallError> {
+        let method = self.method(name).ok_or_else(|| CallError::NotFound {
+            method: name.to_owned(),
+        })?;
+        method.call(self, args)
+    }
+
+    /// Move the value out of the box if it has type `T`, otherwise give the box back.
+    pub fn take<T>(self: Box<Self>) -> Result<T, Box<dyn Reflect>>
+    where
+        T: Reflect,
+    {
+        if self.is::<T>() {
+            match self.into_any().downcast::<T>() {
+                Ok(value) => Ok(*value),
+                Err(_) => unreachable!("type was checked above"),
+            }
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Find every value of type `T` inside `self`, including `self`, along with its key path.
+    ///
+    /// The search is depth first and goes through all structs, enums, tuples, lists, arrays,
+    /// and map values. Opaque values are not searched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::key_path;
+    /// use mirror_mirror::Reflect;
+    ///
+    /// #[derive(Reflect, Clone, Debug, PartialEq)]
+    /// struct AssetHandle(u64);
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Sprite {
+    ///     texture: AssetHandle,
+    ///     frames: Vec<AssetHandle>,
+    /// }
+    ///
+    /// let sprite = Sprite {
+    ///     texture: AssetHandle(1),
+    ///     frames: vec![AssetHandle(2), AssetHandle(3)],
+    /// };
+    ///
+    /// let handles = sprite
+    ///     .as_reflect()
+    ///     .find_all::<AssetHandle>()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     handles,
+    ///     [
+    ///         (key_path!(.texture), &AssetHandle(1)),
+    ///         (key_path!(.frames[0_usize]), &AssetHandle(2)),
+    ///         (key_path!(.frames[1_usize]), &AssetHandle(3)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn find_all<T>(&self) -> impl Iterator<Item = (KeyPath, &T)>
+    where
+        T: Reflect,
+    {
+        self.find_all_by(|value| value.is::<T>())
+            .map(|(path, value)| (path, value.downcast_ref().expect("type was checked above")))
+    }
+
+    /// Find every value inside `self`, including `self`, for which `predicate` returns `true`.
+    ///
+    /// Values are searched in the same order as [`find_all`](Self::find_all).
+    pub fn find_all_by<'a, F>(
+        &'a self,
+        predicate: F,
+    ) -> impl Iterator<Item = (KeyPath, &'a dyn Reflect)> + 'a
+    where
+        F: FnMut(&dyn Reflect) -> bool + 'a,
+    {
+        visit::Search::new(self, predicate)
+    }
 }
 
 impl fmt::Debug for dyn Reflect {
@@ -515,6 +748,50 @@ impl ReflectOwned {
             _ => None,
         }
     }
+
+    /// Move the value out if it has type `T`, otherwise give `self` back.
+    ///
+    /// Note that scalars are stored as their primitive types, so a type that reflects as a scalar
+    /// must be taken as that scalar type.
+    pub fn try_take<T>(self) -> Result<T, ReflectOwned>
+    where
+        T: Reflect,
+    {
+        self.into_reflect()
+            .take::<T>()
+            .map_err(|reflect| reflect.reflect_owned())
+    }
+
+    fn into_reflect(self) -> Box<dyn Reflect> {
+        match self {
+            Self::Struct(inner) => inner.into_reflect(),
+            Self::TupleStruct(inner) => inner.into_reflect(),
+            Self::Tuple(inner) => inner.into_reflect(),
+            Self::Enum(inner) => inner.into_reflect(),
+            Self::Array(inner) => inner.into_reflect(),
+            Self::List(inner) => inner.into_reflect(),
+            Self::Map(inner) => inner.into_reflect(),
+            Self::Scalar(inner) => match inner {
+                ScalarOwned::usize(inner) => Box::new(inner),
+                ScalarOwned::u8(inner) => Box::new(inner),
+                ScalarOwned::u16(inner) => Box::new(inner),
+                ScalarOwned::u32(inner) => Box::new(inner),
+                ScalarOwned::u64(inner) => Box::new(inner),
+                ScalarOwned::u128(inner) => Box::new(inner),
+                ScalarOwned::i8(inner) => Box::new(inner),
+                ScalarOwned::i16(inner) => Box::new(inner),
+                ScalarOwned::i32(inner) => Box::new(inner),
+                ScalarOwned::i64(inner) => Box::new(inner),
+                ScalarOwned::i128(inner) => Box::new(inner),
+                ScalarOwned::bool(inner) => Box::new(inner),
+                ScalarOwned::char(inner) => Box::new(inner),
+                ScalarOwned::f32(inner) => Box::new(inner),
+                ScalarOwned::f64(inner) => Box::new(inner),
+                ScalarOwned::String(inner) => Box::new(inner),
+            },
+            Self::Opaque(inner) => inner,
+        }
+    }
 }
 
 impl Clone for ReflectOwned {
@@ -848,9 +1125,488 @@ pub fn reflect_debug(value: &dyn Reflect, f: &mut core::fmt::Formatter<'_>) -> c
     }
 }
 
+/// Hash the structure of a value. This is the default implementation of
+/// [`Reflect::reflect_hash`].
+///
+/// Fields are hashed with their own [`Reflect::reflect_hash`]. Map entries and named fields are
+/// combined in an order independent way, since [`reflect_partial_eq`] looks them up by key or name,
+/// so values that compare equal hash the same regardless of iteration order.
+pub fn reflect_hash(value: &dyn Reflect) -> Option<u64> {
+    use core::hash::Hash;
+    use core::hash::Hasher;
+
+    let mut hasher = crate::hash::DefaultHasher::default();
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(inner) => {
+            0_u8.hash(&mut hasher);
+            let mut fields = 0_u64;
+            for (name, value) in inner.fields() {
+                fields = fields.wrapping_add(named_field_hash(name, value.reflect_hash()?));
+            }
+            inner.fields_len().hash(&mut hasher);
+            fields.hash(&mut hasher);
+        }
+        ReflectRef::TupleStruct(inner) => {
+            1_u8.hash(&mut hasher);
+            for value in inner.fields() {
+                value.reflect_hash()?.hash(&mut hasher);
+            }
+        }
+        ReflectRef::Tuple(inner) => {
+            2_u8.hash(&mut hasher);
+            for value in inner.fields() {
+                value.reflect_hash()?.hash(&mut hasher);
+            }
+        }
+        ReflectRef::Enum(inner) => {
+            3_u8.hash(&mut hasher);
+            inner.variant_name().hash(&mut hasher);
+            let mut fields = 0_u64;
+            for field in inner.fields() {
+                match field {
+                    VariantField::Struct(name, value) => {
+                        fields = fields.wrapping_add(named_field_hash(name, value.reflect_hash()?));
+                    }
+                    VariantField::Tuple(value) => {
+                        value.reflect_hash()?.hash(&mut hasher);
+                    }
+                }
+            }
+            inner.fields_len().hash(&mut hasher);
+            fields.hash(&mut hasher);
+        }
+        // arrays and lists hash the same, just like they have the same `Value` representation
+        ReflectRef::Array(inner) => {
+            4_u8.hash(&mut hasher);
+            for value in inner.iter() {
+                value.reflect_hash()?.hash(&mut hasher);
+            }
+        }
+        ReflectRef::List(inner) => {
+            4_u8.hash(&mut hasher);
+            for value in inner.iter() {
+                value.reflect_hash()?.hash(&mut hasher);
+            }
+        }
+        ReflectRef::Map(inner) => {
+            5_u8.hash(&mut hasher);
+            let mut entries = 0_u64;
+            for (key, value) in inner.iter() {
+                entries =
+                    entries.wrapping_add(entry_hash(key.reflect_hash()?, value.reflect_hash()?));
+            }
+            inner.len().hash(&mut hasher);
+            entries.hash(&mut hasher);
+        }
+        ReflectRef::Scalar(inner) => return Some(scalar_hash(inner)),
+        ReflectRef::Opaque(_) => return None,
+    }
+
+    Some(hasher.finish())
+}
+
+pub(crate) fn named_field_hash(name: &str, hash: u64) -> u64 {
+    use core::hash::Hash;
+    use core::hash::Hasher;
+
+    let mut hasher = crate::hash::DefaultHasher::default();
+    name.hash(&mut hasher);
+    hash.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub(crate) fn entry_hash(key: u64, value: u64) -> u64 {
+    use core::hash::Hash;
+    use core::hash::Hasher;
+
+    let mut hasher = crate::hash::DefaultHasher::default();
+    key.hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub(crate) fn scalar_hash(scalar: ScalarRef<'_>) -> u64 {
+    use core::hash::Hash;
+    use core::hash::Hasher;
+    use ordered_float::OrderedFloat;
+
+    let mut hasher = crate::hash::DefaultHasher::default();
+    6_u8.hash(&mut hasher);
+    match scalar {
+        ScalarRef::usize(inner) => inner.hash(&mut hasher),
+        ScalarRef::u8(inner) => inner.hash(&mut hasher),
+        ScalarRef::u16(inner) => inner.hash(&mut hasher),
+        ScalarRef::u32(inner) => inner.hash(&mut hasher),
+        ScalarRef::u64(inner) => inner.hash(&mut hasher),
+        ScalarRef::u128(inner) => inner.hash(&mut hasher),
+        ScalarRef::i8(inner) => inner.hash(&mut hasher),
+        ScalarRef::i16(inner) => inner.hash(&mut hasher),
+        ScalarRef::i32(inner) => inner.hash(&mut hasher),
+        ScalarRef::i64(inner) => inner.hash(&mut hasher),
+        ScalarRef::i128(inner) => inner.hash(&mut hasher),
+        ScalarRef::bool(inner) => inner.hash(&mut hasher),
+        ScalarRef::char(inner) => inner.hash(&mut hasher),
+        ScalarRef::f32(inner) => OrderedFloat(inner).hash(&mut hasher),
+        ScalarRef::f64(inner) => OrderedFloat(inner).hash(&mut hasher),
+        ScalarRef::String(inner) => inner.hash(&mut hasher),
+    }
+    hasher.finish()
+}
+
+/// Compare the structure of two values. This is the default implementation of
+/// [`Reflect::reflect_partial_eq`].
+///
+/// Fields are compared with their own [`Reflect::reflect_partial_eq`].
+pub fn reflect_partial_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
+    fn all_eq<'a>(
+        a: impl Iterator<Item = &'a dyn Reflect>,
+        mut b: impl Iterator<Item = &'a dyn Reflect>,
+    ) -> Option<bool> {
+        for a in a {
+            let Some(b) = b.next() else { return Some(false) };
+            if !a.reflect_partial_eq(b)? {
+                return Some(false);
+            }
+        }
+        Some(b.next().is_none())
+    }
+
+    let eq = match (a.reflect_ref(), b.reflect_ref()) {
+        (ReflectRef::Struct(a), ReflectRef::Struct(b)) => {
+            if a.fields_len() != b.fields_len() {
+                return Some(false);
+            }
+            for (name, a) in a.fields() {
+                let Some(b) = b.field(name) else { return Some(false) };
+                if !a.reflect_partial_eq(b)? {
+                    return Some(false);
+                }
+            }
+            true
+        }
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b)) => all_eq(a.fields(), b.fields())?,
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) => all_eq(a.fields(), b.fields())?,
+        (ReflectRef::Enum(a), ReflectRef::Enum(b)) => {
+            if a.variant_name() != b.variant_name()
+                || a.variant_kind() != b.variant_kind()
+                || a.fields_len() != b.fields_len()
+            {
+                return Some(false);
+            }
+            match a.variant_kind() {
+                VariantKind::Struct => {
+                    for field in a.fields() {
+                        let VariantField::Struct(name, a) = field else { continue };
+                        let Some(b) = b.field(name) else { return Some(false) };
+                        if !a.reflect_partial_eq(b)? {
+                            return Some(false);
+                        }
+                    }
+                    true
+                }
+                VariantKind::Tuple => {
+                    let a_fields = (0..a.fields_len()).filter_map(|index| a.field_at(index));
+                    let b_fields = (0..b.fields_len()).filter_map(|index| b.field_at(index));
+                    all_eq(a_fields, b_fields)?
+                }
+                VariantKind::Unit => true,
+            }
+        }
+        // arrays and lists compare equal, just like they have the same `Value` representation
+        (ReflectRef::Array(a), ReflectRef::Array(b)) => all_eq(a.iter(), b.iter())?,
+        (ReflectRef::List(a), ReflectRef::List(b)) => all_eq(a.iter(), b.iter())?,
+        (ReflectRef::Array(a), ReflectRef::List(b)) => all_eq(a.iter(), b.iter())?,
+        (ReflectRef::List(a), ReflectRef::Array(b)) => all_eq(a.iter(), b.iter())?,
+        (ReflectRef::Map(a), ReflectRef::Map(b)) => {
+            if a.len() != b.len() {
+                return Some(false);
+            }
+            for (key, a) in a.iter() {
+                let Some(b) = b.get(key) else { return Some(false) };
+                if !a.reflect_partial_eq(b)? {
+                    return Some(false);
+                }
+            }
+            true
+        }
+        (ReflectRef::Scalar(a), ReflectRef::Scalar(b)) => scalar_eq(a, b),
+        (ReflectRef::Opaque(_), _) | (_, ReflectRef::Opaque(_)) => return None,
+        _ => false,
+    };
+
+    Some(eq)
+}
+
+/// Floats are compared like [`OrderedFloat`](ordered_float::OrderedFloat) so `NaN` is equal to
+/// itself.
+pub(crate) fn scalar_eq(a: ScalarRef<'_>, b: ScalarRef<'_>) -> bool {
+    use ordered_float::OrderedFloat;
+
+    match (a, b) {
+        (ScalarRef::usize(a), ScalarRef::usize(b)) => a == b,
+        (ScalarRef::u8(a), ScalarRef::u8(b)) => a == b,
+        (ScalarRef::u16(a), ScalarRef::u16(b)) => a == b,
+        (ScalarRef::u32(a), ScalarRef::u32(b)) => a == b,
+        (ScalarRef::u64(a), ScalarRef::u64(b)) => a == b,
+        (ScalarRef::u128(a), ScalarRef::u128(b)) => a == b,
+        (ScalarRef::i8(a), ScalarRef::i8(b)) => a == b,
+        (ScalarRef::i16(a), ScalarRef::i16(b)) => a == b,
+        (ScalarRef::i32(a), ScalarRef::i32(b)) => a == b,
+        (ScalarRef::i64(a), ScalarRef::i64(b)) => a == b,
+        (ScalarRef::i128(a), ScalarRef::i128(b)) => a == b,
+        (ScalarRef::bool(a), ScalarRef::bool(b)) => a == b,
+        (ScalarRef::char(a), ScalarRef::char(b)) => a == b,
+        (ScalarRef::f32(a), ScalarRef::f32(b)) => OrderedFloat(a) == OrderedFloat(b),
+        (ScalarRef::f64(a), ScalarRef::f64(b)) => OrderedFloat(a) == OrderedFloat(b),
+        (ScalarRef::String(a), ScalarRef::String(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Order the structure of two values. This is the default implementation of
+/// [`Reflect::reflect_partial_cmp`].
+///
+/// Values of different kinds are ordered the same way as the variants of [`Value`]. Fields are
+/// compared with their own [`Reflect::reflect_partial_cmp`].
+pub fn reflect_partial_cmp(a: &dyn Reflect, b: &dyn Reflect) -> Option<Ordering> {
+    use alloc::vec::Vec;
+    use ordered_float::OrderedFloat;
+
+    // the position of the corresponding `Value` variant
+    fn rank(reflect: &ReflectRef<'_>) -> Option<u8> {
+        let rank = match reflect {
+            ReflectRef::Scalar(scalar) => match scalar {
+                ScalarRef::usize(_) => 0,
+                ScalarRef::u8(_) => 1,
+                ScalarRef::u16(_) => 2,
+                ScalarRef::u32(_) => 3,
+                ScalarRef::u64(_) => 4,
+                ScalarRef::u128(_) => 5,
+                ScalarRef::i8(_) => 6,
+                ScalarRef::i16(_) => 7,
+                ScalarRef::i32(_) => 8,
+                ScalarRef::i64(_) => 9,
+                ScalarRef::i128(_) => 10,
+                ScalarRef::bool(_) => 11,
+                ScalarRef::char(_) => 12,
+                ScalarRef::f32(_) => 13,
+                ScalarRef::f64(_) => 14,
+                ScalarRef::String(_) => 15,
+            },
+            ReflectRef::Struct(_) => 16,
+            ReflectRef::Enum(_) => 17,
+            ReflectRef::TupleStruct(_) => 18,
+            ReflectRef::Tuple(_) => 19,
+            ReflectRef::Array(_) | ReflectRef::List(_) => 20,
+            ReflectRef::Map(_) => 21,
+            ReflectRef::Opaque(_) => return None,
+        };
+        Some(rank)
+    }
+
+    fn cmp_all<'a>(
+        a: impl Iterator<Item = &'a dyn Reflect>,
+        mut b: impl Iterator<Item = &'a dyn Reflect>,
+    ) -> Option<Ordering> {
+        for a in a {
+            let Some(b) = b.next() else { return Some(Ordering::Greater) };
+            match a.reflect_partial_cmp(b)? {
+                Ordering::Equal => {}
+                ordering => return Some(ordering),
+            }
+        }
+        if b.next().is_some() {
+            Some(Ordering::Less)
+        } else {
+            Some(Ordering::Equal)
+        }
+    }
+
+    // `StructValue` orders by field names, in declaration order, and then by values, in field
+    // name order
+    fn cmp_named_fields<'a>(
+        a: impl Iterator<Item = (&'a str, &'a dyn Reflect)>,
+        b: impl Iterator<Item = (&'a str, &'a dyn Reflect)>,
+    ) -> Option<Ordering> {
+        let mut a = a.collect::<Vec<_>>();
+        let mut b = b.collect::<Vec<_>>();
+
+        let names = a
+            .iter()
+            .map(|(name, _)| name)
+            .cmp(b.iter().map(|(name, _)| name));
+        if names != Ordering::Equal {
+            return Some(names);
+        }
+
+        a.sort_by_key(|(name, _)| *name);
+        b.sort_by_key(|(name, _)| *name);
+        cmp_all(
+            a.into_iter().map(|(_, value)| value),
+            b.into_iter().map(|(_, value)| value),
+        )
+    }
+
+    let a = a.reflect_ref();
+    let b = b.reflect_ref();
+
+    let ordering = rank(&a)?.cmp(&rank(&b)?);
+    if ordering != Ordering::Equal {
+        return Some(ordering);
+    }
+
+    match (a, b) {
+        (ReflectRef::Struct(a), ReflectRef::Struct(b)) => cmp_named_fields(a.fields(), b.fields()),
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b)) => cmp_all(a.fields(), b.fields()),
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) => cmp_all(a.fields(), b.fields()),
+        (ReflectRef::Enum(a), ReflectRef::Enum(b)) => {
+            fn variant_rank(kind: VariantKind) -> u8 {
+                match kind {
+                    VariantKind::Struct => 0,
+                    VariantKind::Tuple => 1,
+                    VariantKind::Unit => 2,
+                }
+            }
+
+            fn named_fields(enum_: &dyn Enum) -> impl Iterator<Item = (&str, &dyn Reflect)> {
+                enum_.fields().filter_map(|field| match field {
+                    VariantField::Struct(name, value) => Some((name, value)),
+                    VariantField::Tuple(_) => None,
+                })
+            }
+
+            fn unnamed_fields(enum_: &dyn Enum) -> impl Iterator<Item = &dyn Reflect> {
+                (0..enum_.fields_len()).filter_map(|index| enum_.field_at(index))
+            }
+
+            let ordering = a
+                .variant_name()
+                .cmp(b.variant_name())
+                .then_with(|| variant_rank(a.variant_kind()).cmp(&variant_rank(b.variant_kind())));
+            if ordering != Ordering::Equal {
+                return Some(ordering);
+            }
+
+            match a.variant_kind() {
+                VariantKind::Struct => cmp_named_fields(named_fields(a), named_fields(b)),
+                VariantKind::Tuple => cmp_all(unnamed_fields(a), unnamed_fields(b)),
+                VariantKind::Unit => Some(Ordering::Equal),
+            }
+        }
+        (ReflectRef::Array(a), ReflectRef::Array(b)) => cmp_all(a.iter(), b.iter()),
+        (ReflectRef::List(a), ReflectRef::List(b)) => cmp_all(a.iter(), b.iter()),
+        (ReflectRef::Array(a), ReflectRef::List(b)) => cmp_all(a.iter(), b.iter()),
+        (ReflectRef::List(a), ReflectRef::Array(b)) => cmp_all(a.iter(), b.iter()),
+        (ReflectRef::Map(a), ReflectRef::Map(b)) => {
+            // entries are compared in key order, which might not be the order the map iterates in
+            fn sorted_entries(map: &dyn Map) -> Option<Vec<(&dyn Reflect, &dyn Reflect)>> {
+                let mut entries = map.iter().collect::<Vec<_>>();
+                let mut comparable = true;
+                entries.sort_by(|(a, _), (b, _)| {
+                    a.reflect_partial_cmp(*b).unwrap_or_else(|| {
+                        comparable = false;
+                        Ordering::Equal
+                    })
+                });
+                comparable.then_some(entries)
+            }
+
+            let a = sorted_entries(a)?;
+            let b = sorted_entries(b)?;
+            for (a, b) in a.iter().zip(&b) {
+                let ordering = a.0.reflect_partial_cmp(b.0)?;
+                if ordering != Ordering::Equal {
+                    return Some(ordering);
+                }
+                let ordering = a.1.reflect_partial_cmp(b.1)?;
+                if ordering != Ordering::Equal {
+                    return Some(ordering);
+                }
+            }
+            Some(a.len().cmp(&b.len()))
+        }
+        (ReflectRef::Scalar(a), ReflectRef::Scalar(b)) => {
+            let ordering = match (a, b) {
+                (ScalarRef::usize(a), ScalarRef::usize(b)) => a.cmp(&b),
+                (ScalarRef::u8(a), ScalarRef::u8(b)) => a.cmp(&b),
+                (ScalarRef::u16(a), ScalarRef::u16(b)) => a.cmp(&b),
+                (ScalarRef::u32(a), ScalarRef::u32(b)) => a.cmp(&b),
+                (ScalarRef::u64(a), ScalarRef::u64(b)) => a.cmp(&b),
+                (ScalarRef::u128(a), ScalarRef::u128(b)) => a.cmp(&b),
+                (ScalarRef::i8(a), ScalarRef::i8(b)) => a.cmp(&b),
+                (ScalarRef::i16(a), ScalarRef::i16(b)) => a.cmp(&b),
+                (ScalarRef::i32(a), ScalarRef::i32(b)) => a.cmp(&b),
+                (ScalarRef::i64(a), ScalarRef::i64(b)) => a.cmp(&b),
+                (ScalarRef::i128(a), ScalarRef::i128(b)) => a.cmp(&b),
+                (ScalarRef::bool(a), ScalarRef::bool(b)) => a.cmp(&b),
+                (ScalarRef::char(a), ScalarRef::char(b)) => a.cmp(&b),
+                (ScalarRef::f32(a), ScalarRef::f32(b)) => OrderedFloat(a).cmp(&OrderedFloat(b)),
+                (ScalarRef::f64(a), ScalarRef::f64(b)) => OrderedFloat(a).cmp(&OrderedFloat(b)),
+                (ScalarRef::String(a), ScalarRef::String(b)) => a.cmp(b),
+                _ => unreachable!("scalars of different types have different ranks"),
+            };
+            Some(ordering)
+        }
+        _ => unreachable!("values of different kinds have different ranks"),
+    }
+}
+
+/// Implement [`Reflect`], [`FromReflect`], and [`Typed`] for a type that is reflected as opaque.
+///
+/// The type must implement `Clone` and `Debug`. Its [`Value`] is the type name, since there is no
+/// way to turn a value back into the type.
+///
+/// `inner = T` declares the type the opaque type logically wraps, see
+/// [`OpaqueNode::with_inner_type`](crate::type_info::graph::OpaqueNode::with_inner_type). Types you
+/// own, including generic ones, can use `#[derive(Reflect)]` with `#[reflect(opaque)]` or
+/// `#[reflect(opaque(inner = T))]` instead.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::RwLock;
+///
+/// use mirror_mirror::impl_reflect_opaque;
+/// use mirror_mirror::Reflect;
+/// use mirror_mirror::ReflectRef;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Settings {
+///     volume: f32,
+/// }
+///
+/// #[derive(Clone, Debug)]
+/// struct SharedSettings(Arc<RwLock<Settings>>);
+///
+/// // `<SharedSettings as Typed>::type_info().type_at(&key_path!(.volume))` finds the `f32`
+/// impl_reflect_opaque!(SharedSettings, inner = Settings);
+///
+/// let settings = SharedSettings(Arc::new(RwLock::new(Settings { volume: 1.0 })));
+/// assert!(matches!(settings.reflect_ref(), ReflectRef::Opaque(_)));
+/// ```
+#[macro_export]
+macro_rules! impl_reflect_opaque {
+    ($ty:ident $(,)?) => {
+        $crate::__private_derive_reflect_foreign! {
+            #[reflect(opaque, crate_name($crate))]
+            struct $ty;
+        }
+    };
+
+    ($ty:ident, inner = $inner:ty $(,)?) => {
+        $crate::__private_derive_reflect_foreign! {
+            #[reflect(opaque(inner = $inner), crate_name($crate))]
+            struct $ty;
+        }
+    };
+}
+
 /// Private. Used by macros
 #[doc(hidden)]
 pub mod __private {
+    pub use alloc::boxed::Box;
     pub use alloc::collections::BTreeMap;
     pub use core::any::Any;
     pub use core::fmt;
@@ -862,6 +1618,7 @@ pub mod __private {
     pub use self::tuple_struct::*;
     pub use self::value::*;
     pub use crate::iter::*;
+    #[cfg(feature = "type_info")]
     pub use crate::type_info::graph::*;
     pub use crate::*;
 
@@ -883,4 +1640,158 @@ pub mod __private {
             self.to_owned().into_value()
         }
     }
+
+    pub fn hash_with_default_hasher<T>(value: &T) -> u64
+    where
+        T: core::hash::Hash + ?Sized,
+    {
+        use core::hash::Hasher;
+
+        let mut hasher = crate::hash::DefaultHasher::default();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn strip_raw_prefix(ident: &str) -> &str {
+        ident.strip_prefix("r#").unwrap_or(ident)
+    }
+
+    /// The fields of a type that `#[derive(Reflect)]` reflects. Used by `typed_key_path!` to type
+    /// check `.field` segments, so fields marked with `#[reflect(skip)]` don't type check.
+    ///
+    /// For named structs this is a struct with only the reflected fields. For tuple structs it's a
+    /// tuple where skipped fields are replaced by [`Skipped`], which doesn't implement `Reflect`.
+    ///
+    /// The derive implements this with a private `Marker` type, which keeps the impl as private as
+    /// the marker so field types don't leak into the public interface.
+    pub trait ReflectedFields<Marker> {
+        type Fields;
+    }
+
+    /// Stands in for fields marked with `#[reflect(skip)]` in [`ReflectedFields::Fields`].
+    #[derive(Debug)]
+    pub struct Skipped;
+
+    impl<T, Marker> ReflectedFields<Marker> for alloc::boxed::Box<T>
+    where
+        T: ReflectedFields<Marker>,
+    {
+        type Fields = T::Fields;
+    }
+
+    pub fn reflected_fields<T, Marker>(_: &T) -> &T::Fields
+    where
+        T: ReflectedFields<Marker>,
+    {
+        unreachable!("only used for type checking")
+    }
+
+    /// The type of the elements of a list, array, or map. Used by `typed_key_path!` to type check
+    /// `[...]` segments.
+    pub trait Element {
+        type Element;
+    }
+
+    /// Implemented by lists, arrays, and maps for the types that `[...]` segments can index them
+    /// with.
+    pub trait ElementKey<Q> {}
+
+    /// Integer types that can index lists and arrays.
+    pub trait ListIndex {}
+
+    macro_rules! impl_list_index {
+        ($($ty:ty),* $(,)?) => {
+            $(impl ListIndex for $ty {})*
+        };
+    }
+
+    impl_list_index!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128);
+
+    /// Types that can be used to look up entries in a map with `K` keys.
+    pub trait MapKey<K> {}
+
+    impl<K> MapKey<K> for K {}
+
+    impl MapKey<alloc::string::String> for &str {}
+
+    impl<T> Element for alloc::vec::Vec<T> {
+        type Element = T;
+    }
+
+    impl<T, Q> ElementKey<Q> for alloc::vec::Vec<T> where Q: ListIndex {}
+
+    impl<T, const N: usize> Element for [T; N] {
+        type Element = T;
+    }
+
+    impl<T, Q, const N: usize> ElementKey<Q> for [T; N] where Q: ListIndex {}
+
+    impl<K, V> Element for BTreeMap<K, V> {
+        type Element = V;
+    }
+
+    impl<K, V, Q> ElementKey<Q> for BTreeMap<K, V> where Q: MapKey<K> {}
+
+    #[cfg(feature = "std")]
+    impl<K, V, S> Element for std::collections::HashMap<K, V, S> {
+        type Element = V;
+    }
+
+    #[cfg(feature = "std")]
+    impl<K, V, S, Q> ElementKey<Q> for std::collections::HashMap<K, V, S> where Q: MapKey<K> {}
+
+    #[cfg(feature = "std")]
+    impl<T, S> Element for std::collections::HashSet<T, S> {
+        type Element = ();
+    }
+
+    #[cfg(feature = "std")]
+    impl<T, S, Q> ElementKey<Q> for std::collections::HashSet<T, S> where Q: MapKey<T> {}
+
+    #[cfg(feature = "hashbrown")]
+    impl<K, V, S> Element for hashbrown::HashMap<K, V, S> {
+        type Element = V;
+    }
+
+    #[cfg(feature = "hashbrown")]
+    impl<K, V, S, Q> ElementKey<Q> for hashbrown::HashMap<K, V, S> where Q: MapKey<K> {}
+
+    #[cfg(feature = "hashbrown")]
+    impl<T, S> Element for hashbrown::HashSet<T, S> {
+        type Element = ();
+    }
+
+    #[cfg(feature = "hashbrown")]
+    impl<T, S, Q> ElementKey<Q> for hashbrown::HashSet<T, S> where Q: MapKey<T> {}
+
+    // the keys of an `OrderedMap` can be of any type
+    impl Element for crate::map::OrderedMap {
+        type Element = Value;
+    }
+
+    impl<Q> ElementKey<Q> for crate::map::OrderedMap {}
+
+    impl<T> Element for alloc::boxed::Box<T>
+    where
+        T: Element,
+    {
+        type Element = T::Element;
+    }
+
+    impl<T, Q> ElementKey<Q> for alloc::boxed::Box<T> where T: ElementKey<Q> {}
+
+    pub fn element<C>(_: &C) -> &C::Element
+    where
+        C: Element,
+    {
+        unreachable!("only used for type checking")
+    }
+
+    /// Checks that `key` can index the container the path leading up to it ends in.
+    pub fn checked_key<R, C, Q>(_path: fn(&R) -> &C, key: Q) -> Q
+    where
+        C: ElementKey<Q>,
+    {
+        key
+    }
 }
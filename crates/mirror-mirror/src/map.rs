@@ -1,14 +1,63 @@
 use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::Any;
 use core::fmt;
 
 use crate::iter::PairIterMut;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::OpaqueNode;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::TypeGraph;
+use crate::FromReflect;
 use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::TypeRoot;
+use crate::Typed;
+use crate::Value;
 
 pub trait Map: Reflect {
     fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect>;
 
     fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect>;
 
+    /// Get the value for a string key.
+    ///
+    /// Unlike [`Map::get`] this doesn't require the caller to allocate a `String` for the key.
+    /// The default implementation compares every key in the map.
+    fn get_str(&self, key: &str) -> Option<&dyn Reflect> {
+        find(self.iter(), |k| str_key(k) == Some(key))
+    }
+
+    /// Get the value for a string key mutably.
+    ///
+    /// Unlike [`Map::get_mut`] this doesn't require the caller to allocate a `String` for the
+    /// key. The default implementation compares every key in the map.
+    fn get_str_mut(&mut self, key: &str) -> Option<&mut dyn Reflect> {
+        find_mut(self.iter_mut(), |k| str_key(k) == Some(key))
+    }
+
+    /// Get the value for an integer key.
+    ///
+    /// Keys of any integer type with the same value match, so the caller doesn't have to know the
+    /// map's key type, or convert the key to it. `u128` keys larger than `i128::MAX` can't be
+    /// looked up this way. The default implementation compares every key in the map.
+    fn get_int(&self, key: i128) -> Option<&dyn Reflect> {
+        find(self.iter(), |k| int_key(k) == Some(key))
+    }
+
+    /// Get the value for an integer key mutably.
+    ///
+    /// See [`Map::get_int`] for which keys match. The default implementation compares every key
+    /// in the map.
+    fn get_int_mut(&mut self, key: i128) -> Option<&mut dyn Reflect> {
+        find_mut(self.iter_mut(), |k| int_key(k) == Some(key))
+    }
+
     fn insert(&mut self, key: &dyn Reflect, value: &dyn Reflect) -> Option<Box<dyn Reflect>>;
 
     fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>>;
@@ -29,3 +78,279 @@ impl fmt::Debug for dyn Map {
 }
 
 pub type Iter<'a> = Box<dyn Iterator<Item = (&'a dyn Reflect, &'a dyn Reflect)> + 'a>;
+
+pub(crate) fn find<'a>(
+    mut iter: Iter<'a>,
+    mut predicate: impl FnMut(&dyn Reflect) -> bool,
+) -> Option<&'a dyn Reflect> {
+    iter.find(|(key, _)| predicate(*key))
+        .map(|(_, value)| value)
+}
+
+pub(crate) fn find_mut<'a>(
+    mut iter: PairIterMut<'a, dyn Reflect>,
+    mut predicate: impl FnMut(&dyn Reflect) -> bool,
+) -> Option<&'a mut dyn Reflect> {
+    iter.find(|(key, _)| predicate(*key))
+        .map(|(_, value)| value)
+}
+
+pub(crate) fn str_key(key: &dyn Reflect) -> Option<&str> {
+    match key.as_scalar()? {
+        ScalarRef::String(key) => Some(key),
+        _ => None,
+    }
+}
+
+pub(crate) fn int_key(key: &dyn Reflect) -> Option<i128> {
+    Some(match key.as_scalar()? {
+        ScalarRef::usize(key) => key as i128,
+        ScalarRef::u8(key) => key.into(),
+        ScalarRef::u16(key) => key.into(),
+        ScalarRef::u32(key) => key.into(),
+        ScalarRef::u64(key) => key.into(),
+        ScalarRef::u128(key) => key.try_into().ok()?,
+        ScalarRef::i8(key) => key.into(),
+        ScalarRef::i16(key) => key.into(),
+        ScalarRef::i32(key) => key.into(),
+        ScalarRef::i64(key) => key.into(),
+        ScalarRef::i128(key) => key,
+        _ => return None,
+    })
+}
+
+/// What converting an integer key to the key type of a map gave, see [`int_key_as`].
+pub(crate) enum IntKey<K> {
+    /// The key type isn't an integer type.
+    NotInt,
+    /// The key type is an integer type that can't hold the key.
+    OutOfRange,
+    Fits(K),
+}
+
+/// Convert `key` to `K` if `K` is an integer type.
+pub(crate) fn int_key_as<K>(key: i128) -> IntKey<K>
+where
+    K: 'static,
+{
+    let mut out = IntKey::<K>::NotInt;
+    macro_rules! convert {
+        ($($ty:ty)*) => {
+            $(
+                if let Some(out) = (&mut out as &mut dyn Any).downcast_mut::<IntKey<$ty>>() {
+                    *out = match <$ty>::try_from(key) {
+                        Ok(key) => IntKey::Fits(key),
+                        Err(_) => IntKey::OutOfRange,
+                    };
+                }
+            )*
+        };
+    }
+    convert!(usize u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
+    out
+}
+
+/// A map of [`Value`]s that keeps its entries in insertion order.
+///
+/// [`Value::Map`] is a `BTreeMap` so its entries are always sorted by key. Use this, through
+/// [`Value::OrderedMap`], when the order of the entries is meaningful. It reflects as a
+/// [`Map`] just like [`Value::Map`] does.
+///
+/// Unlike [`Value::Map`] two ordered maps are only equal if their entries are in the same order.
+/// Lookups compare keys one by one so they take time linear in the number of entries.
+#[derive(Default, Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderedMap {
+    entries: Vec<(Value, Value)>,
+}
+
+impl OrderedMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_entry(mut self, key: impl Into<Value>, value: impl Into<Value>) -> Self {
+        self.set_entry(key, value);
+        self
+    }
+
+    /// Insert an entry, or replace the value of an existing entry without changing its position.
+    pub fn set_entry(&mut self, key: impl Into<Value>, value: impl Into<Value>) -> Option<Value> {
+        let key = key.into();
+        let value = value.into();
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, previous)) => Some(core::mem::replace(previous, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// The entries in insertion order.
+    pub fn entries(&self) -> impl Iterator<Item = (&Value, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    pub(crate) fn entries_mut(&mut self) -> &mut Vec<(Value, Value)> {
+        &mut self.entries
+    }
+
+    #[cfg(any(feature = "serde", feature = "speedy"))]
+    pub(crate) fn from_entries(entries: Vec<(Value, Value)>) -> Self {
+        Self { entries }
+    }
+
+    /// Clone the map, creating the values of the entries with `f`.
+    pub(crate) fn map_values(&self, mut f: impl FnMut(&Value) -> Value) -> Self {
+        Self {
+            entries: self
+                .entries
+                .iter()
+                .map(|(key, value)| (key.clone(), f(value)))
+                .collect(),
+        }
+    }
+
+    fn position(&self, key: &dyn Reflect) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|(k, _)| k.reflect_partial_eq(key) == Some(true))
+    }
+}
+
+impl Drop for OrderedMap {
+    fn drop(&mut self) {
+        crate::value::drop_nested(
+            self.entries
+                .iter_mut()
+                .flat_map(|(key, value)| [key, value]),
+        );
+    }
+}
+
+impl Map for OrderedMap {
+    fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
+        let index = self.position(key)?;
+        Some(self.entries[index].1.as_reflect())
+    }
+
+    fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
+        let index = self.position(key)?;
+        Some(self.entries[index].1.as_reflect_mut())
+    }
+
+    fn insert(&mut self, key: &dyn Reflect, value: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let previous = self.set_entry(key.to_value(), value.to_value())?;
+        Some(Box::new(previous))
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let index = self.position(key)?;
+        let (_, previous) = self.entries.remove(index);
+        Some(Box::new(previous))
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn iter(&self) -> Iter<'_> {
+        let iter = self
+            .entries
+            .iter()
+            .map(|(key, value)| (key.as_reflect(), value.as_reflect()));
+        Box::new(iter)
+    }
+
+    fn iter_mut(&mut self) -> PairIterMut<'_, dyn Reflect> {
+        let iter = self
+            .entries
+            .iter_mut()
+            .map(|(key, value)| (key.as_reflect(), value.as_reflect_mut()));
+        Box::new(iter)
+    }
+}
+
+impl Reflect for OrderedMap {
+    fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
+        impl Typed for OrderedMap {
+            fn build(graph: &mut TypeGraph) -> NodeId {
+                graph.get_or_build_node_with::<Self, _>(|graph| {
+                    OpaqueNode::new::<Self>(Default::default(), graph)
+                })
+            }
+        }
+        <Self as Typed>::type_info()
+    }
+
+    trivial_reflect_methods!();
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        if let Some(map) = value.reflect_ref().as_map() {
+            for (key, new_value) in map.iter() {
+                if let Some(value) = Map::get_mut(self, key) {
+                    value.patch(new_value);
+                }
+            }
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        self.clone().into()
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone())
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.entries()).finish()
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Map(self)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::Map(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Map(self)
+    }
+}
+
+impl FromReflect for OrderedMap {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        let map = reflect.reflect_ref().as_map()?;
+        Some(
+            map.iter()
+                .map(|(key, value)| (key.to_value(), value.to_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for OrderedMap
+where
+    K: Into<Value>,
+    V: Into<Value>,
+{
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut out = Self::new();
+        for (key, value) in iter {
+            out.set_entry(key, value);
+        }
+        out
+    }
+}
@@ -0,0 +1,360 @@
+//! Three-way merging of reflected values.
+//!
+//! [`merge3`] combines the changes made to a common ancestor in two diverging copies, for example
+//! when two people edit the same value at the same time. Changes to different parts of the value
+//! merge cleanly, while changes to the same part are reported as [`Conflict`]s.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::key_path;
+//! use mirror_mirror::merge::merge3;
+//! use mirror_mirror::FromReflect;
+//! use mirror_mirror::Reflect;
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Entity {
+//!     name: String,
+//!     position: (f32, f32),
+//! }
+//!
+//! let base = Entity {
+//!     name: "crate".to_owned(),
+//!     position: (0.0, 0.0),
+//! };
+//!
+//! let mut ours = base.clone();
+//! ours.name = "barrel".to_owned();
+//!
+//! let mut theirs = base.clone();
+//! theirs.position.1 = 5.0;
+//!
+//! let merged = merge3(&base, &ours, &theirs).unwrap();
+//! assert_eq!(
+//!     Entity::from_reflect(&merged).unwrap(),
+//!     Entity {
+//!         name: "barrel".to_owned(),
+//!         position: (0.0, 5.0),
+//!     }
+//! );
+//!
+//! theirs.name = "box".to_owned();
+//! let conflicts = merge3(&base, &ours, &theirs).unwrap_err();
+//! assert_eq!(conflicts.len(), 1);
+//! assert_eq!(conflicts[0].path(), &key_path!(.name));
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::enum_::EnumValue;
+use crate::enum_::VariantField;
+use crate::enum_::VariantKind;
+use crate::key_path::KeyPath;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+use crate::Enum;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::Struct;
+use crate::Value;
+
+/// Merge the changes made to `base` in `ours` with those made in `theirs`.
+///
+/// Values are compared recursively, so if one side changed a field and the other side didn't, the
+/// changed version is used. If both sides changed the same field to different values, and the
+/// field can't itself be merged, that's a [`Conflict`]. Structs with different fields, enums with
+/// different variants, and tuples or arrays with different lengths can't be merged.
+///
+/// Lists are merged element by element, by index. Elements that were pushed on one side are kept,
+/// and elements that were popped on one side are removed unless the other side changed them.
+/// Map entries are merged by key in the same way.
+///
+/// The merged value is a [`Value`] that can be converted back with
+/// [`FromReflect`](crate::FromReflect). All conflicts are returned if there are any.
+pub fn merge3(
+    base: &dyn Reflect,
+    ours: &dyn Reflect,
+    theirs: &dyn Reflect,
+) -> Result<Value, Vec<Conflict>> {
+    let mut conflicts = Vec::new();
+    let merged = merge_at(base, ours, theirs, &mut KeyPath::default(), &mut conflicts);
+    if conflicts.is_empty() {
+        Ok(merged)
+    } else {
+        Err(conflicts)
+    }
+}
+
+/// Changes to the same part of a value that [`merge3`] couldn't merge.
+///
+/// The values are `None` if the element or map entry didn't exist on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    path: KeyPath,
+    base: Option<Value>,
+    ours: Option<Value>,
+    theirs: Option<Value>,
+}
+
+impl Conflict {
+    /// The path, relative to the merged values, of the conflicting changes.
+    pub fn path(&self) -> &KeyPath {
+        &self.path
+    }
+
+    pub fn base(&self) -> Option<&Value> {
+        self.base.as_ref()
+    }
+
+    pub fn ours(&self) -> Option<&Value> {
+        self.ours.as_ref()
+    }
+
+    pub fn theirs(&self) -> Option<&Value> {
+        self.theirs.as_ref()
+    }
+}
+
+fn merge_at(
+    base: &dyn Reflect,
+    ours: &dyn Reflect,
+    theirs: &dyn Reflect,
+    path: &mut KeyPath,
+    conflicts: &mut Vec<Conflict>,
+) -> Value {
+    if eq(ours, theirs) || eq(base, theirs) {
+        return ours.to_value();
+    }
+    if eq(base, ours) {
+        return theirs.to_value();
+    }
+
+    match (base.reflect_ref(), ours.reflect_ref(), theirs.reflect_ref()) {
+        (ReflectRef::Struct(base), ReflectRef::Struct(ours), ReflectRef::Struct(theirs))
+            if same_fields(base, ours) && same_fields(base, theirs) =>
+        {
+            let mut value = StructValue::new();
+            for (name, ours) in ours.fields() {
+                let base = base.field(name).expect("checked above");
+                let theirs = theirs.field(name).expect("checked above");
+                path.push_field(name);
+                value.set_field(name, merge_at(base, ours, theirs, path, conflicts));
+                path.pop();
+            }
+            value.into()
+        }
+        (
+            ReflectRef::TupleStruct(base),
+            ReflectRef::TupleStruct(ours),
+            ReflectRef::TupleStruct(theirs),
+        ) if base.fields_len() == ours.fields_len() && base.fields_len() == theirs.fields_len() => {
+            let mut value = TupleStructValue::new();
+            for (index, ((base, ours), theirs)) in base
+                .fields()
+                .zip(ours.fields())
+                .zip(theirs.fields())
+                .enumerate()
+            {
+                path.push_field(index);
+                value.push_field(merge_at(base, ours, theirs, path, conflicts));
+                path.pop();
+            }
+            value.into()
+        }
+        (ReflectRef::Tuple(base), ReflectRef::Tuple(ours), ReflectRef::Tuple(theirs))
+            if base.fields_len() == ours.fields_len()
+                && base.fields_len() == theirs.fields_len() =>
+        {
+            let mut value = TupleValue::new();
+            for (index, ((base, ours), theirs)) in base
+                .fields()
+                .zip(ours.fields())
+                .zip(theirs.fields())
+                .enumerate()
+            {
+                path.push_field(index);
+                value.push_field(merge_at(base, ours, theirs, path, conflicts));
+                path.pop();
+            }
+            value.into()
+        }
+        (ReflectRef::Enum(base), ReflectRef::Enum(ours), ReflectRef::Enum(theirs))
+            if same_variant(base, ours) && same_variant(base, theirs) =>
+        {
+            merge_variant(base, ours, theirs, path, conflicts)
+        }
+        (ReflectRef::Array(base), ReflectRef::Array(ours), ReflectRef::Array(theirs))
+            if base.len() == ours.len() && base.len() == theirs.len() =>
+        {
+            let mut list = Vec::with_capacity(ours.len());
+            for (index, ((base, ours), theirs)) in
+                base.iter().zip(ours.iter()).zip(theirs.iter()).enumerate()
+            {
+                path.push_get(index);
+                list.push(merge_at(base, ours, theirs, path, conflicts));
+                path.pop();
+            }
+            Value::List(Box::new(list))
+        }
+        (ReflectRef::List(base), ReflectRef::List(ours), ReflectRef::List(theirs)) => {
+            let len = base.len().max(ours.len()).max(theirs.len());
+            let mut list = Vec::with_capacity(len);
+            for index in 0..len {
+                path.push_get(index);
+                list.extend(merge_opt(
+                    base.get(index),
+                    ours.get(index),
+                    theirs.get(index),
+                    path,
+                    conflicts,
+                ));
+                path.pop();
+            }
+            Value::List(Box::new(list))
+        }
+        (ReflectRef::Map(base), ReflectRef::Map(ours_map), ReflectRef::Map(theirs)) => {
+            let keys = ours_map
+                .iter()
+                .map(|(key, _)| key)
+                .chain(
+                    theirs
+                        .iter()
+                        .map(|(key, _)| key)
+                        .filter(|key| ours_map.get(*key).is_none()),
+                )
+                .chain(
+                    base.iter()
+                        .map(|(key, _)| key)
+                        .filter(|key| ours_map.get(*key).is_none() && theirs.get(*key).is_none()),
+                )
+                .collect::<Vec<_>>();
+
+            // start from our map so it keeps its kind and order
+            let mut value = ours.to_value();
+            let map = value.as_map_mut().expect("the value of a map is a map");
+            for key in keys {
+                path.push_get(key.to_value());
+                let merged = merge_opt(
+                    base.get(key),
+                    ours_map.get(key),
+                    theirs.get(key),
+                    path,
+                    conflicts,
+                );
+                path.pop();
+                match merged {
+                    Some(merged) => map.insert(key, &merged),
+                    None => map.remove(key),
+                };
+            }
+            value
+        }
+        _ => {
+            push_conflict(Some(base), Some(ours), Some(theirs), path, conflicts);
+            ours.to_value()
+        }
+    }
+}
+
+/// Like [`merge_at`] but for list elements and map entries, which might not exist on every side.
+fn merge_opt(
+    base: Option<&dyn Reflect>,
+    ours: Option<&dyn Reflect>,
+    theirs: Option<&dyn Reflect>,
+    path: &mut KeyPath,
+    conflicts: &mut Vec<Conflict>,
+) -> Option<Value> {
+    if let (Some(base), Some(ours), Some(theirs)) = (base, ours, theirs) {
+        return Some(merge_at(base, ours, theirs, path, conflicts));
+    }
+
+    let eq_opt = |a: Option<&dyn Reflect>, b: Option<&dyn Reflect>| match (a, b) {
+        (Some(a), Some(b)) => eq(a, b),
+        (None, None) => true,
+        _ => false,
+    };
+
+    if eq_opt(ours, theirs) || eq_opt(base, theirs) {
+        ours.map(Reflect::to_value)
+    } else if eq_opt(base, ours) {
+        theirs.map(Reflect::to_value)
+    } else {
+        push_conflict(base, ours, theirs, path, conflicts);
+        ours.map(Reflect::to_value)
+    }
+}
+
+fn merge_variant(
+    base: &dyn Enum,
+    ours: &dyn Enum,
+    theirs: &dyn Enum,
+    path: &mut KeyPath,
+    conflicts: &mut Vec<Conflict>,
+) -> Value {
+    let name = ours.variant_name();
+    path.push_variant(name);
+    let value = match ours.variant_kind() {
+        VariantKind::Struct => {
+            let mut value = EnumValue::new_struct_variant(name);
+            for field in ours.fields() {
+                let VariantField::Struct(field_name, ours) = field else { continue };
+                let base = base.field(field_name).expect("checked by `same_variant`");
+                let theirs = theirs.field(field_name).expect("checked by `same_variant`");
+                path.push_field(field_name);
+                value.set_struct_field(field_name, merge_at(base, ours, theirs, path, conflicts));
+                path.pop();
+            }
+            value.finish()
+        }
+        VariantKind::Tuple => {
+            let mut value = EnumValue::new_tuple_variant(name);
+            for (index, field) in ours.fields().enumerate() {
+                let VariantField::Tuple(ours) = field else { continue };
+                let base = base.field_at(index).expect("checked by `same_variant`");
+                let theirs = theirs.field_at(index).expect("checked by `same_variant`");
+                path.push_field(index);
+                value.push_tuple_field(merge_at(base, ours, theirs, path, conflicts));
+                path.pop();
+            }
+            value.finish()
+        }
+        VariantKind::Unit => EnumValue::new_unit_variant(name),
+    };
+    path.pop();
+    value.into()
+}
+
+fn push_conflict(
+    base: Option<&dyn Reflect>,
+    ours: Option<&dyn Reflect>,
+    theirs: Option<&dyn Reflect>,
+    path: &KeyPath,
+    conflicts: &mut Vec<Conflict>,
+) {
+    conflicts.push(Conflict {
+        path: path.clone(),
+        base: base.map(Reflect::to_value),
+        ours: ours.map(Reflect::to_value),
+        theirs: theirs.map(Reflect::to_value),
+    });
+}
+
+fn eq(a: &dyn Reflect, b: &dyn Reflect) -> bool {
+    a.reflect_partial_eq(b) == Some(true)
+}
+
+fn same_fields(a: &dyn Struct, b: &dyn Struct) -> bool {
+    a.fields_len() == b.fields_len() && a.fields().all(|(name, _)| b.field(name).is_some())
+}
+
+fn same_variant(a: &dyn Enum, b: &dyn Enum) -> bool {
+    a.variant_name() == b.variant_name()
+        && a.variant_kind() == b.variant_kind()
+        && a.fields_len() == b.fields_len()
+        && a.fields().all(|field| match field {
+            VariantField::Struct(name, _) => b.field(name).is_some(),
+            VariantField::Tuple(_) => true,
+        })
+}
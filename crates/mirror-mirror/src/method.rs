@@ -0,0 +1,231 @@
+//! Calling methods on reflected values.
+//!
+//! Inherent methods marked with `#[reflect(func)]`, inside an `impl` block annotated with
+//! [`#[reflect_methods]`](crate::reflect_methods), can be listed and called at runtime. The type
+//! must also opt in with `#[reflect(opt_in(Methods))]` so the methods are available through
+//! [`Reflect::methods`].
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::reflect_methods;
+//! use mirror_mirror::Reflect;
+//!
+//! #[derive(Reflect, Clone, Debug)]
+//! #[reflect(opt_in(Methods))]
+//! struct Player {
+//!     health: u32,
+//! }
+//!
+//! #[reflect_methods]
+//! impl Player {
+//!     /// Restore some health.
+//!     #[reflect(func)]
+//!     fn heal(&mut self, amount: u32) {
+//!         self.health += amount;
+//!     }
+//!
+//!     #[reflect(func)]
+//!     fn is_alive(&self) -> bool {
+//!         self.health > 0
+//!     }
+//! }
+//!
+//! let mut player = Player { health: 0 };
+//! let player: &mut dyn Reflect = &mut player;
+//!
+//! let heal = player.method("heal").unwrap();
+//! assert_eq!(heal.params()[0].name(), "amount");
+//! assert_eq!(heal.docs(), [" Restore some health."]);
+//!
+//! player.call_method("heal", &[&10_u32]).unwrap();
+//! let is_alive = player.call_method("is_alive", &[]).unwrap();
+//! assert_eq!(is_alive.downcast_ref::<bool>(), Some(&true));
+//! ```
+//!
+//! Arguments are converted with [`FromReflect`](crate::FromReflect), so they can also be
+//! [`Value`](crate::Value)s. Methods that take `self` by value, or have generic parameters,
+//! aren't supported.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt;
+
+use crate::Reflect;
+
+/// Implemented by [`#[reflect_methods]`](crate::reflect_methods) for types with
+/// `#[reflect(func)]` methods.
+pub trait ReflectMethods {
+    const METHODS: &'static [Method];
+}
+
+/// The signature of the functions that call a [`Method`].
+type CallFn = fn(&mut dyn Reflect, &[&dyn Reflect]) -> Result<Box<dyn Reflect>, CallError>;
+
+/// A method that can be called on a reflected value.
+#[derive(Clone, Copy)]
+pub struct Method {
+    name: &'static str,
+    receiver: Receiver,
+    params: &'static [Param],
+    return_type: &'static str,
+    docs: &'static [&'static str],
+    call: CallFn,
+}
+
+impl Method {
+    #[doc(hidden)]
+    pub const fn __new(
+        name: &'static str,
+        receiver: Receiver,
+        params: &'static [Param],
+        return_type: &'static str,
+        docs: &'static [&'static str],
+        call: CallFn,
+    ) -> Self {
+        Self {
+            name,
+            receiver,
+            params,
+            return_type,
+            docs,
+            call,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn receiver(&self) -> Receiver {
+        self.receiver
+    }
+
+    /// The parameters, not including the receiver.
+    pub fn params(&self) -> &'static [Param] {
+        self.params
+    }
+
+    /// The return type, as written in the method signature.
+    pub fn return_type(&self) -> &'static str {
+        self.return_type
+    }
+
+    pub fn docs(&self) -> &'static [&'static str] {
+        self.docs
+    }
+
+    /// Call the method on `receiver`, converting `args` to the parameter types with
+    /// [`FromReflect`](crate::FromReflect).
+    ///
+    /// Methods without a return type return `()`.
+    pub fn call(
+        &self,
+        receiver: &mut dyn Reflect,
+        args: &[&dyn Reflect],
+    ) -> Result<Box<dyn Reflect>, CallError> {
+        if args.len() != self.params.len() {
+            return Err(CallError::ArgumentCount {
+                method: self.name,
+                expected: self.params.len(),
+                got: args.len(),
+            });
+        }
+        (self.call)(receiver, args)
+    }
+}
+
+impl fmt::Debug for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Method")
+            .field("name", &self.name)
+            .field("receiver", &self.receiver)
+            .field("params", &self.params)
+            .field("return_type", &self.return_type)
+            .field("docs", &self.docs)
+            .finish()
+    }
+}
+
+/// How a [`Method`] takes `self`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Receiver {
+    /// `&self`
+    Ref,
+    /// `&mut self`
+    Mut,
+}
+
+/// A parameter of a [`Method`].
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    name: &'static str,
+    type_name: &'static str,
+}
+
+impl Param {
+    #[doc(hidden)]
+    pub const fn __new(name: &'static str, type_name: &'static str) -> Self {
+        Self { name, type_name }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The type of the parameter, as written in the method signature.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+/// The error returned by [`Method::call`] and `call_method` on `dyn Reflect`.
+#[derive(Debug, Clone)]
+pub enum CallError {
+    /// The value doesn't have a method with the name.
+    NotFound { method: String },
+    /// The receiver isn't the type the method is defined on.
+    Receiver { method: &'static str },
+    /// The wrong number of arguments was given.
+    ArgumentCount {
+        method: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// An argument couldn't be converted to the parameter's type.
+    ArgumentType {
+        method: &'static str,
+        param: &'static str,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallError::NotFound { method } => write!(f, "no method named `{method}`"),
+            CallError::Receiver { method } => {
+                write!(f, "`{method}` called on a value of the wrong type")
+            }
+            CallError::ArgumentCount {
+                method,
+                expected,
+                got,
+            } => write!(
+                f,
+                "`{method}` takes {expected} argument(s) but {got} were given"
+            ),
+            CallError::ArgumentType {
+                method,
+                param,
+                expected,
+            } => write!(
+                f,
+                "argument `{param}` of `{method}` must be convertible to `{expected}`"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CallError {}
@@ -0,0 +1,217 @@
+//! Print values the way they look in Rust, using their type information.
+//!
+//! [`Reflect::debug`] prints what is actually stored, which for a [`Value`] means the internals of
+//! [`StructValue`](crate::struct_::StructValue) and friends. [`pretty`] instead takes the type
+//! information of the value, so a [`Value`] prints the same as the value it was created from.
+//!
+//! Use `{}` to print on a single line and `{:#}` to print on multiple lines. Values whose
+//! [`Reflect::reflect_display`] returns `Some` are printed with it rather than field by field.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::pretty::pretty;
+//! use mirror_mirror::Reflect;
+//! use mirror_mirror::Typed;
+//!
+//! #[derive(Reflect, Clone, Debug)]
+//! struct Player {
+//!     health: u32,
+//!     position: Vec2,
+//!     weapon: Option<Weapon>,
+//! }
+//!
+//! #[derive(Reflect, Clone, Debug)]
+//! struct Vec2 {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! #[derive(Reflect, Clone, Debug)]
+//! enum Weapon {
+//!     Sword { damage: u32 },
+//! }
+//!
+//! let player = Player {
+//!     health: 10,
+//!     position: Vec2 { x: 1.0, y: 2.0 },
+//!     weapon: Some(Weapon::Sword { damage: 3 }),
+//! };
+//! let value = player.to_value();
+//! let type_info = <Player as Typed>::type_info();
+//!
+//! // prints `my_crate::Player { health: 10, position: my_crate::Vec2 { x: 1.0, y: 2.0 },
+//! // weapon: Some(Sword { damage: 3 }) }`
+//! println!("{}", pretty(&value, &type_info));
+//!
+//! assert_eq!(
+//!     pretty(&value, &type_info).to_string(),
+//!     pretty(&player, &type_info).to_string(),
+//! );
+//! ```
+//!
+//! [`Value`]: crate::Value
+
+use core::fmt;
+
+use crate::enum_::VariantField;
+use crate::enum_::VariantKind;
+use crate::type_info::Type;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::TypeRoot;
+
+/// Pretty print `value` using the type names, and variant names, from `type_info`.
+///
+/// `value` should be of the type described by `type_info`, or a [`Value`](crate::Value) created
+/// from one. Parts of the value that don't match the type information fall back to the names
+/// reported by the value itself.
+pub fn pretty<'a>(value: &'a dyn Reflect, type_info: &'a TypeRoot) -> Pretty<'a> {
+    Pretty {
+        value,
+        ty: Some(type_info.get_type()),
+    }
+}
+
+/// A value formatted with its type information. Returned by [`pretty`].
+#[derive(Clone, Copy)]
+pub struct Pretty<'a> {
+    value: &'a dyn Reflect,
+    ty: Option<Type<'a>>,
+}
+
+impl<'a> Pretty<'a> {
+    fn child(value: &'a dyn Reflect, ty: Option<Type<'a>>) -> Self {
+        Self { value, ty }
+    }
+}
+
+impl fmt::Display for Pretty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+// implemented through `Debug` so the builders in `core::fmt` take care of indentation
+impl fmt::Debug for Pretty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn scalar(scalar: &dyn fmt::Debug, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if f.alternate() {
+                write!(f, "{scalar:#?}")
+            } else {
+                write!(f, "{scalar:?}")
+            }
+        }
+
+        if let Some(display) = self.value.reflect_display() {
+            return fmt::Display::fmt(display, f);
+        }
+
+        let ty = self.ty;
+        match self.value.reflect_ref() {
+            ReflectRef::Struct(inner) => {
+                let struct_type = ty.and_then(Type::as_struct);
+                let name = struct_type.map_or(inner.type_name(), |ty| ty.type_name());
+                let mut f = f.debug_struct(name);
+                for (name, value) in inner.fields() {
+                    let field = struct_type.and_then(|ty| ty.field_type(name));
+                    f.field(name, &Pretty::child(value, field.map(|f| f.get_type())));
+                }
+                f.finish()
+            }
+            ReflectRef::TupleStruct(inner) => {
+                let tuple_struct_type = ty.and_then(Type::as_tuple_struct);
+                let name = tuple_struct_type.map_or(inner.type_name(), |ty| ty.type_name());
+                let mut f = f.debug_tuple(name);
+                for (index, value) in inner.fields().enumerate() {
+                    let field = tuple_struct_type.and_then(|ty| ty.field_type_at(index));
+                    f.field(&Pretty::child(value, field.map(|f| f.get_type())));
+                }
+                f.finish()
+            }
+            ReflectRef::Tuple(inner) => {
+                let tuple_type = ty.and_then(Type::as_tuple);
+                let mut f = f.debug_tuple("");
+                for (index, value) in inner.fields().enumerate() {
+                    let field = tuple_type.and_then(|ty| ty.field_type_at(index));
+                    f.field(&Pretty::child(value, field.map(|f| f.get_type())));
+                }
+                f.finish()
+            }
+            ReflectRef::Enum(inner) => {
+                let variant = ty
+                    .and_then(Type::as_enum)
+                    .and_then(|ty| ty.variant(inner.variant_name()));
+                match inner.variant_kind() {
+                    VariantKind::Struct => {
+                        let mut f = f.debug_struct(inner.variant_name());
+                        for field in inner.fields() {
+                            if let VariantField::Struct(name, value) = field {
+                                let field = variant.and_then(|variant| variant.field_type(name));
+                                f.field(name, &Pretty::child(value, field.map(|f| f.get_type())));
+                            }
+                        }
+                        f.finish()
+                    }
+                    VariantKind::Tuple => {
+                        let mut f = f.debug_tuple(inner.variant_name());
+                        for (index, field) in inner.fields().enumerate() {
+                            if let VariantField::Tuple(value) = field {
+                                let field =
+                                    variant.and_then(|variant| variant.field_type_at(index));
+                                f.field(&Pretty::child(value, field.map(|f| f.get_type())));
+                            }
+                        }
+                        f.finish()
+                    }
+                    VariantKind::Unit => f.write_str(inner.variant_name()),
+                }
+            }
+            ReflectRef::Array(inner) => {
+                let element_type = ty.and_then(Type::as_array).map(|ty| ty.element_type());
+                f.debug_list()
+                    .entries(inner.iter().map(|value| Pretty::child(value, element_type)))
+                    .finish()
+            }
+            ReflectRef::List(inner) => {
+                let element_type = ty.and_then(Type::as_list).map(|ty| ty.element_type());
+                f.debug_list()
+                    .entries(inner.iter().map(|value| Pretty::child(value, element_type)))
+                    .finish()
+            }
+            ReflectRef::Map(inner) => {
+                let map_type = ty.and_then(Type::as_map);
+                let key_type = map_type.map(|ty| ty.key_type());
+                let value_type = map_type.map(|ty| ty.value_type());
+                f.debug_map()
+                    .entries(inner.iter().map(|(key, value)| {
+                        (
+                            Pretty::child(key, key_type),
+                            Pretty::child(value, value_type),
+                        )
+                    }))
+                    .finish()
+            }
+            ReflectRef::Scalar(inner) => match inner {
+                ScalarRef::usize(inner) => scalar(&inner, f),
+                ScalarRef::u8(inner) => scalar(&inner, f),
+                ScalarRef::u16(inner) => scalar(&inner, f),
+                ScalarRef::u32(inner) => scalar(&inner, f),
+                ScalarRef::u64(inner) => scalar(&inner, f),
+                ScalarRef::u128(inner) => scalar(&inner, f),
+                ScalarRef::i8(inner) => scalar(&inner, f),
+                ScalarRef::i16(inner) => scalar(&inner, f),
+                ScalarRef::i32(inner) => scalar(&inner, f),
+                ScalarRef::i64(inner) => scalar(&inner, f),
+                ScalarRef::i128(inner) => scalar(&inner, f),
+                ScalarRef::bool(inner) => scalar(&inner, f),
+                ScalarRef::char(inner) => scalar(&inner, f),
+                ScalarRef::f32(inner) => scalar(&inner, f),
+                ScalarRef::f64(inner) => scalar(&inner, f),
+                ScalarRef::String(inner) => scalar(&inner, f),
+            },
+            ReflectRef::Opaque(inner) => inner.debug(f),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Deserializing types that are only known by name at runtime.
+//!
+//! Types are added to a [`TypeRegistry`] up front. serde input can then be deserialized straight
+//! into a `Box<dyn Reflect>` holding the concrete type, rather than into a [`Value`], by looking
+//! the type up by name. This is useful when the names come from data, such as a list of
+//! components in a manifest.
+//!
+//! Requires the `erased-serde` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::registry::TypeRegistry;
+//! use mirror_mirror::Reflect;
+//! use serde::de::DeserializeSeed;
+//! use serde::Deserialize;
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq, Deserialize)]
+//! struct Health {
+//!     current: u32,
+//!     max: u32,
+//! }
+//!
+//! let mut registry = TypeRegistry::new();
+//! registry.register_with_name::<Health>("Health");
+//!
+//! // deserialize a value of a type given by name
+//! let mut json = serde_json::Deserializer::from_str(r#"{ "current": 5, "max": 10 }"#);
+//! let health = registry.deserialize("Health", &mut json).unwrap();
+//! assert_eq!(
+//!     health.downcast_ref::<Health>(),
+//!     Some(&Health { current: 5, max: 10 })
+//! );
+//!
+//! // or let the input say which type it is
+//! let mut json = serde_json::Deserializer::from_str(r#"{ "Health": { "current": 1, "max": 3 } }"#);
+//! let health = registry.deserializer().deserialize(&mut json).unwrap();
+//! assert_eq!(
+//!     health.downcast_ref::<Health>(),
+//!     Some(&Health { current: 1, max: 3 })
+//! );
+//! ```
+//!
+//! [`Value`]: crate::Value
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::de::DeserializeSeed;
+use serde::de::Deserializer;
+use serde::de::Error as _;
+use serde::de::IgnoredAny;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+
+#[cfg(feature = "type_info")]
+use crate::type_info::TypeRoot;
+use crate::Reflect;
+use crate::Typed;
+
+/// Types that can be deserialized by name. See the [module docs](self) for more details.
+#[derive(Debug, Clone, Default)]
+pub struct TypeRegistry {
+    registrations: BTreeMap<String, Registration>,
+}
+
+impl TypeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `T` under its full type name, as given by [`core::any::type_name`].
+    pub fn register<T>(&mut self) -> &mut Self
+    where
+        T: Reflect + Typed + DeserializeOwned,
+    {
+        self.register_with_name::<T>(core::any::type_name::<T>())
+    }
+
+    /// Register `T` under `name`, replacing the type previously registered under it, if any.
+    pub fn register_with_name<T>(&mut self, name: impl Into<String>) -> &mut Self
+    where
+        T: Reflect + Typed + DeserializeOwned,
+    {
+        self.registrations
+            .insert(name.into(), Registration::of::<T>());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Registration> {
+        self.registrations.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.registrations.contains_key(name)
+    }
+
+    /// The names and registrations of all the types, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Registration)> {
+        self.registrations
+            .iter()
+            .map(|(name, registration)| (&**name, registration))
+    }
+
+    /// Deserialize a value of the type registered under `name`.
+    ///
+    /// Fails if no type is registered under `name`.
+    pub fn deserialize<'de, D>(
+        &self,
+        name: &str,
+        deserializer: D,
+    ) -> Result<Box<dyn Reflect>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.get(name)
+            .ok_or_else(|| unknown_type(name))?
+            .deserialize(deserializer)
+    }
+
+    /// A [`DeserializeSeed`] for a map with a single entry, from the name of a registered type to
+    /// a value of that type, such as `{ "Health": { "current": 5, "max": 10 } }`.
+    pub fn deserializer(&self) -> ReflectDeserializer<'_> {
+        ReflectDeserializer { registry: self }
+    }
+}
+
+/// A type in a [`TypeRegistry`].
+///
+/// `&Registration` is a [`DeserializeSeed`] for values of the type, so it can be used with
+/// [`MapAccess::next_value_seed`] and similar methods.
+#[derive(Clone, Copy)]
+pub struct Registration {
+    type_name: &'static str,
+    deserialize: for<'de> fn(
+        &mut dyn erased_serde::Deserializer<'de>,
+    ) -> Result<Box<dyn Reflect>, erased_serde::Error>,
+    #[cfg(feature = "type_info")]
+    type_info: fn() -> TypeRoot,
+}
+
+impl Registration {
+    pub fn of<T>() -> Self
+    where
+        T: Reflect + Typed + DeserializeOwned,
+    {
+        Self {
+            type_name: core::any::type_name::<T>(),
+            deserialize: deserialize_boxed::<T>,
+            #[cfg(feature = "type_info")]
+            type_info: <T as Typed>::type_info,
+        }
+    }
+
+    /// The full name of the type, which isn't necessarily the name it's registered under.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    #[cfg(feature = "type_info")]
+    pub fn type_info(&self) -> TypeRoot {
+        (self.type_info)()
+    }
+
+    pub fn deserialize<'de, D>(&self, deserializer: D) -> Result<Box<dyn Reflect>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut deserializer = <dyn erased_serde::Deserializer<'de>>::erase(deserializer);
+        (self.deserialize)(&mut deserializer).map_err(D::Error::custom)
+    }
+}
+
+impl fmt::Debug for Registration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registration")
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for &Registration {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Registration::deserialize(self, deserializer)
+    }
+}
+
+fn deserialize_boxed<T>(
+    deserializer: &mut dyn erased_serde::Deserializer<'_>,
+) -> Result<Box<dyn Reflect>, erased_serde::Error>
+where
+    T: Reflect + DeserializeOwned,
+{
+    let value = erased_serde::deserialize::<T>(deserializer)?;
+    Ok(Box::new(value))
+}
+
+/// Deserializes a value of a type that's given by name in the input.
+///
+/// Created with [`TypeRegistry::deserializer`].
+#[derive(Debug, Clone, Copy)]
+pub struct ReflectDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ReflectDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map with a single entry from a type name to a value of that type")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let name = map
+            .next_key::<String>()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let registration = self
+            .registry
+            .get(&name)
+            .ok_or_else(|| unknown_type::<A::Error>(&name))?;
+        let value = map.next_value_seed(registration)?;
+        if map.next_key::<IgnoredAny>()?.is_some() {
+            return Err(A::Error::invalid_length(2, &self));
+        }
+        Ok(value)
+    }
+}
+
+fn unknown_type<E>(name: &str) -> E
+where
+    E: serde::de::Error,
+{
+    E::custom(format_args!("unknown type `{name}`"))
+}
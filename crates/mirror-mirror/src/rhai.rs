@@ -0,0 +1,551 @@
+//! Expose reflected values to [rhai] scripts.
+//!
+//! [`register`] adds [`Value`] to an [`Engine`] as a custom type, so scripts can read and modify
+//! reflected data without any per-type glue:
+//!
+//! - `value["field"]` and `value[0]` get and set struct fields, tuple fields, list elements, and
+//!   map entries. Enum fields are accessed through the active variant.
+//! - `value.get(path)` and `value.set(path, new)` do the same but with a path, which is an
+//!   array of field names and indices such as `["players", 0, "score"]`.
+//! - `value.len()`, `value.keys()`, and `value.values()` allow iterating lists, arrays, maps,
+//!   and structs.
+//! - `value.variant()` returns the name of an enum's active variant.
+//! - `value.to_dynamic()` converts the whole value into rhai's own types. Enums become an object
+//!   map with the name of the active variant under `variant` and its fields under `fields`.
+//!
+//! Scalars are converted to rhai's integer, float, bool, char, and string types when read and
+//! back into the field's actual type when written. Anything else is handed to scripts as a
+//! [`Value`].
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::FromReflect;
+//! use mirror_mirror::Reflect;
+//!
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Player {
+//!     name: String,
+//!     health: u8,
+//! }
+//!
+//! let mut engine = rhai::Engine::new();
+//! mirror_mirror::rhai::register(&mut engine);
+//!
+//! let mut scope = rhai::Scope::new();
+//! let player = Player {
+//!     name: "Alice".to_owned(),
+//!     health: 10,
+//! };
+//! scope.push("player", player.to_value());
+//!
+//! engine
+//!     .run_with_scope(&mut scope, r#"player["health"] += 5;"#)
+//!     .unwrap();
+//!
+//! let player = Player::from_reflect(&scope.get_value::<mirror_mirror::Value>("player").unwrap())
+//!     .unwrap();
+//! assert_eq!(player.health, 15);
+//! ```
+//!
+//! [rhai]: https://rhai.rs
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use ::rhai::Array;
+use ::rhai::Dynamic;
+use ::rhai::Engine;
+use ::rhai::EvalAltResult;
+use ::rhai::ImmutableString;
+use ::rhai::Map as RhaiMap;
+use ::rhai::FLOAT;
+use ::rhai::INT;
+
+use crate::convert::convert_to_value;
+use crate::enum_::VariantField;
+use crate::enum_::VariantKind;
+use crate::key_path::GetPath;
+use crate::key_path::KeyPath;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Value;
+
+type RhaiResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Register [`Value`] and the functions described in the [module docs](self) with `engine`.
+pub fn register(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<Value>("Value")
+        .register_indexer_get(|value: &mut Value, key: ImmutableString| {
+            get(value, &Array::from([key.into()]))
+        })
+        .register_indexer_get(|value: &mut Value, index: INT| {
+            get(value, &Array::from([index.into()]))
+        })
+        .register_indexer_set(|value: &mut Value, key: ImmutableString, new: Dynamic| {
+            set(value, &Array::from([key.into()]), new)
+        })
+        .register_indexer_set(|value: &mut Value, index: INT, new: Dynamic| {
+            set(value, &Array::from([index.into()]), new)
+        })
+        .register_fn("get", |value: &mut Value, path: Array| get(value, &path))
+        .register_fn("set", |value: &mut Value, path: Array, new: Dynamic| {
+            set(value, &path, new)
+        })
+        .register_fn("len", |value: &mut Value| len(value))
+        .register_fn("keys", |value: &mut Value| keys(value))
+        .register_fn("values", |value: &mut Value| values(value))
+        .register_fn("variant", |value: &mut Value| variant(value))
+        .register_fn("to_dynamic", |value: &mut Value| to_dynamic_deep(value))
+        .register_fn("to_string", |value: &mut Value| {
+            format!("{:?}", value.as_reflect())
+        })
+        .register_fn("to_debug", |value: &mut Value| {
+            format!("{:?}", value.as_reflect())
+        });
+}
+
+/// Convert a reflected value into a [`Dynamic`].
+///
+/// Scalars become rhai's own types, if they fit, and everything else becomes a [`Value`].
+pub fn to_dynamic(value: &dyn Reflect) -> Dynamic {
+    if let Some(scalar) = value.as_scalar() {
+        if let Some(dynamic) = scalar_to_dynamic(scalar) {
+            return dynamic;
+        }
+    }
+    Dynamic::from(value.to_value())
+}
+
+/// Convert a [`Dynamic`] into a [`Value`].
+///
+/// rhai arrays become lists and object maps become maps with string keys. Returns `None` for
+/// types that have no `Value` representation, such as `()` or function pointers.
+pub fn from_dynamic(dynamic: Dynamic) -> Option<Value> {
+    if dynamic.is::<Value>() {
+        return dynamic.try_cast::<Value>();
+    }
+    if dynamic.is::<INT>() {
+        return dynamic.try_cast::<INT>().map(Value::from);
+    }
+    if dynamic.is::<FLOAT>() {
+        return dynamic.try_cast::<FLOAT>().map(Value::from);
+    }
+    if dynamic.is::<bool>() {
+        return dynamic.try_cast::<bool>().map(Value::from);
+    }
+    if dynamic.is::<char>() {
+        return dynamic.try_cast::<char>().map(Value::from);
+    }
+    if dynamic.is::<ImmutableString>() {
+        return dynamic
+            .try_cast::<ImmutableString>()
+            .map(|s| Value::from(s.to_string()));
+    }
+    if dynamic.is::<Array>() {
+        let array = dynamic.try_cast::<Array>()?;
+        return array
+            .into_iter()
+            .map(from_dynamic)
+            .collect::<Option<Vec<_>>>()
+            .map(|list| Value::List(Box::new(list)));
+    }
+    if dynamic.is::<RhaiMap>() {
+        let map = dynamic.try_cast::<RhaiMap>()?;
+        return map
+            .into_iter()
+            .map(|(key, value)| Some((Value::from(key.to_string()), from_dynamic(value)?)))
+            .collect::<Option<BTreeMap<_, _>>>()
+            .map(|map| Value::Map(Box::new(map)));
+    }
+    None
+}
+
+// `INT` is `i32` with rhai's `only_i32` feature, in which case `u32` doesn't always fit
+#[allow(clippy::unnecessary_fallible_conversions)]
+fn scalar_to_dynamic(scalar: ScalarRef<'_>) -> Option<Dynamic> {
+    let dynamic = match scalar {
+        ScalarRef::usize(n) => INT::try_from(n).ok()?.into(),
+        ScalarRef::u8(n) => INT::from(n).into(),
+        ScalarRef::u16(n) => INT::from(n).into(),
+        ScalarRef::u32(n) => INT::try_from(n).ok()?.into(),
+        ScalarRef::u64(n) => INT::try_from(n).ok()?.into(),
+        ScalarRef::u128(n) => INT::try_from(n).ok()?.into(),
+        ScalarRef::i8(n) => INT::from(n).into(),
+        ScalarRef::i16(n) => INT::from(n).into(),
+        ScalarRef::i32(n) => INT::from(n).into(),
+        ScalarRef::i64(n) => INT::try_from(n).ok()?.into(),
+        ScalarRef::i128(n) => INT::try_from(n).ok()?.into(),
+        ScalarRef::bool(b) => b.into(),
+        ScalarRef::char(c) => c.into(),
+        ScalarRef::f32(n) => (n as FLOAT).into(),
+        ScalarRef::f64(n) => (n as FLOAT).into(),
+        ScalarRef::String(s) => s.into(),
+    };
+    Some(dynamic)
+}
+
+fn to_dynamic_deep(value: &dyn Reflect) -> Dynamic {
+    match value.reflect_ref() {
+        ReflectRef::Struct(inner) => inner
+            .fields()
+            .map(|(name, value)| (name.into(), to_dynamic_deep(value)))
+            .collect::<RhaiMap>()
+            .into(),
+        ReflectRef::Enum(inner) => {
+            // the fields get their own entry so they can't clash with the variant name
+            let fields: Dynamic = match inner.variant_kind() {
+                VariantKind::Struct => inner
+                    .fields()
+                    .filter_map(|field| match field {
+                        VariantField::Struct(name, value) => {
+                            Some((name.into(), to_dynamic_deep(value)))
+                        }
+                        VariantField::Tuple(_) => None,
+                    })
+                    .collect::<RhaiMap>()
+                    .into(),
+                VariantKind::Tuple | VariantKind::Unit => inner
+                    .fields()
+                    .map(|field| match field {
+                        VariantField::Struct(_, value) | VariantField::Tuple(value) => {
+                            to_dynamic_deep(value)
+                        }
+                    })
+                    .collect::<Array>()
+                    .into(),
+            };
+            let mut map = RhaiMap::new();
+            map.insert("variant".into(), inner.variant_name().into());
+            map.insert("fields".into(), fields);
+            map.into()
+        }
+        ReflectRef::TupleStruct(inner) => inner
+            .fields()
+            .map(to_dynamic_deep)
+            .collect::<Array>()
+            .into(),
+        ReflectRef::Tuple(inner) => inner
+            .fields()
+            .map(to_dynamic_deep)
+            .collect::<Array>()
+            .into(),
+        ReflectRef::List(inner) => inner.iter().map(to_dynamic_deep).collect::<Array>().into(),
+        ReflectRef::Array(inner) => inner.iter().map(to_dynamic_deep).collect::<Array>().into(),
+        ReflectRef::Map(inner) => {
+            let mut map = RhaiMap::new();
+            for (key, value) in inner.iter() {
+                let key = match key.as_scalar() {
+                    Some(ScalarRef::String(key)) => key.into(),
+                    _ => format!("{:?}", key).into(),
+                };
+                map.insert(key, to_dynamic_deep(value));
+            }
+            map.into()
+        }
+        ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => to_dynamic(value),
+    }
+}
+
+/// Build the key path to the value at `path`, which is made of field names and indices.
+///
+/// Which kind of key a segment becomes depends on the value it is applied to, for example an
+/// index is a tuple field on tuples but an element on lists.
+fn key_path(value: &dyn Reflect, path: &Array) -> RhaiResult<KeyPath> {
+    let mut key_path = KeyPath::default();
+    let mut current = value;
+    for segment in path {
+        let step = if let Some(name) = segment.clone().try_cast::<ImmutableString>() {
+            match current.reflect_ref() {
+                ReflectRef::Struct(_) | ReflectRef::Enum(_) => {
+                    KeyPath::default().field(name.as_str())
+                }
+                ReflectRef::Map(_) => KeyPath::default().get(name.to_string()),
+                ReflectRef::TupleStruct(_)
+                | ReflectRef::Tuple(_)
+                | ReflectRef::List(_)
+                | ReflectRef::Array(_)
+                | ReflectRef::Scalar(_)
+                | ReflectRef::Opaque(_) => return Err(not_found(&key_path, segment)),
+            }
+        } else if let Some(index) = segment.clone().try_cast::<INT>() {
+            let index = usize::try_from(index).map_err(|_| not_found(&key_path, segment))?;
+            match current.reflect_ref() {
+                ReflectRef::TupleStruct(_) | ReflectRef::Tuple(_) | ReflectRef::Enum(_) => {
+                    KeyPath::default().field(index)
+                }
+                ReflectRef::List(_) | ReflectRef::Array(_) => KeyPath::default().get(index),
+                ReflectRef::Map(inner) => {
+                    // map keys can be any integer type so look for one that is equal
+                    let key = inner
+                        .iter()
+                        .map(|(key, _)| key)
+                        .find(|key| {
+                            key.as_scalar()
+                                .and_then(scalar_to_dynamic)
+                                .and_then(|key| key.try_cast::<INT>())
+                                == Some(index as INT)
+                        })
+                        .ok_or_else(|| not_found(&key_path, segment))?;
+                    KeyPath::default().get(key.to_value())
+                }
+                ReflectRef::Struct(_) | ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => {
+                    return Err(not_found(&key_path, segment))
+                }
+            }
+        } else {
+            return Err(format!(
+                "path segments must be strings or integers, got `{}`",
+                segment.type_name()
+            )
+            .into());
+        };
+
+        current = current
+            .at(&step)
+            .ok_or_else(|| not_found(&key_path, segment))?;
+        key_path.path.extend(step.path);
+    }
+    Ok(key_path)
+}
+
+fn not_found(key_path: &KeyPath, segment: &Dynamic) -> Box<EvalAltResult> {
+    format!("no value at `{key_path}` with key `{segment}`").into()
+}
+
+fn get(value: &Value, path: &Array) -> RhaiResult<Dynamic> {
+    let key_path = key_path(value, path)?;
+    let value = value
+        .at(&key_path)
+        .expect("key path was built from the value");
+    Ok(to_dynamic(value))
+}
+
+fn set(value: &mut Value, path: &Array, new: Dynamic) -> RhaiResult<()> {
+    let key_path = key_path(value, path)?;
+    let type_name = new.type_name();
+    let new = from_dynamic(new)
+        .ok_or_else(|| format!("`{type_name}` can't be converted to a `Value`"))?;
+
+    // the fields of a `Value` are `Value`s themselves, whose type info doesn't say which scalar
+    // they hold, so convert into the type of the current value first
+    let current = value
+        .at(&key_path)
+        .expect("key path was built from the value")
+        .as_reflect();
+    let new = convert_to_value(&new, current.type_info().get_type())
+        .map_err(|err| format!("can't set `{key_path}`: {err}"))?;
+
+    value
+        .set_at(&key_path, new)
+        .map_err(|err| err.to_string().into())
+}
+
+fn len(value: &Value) -> RhaiResult<INT> {
+    let len = match value.reflect_ref() {
+        ReflectRef::Struct(inner) => inner.fields_len(),
+        ReflectRef::TupleStruct(inner) => inner.fields_len(),
+        ReflectRef::Tuple(inner) => inner.fields_len(),
+        ReflectRef::Enum(inner) => inner.fields_len(),
+        ReflectRef::List(inner) => inner.len(),
+        ReflectRef::Array(inner) => inner.len(),
+        ReflectRef::Map(inner) => inner.len(),
+        ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => {
+            return Err(format!("`{:?}` has no length", value.as_reflect()).into())
+        }
+    };
+    Ok(len as INT)
+}
+
+fn keys(value: &Value) -> RhaiResult<Array> {
+    let keys = match value.reflect_ref() {
+        ReflectRef::Struct(inner) => inner.fields().map(|(name, _)| name.into()).collect(),
+        ReflectRef::Map(inner) => inner.iter().map(|(key, _)| to_dynamic(key)).collect(),
+        ReflectRef::Enum(inner) => inner
+            .fields()
+            .enumerate()
+            .map(|(index, field)| match field {
+                VariantField::Struct(name, _) => name.into(),
+                VariantField::Tuple(_) => (index as INT).into(),
+            })
+            .collect(),
+        ReflectRef::TupleStruct(_)
+        | ReflectRef::Tuple(_)
+        | ReflectRef::List(_)
+        | ReflectRef::Array(_) => (0..len(value)?).map(Dynamic::from).collect(),
+        ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => {
+            return Err(format!("`{:?}` has no keys", value.as_reflect()).into())
+        }
+    };
+    Ok(keys)
+}
+
+fn values(value: &Value) -> RhaiResult<Array> {
+    let values = match value.reflect_ref() {
+        ReflectRef::Struct(inner) => inner.fields().map(|(_, value)| to_dynamic(value)).collect(),
+        ReflectRef::TupleStruct(inner) => inner.fields().map(to_dynamic).collect(),
+        ReflectRef::Tuple(inner) => inner.fields().map(to_dynamic).collect(),
+        ReflectRef::Enum(inner) => inner
+            .fields()
+            .map(|field| match field {
+                VariantField::Struct(_, value) | VariantField::Tuple(value) => to_dynamic(value),
+            })
+            .collect(),
+        ReflectRef::List(inner) => inner.iter().map(to_dynamic).collect(),
+        ReflectRef::Array(inner) => inner.iter().map(to_dynamic).collect(),
+        ReflectRef::Map(inner) => inner.iter().map(|(_, value)| to_dynamic(value)).collect(),
+        ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => {
+            return Err(format!("`{:?}` has no values", value.as_reflect()).into())
+        }
+    };
+    Ok(values)
+}
+
+fn variant(value: &Value) -> RhaiResult<String> {
+    value
+        .as_enum()
+        .map(|enum_| enum_.variant_name().to_owned())
+        .ok_or_else(|| format!("`{:?}` is not an enum", value.as_reflect()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rhai::Engine;
+    use ::rhai::Scope;
+
+    use super::*;
+    use crate::FromReflect;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Player {
+        name: String,
+        health: u8,
+        position: [f32; 2],
+        inventory: Vec<Item>,
+        stats: BTreeMap<String, i64>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Item {
+        Sword { damage: u32 },
+        Potion(u8),
+    }
+
+    fn player() -> Player {
+        Player {
+            name: "Alice".to_owned(),
+            health: 10,
+            position: [1.0, 2.0],
+            inventory: Vec::from([Item::Sword { damage: 3 }, Item::Potion(1)]),
+            stats: BTreeMap::from([("kills".to_owned(), 0)]),
+        }
+    }
+
+    fn run(script: &str) -> Result<Player, Box<EvalAltResult>> {
+        let mut engine = Engine::new();
+        register(&mut engine);
+        let mut scope = Scope::new();
+        scope.push("player", player().to_value());
+        engine.run_with_scope(&mut scope, script)?;
+        let value = scope.get_value::<Value>("player").unwrap();
+        Ok(Player::from_reflect(&value).unwrap())
+    }
+
+    #[test]
+    fn get_and_set() {
+        let player = run(r#"
+                player["health"] += 5;
+                player["position"][0] = 3.5;
+                player["inventory"][0]["damage"] = 10;
+                player.set(["inventory", 1, 0], 2);
+                player["stats"]["kills"] = player.get(["stats", "kills"]) + 1;
+                player["name"] = player["name"] + "!";
+            "#)
+        .unwrap();
+
+        assert_eq!(
+            player,
+            Player {
+                name: "Alice!".to_owned(),
+                health: 15,
+                position: [3.5, 2.0],
+                inventory: Vec::from([Item::Sword { damage: 10 }, Item::Potion(2)]),
+                stats: BTreeMap::from([("kills".to_owned(), 1)]),
+            }
+        );
+    }
+
+    #[test]
+    fn iterate() {
+        let player = run(r#"
+                let total = 0;
+                for item in player["inventory"].values() {
+                    if item.variant() == "Potion" {
+                        total += item[0];
+                    }
+                }
+                player["health"] = total + player["inventory"].len();
+
+                if player.keys() != ["name", "health", "position", "inventory", "stats"] {
+                    throw "wrong keys";
+                }
+                let inventory = player.to_dynamic().inventory;
+                if inventory[0].variant != "Sword" || inventory[0].fields.damage != 3 {
+                    throw "wrong sword";
+                }
+                if inventory[1].variant != "Potion" || inventory[1].fields[0] != 1 {
+                    throw "wrong potion";
+                }
+            "#)
+        .unwrap();
+
+        assert_eq!(player.health, 3);
+    }
+
+    #[test]
+    fn enum_field_named_variant() {
+        #[derive(Reflect, Clone, Debug)]
+        #[reflect(crate_name(crate))]
+        enum Shape {
+            Circle { variant: String },
+        }
+
+        let mut engine = Engine::new();
+        register(&mut engine);
+        let mut scope = Scope::new();
+        scope.push(
+            "shape",
+            Shape::Circle {
+                variant: "big".to_owned(),
+            }
+            .to_value(),
+        );
+        let mut eval = |script| {
+            engine
+                .eval_with_scope::<ImmutableString>(&mut scope, script)
+                .unwrap()
+        };
+        assert_eq!(eval("shape.to_dynamic().variant"), "Circle");
+        assert_eq!(eval("shape.to_dynamic().fields.variant"), "big");
+    }
+
+    #[test]
+    fn errors() {
+        let err = run(r#"player["health"] = 1000;"#).unwrap_err();
+        assert!(err.to_string().contains("health"), "{err}");
+
+        let err = run(r#"player["missing"] = 1;"#).unwrap_err();
+        assert!(err.to_string().contains("missing"), "{err}");
+
+        assert!(run(r#"player["health"] = "a";"#).is_err());
+    }
+}
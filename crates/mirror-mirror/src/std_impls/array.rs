@@ -5,8 +5,11 @@ use core::fmt;
 
 use crate::array::Array;
 use crate::iter::ValueIterMut;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::ArrayNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::Reflect;
@@ -22,6 +25,7 @@ where
     T: FromReflect + Typed,
 {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl<T, const N: usize> Typed for [T; N]
         where
             T: Typed,
@@ -60,7 +64,7 @@ where
 
     fn to_value(&self) -> Value {
         let data = self.iter().map(Reflect::to_value).collect();
-        Value::List(data)
+        Value::List(Box::new(data))
     }
 
     fn clone_reflect(&self) -> Box<dyn Reflect> {
@@ -126,6 +130,6 @@ where
             .iter()
             .map(|value| value.to_value())
             .collect::<Vec<_>>();
-        Value::List(list)
+        Value::List(Box::new(list))
     }
 }
@@ -1,9 +1,16 @@
 use alloc::boxed::Box;
 use core::any::Any;
+use core::any::TypeId;
+use core::cmp::Ordering;
 use core::fmt;
 
+use crate::method::Method;
 use crate::reflect_debug;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::OpaqueNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::Reflect;
@@ -19,6 +26,7 @@ where
     T: Reflect + Typed,
 {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl<T> Typed for Box<T>
         where
             T: Typed,
@@ -75,6 +83,10 @@ where
         <T as Reflect>::to_value(self)
     }
 
+    fn update_value(&self, value: &mut Value) {
+        <T as Reflect>::update_value(self, value)
+    }
+
     fn clone_reflect(&self) -> Box<dyn Reflect> {
         <T as Reflect>::clone_reflect(self)
     }
@@ -82,6 +94,26 @@ where
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         reflect_debug(self, f)
     }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        <T as Reflect>::reflect_hash(self)
+    }
+
+    fn reflect_partial_eq(&self, other: &dyn Reflect) -> Option<bool> {
+        <T as Reflect>::reflect_partial_eq(self, other)
+    }
+
+    fn reflect_partial_cmp(&self, other: &dyn Reflect) -> Option<Ordering> {
+        <T as Reflect>::reflect_partial_cmp(self, other)
+    }
+
+    fn reflect_display(&self) -> Option<&dyn fmt::Display> {
+        <T as Reflect>::reflect_display(self)
+    }
+
+    fn methods(&self) -> &'static [Method] {
+        <T as Reflect>::methods(self)
+    }
 }
 
 impl<T> FromReflect for Box<T>
@@ -101,3 +133,125 @@ where
         (*boxed).into()
     }
 }
+
+/// Trait objects are reflected as whatever type they hold, so `Box<dyn Reflect>` can be used to
+/// store values of different types, for example in a `Vec<Box<dyn Reflect>>`, without converting
+/// them to [`Value`]s.
+///
+/// Its [`Typed`] type info is opaque since the type isn't known until there's a value.
+impl Reflect for Box<dyn Reflect> {
+    fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
+        impl Typed for Box<dyn Reflect> {
+            fn build(graph: &mut TypeGraph) -> NodeId {
+                graph.get_or_build_node_with::<Self, _>(|graph| {
+                    OpaqueNode::new::<Self>(Default::default(), graph)
+                })
+            }
+        }
+
+        (**self).type_info()
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        (*self).into_any()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        (**self).as_any()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        (**self).as_any_mut()
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        *self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        (**self).as_reflect()
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        (**self).as_reflect_mut()
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        (*self).reflect_owned()
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        (**self).reflect_ref()
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        (**self).reflect_mut()
+    }
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        (**self).patch(value)
+    }
+
+    fn to_value(&self) -> Value {
+        (**self).to_value()
+    }
+
+    fn update_value(&self, value: &mut Value) {
+        (**self).update_value(value)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        (**self).clone_reflect()
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).debug(f)
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        (**self).reflect_hash()
+    }
+
+    fn reflect_partial_eq(&self, other: &dyn Reflect) -> Option<bool> {
+        (**self).reflect_partial_eq(other)
+    }
+
+    fn reflect_partial_cmp(&self, other: &dyn Reflect) -> Option<Ordering> {
+        (**self).reflect_partial_cmp(other)
+    }
+
+    fn reflect_display(&self) -> Option<&dyn fmt::Display> {
+        (**self).reflect_display()
+    }
+
+    fn methods(&self) -> &'static [Method] {
+        (**self).methods()
+    }
+
+    fn type_id(&self) -> TypeId {
+        Reflect::type_id(&**self)
+    }
+
+    fn type_name(&self) -> &str {
+        (**self).type_name()
+    }
+}
+
+impl FromReflect for Box<dyn Reflect> {
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        Some(reflect.clone_reflect())
+    }
+}
+
+impl Clone for Box<dyn Reflect> {
+    fn clone(&self) -> Self {
+        self.clone_reflect()
+    }
+}
+
+impl From<Box<dyn Reflect>> for Value {
+    fn from(boxed: Box<dyn Reflect>) -> Self {
+        boxed.to_value()
+    }
+}
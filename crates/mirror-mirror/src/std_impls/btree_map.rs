@@ -1,11 +1,16 @@
 use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
+use alloc::string::String;
 use core::any::Any;
 use core::fmt;
 
 use crate::iter::PairIterMut;
+use crate::map::IntKey;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::MapNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::Map;
@@ -23,17 +28,82 @@ where
     V: FromReflect + Typed,
 {
     fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
-        let key = K::from_reflect(key)?;
-        let value = self.get(&key)?;
+        // avoid converting the key if it already has the right type
+        let value = if let Some(key) = key.downcast_ref::<K>() {
+            self.get(key)?
+        } else if let Some(key) = crate::map::str_key(key) {
+            return Map::get_str(self, key);
+        } else {
+            self.get(&K::from_reflect(key)?)?
+        };
         Some(value.as_reflect())
     }
 
     fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
-        let key = K::from_reflect(key)?;
-        let value = self.get_mut(&key)?;
+        let value = if let Some(key) = key.downcast_ref::<K>() {
+            self.get_mut(key)?
+        } else if let Some(key) = crate::map::str_key(key) {
+            return Map::get_str_mut(self, key);
+        } else {
+            self.get_mut(&K::from_reflect(key)?)?
+        };
         Some(value.as_reflect_mut())
     }
 
+    fn get_str(&self, key: &str) -> Option<&dyn Reflect> {
+        let any = self.as_any();
+        if let Some(map) = any.downcast_ref::<BTreeMap<String, V>>() {
+            Some(map.get(key)?.as_reflect())
+        } else if let Some(map) = any.downcast_ref::<BTreeMap<Value, V>>() {
+            Some(crate::value::get_str(map, key)?.as_reflect())
+        } else {
+            crate::map::find(Map::iter(self), |k| crate::map::str_key(k) == Some(key))
+        }
+    }
+
+    fn get_str_mut(&mut self, key: &str) -> Option<&mut dyn Reflect> {
+        if self.as_any().is::<BTreeMap<String, V>>() {
+            let map = self.as_any_mut().downcast_mut::<BTreeMap<String, V>>()?;
+            Some(map.get_mut(key)?.as_reflect_mut())
+        } else if self.as_any().is::<BTreeMap<Value, V>>() {
+            let map = self.as_any_mut().downcast_mut::<BTreeMap<Value, V>>()?;
+            Some(crate::value::get_str_mut(map, key)?.as_reflect_mut())
+        } else {
+            crate::map::find_mut(Map::iter_mut(self), |k| crate::map::str_key(k) == Some(key))
+        }
+    }
+
+    fn get_int(&self, key: i128) -> Option<&dyn Reflect> {
+        match crate::map::int_key_as::<K>(key) {
+            IntKey::Fits(key) => Some(self.get(&key)?.as_reflect()),
+            IntKey::OutOfRange => None,
+            IntKey::NotInt => {
+                if let Some(map) = self.as_any().downcast_ref::<BTreeMap<Value, V>>() {
+                    Some(crate::value::get_int(map, key)?.as_reflect())
+                } else {
+                    crate::map::find(Map::iter(self), |k| crate::map::int_key(k) == Some(key))
+                }
+            }
+        }
+    }
+
+    fn get_int_mut(&mut self, key: i128) -> Option<&mut dyn Reflect> {
+        match crate::map::int_key_as::<K>(key) {
+            IntKey::Fits(key) => Some(self.get_mut(&key)?.as_reflect_mut()),
+            IntKey::OutOfRange => None,
+            IntKey::NotInt => {
+                if self.as_any().is::<BTreeMap<Value, V>>() {
+                    let map = self.as_any_mut().downcast_mut::<BTreeMap<Value, V>>()?;
+                    Some(crate::value::get_int_mut(map, key)?.as_reflect_mut())
+                } else {
+                    crate::map::find_mut(Map::iter_mut(self), |k| {
+                        crate::map::int_key(k) == Some(key)
+                    })
+                }
+            }
+        }
+    }
+
     fn insert(&mut self, key: &dyn Reflect, value: &dyn Reflect) -> Option<Box<dyn Reflect>> {
         let key = K::from_reflect(key)?;
         let value = V::from_reflect(value)?;
@@ -42,8 +112,11 @@ where
     }
 
     fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
-        let key = K::from_reflect(key)?;
-        let previous = BTreeMap::remove(self, &key)?;
+        let previous = if let Some(key) = key.downcast_ref::<K>() {
+            BTreeMap::remove(self, key)?
+        } else {
+            BTreeMap::remove(self, &K::from_reflect(key)?)?
+        };
         Some(Box::new(previous))
     }
 
@@ -76,6 +149,7 @@ where
     V: FromReflect + Typed,
 {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl<K, V> Typed for BTreeMap<K, V>
         where
             K: Typed,
@@ -118,7 +192,7 @@ where
             .iter()
             .map(|(key, value)| (key.to_value(), value.to_value()))
             .collect();
-        Value::Map(data)
+        Value::Map(Box::new(data))
     }
 
     fn clone_reflect(&self) -> Box<dyn Reflect> {
@@ -156,6 +230,6 @@ where
             .into_iter()
             .map(|(key, value)| (key.to_value(), value.to_value()))
             .collect();
-        Value::Map(map)
+        Value::Map(Box::new(map))
     }
 }
@@ -0,0 +1,157 @@
+use alloc::string::ToString;
+#[cfg(feature = "type_info")]
+use alloc::vec::Vec;
+use core::any::type_name;
+
+// Function pointers are reflected as opaque types so callback tables can live inside reflected
+// types. They can't be called through reflection but their signature is recorded in the
+// metadata, under `fn_args` and `fn_return`, so tools can display it.
+//
+// Their `Value` is the type name since there is no way to turn a value back into a function.
+macro_rules! impl_reflect_for_fn_ptr {
+    ($($arg:ident)*) => {
+        const _: () = {
+            use $crate::__private::*;
+
+            #[cfg(feature = "type_info")]
+            impl<$($arg,)* R> Typed for fn($($arg),*) -> R
+            where
+                $($arg: 'static,)*
+                R: 'static,
+            {
+                fn build(graph: &mut TypeGraph) -> NodeId {
+                    graph.get_or_build_node_with::<Self, _>(|graph| {
+                        let args: Vec<Value> =
+                            Vec::from([$(type_name::<$arg>().to_string().to_value()),*]);
+                        let mut metadata = BTreeMap::new();
+                        metadata.insert("fn_args", Value::List(Box::new(args)));
+                        metadata.insert("fn_return", type_name::<R>().to_string().to_value());
+                        OpaqueNode::new::<Self>(metadata, graph)
+                    })
+                }
+            }
+
+            impl<$($arg,)* R> Reflect for fn($($arg),*) -> R
+            where
+                $($arg: 'static,)*
+                R: 'static,
+            {
+                fn type_info(&self) -> TypeRoot {
+                    <Self as Typed>::type_info()
+                }
+
+                trivial_reflect_methods!();
+
+                fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                    ReflectOwned::Opaque(self)
+                }
+
+                fn reflect_ref(&self) -> ReflectRef<'_> {
+                    ReflectRef::Opaque(self)
+                }
+
+                fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                    ReflectMut::Opaque(self)
+                }
+
+                fn patch(&mut self, value: &dyn Reflect) {
+                    if let Some(f) = value.downcast_ref::<Self>() {
+                        *self = *f;
+                    }
+                }
+
+                fn to_value(&self) -> Value {
+                    type_name::<Self>().to_string().to_value()
+                }
+
+                fn clone_reflect(&self) -> Box<dyn Reflect> {
+                    Box::new(*self)
+                }
+
+                fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", type_name::<Self>())
+                }
+            }
+
+            impl<$($arg,)* R> FromReflect for fn($($arg),*) -> R
+            where
+                $($arg: 'static,)*
+                R: 'static,
+            {
+                fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                    reflect.downcast_ref::<Self>().copied()
+                }
+            }
+        };
+    };
+}
+
+impl_reflect_for_fn_ptr!();
+impl_reflect_for_fn_ptr!(A1);
+impl_reflect_for_fn_ptr!(A1 A2);
+impl_reflect_for_fn_ptr!(A1 A2 A3);
+impl_reflect_for_fn_ptr!(A1 A2 A3 A4);
+impl_reflect_for_fn_ptr!(A1 A2 A3 A4 A5);
+impl_reflect_for_fn_ptr!(A1 A2 A3 A4 A5 A6);
+
+#[cfg(all(test, feature = "type_info"))]
+mod tests {
+    use alloc::borrow::ToOwned;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::type_info::GetMeta;
+    use crate::FromReflect;
+    use crate::Reflect;
+    use crate::Typed;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Callbacks {
+        on_click: fn(u32, u32) -> bool,
+        on_close: fn(),
+    }
+
+    fn on_click(x: u32, y: u32) -> bool {
+        x == y
+    }
+
+    fn on_close() {}
+
+    #[test]
+    fn records_signature() {
+        let type_info = <Callbacks as Typed>::type_info();
+        let on_click = type_info
+            .as_struct()
+            .unwrap()
+            .field_type("on_click")
+            .unwrap()
+            .get_type()
+            .as_opaque()
+            .unwrap();
+
+        assert_eq!(
+            on_click.get_meta::<Vec<String>>("fn_args").unwrap(),
+            Vec::from(["u32".to_owned(), "u32".to_owned()])
+        );
+        assert_eq!(on_click.get_meta::<String>("fn_return").unwrap(), "bool");
+    }
+
+    #[test]
+    fn round_trips_through_reflect() {
+        let callbacks = Callbacks { on_click, on_close };
+
+        let f = <fn(u32, u32) -> bool>::from_reflect(&callbacks.on_click).unwrap();
+        assert!(f(1, 1));
+
+        let mut other = Callbacks {
+            on_click: |_, _| false,
+            on_close,
+        };
+        other.patch(&callbacks);
+        assert!((other.on_click)(2, 2));
+
+        let cloned = Callbacks::from_reflect(&callbacks).unwrap();
+        assert!((cloned.on_click)(3, 3));
+    }
+}
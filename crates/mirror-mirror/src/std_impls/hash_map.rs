@@ -0,0 +1,425 @@
+//! `HashMap`s and `HashSet`s from `std` and `hashbrown`, with any hasher.
+//!
+//! Sets are reflected as maps whose values are all `()`.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::any::Any;
+use core::fmt;
+use core::hash::BuildHasher;
+use core::hash::Hash;
+
+use crate::iter::PairIterMut;
+use crate::map::IntKey;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::MapNode;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::TypeGraph;
+use crate::FromReflect;
+use crate::Map;
+use crate::MaybeSync;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::TypeRoot;
+use crate::Typed;
+use crate::Value;
+
+macro_rules! impl_hash_map {
+    ($map:ident) => {
+        impl<K, V, S> Map for $map<K, V, S>
+        where
+            K: FromReflect + Typed + Eq + Hash,
+            V: FromReflect + Typed,
+            S: BuildHasher + Default + Send + MaybeSync + 'static,
+        {
+            fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
+                // avoid converting the key if it already has the right type
+                let value = if let Some(key) = key.downcast_ref::<K>() {
+                    self.get(key)?
+                } else if let Some(key) = crate::map::str_key(key) {
+                    return Map::get_str(self, key);
+                } else {
+                    self.get(&K::from_reflect(key)?)?
+                };
+                Some(value.as_reflect())
+            }
+
+            fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
+                let value = if let Some(key) = key.downcast_ref::<K>() {
+                    self.get_mut(key)?
+                } else if let Some(key) = crate::map::str_key(key) {
+                    return Map::get_str_mut(self, key);
+                } else {
+                    self.get_mut(&K::from_reflect(key)?)?
+                };
+                Some(value.as_reflect_mut())
+            }
+
+            fn get_str(&self, key: &str) -> Option<&dyn Reflect> {
+                if let Some(map) = self.as_any().downcast_ref::<$map<String, V, S>>() {
+                    Some(map.get(key)?.as_reflect())
+                } else {
+                    crate::map::find(Map::iter(self), |k| crate::map::str_key(k) == Some(key))
+                }
+            }
+
+            fn get_str_mut(&mut self, key: &str) -> Option<&mut dyn Reflect> {
+                if self.as_any().is::<$map<String, V, S>>() {
+                    let map = self.as_any_mut().downcast_mut::<$map<String, V, S>>()?;
+                    Some(map.get_mut(key)?.as_reflect_mut())
+                } else {
+                    crate::map::find_mut(Map::iter_mut(self), |k| {
+                        crate::map::str_key(k) == Some(key)
+                    })
+                }
+            }
+
+            fn get_int(&self, key: i128) -> Option<&dyn Reflect> {
+                match crate::map::int_key_as::<K>(key) {
+                    IntKey::Fits(key) => Some(self.get(&key)?.as_reflect()),
+                    IntKey::OutOfRange => None,
+                    IntKey::NotInt => {
+                        crate::map::find(Map::iter(self), |k| crate::map::int_key(k) == Some(key))
+                    }
+                }
+            }
+
+            fn get_int_mut(&mut self, key: i128) -> Option<&mut dyn Reflect> {
+                match crate::map::int_key_as::<K>(key) {
+                    IntKey::Fits(key) => Some(self.get_mut(&key)?.as_reflect_mut()),
+                    IntKey::OutOfRange => None,
+                    IntKey::NotInt => crate::map::find_mut(Map::iter_mut(self), |k| {
+                        crate::map::int_key(k) == Some(key)
+                    }),
+                }
+            }
+
+            fn insert(
+                &mut self,
+                key: &dyn Reflect,
+                value: &dyn Reflect,
+            ) -> Option<Box<dyn Reflect>> {
+                let key = K::from_reflect(key)?;
+                let value = V::from_reflect(value)?;
+                let previous = $map::insert(self, key, value)?;
+                Some(Box::new(previous))
+            }
+
+            fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+                let previous = if let Some(key) = key.downcast_ref::<K>() {
+                    $map::remove(self, key)?
+                } else {
+                    $map::remove(self, &K::from_reflect(key)?)?
+                };
+                Some(Box::new(previous))
+            }
+
+            fn len(&self) -> usize {
+                self.len()
+            }
+
+            fn is_empty(&self) -> bool {
+                self.is_empty()
+            }
+
+            fn iter(&self) -> crate::map::Iter<'_> {
+                let iter = self
+                    .iter()
+                    .map(|(key, value)| (key.as_reflect(), value.as_reflect()));
+                Box::new(iter)
+            }
+
+            fn iter_mut(&mut self) -> PairIterMut<'_, dyn Reflect> {
+                let iter = self
+                    .iter_mut()
+                    .map(|(key, value)| (key.as_reflect(), value.as_reflect_mut()));
+                Box::new(iter)
+            }
+        }
+
+        impl<K, V, S> Reflect for $map<K, V, S>
+        where
+            K: FromReflect + Typed + Eq + Hash,
+            V: FromReflect + Typed,
+            S: BuildHasher + Default + Send + MaybeSync + 'static,
+        {
+            fn type_info(&self) -> TypeRoot {
+                #[cfg(feature = "type_info")]
+                impl<K, V, S> Typed for $map<K, V, S>
+                where
+                    K: Typed,
+                    V: Typed,
+                    S: 'static,
+                {
+                    fn build(graph: &mut TypeGraph) -> NodeId {
+                        graph.get_or_build_node_with::<Self, _>(|graph| {
+                            MapNode::new::<Self, K, V>(graph)
+                        })
+                    }
+                }
+
+                <Self as Typed>::type_info()
+            }
+
+            trivial_reflect_methods!();
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Map(self)
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Map(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Map(self)
+            }
+
+            fn patch(&mut self, value: &dyn Reflect) {
+                if let Some(map) = value.reflect_ref().as_map() {
+                    for (key, new_value) in map.iter() {
+                        if let Some(value) = Map::get_mut(self, key) {
+                            value.patch(new_value);
+                        }
+                    }
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                let data = self
+                    .iter()
+                    .map(|(key, value)| (key.to_value(), value.to_value()))
+                    .collect();
+                Value::Map(Box::new(data))
+            }
+
+            fn clone_reflect(&self) -> Box<dyn Reflect> {
+                let value = self.to_value();
+                Box::new(Self::from_reflect(&value).unwrap())
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_map().entries(Map::iter(self)).finish()
+            }
+        }
+
+        impl<K, V, S> FromReflect for $map<K, V, S>
+        where
+            K: FromReflect + Typed + Eq + Hash,
+            V: FromReflect + Typed,
+            S: BuildHasher + Default + Send + MaybeSync + 'static,
+        {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                let map = reflect.as_reflect().as_map()?;
+                let mut out = $map::with_capacity_and_hasher(map.len(), S::default());
+                for (key, value) in map.iter() {
+                    out.insert(K::from_reflect(key)?, V::from_reflect(value)?);
+                }
+                Some(out)
+            }
+        }
+
+        impl<K, V, S> From<$map<K, V, S>> for Value
+        where
+            K: Reflect,
+            V: Reflect,
+        {
+            fn from(map: $map<K, V, S>) -> Self {
+                let map = map
+                    .into_iter()
+                    .map(|(key, value)| (key.to_value(), value.to_value()))
+                    .collect();
+                Value::Map(Box::new(map))
+            }
+        }
+    };
+}
+
+macro_rules! impl_hash_set {
+    ($set:ident) => {
+        impl<T, S> Map for $set<T, S>
+        where
+            T: FromReflect + Typed + Eq + Hash,
+            S: BuildHasher + Default + Send + MaybeSync + 'static,
+        {
+            fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
+                let contains = if let Some(key) = key.downcast_ref::<T>() {
+                    self.contains(key)
+                } else {
+                    self.contains(&T::from_reflect(key)?)
+                };
+                contains.then_some(&() as &dyn Reflect)
+            }
+
+            fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
+                Map::get(self, key)?;
+                Some(unit_mut())
+            }
+
+            fn insert(
+                &mut self,
+                key: &dyn Reflect,
+                value: &dyn Reflect,
+            ) -> Option<Box<dyn Reflect>> {
+                let key = T::from_reflect(key)?;
+                <()>::from_reflect(value)?;
+                if $set::insert(self, key) {
+                    None
+                } else {
+                    Some(Box::new(()))
+                }
+            }
+
+            fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+                let removed = if let Some(key) = key.downcast_ref::<T>() {
+                    $set::remove(self, key)
+                } else {
+                    $set::remove(self, &T::from_reflect(key)?)
+                };
+                removed.then(|| Box::new(()) as Box<dyn Reflect>)
+            }
+
+            fn len(&self) -> usize {
+                self.len()
+            }
+
+            fn is_empty(&self) -> bool {
+                self.is_empty()
+            }
+
+            fn iter(&self) -> crate::map::Iter<'_> {
+                let iter = self
+                    .iter()
+                    .map(|element| (element.as_reflect(), &() as &dyn Reflect));
+                Box::new(iter)
+            }
+
+            fn iter_mut(&mut self) -> PairIterMut<'_, dyn Reflect> {
+                let iter = self
+                    .iter()
+                    .map(|element| (element.as_reflect(), unit_mut()));
+                Box::new(iter)
+            }
+        }
+
+        impl<T, S> Reflect for $set<T, S>
+        where
+            T: FromReflect + Typed + Eq + Hash,
+            S: BuildHasher + Default + Send + MaybeSync + 'static,
+        {
+            fn type_info(&self) -> TypeRoot {
+                #[cfg(feature = "type_info")]
+                impl<T, S> Typed for $set<T, S>
+                where
+                    T: Typed,
+                    S: 'static,
+                {
+                    fn build(graph: &mut TypeGraph) -> NodeId {
+                        graph.get_or_build_node_with::<Self, _>(|graph| {
+                            MapNode::new::<Self, T, ()>(graph)
+                        })
+                    }
+                }
+
+                <Self as Typed>::type_info()
+            }
+
+            trivial_reflect_methods!();
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Map(self)
+            }
+
+            fn reflect_ref(&self) -> ReflectRef<'_> {
+                ReflectRef::Map(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                ReflectMut::Map(self)
+            }
+
+            fn patch(&mut self, _value: &dyn Reflect) {
+                // the values are all `()` so there is nothing to patch
+            }
+
+            fn to_value(&self) -> Value {
+                let data = self
+                    .iter()
+                    .map(|element| (element.to_value(), ().to_value()))
+                    .collect();
+                Value::Map(Box::new(data))
+            }
+
+            fn clone_reflect(&self) -> Box<dyn Reflect> {
+                let value = self.to_value();
+                Box::new(Self::from_reflect(&value).unwrap())
+            }
+
+            fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_set()
+                    .entries(self.iter().map(|element| element.as_reflect()))
+                    .finish()
+            }
+        }
+
+        impl<T, S> FromReflect for $set<T, S>
+        where
+            T: FromReflect + Typed + Eq + Hash,
+            S: BuildHasher + Default + Send + MaybeSync + 'static,
+        {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                let map = reflect.as_reflect().as_map()?;
+                let mut out = $set::with_capacity_and_hasher(map.len(), S::default());
+                for (key, _) in map.iter() {
+                    out.insert(T::from_reflect(key)?);
+                }
+                Some(out)
+            }
+        }
+
+        impl<T, S> From<$set<T, S>> for Value
+        where
+            T: Reflect,
+        {
+            fn from(set: $set<T, S>) -> Self {
+                let map = set
+                    .into_iter()
+                    .map(|element| (element.to_value(), ().to_value()))
+                    .collect();
+                Value::Map(Box::new(map))
+            }
+        }
+    };
+}
+
+/// The value of every element in a set.
+fn unit_mut() -> &'static mut dyn Reflect {
+    // doesn't allocate since `()` is zero sized
+    Box::leak(Box::new(()))
+}
+
+#[cfg(feature = "std")]
+mod std_collections {
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    use super::*;
+
+    impl_hash_map!(HashMap);
+    impl_hash_set!(HashSet);
+}
+
+#[cfg(feature = "hashbrown")]
+mod hashbrown_collections {
+    use hashbrown::HashMap;
+    use hashbrown::HashSet;
+
+    use super::*;
+
+    impl_hash_map!(HashMap);
+    impl_hash_set!(HashSet);
+}
@@ -9,6 +9,11 @@ use mirror_mirror_macros::__private_derive_reflect_foreign;
 mod array;
 mod boxed;
 mod btree_map;
+mod fn_ptr;
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+mod hash_map;
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
 mod vec;
 mod via_scalar;
 
@@ -0,0 +1,112 @@
+use alloc::string::ToString;
+use core::str::FromStr;
+
+use num_bigint::BigInt;
+use num_bigint::BigUint;
+use num_bigint::ToBigInt;
+use num_bigint::ToBigUint;
+
+// Big integers are reflected as opaque types whose `Value` is the number in base 10, so they
+// round trip losslessly regardless of size.
+macro_rules! impl_reflect_for_big_integer {
+    ($ty:ident, $from_primitive:ident) => {
+        const _: () = {
+            use $crate::__private::*;
+
+            #[cfg(feature = "type_info")]
+            impl Typed for $ty {
+                fn build(graph: &mut TypeGraph) -> NodeId {
+                    graph.get_or_build_node_with::<Self, _>(|graph| {
+                        OpaqueNode::new::<Self>(Default::default(), graph)
+                    })
+                }
+            }
+
+            impl Reflect for $ty {
+                fn type_info(&self) -> TypeRoot {
+                    <Self as Typed>::type_info()
+                }
+
+                trivial_reflect_methods!();
+
+                fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                    ReflectOwned::Opaque(self)
+                }
+
+                fn reflect_ref(&self) -> ReflectRef<'_> {
+                    ReflectRef::Opaque(self)
+                }
+
+                fn reflect_mut(&mut self) -> ReflectMut<'_> {
+                    ReflectMut::Opaque(self)
+                }
+
+                fn patch(&mut self, value: &dyn Reflect) {
+                    if let Some(n) = Self::from_reflect(value) {
+                        *self = n;
+                    }
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::from(self.to_string())
+                }
+
+                fn clone_reflect(&self) -> Box<dyn Reflect> {
+                    Box::new(self.clone())
+                }
+
+                fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    if f.alternate() {
+                        write!(f, "{:#?}", self)
+                    } else {
+                        write!(f, "{:?}", self)
+                    }
+                }
+
+                fn reflect_hash(&self) -> Option<u64> {
+                    self.to_value().reflect_hash()
+                }
+            }
+
+            impl FromReflect for $ty {
+                fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                    // either type of big integer converts to the other, if it fits
+                    if let Some(n) = reflect.downcast_ref::<BigInt>() {
+                        return n.$from_primitive();
+                    }
+                    if let Some(n) = reflect.downcast_ref::<BigUint>() {
+                        return n.$from_primitive();
+                    }
+
+                    match reflect.as_scalar()? {
+                        ScalarRef::String(s) => $ty::from_str(s).ok(),
+                        ScalarRef::usize(n) => n.$from_primitive(),
+                        ScalarRef::u8(n) => n.$from_primitive(),
+                        ScalarRef::u16(n) => n.$from_primitive(),
+                        ScalarRef::u32(n) => n.$from_primitive(),
+                        ScalarRef::u64(n) => n.$from_primitive(),
+                        ScalarRef::u128(n) => n.$from_primitive(),
+                        ScalarRef::i8(n) => n.$from_primitive(),
+                        ScalarRef::i16(n) => n.$from_primitive(),
+                        ScalarRef::i32(n) => n.$from_primitive(),
+                        ScalarRef::i64(n) => n.$from_primitive(),
+                        ScalarRef::i128(n) => n.$from_primitive(),
+                        ScalarRef::bool(_)
+                        | ScalarRef::char(_)
+                        | ScalarRef::f32(_)
+                        | ScalarRef::f64(_) => None,
+                    }
+                }
+            }
+
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    n.to_value()
+                }
+            }
+        };
+    };
+}
+
+impl_reflect_for_big_integer!(BigInt, to_bigint);
+impl_reflect_for_big_integer!(BigUint, to_biguint);
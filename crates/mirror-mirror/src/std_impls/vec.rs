@@ -4,8 +4,11 @@ use core::any::Any;
 
 use crate::array::Array;
 use crate::iter::ValueIterMut;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::ListNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::List;
@@ -82,6 +85,7 @@ where
     T: FromReflect + Typed,
 {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl<T> Typed for Vec<T>
         where
             T: Typed,
@@ -108,7 +112,7 @@ where
 
     fn to_value(&self) -> Value {
         let data = self.iter().map(Reflect::to_value).collect();
-        Value::List(data)
+        Value::List(Box::new(data))
     }
 
     fn clone_reflect(&self) -> Box<dyn Reflect> {
@@ -156,6 +160,6 @@ where
             .into_iter()
             .map(|value| value.to_value())
             .collect::<Vec<_>>();
-        Value::List(list)
+        Value::List(Box::new(list))
     }
 }
@@ -18,6 +18,7 @@ macro_rules! impl_reflect_via_scalar {
 
             impl Reflect for $ty {
                 fn type_info(&self) -> TypeRoot {
+                    #[cfg(feature = "type_info")]
                     impl Typed for $ty {
                         fn build(graph: &mut TypeGraph) -> NodeId {
                             graph.get_or_build_node_with::<Self, _>(|graph| {
@@ -115,7 +116,7 @@ impl<T> IntoOption<T> for T {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "type_info"))]
 mod tests {
     use super::*;
     use crate::Typed;
@@ -3,11 +3,15 @@ use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::any::Any;
+use core::cmp::Ordering;
 use core::fmt;
 
 use crate::iter::PairIterMut;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::OpaqueNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::Reflect;
@@ -27,10 +31,13 @@ pub trait Struct: Reflect {
 
     fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
 
+    /// The name of the field at `index`, in declaration order.
     fn name_at(&self, index: usize) -> Option<&str>;
 
+    /// Iterate over the names and values of the fields, in declaration order.
     fn fields(&self) -> Iter<'_>;
 
+    /// Iterate over the names and values of the fields mutably, in declaration order.
     fn fields_mut(&mut self) -> PairIterMut<'_>;
 
     fn fields_len(&self) -> usize;
@@ -42,13 +49,15 @@ impl fmt::Debug for dyn Struct {
     }
 }
 
-#[derive(Default, Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
-#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Clone, Eq, PartialEq)]
 pub struct StructValue {
     field_names: Vec<String>,
+    // the values in the same order as `field_names`, so they can be iterated without looking up
+    // each name
+    values: Vec<Value>,
+    // the position of each field, by name
     // use a `BTreeMap` because `HashMap` isn't `Hash`
-    fields: BTreeMap<String, Value>,
+    indices: BTreeMap<String, usize>,
 }
 
 impl StructValue {
@@ -61,15 +70,241 @@ impl StructValue {
         self
     }
 
+    /// Set the value of a field. New fields are added after the existing ones, while setting an
+    /// existing field keeps its position.
     pub fn set_field(&mut self, name: impl Into<String>, value: impl Into<Value>) {
         let name = name.into();
-        self.field_names.push(name.clone());
-        self.fields.insert(name, value.into());
+        let value = value.into();
+        if let Some(index) = self.indices.get(&name) {
+            self.values[*index] = value;
+        } else {
+            self.indices.insert(name.clone(), self.field_names.len());
+            self.field_names.push(name);
+            self.values.push(value);
+        }
+    }
+
+    /// Remove a field and return its value.
+    pub fn remove_field(&mut self, name: &str) -> Option<Value> {
+        let index = self.indices.remove(name)?;
+        self.field_names.remove(index);
+        let value = self.values.remove(index);
+        for other in self.indices.values_mut() {
+            if *other > index {
+                *other -= 1;
+            }
+        }
+        Some(value)
+    }
+
+    /// Rename a field while keeping its value and position.
+    ///
+    /// Returns `false`, and leaves the struct unchanged, if there is no field called `from` or if
+    /// a field called `to` already exists.
+    pub fn rename_field(&mut self, from: &str, to: impl Into<String>) -> bool {
+        let to = to.into();
+        if self.indices.contains_key(&to) {
+            return false;
+        }
+        let Some(index) = self.indices.remove(from) else { return false };
+        self.field_names[index] = to.clone();
+        self.indices.insert(to, index);
+        true
+    }
+
+    /// Move a field to `index`, shifting the fields after it.
+    ///
+    /// Returns `false` if there is no field called `name` or `index` is out of bounds.
+    pub fn move_field(&mut self, name: &str, index: usize) -> bool {
+        let Some(from) = self.position(name) else { return false };
+        if index >= self.field_names.len() {
+            return false;
+        }
+        let name = self.field_names.remove(from);
+        self.field_names.insert(index, name);
+        let value = self.values.remove(from);
+        self.values.insert(index, value);
+        self.reindex();
+        true
+    }
+
+    /// Order the fields as in `names`, for example to match the declaration order of a type.
+    ///
+    /// Fields not in `names` are placed after the others and keep their relative order. Names of
+    /// fields that don't exist are ignored.
+    pub fn reorder_fields<'a>(&mut self, names: impl IntoIterator<Item = &'a str>) {
+        let mut fields = core::mem::take(&mut self.field_names)
+            .into_iter()
+            .zip(core::mem::take(&mut self.values))
+            .map(Some)
+            .collect::<Vec<_>>();
+        let named = names
+            .into_iter()
+            .filter_map(|name| self.indices.get(name).copied())
+            .collect::<Vec<_>>();
+        for index in named.into_iter().chain(0..fields.len()) {
+            if let Some((name, value)) = fields[index].take() {
+                self.field_names.push(name);
+                self.values.push(value);
+            }
+        }
+        self.reindex();
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    fn reindex(&mut self) {
+        for (index, name) in self.field_names.iter().enumerate() {
+            if let Some(position) = self.indices.get_mut(name) {
+                *position = index;
+            }
+        }
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.field_names
+            .iter()
+            .map(|name| &**name)
+            .zip(&self.values)
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = (&str, &mut Value)> {
+        self.field_names
+            .iter()
+            .map(|name| &**name)
+            .zip(&mut self.values)
+    }
+
+    pub(crate) fn value_mut(&mut self, name: &str) -> Option<&mut Value> {
+        let index = self.position(name)?;
+        self.values.get_mut(index)
+    }
+
+    pub(crate) fn field_names(&self) -> &[String] {
+        &self.field_names
+    }
+
+    /// Build a struct from its encoded parts. Names without a value, repeated names, and values
+    /// without a name are dropped.
+    #[cfg(any(feature = "serde", feature = "speedy"))]
+    pub(crate) fn from_parts(field_names: Vec<String>, mut fields: BTreeMap<String, Value>) -> Self {
+        let mut out = Self::default();
+        for name in field_names {
+            if let Some(value) = fields.remove(&name) {
+                out.set_field(name, value);
+            }
+        }
+        out
+    }
+
+    /// The fields ordered by name, rather than in field order like [`StructValue::values`].
+    pub(crate) fn fields_by_name(&self) -> impl ExactSizeIterator<Item = (&str, &Value)> {
+        self.indices
+            .iter()
+            .map(|(name, index)| (&**name, &self.values[*index]))
+    }
+
+    /// Clone the struct, creating the values of the fields with `f`.
+    pub(crate) fn map_values(&self, f: impl FnMut(&Value) -> Value) -> Self {
+        Self {
+            field_names: self.field_names.clone(),
+            values: self.values.iter().map(f).collect(),
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+/// Orders by the field names, in field order, and then by the fields ordered by name.
+impl Ord for StructValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.field_names
+            .cmp(&other.field_names)
+            .then_with(|| self.fields_by_name().cmp(other.fields_by_name()))
+    }
+}
+
+impl PartialOrd for StructValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The fields ordered by name. This is how they're encoded, and shown by `Debug`.
+struct FieldsByName<'a>(&'a StructValue);
+
+impl fmt::Debug for FieldsByName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.fields_by_name()).finish()
+    }
+}
+
+impl fmt::Debug for StructValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructValue")
+            .field("field_names", &self.field_names)
+            .field("fields", &FieldsByName(self))
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldsByName<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.0.fields_by_name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StructValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut struct_ = serializer.serialize_struct("StructValue", 2)?;
+        struct_.serialize_field("field_names", &self.field_names)?;
+        struct_.serialize_field("fields", &FieldsByName(self))?;
+        struct_.end()
+    }
+}
+
+// the same layout as `(Vec<String>, BTreeMap<String, Value>)`
+#[cfg(feature = "speedy")]
+impl<C> speedy::Writable<C> for StructValue
+where
+    C: speedy::Context,
+{
+    fn write_to<W>(&self, writer: &mut W) -> Result<(), C::Error>
+    where
+        W: ?Sized + speedy::Writer<C>,
+    {
+        self.field_names.write_to(writer)?;
+        let len = u32::try_from(self.values.len())
+            .map_err(|_| speedy::Error::custom("struct has too many fields"))?;
+        writer.write_u32(len)?;
+        for (name, value) in self.fields_by_name() {
+            name.write_to(writer)?;
+            value.write_to(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for StructValue {
+    fn drop(&mut self) {
+        crate::value::drop_nested(self.values.iter_mut());
     }
 }
 
 impl Reflect for StructValue {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl Typed for StructValue {
             fn build(graph: &mut TypeGraph) -> NodeId {
                 graph.get_or_build_node_with::<Self, _>(|graph| {
@@ -123,11 +358,11 @@ impl Reflect for StructValue {
 
 impl Struct for StructValue {
     fn field(&self, name: &str) -> Option<&dyn Reflect> {
-        Some(self.fields.get(name)?)
+        Some(self.values.get(self.position(name)?)?)
     }
 
     fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
-        Some(self.fields.get_mut(name)?)
+        Some(self.value_mut(name)?)
     }
 
     fn fields(&self) -> Iter<'_> {
@@ -136,9 +371,8 @@ impl Struct for StructValue {
 
     fn fields_mut(&mut self) -> PairIterMut<'_> {
         let iter = self
-            .fields
-            .iter_mut()
-            .map(|(key, value)| (&**key, value.as_reflect_mut()));
+            .values_mut()
+            .map(|(name, value)| (name, value.as_reflect_mut()));
         Box::new(iter)
     }
 
@@ -147,8 +381,7 @@ impl Struct for StructValue {
     }
 
     fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
-        let key = self.field_names.get(index)?;
-        Some(self.fields.get(key)?)
+        Some(self.values.get(index)?)
     }
 
     fn name_at(&self, index: usize) -> Option<&str> {
@@ -156,8 +389,7 @@ impl Struct for StructValue {
     }
 
     fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
-        let key = self.field_names.get(index)?;
-        Some(self.fields.get_mut(key)?)
+        Some(self.values.get_mut(index)?)
     }
 }
 
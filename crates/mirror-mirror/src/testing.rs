@@ -0,0 +1,157 @@
+//! Assertions for tests that compare reflected values.
+//!
+//! [`assert_reflect_eq!`](crate::assert_reflect_eq) compares two values like
+//! [`Reflect::reflect_partial_eq`] and, if they differ, panics with the key path of every
+//! difference and the values on either side, rather than printing both values in full.
+//!
+//! # Example
+//!
+//! ```should_panic
+//! use mirror_mirror::assert_reflect_eq;
+//! use mirror_mirror::Reflect;
+//!
+//! #[derive(Reflect, Clone, Debug)]
+//! struct Player {
+//!     name: String,
+//!     position: (f32, f32),
+//!     scores: Vec<u32>,
+//! }
+//!
+//! let player = Player {
+//!     name: "Alice".to_owned(),
+//!     position: (1.0, 2.0),
+//!     scores: vec![1, 2],
+//! };
+//!
+//! let mut moved = player.clone();
+//! moved.position.1 = 3.0;
+//! moved.scores.push(3);
+//!
+//! // panics with:
+//! //
+//! // assertion `left == right` failed
+//! // .position.1: left: 2.0_f32, right: 3.0_f32
+//! // .scores: only in right: 3_u32
+//! assert_reflect_eq!(player, moved);
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+use core::fmt::Write;
+
+use crate::diff::diff;
+use crate::diff::Change;
+use crate::key_path::KeyPath;
+use crate::Reflect;
+
+/// Assert that two values are equal according to [`Reflect::reflect_partial_eq`].
+///
+/// On failure the panic message lists the key path of every difference, along with the values on
+/// either side, in the format of [`Value::to_canonical_string`]. Like [`assert_eq!`] a custom
+/// message can be given after the values.
+///
+/// Values that can't be compared, because they contain opaque types, are considered different.
+///
+/// See the [`testing`](crate::testing) module for an example.
+///
+/// [`Value::to_canonical_string`]: crate::Value::to_canonical_string
+#[macro_export]
+macro_rules! assert_reflect_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        use $crate::Reflect as _;
+        $crate::testing::assert_reflect_eq(
+            (&$left).as_reflect(),
+            (&$right).as_reflect(),
+            ::core::option::Option::None,
+        )
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        use $crate::Reflect as _;
+        $crate::testing::assert_reflect_eq(
+            (&$left).as_reflect(),
+            (&$right).as_reflect(),
+            ::core::option::Option::Some(::core::format_args!($($arg)+)),
+        )
+    }};
+}
+
+/// The function behind [`assert_reflect_eq!`](crate::assert_reflect_eq).
+#[track_caller]
+pub fn assert_reflect_eq(
+    left: &dyn Reflect,
+    right: &dyn Reflect,
+    message: Option<fmt::Arguments<'_>>,
+) {
+    if let Some(differences) = describe_differences(left, right) {
+        match message {
+            Some(message) => panic!("assertion `left == right` failed: {message}\n{differences}"),
+            None => panic!("assertion `left == right` failed\n{differences}"),
+        }
+    }
+}
+
+/// Describe how `left` and `right` differ, one line per difference, or `None` if they are
+/// equal.
+///
+/// This is the text that [`assert_reflect_eq!`](crate::assert_reflect_eq) panics with.
+pub fn describe_differences(left: &dyn Reflect, right: &dyn Reflect) -> Option<String> {
+    if left.reflect_partial_eq(right) == Some(true) {
+        return None;
+    }
+
+    let mut out = String::new();
+    for change in diff(left, right).changes() {
+        let path = DisplayPath(change.path());
+        let _ = match change {
+            Change::Replace { old, new, .. } => writeln!(
+                out,
+                "{path}: left: {}, right: {}",
+                old.to_canonical_string(),
+                new.to_canonical_string(),
+            ),
+            Change::Pop { value, .. } => {
+                writeln!(out, "{path}: only in left: {}", value.to_canonical_string())
+            }
+            Change::Push { value, .. } => {
+                writeln!(
+                    out,
+                    "{path}: only in right: {}",
+                    value.to_canonical_string()
+                )
+            }
+            Change::Remove { key, value, .. } => writeln!(
+                out,
+                "{path}: only in left: {}: {}",
+                key.to_canonical_string(),
+                value.to_canonical_string(),
+            ),
+            Change::Insert { key, value, .. } => writeln!(
+                out,
+                "{path}: only in right: {}: {}",
+                key.to_canonical_string(),
+                value.to_canonical_string(),
+            ),
+        };
+    }
+
+    if out.is_empty() {
+        // the values are structurally the same but still not equal, for example because they
+        // contain opaque values that can't be compared
+        let _ = writeln!(out, "left: {left:?}\nright: {right:?}");
+    }
+
+    out.pop();
+    Some(out)
+}
+
+struct DisplayPath<'a>(&'a KeyPath);
+
+impl fmt::Display for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            f.write_str("<root>")
+        } else {
+            fmt::Display::fmt(self.0, f)
+        }
+    }
+}
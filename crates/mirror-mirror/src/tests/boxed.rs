@@ -0,0 +1,95 @@
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::FromReflect;
+use crate::Reflect;
+#[cfg(feature = "type_info")]
+use crate::Typed;
+use crate::Value;
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Reflect, Clone, Debug)]
+#[reflect(crate_name(crate))]
+struct Entity {
+    name: String,
+    components: Vec<Box<dyn Reflect>>,
+}
+
+fn entity() -> Entity {
+    Entity {
+        name: "player".to_owned(),
+        components: Vec::from([
+            Box::new(Position { x: 1.0, y: 2.0 }) as Box<dyn Reflect>,
+            Box::new(100_u32),
+        ]),
+    }
+}
+
+#[test]
+fn reflects_as_the_boxed_value() {
+    let boxed: Box<dyn Reflect> = Box::new(Position { x: 1.0, y: 2.0 });
+
+    assert!(boxed.as_struct().is_some());
+    assert_eq!(boxed.type_name(), core::any::type_name::<Position>());
+    assert_eq!(
+        Reflect::type_id(&boxed),
+        core::any::TypeId::of::<Position>()
+    );
+    assert_eq!(boxed.to_value(), Position { x: 1.0, y: 2.0 }.to_value());
+    assert_eq!(
+        boxed.reflect_partial_eq(&Position { x: 1.0, y: 2.0 }),
+        Some(true)
+    );
+    #[cfg(feature = "type_info")]
+    assert_eq!(
+        boxed.type_info().type_name(),
+        <Position as Typed>::type_info().type_name()
+    );
+
+    let cloned = boxed.clone();
+    assert_eq!(
+        cloned.downcast_ref::<Position>(),
+        Some(&Position { x: 1.0, y: 2.0 })
+    );
+
+    let from_reflect = Box::<dyn Reflect>::from_reflect(&boxed).unwrap();
+    assert!(from_reflect.downcast_ref::<Position>().is_some());
+    assert!(from_reflect.downcast_ref::<Box<dyn Reflect>>().is_none());
+}
+
+#[test]
+fn in_containers_and_fields() {
+    let mut entity = entity();
+
+    assert_eq!(
+        entity.get_at::<f32>(&key_path!(.components[0].y)),
+        Some(&2.0)
+    );
+    assert_eq!(entity.get_at::<u32>(&key_path!(.components[1])), Some(&100));
+
+    *entity
+        .get_at_mut::<f32>(&key_path!(.components[0].x))
+        .unwrap() = 5.0;
+    assert_eq!(
+        entity.components[0].downcast_ref::<Position>(),
+        Some(&Position { x: 5.0, y: 2.0 })
+    );
+
+    let mut patch = entity.to_value();
+    *patch.get_at_mut::<u32>(&key_path!(.components[1])).unwrap() = 50;
+    patch.set_at(&key_path!(.name), "enemy".to_owned()).unwrap();
+    entity.patch(&patch);
+    assert_eq!(entity.name, "enemy");
+    assert_eq!(entity.components[1].downcast_ref::<u32>(), Some(&50));
+
+    let value = Value::from(entity.components[0].clone());
+    assert_eq!(
+        Position::from_reflect(&value),
+        Some(Position { x: 5.0, y: 2.0 })
+    );
+}
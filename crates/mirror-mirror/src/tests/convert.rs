@@ -0,0 +1,241 @@
+use alloc::collections::BTreeMap;
+use core::num::NonZeroU32;
+
+use crate::convert::ConvertError;
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+struct PlayerDto {
+    name: String,
+    score: u32,
+    inventory: Vec<ItemDto>,
+    stats: BTreeMap<String, i32>,
+    status: StatusDto,
+    is_admin: bool,
+}
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+struct ItemDto(String, u8);
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+enum StatusDto {
+    Online { since: u32 },
+    Offline,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Player {
+    name: String,
+    score: u64,
+    inventory: Vec<Item>,
+    stats: BTreeMap<String, f64>,
+    status: Status,
+    nickname: Option<String>,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Item(String, u16);
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+enum Status {
+    Online { since: i64 },
+    Offline,
+    Away,
+}
+
+fn dto() -> PlayerDto {
+    PlayerDto {
+        name: "Alice".to_owned(),
+        score: 42,
+        inventory: Vec::from([ItemDto("sword".to_owned(), 1)]),
+        stats: BTreeMap::from([("strength".to_owned(), 10)]),
+        status: StatusDto::Online { since: 1337 },
+        is_admin: true,
+    }
+}
+
+#[test]
+fn converts_by_field_name() {
+    let player = crate::convert::<_, Player>(&dto()).unwrap();
+
+    assert_eq!(
+        player,
+        Player {
+            name: "Alice".to_owned(),
+            score: 42,
+            inventory: Vec::from([Item("sword".to_owned(), 1)]),
+            stats: BTreeMap::from([("strength".to_owned(), 10.0)]),
+            status: Status::Online { since: 1337 },
+            nickname: None,
+        }
+    );
+}
+
+#[test]
+fn converts_from_dyn_reflect_and_values() {
+    let dto: Box<dyn Reflect> = Box::new(dto());
+    let from_dyn = crate::convert::<dyn Reflect, Player>(&*dto).unwrap();
+    let from_value = crate::convert::<_, Player>(&dto.to_value()).unwrap();
+    assert_eq!(from_dyn, from_value);
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(crate::convert::<_, u8>(&255_i64).unwrap(), 255);
+    assert_eq!(crate::convert::<_, i8>(&-1_i32).unwrap(), -1);
+    assert_eq!(crate::convert::<_, f32>(&3_u8).unwrap(), 3.0);
+    assert_eq!(crate::convert::<_, f64>(&1.5_f32).unwrap(), 1.5);
+
+    assert!(matches!(
+        crate::convert::<_, u8>(&256_u16).unwrap_err(),
+        ConvertError::OutOfRange { .. }
+    ));
+    assert!(matches!(
+        crate::convert::<_, u32>(&-1_i32).unwrap_err(),
+        ConvertError::OutOfRange { .. }
+    ));
+    assert!(matches!(
+        crate::convert::<_, i32>(&1.5_f64).unwrap_err(),
+        ConvertError::ScalarMismatch { .. }
+    ));
+    assert!(matches!(
+        crate::convert::<_, String>(&true).unwrap_err(),
+        ConvertError::ScalarMismatch { .. }
+    ));
+}
+
+#[test]
+fn errors_point_at_the_failing_value() {
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate))]
+    struct Narrow {
+        inventory: Vec<NarrowItem>,
+    }
+
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate))]
+    struct NarrowItem(String, bool);
+
+    let err = crate::convert::<_, Narrow>(&dto()).unwrap_err();
+    assert!(matches!(err, ConvertError::ScalarMismatch { .. }));
+    assert_eq!(err.path().unwrap().to_string(), ".inventory[0].1");
+
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate))]
+    struct Required {
+        name: String,
+        level: NonZeroU32,
+    }
+
+    let err = crate::convert::<_, Required>(&dto()).unwrap_err();
+    assert!(matches!(err, ConvertError::MissingField { .. }));
+    assert_eq!(err.path().unwrap().to_string(), ".level");
+
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate))]
+    struct OnlyOffline {
+        status: OfflineOnly,
+    }
+
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate))]
+    enum OfflineOnly {
+        Offline,
+    }
+
+    let err = crate::convert::<_, OnlyOffline>(&dto()).unwrap_err();
+    assert!(matches!(
+        &err,
+        ConvertError::UnknownVariant { variant, .. } if variant == "Online"
+    ));
+    assert_eq!(err.path().unwrap().to_string(), ".status");
+
+    let err = crate::convert::<_, Vec<String>>(&dto()).unwrap_err();
+    assert!(matches!(err, ConvertError::KindMismatch { .. }));
+}
+
+#[test]
+fn coercion_policy() {
+    use crate::convert::convert_with;
+    use crate::convert::CoercionPolicy;
+    use crate::convert::NumberCoercion;
+
+    let widening = CoercionPolicy::new().numbers(NumberCoercion::Widening);
+    assert_eq!(convert_with::<_, u32>(&1_u8, &widening).unwrap(), 1);
+    assert_eq!(convert_with::<_, i64>(&-1_i32, &widening).unwrap(), -1);
+    assert_eq!(convert_with::<_, i16>(&1_u8, &widening).unwrap(), 1);
+    assert_eq!(convert_with::<_, f32>(&-1_i16, &widening).unwrap(), -1.0);
+    assert_eq!(convert_with::<_, f64>(&1_u32, &widening).unwrap(), 1.0);
+    assert_eq!(convert_with::<_, f64>(&1.5_f32, &widening).unwrap(), 1.5);
+    for err in [
+        convert_with::<_, u8>(&1_u32, &widening).unwrap_err(),
+        convert_with::<_, u32>(&1_i32, &widening).unwrap_err(),
+        convert_with::<_, i8>(&1_u8, &widening).unwrap_err(),
+        convert_with::<_, f32>(&1_u32, &widening).unwrap_err(),
+        convert_with::<_, f32>(&1.5_f64, &widening).unwrap_err(),
+    ] {
+        assert!(matches!(err, ConvertError::ScalarMismatch { .. }));
+    }
+
+    assert!(matches!(
+        crate::convert::<_, char>(&"a".to_owned()).unwrap_err(),
+        ConvertError::ScalarMismatch { .. }
+    ));
+    let chars = CoercionPolicy::new().strings_to_chars(true);
+    assert_eq!(
+        convert_with::<_, char>(&"a".to_owned(), &chars).unwrap(),
+        'a'
+    );
+    assert!(matches!(
+        convert_with::<_, char>(&"ab".to_owned(), &chars).unwrap_err(),
+        ConvertError::ScalarMismatch { .. }
+    ));
+
+    assert!(matches!(
+        crate::convert::<_, Status>(&"Away".to_owned()).unwrap_err(),
+        ConvertError::KindMismatch { .. }
+    ));
+    let variants = CoercionPolicy::new().strings_to_unit_variants(true);
+    assert_eq!(
+        convert_with::<_, Status>(&"Away".to_owned(), &variants).unwrap(),
+        Status::Away
+    );
+    assert!(matches!(
+        convert_with::<_, Status>(&"Online".to_owned(), &variants).unwrap_err(),
+        ConvertError::KindMismatch { .. }
+    ));
+    assert!(matches!(
+        convert_with::<_, Status>(&"Busy".to_owned(), &variants).unwrap_err(),
+        ConvertError::UnknownVariant { variant, .. } if variant == "Busy"
+    ));
+}
+
+#[test]
+fn patch_with_policy() {
+    use crate::convert::patch_with;
+    use crate::convert::CoercionPolicy;
+    use crate::struct_::StructValue;
+
+    let mut player = crate::convert::<_, Player>(&dto()).unwrap();
+    let policy = CoercionPolicy::new().strings_to_unit_variants(true);
+
+    let patch = StructValue::new()
+        .with_field("score", 100_u8)
+        .with_field("status", "Away");
+    patch_with(&mut player, &patch, &policy).unwrap();
+    assert_eq!(player.score, 100);
+    assert_eq!(player.status, Status::Away);
+    assert_eq!(player.name, "Alice");
+
+    let patch = StructValue::new().with_field("score", -1_i32);
+    let err = patch_with(&mut player, &patch, &policy).unwrap_err();
+    assert!(matches!(err, ConvertError::OutOfRange { .. }));
+    assert_eq!(err.path().unwrap().to_string(), ".score");
+    assert_eq!(player.score, 100);
+}
@@ -0,0 +1,140 @@
+#[cfg(feature = "type_info")]
+use crate::FromReflect;
+use crate::Reflect;
+#[cfg(feature = "type_info")]
+use crate::Typed;
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate), opt_in(Default))]
+struct Config {
+    #[reflect(default = 8080)]
+    port: u16,
+    #[reflect(default = "localhost".to_owned())]
+    host: String,
+    verbose: bool,
+    #[reflect(skip, default = NotReflect(1))]
+    not_reflect: NotReflect,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct NotReflect(i32);
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate), opt_in(Default))]
+struct Ratio(#[reflect(default = 1.0)] f32, u8);
+
+#[test]
+fn derives_default() {
+    assert_eq!(
+        Config::default(),
+        Config {
+            port: 8080,
+            host: "localhost".to_owned(),
+            verbose: false,
+            not_reflect: NotReflect(1),
+        }
+    );
+
+    assert_eq!(Ratio::default(), Ratio(1.0, 0));
+}
+
+#[cfg(feature = "type_info")]
+#[test]
+fn defaults_are_recorded_in_type_info() {
+    let type_info = <Config as Typed>::type_info();
+    let struct_ = type_info.as_struct().unwrap();
+
+    let port = struct_.field_type("port").unwrap();
+    assert_eq!(port.default_value().unwrap(), 8080_u16.to_value());
+
+    let verbose = struct_.field_type("verbose").unwrap();
+    assert_eq!(verbose.default_value().unwrap(), false.to_value());
+
+    let value = type_info.default_value().unwrap();
+    assert_eq!(Config::from_reflect(&value).unwrap(), Config::default());
+
+    let type_info = <Ratio as Typed>::type_info();
+    let value = type_info.default_value().unwrap();
+    assert_eq!(Ratio::from_reflect(&value).unwrap(), Ratio::default());
+}
+
+#[cfg(feature = "type_info")]
+#[test]
+fn enum_fields() {
+    #[derive(Reflect, Debug, Clone, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Shape {
+        Circle {
+            #[reflect(default = 1.0)]
+            radius: f32,
+        },
+        Square(#[reflect(default = 2.0)] f32),
+    }
+
+    let type_info = <Shape as Typed>::type_info();
+    let value = type_info.default_value().unwrap();
+    assert_eq!(
+        Shape::from_reflect(&value).unwrap(),
+        Shape::Circle { radius: 1.0 }
+    );
+
+    let square = type_info.as_enum().unwrap().variant("Square").unwrap();
+    let field = square.field_type_at(0).unwrap();
+    assert_eq!(field.default_value().unwrap(), 2.0_f32.to_value());
+
+    let value = square.default_value().unwrap();
+    assert_eq!(Shape::from_reflect(&value).unwrap(), Shape::Square(2.0));
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Volume(u8);
+
+impl Default for Volume {
+    fn default() -> Self {
+        Self(50)
+    }
+}
+
+#[cfg(feature = "type_info")]
+#[test]
+fn defaults_of_field_types_are_recorded() {
+    #[derive(Reflect, Debug, Clone, PartialEq)]
+    #[reflect(crate_name(crate), opt_in(Default))]
+    struct Audio {
+        music: Volume,
+        #[reflect(default = Volume(10))]
+        effects: Volume,
+    }
+
+    #[derive(Reflect, Debug, Clone, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Settings {
+        #[reflect(default)]
+        volume: Volume,
+        muted: bool,
+    }
+
+    let type_info = <Audio as Typed>::type_info();
+    let struct_ = type_info.as_struct().unwrap();
+    let music = struct_.field_type("music").unwrap();
+    assert_eq!(music.declared_default_value(), Some(&Volume(50).to_value()));
+    assert_eq!(music.default_value().unwrap(), Volume(50).to_value());
+    let effects = struct_.field_type("effects").unwrap();
+    assert_eq!(effects.default_value().unwrap(), Volume(10).to_value());
+    assert_eq!(
+        Audio::from_reflect(&type_info.default_value().unwrap()).unwrap(),
+        Audio::default()
+    );
+
+    let type_info = <Settings as Typed>::type_info();
+    let struct_ = type_info.as_struct().unwrap();
+    let volume = struct_.field_type("volume").unwrap();
+    assert_eq!(
+        volume.declared_default_value(),
+        Some(&Volume(50).to_value())
+    );
+    let muted = struct_.field_type("muted").unwrap();
+    assert_eq!(muted.declared_default_value(), None);
+    assert_eq!(muted.default_value().unwrap(), false.to_value());
+}
@@ -0,0 +1,172 @@
+use alloc::collections::BTreeMap;
+
+use crate::diff::diff;
+use crate::diff::Change;
+use crate::diff::DiffError;
+use crate::diff::History;
+use crate::key_path;
+use crate::struct_::StructValue;
+use crate::FromReflect;
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Scene {
+    name: String,
+    entities: Vec<Entity>,
+    tags: BTreeMap<String, u32>,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Entity {
+    position: [f32; 2],
+    shape: Shape,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+enum Shape {
+    Circle { radius: f32 },
+    Square(f32),
+}
+
+fn scene() -> Scene {
+    Scene {
+        name: "level".to_owned(),
+        entities: Vec::from([
+            Entity {
+                position: [0.0, 0.0],
+                shape: Shape::Circle { radius: 1.0 },
+            },
+            Entity {
+                position: [1.0, 1.0],
+                shape: Shape::Square(2.0),
+            },
+        ]),
+        tags: BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]),
+    }
+}
+
+#[test]
+fn only_records_what_changed() {
+    let old = scene();
+    let mut new = scene();
+    new.entities[0].position[1] = 5.0;
+    new.entities[1].shape = Shape::Square(3.0);
+
+    let diff = diff(&old, &new);
+    assert_eq!(
+        diff.changes().cloned().collect::<Vec<_>>(),
+        Vec::from([
+            Change::Replace {
+                path: key_path!(.entities[0_usize].position[1_usize]),
+                old: 0.0_f32.to_value(),
+                new: 5.0_f32.to_value(),
+            },
+            Change::Replace {
+                path: key_path!(.entities[1_usize].shape::Square.0),
+                old: 2.0_f32.to_value(),
+                new: 3.0_f32.to_value(),
+            },
+        ])
+    );
+
+    assert!(crate::diff::diff(&old, &old).is_empty());
+}
+
+#[test]
+fn apply_and_invert() {
+    let old = scene();
+    let mut new = scene();
+    new.name = "other".to_owned();
+    new.entities[0].shape = Shape::Square(1.0);
+    new.entities.pop();
+    new.entities.push(Entity {
+        position: [2.0, 2.0],
+        shape: Shape::Circle { radius: 3.0 },
+    });
+    new.entities.push(Entity {
+        position: [3.0, 3.0],
+        shape: Shape::Square(4.0),
+    });
+    new.tags.remove("a");
+    new.tags.insert("b".to_owned(), 3);
+    new.tags.insert("c".to_owned(), 4);
+
+    let diff = diff(&old, &new);
+
+    let mut value = old.clone();
+    diff.apply(&mut value).unwrap();
+    assert_eq!(value, new);
+
+    diff.invert().apply(&mut value).unwrap();
+    assert_eq!(value, old);
+
+    // diffs also apply to `Value`s
+    let mut value = old.to_value();
+    diff.apply(&mut value).unwrap();
+    assert_eq!(Scene::from_reflect(&value).unwrap(), new);
+}
+
+#[test]
+fn apply_errors() {
+    let mut new = scene();
+    new.entities.clear();
+    let diff = diff(&scene(), &new);
+
+    let mut target = 1_i32;
+    assert!(matches!(
+        diff.apply(&mut target).unwrap_err(),
+        DiffError::NotFound { path } if path == key_path!(.entities)
+    ));
+
+    let mut target = StructValue::new().with_field("entities", 1_i32);
+    assert!(matches!(
+        diff.apply(&mut target).unwrap_err(),
+        DiffError::WrongKind { path, expected: "list" } if path == key_path!(.entities)
+    ));
+}
+
+#[test]
+fn history() {
+    let mut scene = scene();
+    let mut history = History::new();
+    assert!(!history.can_undo());
+    assert!(!history.undo(&mut scene).unwrap());
+
+    history.edit(&mut scene, |scene| {
+        let scene = scene.downcast_mut::<Scene>().unwrap();
+        scene.name = "first".to_owned();
+    });
+    history.edit(&mut scene, |scene| {
+        let scene = scene.downcast_mut::<Scene>().unwrap();
+        scene.entities.remove(0);
+        scene.tags.clear();
+    });
+    // edits that don't change anything aren't recorded
+    history.edit(&mut scene, |_| {});
+
+    assert!(history.undo(&mut scene).unwrap());
+    assert_eq!(scene.entities.len(), 2);
+    assert_eq!(scene.tags.len(), 2);
+    assert_eq!(scene.name, "first");
+
+    assert!(history.undo(&mut scene).unwrap());
+    assert_eq!(scene, self::scene());
+    assert!(!history.can_undo());
+
+    assert!(history.redo(&mut scene).unwrap());
+    assert_eq!(scene.name, "first");
+
+    // a new edit clears the redo stack
+    history.edit(&mut scene, |scene| {
+        let scene = scene.downcast_mut::<Scene>().unwrap();
+        scene.name = "second".to_owned();
+    });
+    assert!(!history.can_redo());
+    assert!(!history.redo(&mut scene).unwrap());
+
+    assert!(history.undo(&mut scene).unwrap());
+    assert_eq!(scene.name, "first");
+}
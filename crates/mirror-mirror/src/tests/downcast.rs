@@ -0,0 +1,35 @@
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Foo {
+    n: i32,
+}
+
+#[test]
+fn take() {
+    let boxed: Box<dyn Reflect> = Box::new(Foo { n: 1 });
+    assert!(boxed.is::<Foo>());
+    assert!(!boxed.is::<i32>());
+
+    let boxed = boxed.take::<i32>().unwrap_err();
+    assert_eq!(boxed.take::<Foo>().unwrap(), Foo { n: 1 });
+
+    let boxed: Box<dyn Reflect> = Box::new(String::from("foo"));
+    assert_eq!(boxed.take::<String>().unwrap(), "foo");
+}
+
+#[test]
+fn try_take() {
+    let owned = Box::new(Foo { n: 1 }).reflect_owned();
+    let owned = owned.try_take::<String>().unwrap_err();
+    assert!(owned.clone().into_struct().is_some());
+    assert_eq!(owned.try_take::<Foo>().unwrap(), Foo { n: 1 });
+
+    let owned = Box::new(1_u8).reflect_owned();
+    let owned = owned.try_take::<i32>().unwrap_err();
+    assert_eq!(owned.try_take::<u8>().unwrap(), 1);
+
+    let owned = Box::new(Vec::from([1, 2])).reflect_owned();
+    assert_eq!(owned.try_take::<Vec<i32>>().unwrap(), [1, 2]);
+}
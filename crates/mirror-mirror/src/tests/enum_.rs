@@ -5,6 +5,8 @@ use crate::get_field::GetFieldMut;
 use crate::Enum;
 use crate::FromReflect;
 use crate::Reflect;
+#[cfg(feature = "type_info")]
+use crate::Typed;
 
 #[test]
 fn enum_value() {
@@ -420,3 +422,63 @@ fn from_reflect_with_value() {
         .finish();
     assert!(Foo::from_reflect(&value).is_some());
 }
+
+#[cfg(feature = "type_info")]
+#[test]
+fn discriminants() {
+    const BASE: u8 = 10;
+
+    #[derive(Debug, Clone, Copy, Reflect)]
+    #[reflect(crate_name(crate))]
+    #[repr(u8)]
+    pub enum Flags {
+        A = 1,
+        B = 4,
+        C,
+        D = BASE,
+        E,
+    }
+
+    let info = <Flags as Typed>::type_info();
+    let enum_ = info.get_type().as_enum().unwrap();
+
+    assert_eq!(enum_.repr(), Some("u8"));
+    for flag in [Flags::A, Flags::B, Flags::C, Flags::D, Flags::E] {
+        let variant = enum_.variant(flag.variant_name()).unwrap();
+        assert_eq!(variant.discriminant(), Some(flag as i128));
+    }
+
+    assert_eq!(enum_.variant_name_for_discriminant(5), Some("C"));
+    assert_eq!(enum_.variant_name_for_discriminant(11), Some("E"));
+    assert_eq!(enum_.variant_name_for_discriminant(2), None);
+    assert_eq!(enum_.discriminant_for_variant_name("D"), Some(10));
+    assert_eq!(enum_.discriminant_for_variant_name("Z"), None);
+
+    #[derive(Debug, Clone, Reflect)]
+    #[reflect(crate_name(crate))]
+    pub enum Implicit {
+        A,
+        B,
+    }
+
+    let info = <Implicit as Typed>::type_info();
+    let enum_ = info.get_type().as_enum().unwrap();
+    assert_eq!(enum_.repr(), None);
+    assert_eq!(enum_.variant("B").unwrap().discriminant(), None);
+
+    #[derive(Debug, Clone, Reflect)]
+    #[reflect(crate_name(crate))]
+    #[repr(i16)]
+    pub enum WithData {
+        A { a: i32 } = -2,
+        B(bool),
+        C,
+    }
+
+    let info = <WithData as Typed>::type_info();
+    let enum_ = info.get_type().as_enum().unwrap();
+    assert_eq!(enum_.repr(), Some("i16"));
+    assert_eq!(enum_.discriminant_for_variant_name("A"), Some(-2));
+    assert_eq!(enum_.discriminant_for_variant_name("B"), Some(-1));
+    assert_eq!(enum_.variant_name_for_discriminant(0), Some("C"));
+}
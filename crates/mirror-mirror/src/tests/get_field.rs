@@ -0,0 +1,129 @@
+use crate::get_field::FieldError;
+use crate::get_field::FieldKey;
+use crate::get_field::GetField;
+use crate::get_field::GetFieldMut;
+use crate::Reflect;
+use crate::Value;
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Foo {
+    a: i32,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+enum Bar {
+    A { a: i32 },
+    B(i32),
+}
+
+#[test]
+fn missing() {
+    let foo = Foo { a: 1 };
+    assert_eq!(foo.try_get_field::<i32>("a").unwrap(), &1);
+    assert_eq!(
+        foo.try_get_field::<i32>("b").unwrap_err(),
+        FieldError::Missing {
+            field: FieldKey::Name("b".to_owned())
+        }
+    );
+    assert!(foo.get_field::<i32>("b").is_none());
+
+    let tuple = (1_i32, false);
+    assert_eq!(
+        tuple.try_get_field::<i32>(2).unwrap_err(),
+        FieldError::Missing {
+            field: FieldKey::Index(2)
+        }
+    );
+}
+
+#[test]
+fn wrong_type() {
+    let mut foo = Foo { a: 1 };
+    let err = foo.try_get_field::<bool>("a").unwrap_err();
+    assert_eq!(
+        err,
+        FieldError::WrongType {
+            field: FieldKey::Name("a".to_owned()),
+            expected: "bool",
+            actual: "i32".to_owned(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "field `a` has type `i32` but `bool` was expected"
+    );
+    assert!(foo.try_get_field_mut::<bool>("a").is_err());
+
+    *foo.try_get_field_mut::<i32>("a").unwrap() = 2;
+    assert_eq!(foo, Foo { a: 2 });
+}
+
+#[test]
+fn missing_in_variant() {
+    let mut bar = Bar::B(1);
+    let err = bar.try_get_field::<i32>("a").unwrap_err();
+    assert_eq!(
+        err,
+        FieldError::MissingInVariant {
+            field: FieldKey::Name("a".to_owned()),
+            variant: "B".to_owned(),
+        }
+    );
+    assert_eq!(err.to_string(), "variant `B` has no field `a`");
+    assert!(matches!(
+        bar.try_get_field_mut::<i32>(1).unwrap_err(),
+        FieldError::MissingInVariant { .. }
+    ));
+    assert_eq!(bar.try_get_field::<i32>(0).unwrap(), &1);
+}
+
+#[test]
+fn value() {
+    let value = Foo { a: 1 }.to_value();
+    assert_eq!(value.try_get_field::<i32>("a").unwrap(), &1);
+    assert_eq!(
+        value.try_get_field::<i32>(0_usize).unwrap_err().field(),
+        Some(&FieldKey::Index(0))
+    );
+
+    let map = Value::from(std::collections::BTreeMap::from([(1_i32, 2_i32)]));
+    assert_eq!(map.try_get_field::<i32>(1_i32).unwrap(), &2);
+    assert_eq!(
+        map.try_get_field::<i32>(2_i32).unwrap_err(),
+        FieldError::Missing {
+            field: FieldKey::Key(Value::i32(2))
+        }
+    );
+}
+
+#[test]
+fn impls_only_need_get_field() {
+    struct Config {
+        value: Foo,
+    }
+
+    struct ByName;
+
+    impl<'a> GetField<'a, &str, ByName> for &'a Config {
+        fn get_field<T>(self, key: &str) -> Option<&'a T>
+        where
+            T: Reflect,
+        {
+            match key {
+                "value" => self.value.as_reflect().downcast_ref(),
+                _ => None,
+            }
+        }
+    }
+
+    let config = Config {
+        value: Foo { a: 1 },
+    };
+    assert_eq!(config.try_get_field::<Foo>("value").unwrap(), &Foo { a: 1 });
+    let err = config.try_get_field::<Foo>("other").unwrap_err();
+    assert_eq!(err, FieldError::Unavailable);
+    assert_eq!(err.field(), None);
+}
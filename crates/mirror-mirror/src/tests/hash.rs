@@ -0,0 +1,108 @@
+use alloc::collections::BTreeMap;
+
+use crate::struct_::StructValue;
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+struct Foo {
+    a: i32,
+    b: Vec<f32>,
+    c: Bar,
+}
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+enum Bar {
+    A(String),
+    B { b: bool },
+}
+
+#[test]
+fn equal_values_hash_the_same() {
+    let foo = Foo {
+        a: 1,
+        b: Vec::from([1.0, 2.0]),
+        c: Bar::B { b: true },
+    };
+
+    assert!(foo.reflect_hash().is_some());
+    assert_eq!(foo.reflect_hash(), foo.clone().reflect_hash());
+    assert_eq!(foo.reflect_hash(), foo.to_value().reflect_hash());
+
+    let other = Foo {
+        c: Bar::A("foo".to_owned()),
+        ..foo.clone()
+    };
+    assert_ne!(foo.reflect_hash(), other.reflect_hash());
+
+    let other = Foo {
+        b: Vec::from([2.0, 1.0]),
+        ..foo.clone()
+    };
+    assert_ne!(foo.reflect_hash(), other.reflect_hash());
+}
+
+#[test]
+fn field_order_doesnt_matter() {
+    let foo = Foo {
+        a: 1,
+        b: Vec::from([1.0, 2.0]),
+        c: Bar::B { b: true },
+    };
+
+    let reordered = StructValue::new()
+        .with_field("c", foo.c.to_value())
+        .with_field("b", foo.b.to_value())
+        .with_field("a", foo.a);
+
+    assert_eq!(foo.reflect_partial_eq(&reordered), Some(true));
+    assert_eq!(foo.reflect_hash(), reordered.reflect_hash());
+}
+
+#[test]
+fn maps() {
+    let mut a = BTreeMap::new();
+    let mut b = BTreeMap::new();
+    for n in 0..100 {
+        a.insert(n, n.to_string());
+        b.insert(99 - n, (99 - n).to_string());
+    }
+    assert_eq!(a.reflect_hash(), b.reflect_hash());
+    assert_eq!(a.reflect_hash(), a.to_value().reflect_hash());
+
+    b.insert(0, "changed".to_owned());
+    assert_ne!(a.reflect_hash(), b.reflect_hash());
+}
+
+#[test]
+fn opt_in_hash() {
+    #[derive(Reflect, Debug, Clone, Hash)]
+    #[reflect(crate_name(crate), opt_in(Hash))]
+    struct Id(#[reflect(skip)] u64, String);
+
+    let a = Id(1, "a".to_owned());
+    let b = Id(2, "a".to_owned());
+
+    // the skipped field isn't visible to the structural hash
+    assert_eq!(a.to_value().reflect_hash(), b.to_value().reflect_hash());
+    // but it is used by the type's own `Hash` impl
+    assert_ne!(a.reflect_hash(), b.reflect_hash());
+    assert_eq!(Box::new(a.clone()).reflect_hash(), a.reflect_hash());
+}
+
+#[test]
+fn stable_hasher_is_fnv_1a() {
+    use crate::hash::StableHasher;
+    use core::hash::Hasher;
+
+    let hash = |bytes: &[u8]| {
+        let mut hasher = StableHasher::default();
+        hasher.write(bytes);
+        hasher.finish()
+    };
+
+    assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+}
@@ -2,9 +2,13 @@ use alloc::collections::BTreeMap;
 
 use crate::key_path;
 use crate::key_path::*;
+use crate::struct_::StructValue;
+#[cfg(feature = "type_info")]
 use crate::type_info::ScalarType;
+#[cfg(feature = "type_info")]
 use crate::type_info::TypeAtPath;
 use crate::Reflect;
+#[cfg(feature = "type_info")]
 use crate::Typed;
 
 #[test]
@@ -67,6 +71,7 @@ fn display() {
     );
 }
 
+#[cfg(feature = "type_info")]
 #[test]
 fn query_type_info_struct() {
     #[derive(Reflect, Clone, Debug)]
@@ -107,6 +112,7 @@ fn query_type_info_struct() {
     ));
 }
 
+#[cfg(feature = "type_info")]
 #[test]
 fn query_type_info_enum() {
     #[derive(Reflect, Clone, Debug)]
@@ -152,3 +158,358 @@ fn select_tuple_field() {
     assert_eq!(foo.get_at::<i32>(&key_path!(.0)).unwrap(), &42);
     assert_eq!(foo.get_at::<bool>(&key_path!(.1)).unwrap(), &true);
 }
+
+#[test]
+fn raw_identifiers_and_string_segments() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        r#type: i32,
+        r#match: Bar,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Bar {
+        Baz { r#ref: bool },
+    }
+
+    let foo = Foo {
+        r#type: 42,
+        r#match: Bar::Baz { r#ref: true },
+    };
+
+    assert_eq!(foo.get_at::<i32>(&key_path!(.r#type)).unwrap(), &42);
+    assert_eq!(foo.get_at::<i32>(&key_path!(."type")).unwrap(), &42);
+    assert_eq!(
+        foo.get_at::<bool>(&key_path!(.r#match::Baz.r#ref)).unwrap(),
+        &true
+    );
+    assert_eq!(
+        foo.get_at::<bool>(&key_path!(."match"::"Baz"."ref"))
+            .unwrap(),
+        &true
+    );
+    assert!(foo.at(&key_path!(.r#match::"Qux")).is_none());
+
+    assert_eq!(key_path!(.r#type).to_string(), ".type");
+    assert_eq!(key_path!(.r#match::Baz).to_string(), ".match::Baz");
+}
+
+// without type info values aren't converted to the type of the target
+#[cfg(feature = "type_info")]
+#[test]
+fn set_at() {
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct A {
+        a: u8,
+        b: Vec<i32>,
+        c: BTreeMap<String, B>,
+        d: Option<String>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct B(f64);
+
+    let mut a = A {
+        a: 1,
+        b: Vec::from([1, 2, 3]),
+        c: BTreeMap::from([("one".to_owned(), B(1.0)), ("two".to_owned(), B(2.0))]),
+        d: None,
+    };
+
+    a.set_at(&key_path!(.a), 42_i64).unwrap();
+    assert_eq!(a.a, 42);
+
+    a.set_at(&key_path!(.b), Vec::from([4_u8]).to_value())
+        .unwrap();
+    assert_eq!(a.b, Vec::from([4]));
+
+    a.set_at(&key_path!(.b[0]), 5).unwrap();
+    assert_eq!(a.b, Vec::from([5]));
+
+    a.set_at(
+        &key_path!(.c),
+        BTreeMap::from([("two".to_owned(), B(3.0)), ("three".to_owned(), B(4.0))]).to_value(),
+    )
+    .unwrap();
+    assert_eq!(
+        a.c,
+        BTreeMap::from([("two".to_owned(), B(3.0)), ("three".to_owned(), B(4.0))])
+    );
+
+    a.set_at(&key_path!(.c["two"].0), 1_u32).unwrap();
+    assert_eq!(a.c["two"], B(1.0));
+
+    a.set_at(&key_path!(.d), Some("hi".to_owned()).to_value())
+        .unwrap();
+    assert_eq!(a.d.as_deref(), Some("hi"));
+
+    let err = a.set_at(&key_path!(.a), 1337).unwrap_err();
+    assert!(matches!(
+        err,
+        SetError::Convert {
+            error: crate::convert::ConvertError::OutOfRange { .. },
+            ..
+        }
+    ));
+    assert_eq!(a.a, 42);
+
+    let err = a.set_at(&key_path!(.b), "nope").unwrap_err();
+    assert!(matches!(err, SetError::Convert { .. }));
+
+    let err = a.set_at(&key_path!(.missing), 1).unwrap_err();
+    assert!(matches!(err, SetError::NotFound { .. }));
+    assert_eq!(err.to_string(), "no value at `.missing`");
+
+    let snapshot = a.clone();
+    let mut other = A {
+        a: 0,
+        b: Vec::new(),
+        c: BTreeMap::new(),
+        d: None,
+    };
+    other
+        .set_at(&KeyPath::default(), snapshot.to_value())
+        .unwrap();
+    assert_eq!(other, snapshot);
+}
+
+#[cfg(feature = "type_info")]
+#[test]
+fn typed_key_path() {
+    use crate::typed_key_path;
+
+    #[derive(Reflect, Clone, Debug, Default)]
+    #[reflect(crate_name(crate))]
+    struct Person {
+        employer: Company,
+        scores: BTreeMap<String, (u8, Vec<u16>)>,
+        r#type: Box<[i8; 2]>,
+    }
+
+    #[derive(Reflect, Clone, Debug, Default)]
+    #[reflect(crate_name(crate))]
+    struct Company {
+        countries: Vec<Country>,
+    }
+
+    #[derive(Reflect, Clone, Debug, Default)]
+    #[reflect(crate_name(crate))]
+    struct Country {
+        name: String,
+    }
+
+    let mut person = Person {
+        employer: Company {
+            countries: Vec::from([Country {
+                name: "Denmark".to_owned(),
+            }]),
+        },
+        scores: BTreeMap::from([("a".to_owned(), (1, Vec::from([2, 3])))]),
+        r#type: Box::new([4, 5]),
+    };
+
+    let name = typed_key_path!(Person => .employer.countries[0].name);
+    assert_eq!(name.key_path(), &key_path!(.employer.countries[0].name));
+    assert_eq!(name.get(&person).unwrap(), "Denmark");
+    *name.get_mut(&mut person).unwrap() = "Sweden".to_owned();
+    assert_eq!(person.employer.countries[0].name, "Sweden");
+
+    let missing = typed_key_path!(Person => .employer.countries[1].name);
+    assert!(missing.get(&person).is_none());
+
+    let score = typed_key_path!(Person => .scores["a"].1[1]);
+    let score: &u16 = score.get(&person).unwrap();
+    assert_eq!(*score, 3);
+
+    let raw = typed_key_path!(Person => .r#type[1]);
+    assert_eq!(*raw.get(&person).unwrap(), 5);
+
+    let type_info = <Person as Typed>::type_info();
+    assert!(matches!(
+        type_info.type_at(name.key_path()).unwrap().as_scalar(),
+        Some(ScalarType::String)
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn typed_key_path_through_maps_and_tuple_structs() {
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    use crate::map::OrderedMap;
+    use crate::typed_key_path;
+    use crate::Value;
+
+    #[derive(Reflect, Clone, Debug, Default)]
+    #[reflect(crate_name(crate))]
+    struct Inventory {
+        counts: HashMap<String, u32>,
+        slots: HashMap<u8, Item>,
+        tags: HashSet<String>,
+        extra: OrderedMap,
+        #[reflect(skip)]
+        #[allow(dead_code)]
+        cache: Vec<u32>,
+    }
+
+    #[derive(Reflect, Clone, Debug, Default)]
+    #[reflect(crate_name(crate))]
+    struct Item(#[reflect(skip)] u64, String);
+
+    let inventory = Inventory {
+        counts: HashMap::from([("apple".to_owned(), 3)]),
+        slots: HashMap::from([(2, Item(0, "sword".to_owned()))]),
+        tags: HashSet::from(["food".to_owned()]),
+        extra: OrderedMap::new().with_entry("weight", 7_i32),
+        cache: Vec::new(),
+    };
+
+    let key = "apple".to_owned();
+    let count = typed_key_path!(Inventory => .counts[key]);
+    assert_eq!(count.get(&inventory), Some(&3));
+
+    let name = typed_key_path!(Inventory => .slots[2].1);
+    assert_eq!(name.get(&inventory).unwrap(), "sword");
+
+    let tag = typed_key_path!(Inventory => .tags["food"]);
+    assert_eq!(tag.get(&inventory), Some(&()));
+
+    // entries of an `OrderedMap` are `Value`s, which reflect as the type they contain
+    let weight = typed_key_path!(Inventory => .extra["weight"]);
+    let _: &TypedKeyPath<Inventory, Value> = &weight;
+    assert!(weight.get(&inventory).is_none());
+    assert_eq!(inventory.get_at::<i32>(weight.key_path()), Some(&7));
+}
+
+#[test]
+fn negative_indices_count_from_the_end() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Console {
+        log: Vec<String>,
+        recent: [u8; 3],
+    }
+
+    let mut console = Console {
+        log: Vec::from(["a".to_owned(), "b".to_owned(), "c".to_owned()]),
+        recent: [1, 2, 3],
+    };
+
+    assert_eq!(console.get_at::<String>(&key_path!(.log[-1])).unwrap(), "c");
+    assert_eq!(console.get_at::<String>(&key_path!(.log[-3])).unwrap(), "a");
+    assert!(console.at(&key_path!(.log[-4])).is_none());
+    assert_eq!(console.get_at::<String>(&field("log").last()).unwrap(), "c");
+    assert_eq!(
+        console.get_at::<u8>(&key_path!(.recent[-2_i64])).unwrap(),
+        &2
+    );
+
+    *console.get_at_mut::<u8>(&field("recent").last()).unwrap() = 4;
+    assert_eq!(console.recent, [1, 2, 4]);
+
+    console.set_at(&key_path!(.log[-1]), "d").unwrap();
+    assert_eq!(console.log[2], "d");
+
+    let value = console.to_value();
+    assert_eq!(value.get_at::<String>(&key_path!(.log[-1])).unwrap(), "d");
+    assert_eq!(last().to_string(), "[-1]");
+
+    let mut empty: Vec<i32> = Vec::new();
+    assert!(empty.at(&last()).is_none());
+    assert!(empty.at_mut(&last()).is_none());
+}
+
+#[test]
+fn relative_paths() {
+    let path = key_path!(.settings.audio[0]::Some.0);
+
+    assert_eq!(path.parent().unwrap(), key_path!(.settings.audio[0]::Some));
+    assert_eq!(KeyPath::default().parent(), None);
+
+    let prefix = key_path!(.settings.audio);
+    assert!(path.starts_with(&prefix));
+    assert!(path.starts_with(&KeyPath::default()));
+    assert!(!path.starts_with(&key_path!(.audio)));
+    assert!(path.ends_with(&key_path!(::Some.0)));
+    assert!(!path.ends_with(&key_path!(::None)));
+
+    let relative = path.strip_prefix(&prefix).unwrap();
+    assert_eq!(relative, key_path!([0]::Some.0));
+    assert_eq!(path.strip_prefix(&key_path!(.audio)), None);
+    assert_eq!(prefix.join(&relative), path);
+
+    let mut pushed = prefix.clone();
+    pushed.push(&relative);
+    assert_eq!(pushed, path);
+}
+
+#[test]
+fn resolved_key_path() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        a: i32,
+        b: Bar,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Bar {
+        First { x: i32, y: i32 },
+        Second { y: i32 },
+    }
+
+    let mut foo = Foo {
+        a: 1,
+        b: Bar::First { x: 2, y: 3 },
+    };
+
+    let path = key_path!(.b::First.y);
+    let resolved = path.resolve(&foo);
+    assert_eq!(resolved.key_path(), &path);
+    assert_eq!(resolved.get_at::<i32>(&foo), Some(&3));
+    *resolved.get_at_mut::<i32>(&mut foo).unwrap() = 4;
+    assert!(matches!(foo.b, Bar::First { x: 2, y: 4 }));
+
+    // `y` isn't at the same index in the other variant
+    let path = key_path!(.b.y);
+    let resolved = path.resolve(&foo);
+    foo.b = Bar::Second { y: 5 };
+    assert_eq!(resolved.get_at::<i32>(&foo), Some(&5));
+    assert!(resolved
+        .at(&Foo {
+            a: 0,
+            b: Bar::Second { y: 6 }
+        }
+        .to_value())
+        .is_some());
+
+    // a value with the same fields in a different order
+    let mut value = StructValue::new();
+    value.set_field("b", Bar::Second { y: 7 });
+    value.set_field("a", 8);
+    assert_eq!(resolved.get_at::<i32>(&value), Some(&7));
+    assert_eq!(key_path!(.a).resolve(&foo).get_at::<i32>(&value), Some(&8));
+
+    // another type with the same fields at different indices
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Baz {
+        b: i32,
+        a: i32,
+    }
+
+    let resolved = key_path!(.a).resolve(&foo);
+    assert_eq!(resolved.get_at::<i32>(&Baz { b: 9, a: 10 }), Some(&10));
+
+    let missing = key_path!(.c.d).resolve(&foo);
+    assert!(missing.at(&foo).is_none());
+
+    let empty = KeyPath::default().resolve(&foo);
+    assert!(empty.get_at::<Foo>(&foo).is_some());
+}
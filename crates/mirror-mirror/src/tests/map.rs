@@ -1,11 +1,14 @@
+use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
 
 use crate::key_path;
 use crate::key_path::GetPath;
+use crate::map::OrderedMap;
 use crate::GetField;
 use crate::GetFieldMut;
 use crate::Map;
 use crate::Reflect;
+use crate::Value;
 
 #[test]
 fn works() {
@@ -45,3 +48,233 @@ fn exotic_key_type() {
     assert_eq!(map.get_at::<i32>(&key_path!([Foo(2)])).unwrap(), &2);
     assert!(map.get_at::<i32>(&key_path!([Foo(3)])).is_none());
 }
+
+#[test]
+fn borrowed_keys() {
+    let mut map = BTreeMap::from([("foo".to_owned(), 1), ("bar".to_owned(), 2)]);
+    let map: &mut dyn Map = map.as_map_mut().unwrap();
+
+    assert_eq!(
+        map.get_str("foo").unwrap().downcast_ref::<i32>().unwrap(),
+        &1
+    );
+    assert!(map.get_str("baz").is_none());
+    *map.get_str_mut("bar")
+        .unwrap()
+        .downcast_mut::<i32>()
+        .unwrap() = 3;
+    assert_eq!(map.get_field::<i32>("bar").unwrap(), &3);
+
+    // a string `Value` works as a key as well
+    let key = Value::from("foo".to_owned());
+    assert_eq!(map.get(&key).unwrap().downcast_ref::<i32>().unwrap(), &1);
+
+    // maps with `Value` keys
+    let mut map = BTreeMap::from([("foo".to_owned(), 1)]).to_value();
+    let map = map.as_map_mut().unwrap();
+    assert_eq!(
+        map.get_str("foo").unwrap().downcast_ref::<i32>().unwrap(),
+        &1
+    );
+    assert!(map.get_str_mut("foo").is_some());
+    assert!(map.get_str("bar").is_none());
+
+    let mut map = BTreeMap::from([(1_u8, 1)]).to_value();
+    let map = map.as_map_mut().unwrap();
+    assert_eq!(map.get(&1_u8).unwrap().downcast_ref::<i32>().unwrap(), &1);
+    assert!(map.get(&1_i32).is_none());
+    assert!(map.get_str("1").is_none());
+}
+
+#[test]
+fn integer_keys() {
+    let get = |map: &dyn Map, key| map.get_int(key)?.downcast_ref::<i32>().copied();
+
+    let mut map = BTreeMap::from([(1_u8, 10), (200_u8, 2000)]);
+    assert_eq!(get(map.as_map().unwrap(), 1), Some(10));
+    assert_eq!(get(map.as_map().unwrap(), 200), Some(2000));
+    assert_eq!(get(map.as_map().unwrap(), 2), None);
+    // out of range for the key type
+    assert_eq!(get(map.as_map().unwrap(), -1), None);
+    assert_eq!(get(map.as_map().unwrap(), 1000), None);
+    *map.as_map_mut()
+        .unwrap()
+        .get_int_mut(1)
+        .unwrap()
+        .downcast_mut::<i32>()
+        .unwrap() = 11;
+    assert_eq!(map[&1], 11);
+
+    // maps with `Value` keys match keys of any integer type
+    let mut value = BTreeMap::from([(-5_i64, 1), (i64::MAX, 2)]).to_value();
+    let map = value.as_map_mut().unwrap();
+    assert_eq!(get(map, -5), Some(1));
+    assert_eq!(get(map, i64::MAX.into()), Some(2));
+    assert_eq!(get(map, 5), None);
+    assert!(map.get_int_mut(-5).is_some());
+    assert!(map.get_str("-5").is_none());
+
+    let value = Value::from(BTreeMap::from([(Value::u128(Box::new(7)), Value::i32(3))]));
+    assert_eq!(get(value.as_map().unwrap(), 7), Some(3));
+
+    // other key types are compared one by one
+    let map = BTreeMap::from([(Some(1_u8), 1)]);
+    assert_eq!(get(map.as_map().unwrap(), 1), None);
+    let map = OrderedMap::new().with_entry(3_u16, 4_i32);
+    assert_eq!(get(&map, 3), Some(4));
+    assert_eq!(get(&map, 4), None);
+}
+
+#[test]
+fn ordered_map() {
+    use crate::value::ValueArena;
+    use crate::FromReflect;
+
+    let mut map = OrderedMap::new()
+        .with_entry("b", 1_i32)
+        .with_entry("a", 2_i32)
+        .with_entry("c", 3_i32);
+    // replacing a value keeps the entry in place
+    assert_eq!(map.set_entry("a", 4_i32), Some(Value::i32(2)));
+
+    let keys = |map: &dyn Map| {
+        map.iter()
+            .map(|(key, _)| key.downcast_ref::<String>().unwrap().clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(keys(&map), ["b", "a", "c"]);
+
+    assert_eq!(
+        Map::get(&map, &"a".to_owned()).unwrap().to_value(),
+        Value::i32(4)
+    );
+    assert!(Map::remove(&mut map, &"b".to_owned()).is_some());
+    Map::insert(&mut map, &"b".to_owned(), &5_i32);
+    assert_eq!(keys(&map), ["a", "c", "b"]);
+
+    let mut value = Value::from(map.clone());
+    assert_eq!(keys(value.as_map().unwrap()), ["a", "c", "b"]);
+    assert_eq!(value.get_at::<i32>(&key_path!(["c"])).unwrap(), &3);
+    value.set_at(&key_path!(["c"]), 6_i32).unwrap();
+    assert_eq!(value.get_at::<i32>(&key_path!(["c"])).unwrap(), &6);
+
+    // patching only touches entries whose keys are in the patch
+    value.patch(&BTreeMap::from([
+        ("b".to_owned(), 7_i32),
+        ("d".to_owned(), 8_i32),
+    ]));
+    assert_eq!(keys(value.as_map().unwrap()), ["a", "c", "b"]);
+    assert_eq!(value.get_at::<i32>(&key_path!(["b"])).unwrap(), &7);
+    assert_eq!(value.get_at::<i32>(&key_path!(["c"])).unwrap(), &6);
+
+    // order matters when comparing ordered maps
+    let mut reversed = map
+        .entries()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect::<Vec<_>>();
+    reversed.reverse();
+    let reversed = reversed.into_iter().collect::<OrderedMap>();
+    assert_ne!(reversed, map);
+
+    let sorted = BTreeMap::<String, i32>::from_reflect(&value).unwrap();
+    assert_eq!(Vec::from_iter(sorted.keys().cloned()), ["a", "b", "c"]);
+
+    let mut arena = ValueArena::new();
+    let id = arena.alloc(&value);
+    assert_eq!(arena.to_value(id).unwrap(), value);
+
+    #[cfg(feature = "speedy")]
+    {
+        let bytes = value.write_interned_to_vec().unwrap();
+        assert_eq!(Value::read_interned_from_buffer(&bytes).unwrap(), value);
+    }
+}
+
+#[test]
+fn hash_map_with_custom_hasher() {
+    use core::hash::BuildHasherDefault;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    use crate::FromReflect;
+    #[cfg(feature = "type_info")]
+    use crate::Typed;
+
+    type Hasher = BuildHasherDefault<DefaultHasher>;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Inventory {
+        counts: HashMap<String, u32, Hasher>,
+        tags: HashSet<String, Hasher>,
+    }
+
+    let mut inventory = Inventory {
+        counts: HashMap::from_iter([("apple".to_owned(), 3)]),
+        tags: HashSet::from_iter(["food".to_owned()]),
+    };
+
+    assert_eq!(
+        inventory.get_at::<u32>(&key_path!(.counts["apple"])),
+        Some(&3)
+    );
+    *inventory
+        .get_at_mut::<u32>(&key_path!(.counts["apple"]))
+        .unwrap() = 4;
+    assert_eq!(inventory.counts["apple"], 4);
+
+    let tags = inventory.tags.as_map_mut().unwrap();
+    assert!(tags.get(&"food".to_owned()).is_some());
+    assert!(tags.get(&"toy".to_owned()).is_none());
+    assert!(tags.insert(&"toy".to_owned(), &()).is_none());
+    assert!(tags.insert(&"toy".to_owned(), &()).is_some());
+    assert!(tags.remove(&"food".to_owned()).is_some());
+    assert_eq!(inventory.tags, HashSet::from_iter(["toy".to_owned()]));
+
+    let value = inventory.to_value();
+    let inventory = Inventory::from_reflect(&value).unwrap();
+    assert_eq!(inventory.counts["apple"], 4);
+    assert!(inventory.tags.contains("toy"));
+
+    #[cfg(feature = "type_info")]
+    {
+        let type_info = <Inventory as Typed>::type_info();
+        let tags = type_info
+            .as_struct()
+            .unwrap()
+            .field_type("tags")
+            .unwrap()
+            .get_type()
+            .as_map()
+            .unwrap();
+        assert_eq!(tags.key_type().type_name(), "String");
+        assert_eq!(
+            tags.value_type().type_name(),
+            <() as Typed>::type_info().type_name()
+        );
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown() {
+    use std::collections::hash_map::RandomState;
+
+    use crate::FromReflect;
+
+    let mut map = hashbrown::HashMap::<_, _, RandomState>::default();
+    map.insert(1_u8, "one".to_owned());
+    let map_ref = map.as_map_mut().unwrap();
+    map_ref.insert(&2_u8, &"two".to_owned());
+    assert_eq!(map_ref.get_field::<String>(2_u8).unwrap(), "two");
+    assert_eq!(map.len(), 2);
+
+    let value = map.to_value();
+    assert_eq!(hashbrown::HashMap::from_reflect(&value), Some(map));
+
+    let set = hashbrown::HashSet::<_, RandomState>::from_iter([1_u8, 2]);
+    let value = set.to_value();
+    assert!(matches!(&value, Value::Map(map) if map.len() == 2));
+    assert_eq!(hashbrown::HashSet::from_reflect(&value), Some(set));
+}
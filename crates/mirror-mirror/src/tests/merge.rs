@@ -0,0 +1,166 @@
+use alloc::collections::BTreeMap;
+
+use crate::key_path;
+use crate::merge::merge3;
+use crate::FromReflect;
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Scene {
+    name: String,
+    entities: Vec<Entity>,
+    tags: BTreeMap<String, u32>,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+struct Entity {
+    position: [f32; 2],
+    shape: Shape,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(crate_name(crate))]
+enum Shape {
+    Circle { radius: f32, filled: bool },
+    Square(f32),
+}
+
+fn scene() -> Scene {
+    Scene {
+        name: "level".to_owned(),
+        entities: Vec::from([
+            Entity {
+                position: [0.0, 0.0],
+                shape: Shape::Circle {
+                    radius: 1.0,
+                    filled: false,
+                },
+            },
+            Entity {
+                position: [1.0, 1.0],
+                shape: Shape::Square(2.0),
+            },
+        ]),
+        tags: BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]),
+    }
+}
+
+fn merge(base: &Scene, ours: &Scene, theirs: &Scene) -> Scene {
+    Scene::from_reflect(&merge3(base, ours, theirs).unwrap()).unwrap()
+}
+
+#[test]
+fn non_overlapping_edits() {
+    let base = scene();
+
+    let mut ours = base.clone();
+    ours.name = "renamed".to_owned();
+    ours.entities[0].position[1] = 5.0;
+    let Shape::Circle { radius, .. } = &mut ours.entities[0].shape else {
+        unreachable!()
+    };
+    *radius = 3.0;
+    ours.tags.remove("a");
+
+    let mut theirs = base.clone();
+    theirs.entities[0].position[0] = 4.0;
+    let Shape::Circle { filled, .. } = &mut theirs.entities[0].shape else {
+        unreachable!()
+    };
+    *filled = true;
+    theirs.tags.insert("c".to_owned(), 3);
+    theirs.tags.insert("b".to_owned(), 20);
+
+    let mut expected = base.clone();
+    expected.name = "renamed".to_owned();
+    expected.entities[0] = Entity {
+        position: [4.0, 5.0],
+        shape: Shape::Circle {
+            radius: 3.0,
+            filled: true,
+        },
+    };
+    expected.tags = BTreeMap::from([("b".to_owned(), 20), ("c".to_owned(), 3)]);
+
+    assert_eq!(merge(&base, &ours, &theirs), expected);
+    assert_eq!(merge(&base, &theirs, &ours), expected);
+
+    // the same edit on both sides isn't a conflict
+    assert_eq!(merge(&base, &ours, &ours), ours);
+    assert_eq!(merge(&base, &base, &base), base);
+}
+
+#[test]
+fn list_edits() {
+    let base = scene();
+
+    let mut ours = base.clone();
+    ours.entities[0].position = [9.0, 9.0];
+
+    let mut theirs = base.clone();
+    theirs.entities.push(Entity {
+        position: [2.0, 2.0],
+        shape: Shape::Square(1.0),
+    });
+
+    let merged = merge(&base, &ours, &theirs);
+    assert_eq!(merged.entities.len(), 3);
+    assert_eq!(merged.entities[0].position, [9.0, 9.0]);
+    assert_eq!(merged.entities[2], theirs.entities[2]);
+
+    // popping an element the other side didn't touch removes it
+    let mut theirs = base.clone();
+    theirs.entities.pop();
+    let merged = merge(&base, &ours, &theirs);
+    assert_eq!(merged.entities, [ours.entities[0].clone()]);
+}
+
+#[test]
+fn conflicts() {
+    let base = scene();
+
+    let mut ours = base.clone();
+    ours.name = "ours".to_owned();
+    ours.entities[1].shape = Shape::Circle {
+        radius: 1.0,
+        filled: true,
+    };
+    ours.tags.insert("c".to_owned(), 3);
+
+    let mut theirs = base.clone();
+    theirs.name = "theirs".to_owned();
+    theirs.entities[1].shape = Shape::Square(5.0);
+    theirs.tags.insert("c".to_owned(), 4);
+
+    let conflicts = merge3(&base, &ours, &theirs).unwrap_err();
+    let paths = conflicts
+        .iter()
+        .map(|conflict| conflict.path().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            key_path!(.name),
+            key_path!(.entities[1_usize].shape),
+            key_path!(.tags["c"]),
+        ]
+    );
+
+    assert_eq!(conflicts[0].base(), Some(&"level".to_owned().to_value()));
+    assert_eq!(conflicts[0].ours(), Some(&"ours".to_owned().to_value()));
+    assert_eq!(conflicts[0].theirs(), Some(&"theirs".to_owned().to_value()));
+    assert!(conflicts[2].base().is_none());
+
+    // editing an element the other side removed
+    let mut ours = base.clone();
+    ours.entities[1].position = [0.0, 9.0];
+    let mut theirs = base.clone();
+    theirs.entities.pop();
+
+    let conflicts = merge3(&base, &ours, &theirs).unwrap_err();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].path(), &key_path!(.entities[1_usize]));
+    assert!(conflicts[0].theirs().is_none());
+}
@@ -1,6 +1,11 @@
+use core::any::type_name;
+
+use crate::key_path;
 use crate::type_info::GetMeta;
+use crate::type_info::MetadataOverlay;
 use crate::Reflect;
 use crate::Typed;
+use crate::Value;
 
 #[test]
 fn works() {
@@ -56,3 +61,169 @@ enum C {
     #[reflect(meta(n = 1))]
     C,
 }
+
+#[test]
+fn overlay() {
+    let overlay = MetadataOverlay::new()
+        .with(type_name::<A>(), key_path!(), "n", 2)
+        .with(type_name::<A>(), key_path!(.a), "n", 2)
+        .with(type_name::<A>(), key_path!(.a), "label", "A")
+        .with(type_name::<B>(), key_path!(.0), "n", 2)
+        .with(type_name::<C>(), key_path!(::A), "n", 2)
+        .with(type_name::<C>(), key_path!(::A.a), "n", 2)
+        .with(type_name::<C>(), key_path!(::B), "n", 2)
+        .with(type_name::<C>(), key_path!(::B.0), "n", 2)
+        .with(type_name::<C>(), key_path!(::C), "n", 2)
+        .with(type_name::<C>(), key_path!(.missing), "n", 2);
+
+    assert_eq!(
+        overlay.get(type_name::<A>(), &key_path!(.a), "label"),
+        Some(&Value::from("A"))
+    );
+
+    let mut type_info = <A as Typed>::type_info();
+    overlay.apply(&mut type_info);
+    let a = type_info.as_struct().unwrap();
+    assert_eq!(a.get_meta::<i32>("n").unwrap(), 2);
+    let field = a.field_type("a").unwrap();
+    assert_eq!(field.get_meta::<i32>("n").unwrap(), 2);
+    assert_eq!(field.get_meta::<String>("label").unwrap(), "A");
+
+    let mut type_info = <B as Typed>::type_info();
+    overlay.apply(&mut type_info);
+    let b = type_info.as_tuple_struct().unwrap();
+    assert_eq!(b.get_meta::<i32>("n").unwrap(), 1);
+    assert_eq!(b.field_type_at(0).unwrap().get_meta::<i32>("n").unwrap(), 2);
+
+    let mut type_info = <C as Typed>::type_info();
+    overlay.apply(&mut type_info);
+    let c = type_info.as_enum().unwrap();
+    assert_eq!(c.get_meta::<i32>("n").unwrap(), 1);
+    for variant in c.variants() {
+        assert_eq!(variant.get_meta::<i32>("n").unwrap(), 2);
+        for field in variant.field_types() {
+            assert_eq!(field.get_meta::<i32>("n").unwrap(), 2);
+        }
+    }
+}
+
+#[test]
+fn overlay_applies_to_nested_types() {
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate))]
+    struct Outer {
+        a: A,
+    }
+
+    let mut overlay = MetadataOverlay::new();
+    overlay.insert(type_name::<A>(), key_path!(.a), "n", 3);
+
+    let mut type_info = <Outer as Typed>::type_info();
+    overlay.apply(&mut type_info);
+    let a = type_info
+        .as_struct()
+        .unwrap()
+        .field_type("a")
+        .unwrap()
+        .get_type()
+        .as_struct()
+        .unwrap();
+    assert_eq!(a.field_type("a").unwrap().get_meta::<i32>("n").unwrap(), 3);
+
+    assert_eq!(
+        overlay.remove(type_name::<A>(), &key_path!(.a), "n"),
+        Some(Value::i32(3))
+    );
+    assert!(overlay.is_empty());
+}
+
+#[test]
+fn typed_meta_and_entries() {
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate), meta(label = "Volume", max = 100_u8))]
+    struct Volume {
+        #[reflect(meta(hidden = true))]
+        level: u8,
+    }
+
+    let type_info = <Volume as Typed>::type_info();
+    let ty = type_info.get_type();
+
+    assert_eq!(ty.get_meta::<String>("label").unwrap(), "Volume");
+    assert_eq!(ty.get_meta::<u8>("max").unwrap(), 100);
+    assert_eq!(ty.get_meta::<bool>("max"), None);
+    assert!(ty.has_meta("label"));
+    assert!(!ty.has_meta("missing"));
+
+    let entries = ty
+        .meta_entries()
+        .map(|(key, value)| (key, value.to_value()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        [
+            ("label", Value::from("Volume")),
+            ("max", Value::from(100_u8)),
+        ]
+    );
+
+    let field = ty.as_struct().unwrap().field_type("level").unwrap();
+    assert!(field.get_meta::<bool>("hidden").unwrap());
+    assert_eq!(field.meta_entries().count(), 1);
+
+    let list = <Vec<u8> as Typed>::type_info();
+    assert!(!list.get_type().has_meta("label"));
+    assert_eq!(list.get_type().meta_entries().count(), 0);
+}
+
+#[test]
+fn variant_meta_at_path() {
+    use crate::key_path::GetTypePath;
+
+    let type_info = <C as Typed>::type_info();
+    let variant = type_info.type_at(&key_path!(::B)).unwrap();
+    assert_eq!(variant.get_meta::<i32>("n"), Some(1));
+    assert_eq!(variant.meta_entries().count(), 1);
+}
+
+#[test]
+fn find_meta() {
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate))]
+    struct Root {
+        a: A,
+        b: Vec<B>,
+        c: Option<C>,
+    }
+
+    let type_info = <Root as Typed>::type_info();
+    let found = type_info
+        .find_meta("n")
+        .into_iter()
+        .map(|found| {
+            (
+                found.type_name(),
+                found.key_path().clone(),
+                found.value().to_value(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        found,
+        [
+            (type_name::<A>(), key_path!(), Value::from(1)),
+            (type_name::<A>(), key_path!(.a), Value::from(1)),
+            (type_name::<B>(), key_path!(), Value::from(1)),
+            (type_name::<B>(), key_path!(.0), Value::from(1)),
+            (type_name::<C>(), key_path!(), Value::from(1)),
+            (type_name::<C>(), key_path!(::A), Value::from(1)),
+            (type_name::<C>(), key_path!(::A.a), Value::from(1)),
+            (type_name::<C>(), key_path!(::B), Value::from(1)),
+            (type_name::<C>(), key_path!(::B.0), Value::from(1)),
+            (type_name::<C>(), key_path!(::C), Value::from(1)),
+        ]
+    );
+
+    assert!(type_info.find_meta("missing").is_empty());
+}
@@ -0,0 +1,137 @@
+use crate::method::CallError;
+use crate::method::Receiver;
+use crate::method::ReflectMethods;
+use crate::reflect_methods;
+use crate::Reflect;
+use crate::Value;
+
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(crate_name(crate), opt_in(Methods))]
+struct Player {
+    name: String,
+    health: u32,
+}
+
+#[reflect_methods(crate_name(crate))]
+impl Player {
+    /// Restore some health.
+    #[reflect(func)]
+    fn heal(&mut self, amount: u32) {
+        self.health += amount;
+    }
+
+    #[reflect(func)]
+    fn greeting(&self, greeting: String, times: usize) -> String {
+        format!("{greeting} {}", self.name).repeat(times)
+    }
+
+    #[allow(dead_code)]
+    fn not_reflected() {}
+}
+
+#[test]
+fn lists_methods() {
+    let methods = <Player as ReflectMethods>::METHODS;
+    assert_eq!(
+        methods
+            .iter()
+            .map(|method| method.name())
+            .collect::<Vec<_>>(),
+        ["heal", "greeting"]
+    );
+
+    let player = Player {
+        name: "Alice".to_owned(),
+        health: 0,
+    };
+    assert_eq!(player.methods().len(), 2);
+
+    let heal = player.as_reflect().method("heal").unwrap();
+    assert_eq!(heal.receiver(), Receiver::Mut);
+    assert_eq!(heal.params().len(), 1);
+    assert_eq!(heal.params()[0].name(), "amount");
+    assert_eq!(heal.params()[0].type_name(), "u32");
+    assert_eq!(heal.return_type(), "()");
+    assert_eq!(heal.docs(), [" Restore some health."]);
+
+    let greeting = player.as_reflect().method("greeting").unwrap();
+    assert_eq!(greeting.receiver(), Receiver::Ref);
+    assert_eq!(greeting.return_type(), "String");
+
+    assert!(player.as_reflect().method("not_reflected").is_none());
+    assert!(Value::u32(1).as_reflect().method("heal").is_none());
+}
+
+#[test]
+fn calls_methods() {
+    let mut player = Player {
+        name: "Alice".to_owned(),
+        health: 0,
+    };
+    let reflect = player.as_reflect_mut();
+
+    let output = reflect.call_method("heal", &[&10_u32]).unwrap();
+    assert!(output.downcast_ref::<()>().is_some());
+
+    // arguments are converted with `FromReflect`
+    reflect.call_method("heal", &[&Value::u32(5)]).unwrap();
+
+    let output = reflect
+        .call_method("greeting", &[&"hi".to_owned(), &2_usize])
+        .unwrap();
+    assert_eq!(output.downcast_ref::<String>().unwrap(), "hi Alicehi Alice");
+
+    assert_eq!(player.health, 15);
+}
+
+#[test]
+fn calls_methods_through_boxes() {
+    let mut player = Box::new(Player {
+        name: "Alice".to_owned(),
+        health: 0,
+    });
+    let reflect: &mut dyn Reflect = &mut player;
+
+    assert_eq!(reflect.methods().len(), 2);
+    reflect.call_method("heal", &[&10_u32]).unwrap();
+
+    assert_eq!(player.health, 10);
+}
+
+#[test]
+fn call_errors() {
+    let mut player = Player {
+        name: "Alice".to_owned(),
+        health: 0,
+    };
+    let reflect = player.as_reflect_mut();
+
+    assert!(matches!(
+        reflect.call_method("jump", &[]),
+        Err(CallError::NotFound { method }) if method == "jump"
+    ));
+    assert!(matches!(
+        reflect.call_method("heal", &[]),
+        Err(CallError::ArgumentCount {
+            method: "heal",
+            expected: 1,
+            got: 0
+        })
+    ));
+    assert!(matches!(
+        reflect.call_method("heal", &[&"ten".to_owned()]),
+        Err(CallError::ArgumentType {
+            method: "heal",
+            param: "amount",
+            expected: "u32"
+        })
+    ));
+
+    let heal = reflect.method("heal").unwrap();
+    assert!(matches!(
+        heal.call(&mut 1_u32, &[&1_u32]),
+        Err(CallError::Receiver { method: "heal" })
+    ));
+
+    assert_eq!(player.health, 0);
+}
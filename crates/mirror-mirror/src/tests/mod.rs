@@ -1,15 +1,40 @@
 use crate::Reflect;
 
+mod boxed;
+#[cfg(feature = "type_info")]
+mod convert;
+mod default;
+mod diff;
+mod downcast;
 mod enum_;
+mod get_field;
+mod hash;
 mod key_path;
 mod list;
 mod map;
+mod merge;
+#[cfg(feature = "type_info")]
 mod meta;
+mod method;
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
+mod partial_cmp;
+mod partial_eq;
+#[cfg(feature = "type_info")]
+mod pretty;
+#[cfg(feature = "erased-serde")]
+mod registry;
 mod struct_;
+#[cfg(feature = "sync")]
+mod sync;
+mod testing;
+mod tracked;
 mod tuple;
 mod tuple_struct;
+#[cfg(feature = "type_info")]
 mod type_info;
 mod value;
+mod visit;
 
 #[derive(Reflect)]
 #[reflect(crate_name(crate), opt_out(Debug, Clone))]
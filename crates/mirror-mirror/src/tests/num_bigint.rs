@@ -0,0 +1,85 @@
+use core::str::FromStr;
+
+use num_bigint::BigInt;
+use num_bigint::BigUint;
+
+use crate::FromReflect;
+use crate::Reflect;
+use crate::Value;
+
+#[test]
+fn round_trips_through_value() {
+    let n = BigInt::from(1337);
+    let value = n.to_value();
+    assert_eq!(value, Value::from("1337"));
+    assert_eq!(BigInt::from_reflect(&value), Some(n.clone()));
+    assert_eq!(BigInt::from_reflect(&n), Some(n.clone()));
+    assert_eq!(n.reflect_hash(), value.reflect_hash());
+
+    let n = BigUint::from(1337_u32);
+    let value = n.to_value();
+    assert_eq!(value, Value::from("1337"));
+    assert_eq!(BigUint::from_reflect(&value), Some(n));
+}
+
+#[test]
+fn very_large() {
+    let digits = "123456789".repeat(50);
+    let n = BigUint::from_str(&digits).unwrap();
+    let value = n.to_value();
+    assert_eq!(value, Value::from(digits.clone()));
+    assert_eq!(BigUint::from_reflect(&value), Some(n.clone()));
+
+    let n = BigInt::from_str(&digits).unwrap();
+    assert_eq!(BigInt::from_reflect(&n.to_value()), Some(n));
+    assert!(BigInt::from_reflect(&Value::from(u128::MAX)).is_some());
+}
+
+#[test]
+fn negative() {
+    let digits = format!("-{}", "987654321".repeat(50));
+    let n = BigInt::from_str(&digits).unwrap();
+    let value = n.to_value();
+    assert_eq!(value, Value::from(digits));
+    assert_eq!(BigInt::from_reflect(&value), Some(n));
+
+    assert_eq!(BigInt::from_reflect(&-1_i8), Some(BigInt::from(-1)));
+    assert_eq!(
+        BigInt::from_reflect(&i128::MIN),
+        Some(BigInt::from(i128::MIN))
+    );
+    assert!(BigUint::from_reflect(&-1_i8).is_none());
+    assert!(BigUint::from_reflect(&Value::from("-1")).is_none());
+}
+
+#[test]
+fn from_other_scalars() {
+    assert_eq!(
+        BigUint::from_reflect(&1337_u64),
+        Some(BigUint::from(1337_u32))
+    );
+    assert!(BigUint::from_reflect(&1.0_f32).is_none());
+    assert!(BigInt::from_reflect(&true).is_none());
+    assert!(BigInt::from_reflect(&Value::from("one")).is_none());
+}
+
+#[test]
+fn patching() {
+    let mut n = BigUint::from(1_u32);
+    n.patch(&String::from("42"));
+    assert_eq!(n, BigUint::from(42_u32));
+
+    let mut signed = BigInt::from(-1);
+    signed.patch(&BigUint::from(42_u32));
+    assert_eq!(signed, BigInt::from(42));
+
+    let mut unsigned = BigUint::from(1_u32);
+    unsigned.patch(&BigInt::from(7));
+    assert_eq!(unsigned, BigUint::from(7_u32));
+
+    // negative numbers don't fit, so the value is left alone
+    unsigned.patch(&BigInt::from(-7));
+    assert_eq!(unsigned, BigUint::from(7_u32));
+    unsigned.patch(&Value::from("-7"));
+    assert_eq!(unsigned, BigUint::from(7_u32));
+}
@@ -0,0 +1,114 @@
+use alloc::collections::BTreeMap;
+use core::cmp::Ordering;
+
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+struct Foo {
+    b: i32,
+    a: Vec<f32>,
+    c: Bar,
+}
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+enum Bar {
+    A(String),
+    B { b: bool },
+    C,
+}
+
+// iterating a map with these keys yields entries in a different order than the keys' `Value`s
+#[derive(Reflect, Debug, Clone, PartialEq, Eq)]
+#[reflect(crate_name(crate))]
+struct Reversed(i32);
+
+impl PartialOrd for Reversed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Reversed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+#[test]
+fn matches_value_ordering() {
+    let values: Vec<Box<dyn Reflect>> = Vec::from([
+        Box::new(1_i32) as Box<dyn Reflect>,
+        Box::new(2_i32),
+        Box::new(1_u8),
+        Box::new(f32::NAN),
+        Box::new(-1.0_f32),
+        Box::new("a".to_owned()),
+        Box::new("b".to_owned()),
+        Box::new(Foo {
+            b: 1,
+            a: Vec::from([1.0]),
+            c: Bar::C,
+        }),
+        Box::new(Foo {
+            b: 0,
+            a: Vec::from([1.0, 2.0]),
+            c: Bar::C,
+        }),
+        Box::new(Foo {
+            b: 0,
+            a: Vec::from([1.0]),
+            c: Bar::A("a".to_owned()),
+        }),
+        Box::new(Bar::B { b: false }),
+        Box::new(Bar::A("b".to_owned())),
+        Box::new(Bar::C),
+        Box::new(Reversed(1)),
+        Box::new((1, "a".to_owned())),
+        Box::new((1, "b".to_owned())),
+        Box::new([1, 2]),
+        Box::new(Vec::from([1, 2, 3])),
+        Box::new(BTreeMap::from([(1, 1), (2, 2)])),
+        Box::new(BTreeMap::from([(1, 1), (2, 3)])),
+        Box::new(BTreeMap::from([(Reversed(1), 1), (Reversed(2), 2)])),
+        Box::new(BTreeMap::from([(Reversed(1), 2), (Reversed(2), 1)])),
+    ]);
+
+    for a in &values {
+        for b in &values {
+            assert_eq!(
+                a.reflect_partial_cmp(b.as_reflect()),
+                Some(a.to_value().cmp(&b.to_value())),
+                "{a:?} {b:?}",
+            );
+        }
+    }
+}
+
+#[test]
+fn opt_in_partial_ord() {
+    #[derive(Reflect, Debug, Clone, PartialEq, Eq)]
+    #[reflect(crate_name(crate), opt_in(PartialOrd))]
+    struct Desc(i32);
+
+    impl PartialOrd for Desc {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            other.0.partial_cmp(&self.0)
+        }
+    }
+
+    assert_eq!(
+        Desc(1).reflect_partial_cmp(&Desc(2)),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        Box::new(Desc(1)).reflect_partial_cmp(&Desc(2)),
+        Some(Ordering::Greater)
+    );
+    // the structural ordering is used for other types
+    assert_eq!(
+        Desc(1).reflect_partial_cmp(&Desc(2).to_value()),
+        Some(Ordering::Less)
+    );
+}
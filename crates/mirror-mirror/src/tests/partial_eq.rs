@@ -0,0 +1,82 @@
+use alloc::collections::BTreeMap;
+
+use crate::enum_::EnumValue;
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+struct Foo {
+    a: i32,
+    b: Vec<f32>,
+    c: Bar,
+    d: BTreeMap<String, (u8, bool)>,
+}
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+enum Bar {
+    A(String),
+    B { b: bool },
+}
+
+fn foo() -> Foo {
+    Foo {
+        a: 1,
+        b: Vec::from([1.0, f32::NAN]),
+        c: Bar::B { b: true },
+        d: BTreeMap::from([("one".to_owned(), (1, true))]),
+    }
+}
+
+#[test]
+fn structural() {
+    let foo = foo();
+
+    assert_eq!(foo.reflect_partial_eq(&foo.clone()), Some(true));
+    assert_eq!(foo.reflect_partial_eq(&foo.to_value()), Some(true));
+    assert_eq!(foo.to_value().reflect_partial_eq(&foo), Some(true));
+
+    let mut other = foo.clone();
+    other.c = Bar::A("foo".to_owned());
+    assert_eq!(foo.reflect_partial_eq(&other), Some(false));
+
+    let mut other = foo.clone();
+    other.b.push(3.0);
+    assert_eq!(foo.reflect_partial_eq(&other), Some(false));
+
+    let mut other = foo.clone();
+    other.d.insert("two".to_owned(), (2, false));
+    assert_eq!(foo.reflect_partial_eq(&other), Some(false));
+
+    let mut other = foo.clone();
+    other.d.get_mut("one").unwrap().1 = false;
+    assert_eq!(foo.reflect_partial_eq(&other), Some(false));
+
+    assert_eq!(1_i32.reflect_partial_eq(&1_i64), Some(false));
+    assert_eq!(foo.reflect_partial_eq(&1_i32), Some(false));
+    assert_eq!([1, 2].reflect_partial_eq(&Vec::from([1, 2])), Some(true));
+
+    let unit = EnumValue::new_unit_variant("B");
+    assert_eq!(foo.c.reflect_partial_eq(&unit), Some(false));
+}
+
+#[test]
+fn opt_in_partial_eq() {
+    #[derive(Reflect, Debug, Clone)]
+    #[reflect(crate_name(crate), opt_in(PartialEq))]
+    struct CaseInsensitive(String);
+
+    impl PartialEq for CaseInsensitive {
+        fn eq(&self, other: &Self) -> bool {
+            self.0.eq_ignore_ascii_case(&other.0)
+        }
+    }
+
+    let a = CaseInsensitive("foo".to_owned());
+    let b = CaseInsensitive("FOO".to_owned());
+    assert_eq!(a.reflect_partial_eq(&b), Some(true));
+    assert_eq!(Box::new(a.clone()).reflect_partial_eq(&b), Some(true));
+    // the structural comparison is used for other types
+    assert_eq!(a.reflect_partial_eq(&b.to_value()), Some(false));
+    assert_eq!(a.reflect_partial_eq(&a.to_value()), Some(true));
+}
@@ -0,0 +1,117 @@
+use alloc::collections::BTreeMap;
+
+use crate::pretty::pretty;
+use crate::Reflect;
+use crate::Typed;
+
+#[derive(Reflect, Clone, Debug)]
+#[reflect(crate_name(crate))]
+struct Player {
+    health: u32,
+    position: Vec2,
+    inventory: BTreeMap<String, Item>,
+    state: State,
+}
+
+#[derive(Reflect, Clone, Debug)]
+#[reflect(crate_name(crate))]
+struct Vec2 {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Reflect, Clone, Debug)]
+#[reflect(crate_name(crate))]
+struct Item(u8, (bool, char));
+
+#[derive(Reflect, Clone, Debug)]
+#[reflect(crate_name(crate))]
+enum State {
+    Idle,
+    Walking(Vec2),
+}
+
+fn player() -> Player {
+    Player {
+        health: 10,
+        position: Vec2 { x: 1.0, y: 2.0 },
+        inventory: BTreeMap::from([("sword".to_owned(), Item(1, (true, 'x')))]),
+        state: State::Walking(Vec2 { x: 0.0, y: -1.0 }),
+    }
+}
+
+#[test]
+fn value_prints_like_the_original_type() {
+    let player = player();
+    let type_info = <Player as Typed>::type_info();
+
+    let expected = concat!(
+        "mirror_mirror::tests::pretty::Player { health: 10, ",
+        "position: mirror_mirror::tests::pretty::Vec2 { x: 1.0, y: 2.0 }, ",
+        r#"inventory: {"sword": mirror_mirror::tests::pretty::Item(1, (true, 'x'))}, "#,
+        "state: Walking(mirror_mirror::tests::pretty::Vec2 { x: 0.0, y: -1.0 }) }",
+    );
+    assert_eq!(pretty(&player, &type_info).to_string(), expected);
+    assert_eq!(pretty(&player.to_value(), &type_info).to_string(), expected);
+
+    let idle = State::Idle;
+    assert_eq!(
+        pretty(&idle.to_value(), &idle.type_info()).to_string(),
+        "Idle"
+    );
+}
+
+#[test]
+fn alternate_prints_on_multiple_lines() {
+    let value = Vec2 { x: 1.0, y: 2.0 }.to_value();
+    let type_info = <Vec2 as Typed>::type_info();
+
+    assert_eq!(
+        format!("{:#}", pretty(&value, &type_info)),
+        "mirror_mirror::tests::pretty::Vec2 {\n    x: 1.0,\n    y: 2.0,\n}"
+    );
+}
+
+#[test]
+fn uses_reflect_display() {
+    use core::fmt;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate), opt_in(Display))]
+    struct Color {
+        r: u8,
+        g: u8,
+        b: u8,
+    }
+
+    impl fmt::Display for Color {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        }
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Light {
+        color: Color,
+    }
+
+    let light = Light {
+        color: Color {
+            r: 255,
+            g: 0,
+            b: 128,
+        },
+    };
+    let type_info = <Light as Typed>::type_info();
+
+    assert_eq!(
+        light.color.reflect_display().unwrap().to_string(),
+        "#ff0080"
+    );
+    assert!(light.reflect_display().is_none());
+    assert_eq!(
+        pretty(&light, &type_info).to_string(),
+        "mirror_mirror::tests::pretty::uses_reflect_display::Light { color: #ff0080 }"
+    );
+}
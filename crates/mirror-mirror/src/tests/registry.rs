@@ -0,0 +1,110 @@
+use alloc::collections::BTreeMap;
+
+use serde::de::DeserializeSeed;
+use serde::Deserialize;
+
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::registry::TypeRegistry;
+use crate::Reflect;
+use crate::Typed;
+
+#[derive(Reflect, Clone, Debug, PartialEq, Deserialize)]
+#[reflect(crate_name(crate))]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Reflect, Clone, Debug, PartialEq, Deserialize)]
+#[reflect(crate_name(crate))]
+enum Team {
+    Red,
+    Blue,
+}
+
+fn registry() -> TypeRegistry {
+    let mut registry = TypeRegistry::new();
+    registry
+        .register_with_name::<Position>("Position")
+        .register::<Team>();
+    registry
+}
+
+#[test]
+fn deserializes_registered_types_by_name() {
+    let registry = registry();
+    assert!(registry.contains("Position"));
+    assert!(registry.contains(core::any::type_name::<Team>()));
+    assert_eq!(
+        registry.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["Position", core::any::type_name::<Team>()]
+    );
+
+    let registration = registry.get("Position").unwrap();
+    assert_eq!(registration.type_name(), core::any::type_name::<Position>());
+    assert_eq!(
+        registration.type_info().type_name(),
+        <Position as Typed>::type_info().type_name()
+    );
+
+    let mut json = serde_json::Deserializer::from_str(r#"{ "x": 1.0, "y": 2.0 }"#);
+    let position = registry.deserialize("Position", &mut json).unwrap();
+    assert_eq!(
+        position.downcast_ref::<Position>(),
+        Some(&Position { x: 1.0, y: 2.0 })
+    );
+
+    let mut json = serde_json::Deserializer::from_str(r#""Blue""#);
+    let team = registry
+        .deserialize(core::any::type_name::<Team>(), &mut json)
+        .unwrap();
+    assert_eq!(team.downcast_ref::<Team>(), Some(&Team::Blue));
+}
+
+#[test]
+fn components_from_a_manifest() {
+    #[derive(Deserialize)]
+    struct Manifest {
+        components: BTreeMap<String, serde_json::Value>,
+    }
+
+    let registry = registry();
+    let manifest: Manifest =
+        serde_json::from_str(r#"{ "components": { "Position": { "x": 3.0, "y": 4.0 } } }"#)
+            .unwrap();
+
+    let components = manifest
+        .components
+        .into_iter()
+        .map(|(name, data)| registry.deserialize(&name, data).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(components.get_at::<f32>(&key_path!([0].y)), Some(&4.0));
+
+    let mut json = serde_json::Deserializer::from_str(r#"{ "Position": { "x": 5.0, "y": 6.0 } }"#);
+    let position = registry.deserializer().deserialize(&mut json).unwrap();
+    assert_eq!(
+        position.downcast_ref::<Position>(),
+        Some(&Position { x: 5.0, y: 6.0 })
+    );
+}
+
+#[test]
+fn errors() {
+    let registry = registry();
+
+    let mut json = serde_json::Deserializer::from_str(r#"{ "x": 1.0, "y": 2.0 }"#);
+    let err = registry.deserialize("Velocity", &mut json).unwrap_err();
+    assert_eq!(err.to_string(), "unknown type `Velocity`");
+
+    let mut json = serde_json::Deserializer::from_str(r#"{ "x": "one", "y": 2.0 }"#);
+    assert!(registry.deserialize("Position", &mut json).is_err());
+
+    let err = registry
+        .deserializer()
+        .deserialize(&mut serde_json::Deserializer::from_str(
+            r#"{ "Position": { "x": 1.0, "y": 2.0 }, "Velocity": {} }"#,
+        ))
+        .unwrap_err();
+    assert!(err.to_string().contains("invalid length 2"), "{err}");
+}
@@ -1,16 +1,22 @@
 use alloc::boxed::Box;
+#[cfg(feature = "type_info")]
 use alloc::collections::BTreeMap;
+#[cfg(feature = "type_info")]
 use alloc::string::String;
 use alloc::vec::Vec;
 
+#[cfg(feature = "type_info")]
 use crate::key_path;
+#[cfg(feature = "type_info")]
 use crate::key_path::GetTypePath;
 use crate::struct_::StructValue;
+#[cfg(feature = "type_info")]
 use crate::type_info::GetMeta;
 use crate::FromReflect;
 use crate::GetField;
 use crate::Reflect;
 use crate::Struct;
+#[cfg(feature = "type_info")]
 use crate::Typed;
 use crate::Value;
 
@@ -190,6 +196,7 @@ fn from_reflect_with_value() {
     assert!(Foo::from_reflect(&value).is_some());
 }
 
+#[cfg(feature = "type_info")]
 #[test]
 fn accessing_docs_in_type_info() {
     /// Here are the docs.
@@ -227,3 +234,183 @@ fn accessing_docs_in_type_info() {
     assert_eq!(field.name().unwrap(), "field");
     assert_eq!(field.docs(), &[" Bingo!"]);
 }
+
+#[cfg(feature = "type_info")]
+#[test]
+fn fields_are_in_declaration_order() {
+    #[derive(Reflect, Default, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Unsorted {
+        b: i32,
+        c: i32,
+        a: i32,
+    }
+
+    let mut unsorted = Unsorted::default();
+    let mut value = unsorted.to_value();
+    let type_info = <Unsorted as Typed>::type_info();
+    let declared = type_info
+        .as_struct()
+        .unwrap()
+        .field_types()
+        .map(|field| field.name())
+        .collect::<Vec<_>>();
+    assert_eq!(declared, ["b", "c", "a"]);
+
+    for struct_ in [
+        unsorted.as_struct_mut().unwrap(),
+        value.as_struct_mut().unwrap(),
+    ] {
+        assert_eq!(struct_.fields_len(), 3);
+        assert_eq!(
+            (0..struct_.fields_len())
+                .map(|index| struct_.name_at(index).unwrap())
+                .collect::<Vec<_>>(),
+            declared
+        );
+        assert_eq!(
+            struct_.fields().map(|(name, _)| name).collect::<Vec<_>>(),
+            declared
+        );
+        assert_eq!(
+            struct_
+                .fields_mut()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            declared
+        );
+    }
+}
+
+#[test]
+fn setting_existing_field_keeps_its_position() {
+    let mut value = StructValue::new()
+        .with_field("b", 1)
+        .with_field("a", 2)
+        .with_field("b", 3);
+
+    assert_eq!(value.fields_len(), 2);
+    assert_eq!(
+        value.fields_mut().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["b", "a"]
+    );
+    assert_eq!(value.get_field::<i32>("b").unwrap(), &3);
+}
+
+#[cfg(feature = "type_info")]
+#[test]
+fn remove_rename_and_reorder_fields() {
+    #[derive(Reflect, Default, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Save {
+        name: String,
+        level: u32,
+    }
+
+    // an older version of `Save` with a stale field and `level` called `lvl`
+    let mut value = StructValue::new()
+        .with_field("lvl", 3_u32)
+        .with_field("stale", true)
+        .with_field("name", "Alice");
+
+    assert_eq!(value.remove_field("stale"), Some(Value::from(true)));
+    assert_eq!(value.remove_field("stale"), None);
+
+    assert!(!value.rename_field("missing", "level"));
+    assert!(!value.rename_field("lvl", "name"));
+    assert!(value.rename_field("lvl", "level"));
+    assert_eq!(value.get_field::<u32>("level").unwrap(), &3);
+
+    fn names(value: &StructValue) -> Vec<&str> {
+        value.fields().map(|(name, _)| name).collect()
+    }
+    assert_eq!(names(&value), ["level", "name"]);
+
+    let type_info = <Save as Typed>::type_info();
+    let struct_type = type_info.as_struct().unwrap();
+    value.reorder_fields(struct_type.field_types().map(|field| field.name()));
+    assert_eq!(names(&value), ["name", "level"]);
+    assert_eq!(
+        Value::from(value.clone()),
+        Save::from_reflect(&value).unwrap().to_value()
+    );
+
+    assert!(value.move_field("level", 0));
+    assert!(!value.move_field("level", 2));
+    assert!(!value.move_field("missing", 0));
+    assert_eq!(names(&value), ["level", "name"]);
+
+    assert_eq!(
+        Save::from_reflect(&value).unwrap(),
+        Save {
+            name: "Alice".to_owned(),
+            level: 3
+        }
+    );
+}
+
+#[test]
+fn editing_fields_keeps_names_and_values_together() {
+    let mut value = StructValue::new()
+        .with_field("a", 1)
+        .with_field("b", 2)
+        .with_field("c", 3)
+        .with_field("d", 4);
+
+    fn check(value: &mut StructValue, expected: &[(&str, i32)]) {
+        let fields = value
+            .fields_mut()
+            .map(|(name, value)| (name, *value.downcast_mut::<i32>().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(fields, expected);
+        for (index, (name, n)) in expected.iter().enumerate() {
+            assert_eq!(value.get_field::<i32>(*name).unwrap(), n);
+            assert_eq!(value.field_at(index).unwrap().downcast_ref(), Some(n));
+        }
+    }
+
+    assert_eq!(value.remove_field("b"), Some(Value::from(2)));
+    check(&mut value, &[("a", 1), ("c", 3), ("d", 4)]);
+
+    assert!(value.move_field("d", 0));
+    check(&mut value, &[("d", 4), ("a", 1), ("c", 3)]);
+
+    value.reorder_fields(["c", "missing", "c"]);
+    check(&mut value, &[("c", 3), ("d", 4), ("a", 1)]);
+
+    assert!(value.rename_field("d", "b"));
+    value.set_field("b", 5);
+    check(&mut value, &[("c", 3), ("b", 5), ("a", 1)]);
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn struct_value_speedy_round_trip() {
+    use speedy::Readable;
+    use speedy::Writable;
+
+    let value = StructValue::new().with_field("b", 1).with_field("a", 2);
+    let bytes = value.write_to_vec().unwrap();
+    // the same layout as the field names followed by the fields sorted by name
+    let parts = (
+        Vec::from(["b".to_owned(), "a".to_owned()]),
+        alloc::collections::BTreeMap::from([
+            ("a".to_owned(), Value::from(2)),
+            ("b".to_owned(), Value::from(1)),
+        ]),
+    );
+    assert_eq!(bytes, parts.write_to_vec().unwrap());
+    assert_eq!(StructValue::read_from_buffer(&bytes).unwrap(), value);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn struct_value_serde_round_trip() {
+    let value = StructValue::new().with_field("b", 1).with_field("a", 2);
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(
+        json,
+        r#"{"field_names":["b","a"],"fields":{"a":{"i32":2},"b":{"i32":1}}}"#
+    );
+    assert_eq!(serde_json::from_str::<StructValue>(&json).unwrap(), value);
+}
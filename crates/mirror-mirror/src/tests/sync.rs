@@ -0,0 +1,25 @@
+use crate::Reflect;
+
+#[test]
+fn reflected_values_can_be_shared_between_threads() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        n: i32,
+    }
+
+    let values: Vec<Box<dyn Reflect>> = Vec::from([
+        Box::new(Foo { n: 1 }) as Box<dyn Reflect>,
+        Box::new(Foo { n: 2 }.to_value()),
+    ]);
+
+    std::thread::scope(|scope| {
+        let handles = values
+            .iter()
+            .map(|value| scope.spawn(move || value.to_value()))
+            .collect::<Vec<_>>();
+        for (handle, value) in handles.into_iter().zip(&values) {
+            assert_eq!(handle.join().unwrap(), value.to_value());
+        }
+    });
+}
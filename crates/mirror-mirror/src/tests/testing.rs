@@ -0,0 +1,80 @@
+use alloc::collections::BTreeMap;
+
+use crate::assert_reflect_eq;
+use crate::testing::describe_differences;
+use crate::Reflect;
+
+#[derive(Reflect, Clone, Debug)]
+#[reflect(crate_name(crate))]
+struct Player {
+    name: String,
+    position: (f32, f32),
+    scores: Vec<u32>,
+    tags: BTreeMap<String, bool>,
+}
+
+fn player() -> Player {
+    Player {
+        name: "Alice".to_owned(),
+        position: (1.0, 2.0),
+        scores: Vec::from([1, 2]),
+        tags: BTreeMap::from([("admin".to_owned(), true)]),
+    }
+}
+
+#[test]
+fn equal_values_have_no_differences() {
+    assert!(describe_differences(&player(), &player()).is_none());
+    assert_reflect_eq!(player(), player());
+    assert_reflect_eq!(player(), player().to_value());
+}
+
+#[test]
+fn describes_each_difference() {
+    let mut other = player();
+    other.name = "Bob".to_owned();
+    other.position.1 = 3.0;
+    other.scores.pop();
+    other.tags.remove("admin");
+    other.tags.insert("banned".to_owned(), false);
+
+    assert_eq!(
+        describe_differences(&player(), &other).unwrap(),
+        [
+            r#".name: left: "Alice", right: "Bob""#,
+            ".position.1: left: 2.0_f32, right: 3.0_f32",
+            ".scores: only in left: 2_u32",
+            r#".tags: only in left: "admin": true"#,
+            r#".tags: only in right: "banned": false"#,
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn differences_at_the_root() {
+    assert_eq!(
+        describe_differences(&1_i32, &2_i32).unwrap(),
+        "<root>: left: 1_i32, right: 2_i32"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion `left == right` failed\n.position.1: left: 2.0_f32, right: 3.0_f32"
+)]
+fn panics_with_differences() {
+    let mut other = player();
+    other.position.1 = 3.0;
+    assert_reflect_eq!(player(), other);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion `left == right` failed: after 3 frames\n.scores: only in right: 3_u32"
+)]
+fn panics_with_custom_message() {
+    let mut other = player();
+    other.scores.push(3);
+    assert_reflect_eq!(player(), other, "after {} frames", 3);
+}
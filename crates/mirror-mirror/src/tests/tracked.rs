@@ -0,0 +1,132 @@
+use alloc::collections::BTreeMap;
+
+use crate::key_path;
+use crate::key_path::KeyPath;
+use crate::tracked::Tracked;
+use crate::FromReflect;
+use crate::Reflect;
+
+#[derive(Reflect, Debug, Clone, PartialEq, Default)]
+#[reflect(crate_name(crate))]
+struct Player {
+    name: String,
+    position: [f32; 2],
+    inventory: BTreeMap<String, u32>,
+    state: State,
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq, Default)]
+#[reflect(crate_name(crate))]
+enum State {
+    #[default]
+    Idle,
+    Walking {
+        speed: f32,
+    },
+}
+
+#[test]
+fn records_paths() {
+    let mut player = Tracked::new(Player::default());
+    assert!(!player.has_changes());
+
+    player.set_at(&key_path!(.name), "Alice").unwrap();
+    player
+        .at_mut(&key_path!(.position[1_usize]))
+        .unwrap()
+        .patch(&2.0_f32);
+    // failed changes aren't recorded
+    player.set_at(&key_path!(.missing), 1).unwrap_err();
+    assert!(player.at_mut(&key_path!(.missing)).is_none());
+
+    assert_eq!(player.name, "Alice");
+    assert_eq!(player.position, [0.0, 2.0]);
+    assert_eq!(
+        player.take_changes(),
+        [key_path!(.name), key_path!(.position[1_usize])]
+    );
+    assert!(!player.has_changes());
+
+    player.get_mut().position[0] = 1.0;
+    assert_eq!(player.take_changes(), [KeyPath::default()]);
+
+    player.as_reflect_mut().reflect_mut();
+    assert_eq!(player.take_changes(), [KeyPath::default()]);
+
+    player.as_reflect_mut().downcast_mut::<Player>().unwrap();
+    assert_eq!(player.take_changes(), [KeyPath::default()]);
+}
+
+#[test]
+fn records_patched_paths() {
+    let mut player = Tracked::new(Player {
+        inventory: BTreeMap::from([("sword".to_owned(), 1)]),
+        ..Default::default()
+    });
+
+    let mut patch = crate::struct_::StructValue::new();
+    patch.set_field("inventory", BTreeMap::from([("sword".to_owned(), 2_u32)]));
+    patch.set_field("state", State::Walking { speed: 1.0 });
+    player.patch(&patch);
+
+    assert_eq!(player.inventory["sword"], 2);
+    assert_eq!(player.state, State::Walking { speed: 1.0 });
+    assert_eq!(
+        player.take_changes(),
+        [
+            key_path!(.inventory["sword"]),
+            key_path!(.state),
+            key_path!(.state::Walking.speed),
+        ]
+    );
+}
+
+#[test]
+fn is_transparent() {
+    use core::any::TypeId;
+
+    let player = Tracked::new(Player {
+        name: "Alice".to_owned(),
+        ..Default::default()
+    });
+
+    let reflect: &dyn Reflect = &player;
+    assert_eq!(reflect.downcast_ref::<Player>().unwrap().name, "Alice");
+    assert_eq!(Reflect::type_id(reflect), TypeId::of::<Player>());
+    assert_eq!(reflect.type_name(), Player::default().type_name());
+    #[cfg(feature = "type_info")]
+    assert_eq!(
+        reflect.type_info().type_name(),
+        Player::default().type_info().type_name()
+    );
+    assert_eq!(reflect.to_value(), (*player).to_value());
+    assert_eq!(reflect.reflect_partial_eq(&*player), Some(true));
+    assert_eq!(
+        reflect.reflect_partial_cmp(&*player),
+        Some(core::cmp::Ordering::Equal)
+    );
+
+    let tracked = Tracked::<Player>::from_reflect(&player.to_value()).unwrap();
+    assert_eq!(tracked.into_inner(), player.clone().into_inner());
+}
+
+#[test]
+fn forwards_opt_ins() {
+    #[derive(Reflect, Debug, Clone, Hash)]
+    #[reflect(crate_name(crate), opt_in(Hash, PartialEq))]
+    struct Id(#[reflect(skip)] u64, String);
+
+    impl PartialEq for Id {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    let a = Tracked::new(Id(1, "a".to_owned()));
+    let b = Id(1, "b".to_owned());
+
+    // the wrapped type's own impls are used, rather than the structural defaults
+    assert_eq!(Reflect::reflect_hash(&a), (*a).reflect_hash());
+    assert_ne!(Reflect::reflect_hash(&a), a.to_value().reflect_hash());
+    assert_eq!(Reflect::reflect_partial_eq(&a, &b), Some(true));
+}
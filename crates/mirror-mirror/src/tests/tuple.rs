@@ -25,3 +25,89 @@ fn static_tuple() {
     assert_eq!(tuple.get_field::<i32>(0).unwrap(), &42);
     assert_eq!(tuple.get_field::<bool>(1).unwrap(), &false);
 }
+
+#[test]
+fn from_reflect() {
+    use crate::FromReflect;
+
+    let tuple = (1_i32, "foo".to_owned(), false);
+    assert_eq!(
+        <(i32, String, bool)>::from_reflect(&tuple.to_value()).unwrap(),
+        tuple
+    );
+
+    let short = (1_i32,).to_value();
+    assert!(<(i32, String)>::from_reflect(&short).is_none());
+
+    let long = (1_i32, "foo".to_owned(), false).to_value();
+    assert!(<(i32, String)>::from_reflect(&long).is_none());
+    assert!(<()>::from_reflect(&long).is_none());
+}
+
+#[test]
+fn wide_tuple() {
+    use crate::FromReflect;
+    #[cfg(feature = "type_info")]
+    use crate::Typed;
+    use crate::Value;
+
+    type Wide = (
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        bool,
+    );
+
+    let value: Value = (0..31_u8)
+        .map(Value::from)
+        .chain([Value::from(true)])
+        .collect::<TupleValue>()
+        .into();
+
+    let mut tuple = Wide::from_reflect(&value).unwrap();
+    assert_eq!(tuple.30, 30);
+    assert!(tuple.31);
+    assert_eq!(tuple.to_value(), value);
+
+    tuple.patch(&TupleValue::new().with_field(100_u8));
+    assert_eq!(tuple.get_field::<u8>(0).unwrap(), &100);
+    assert_eq!(tuple.get_field::<bool>(31).unwrap(), &true);
+
+    #[cfg(feature = "type_info")]
+    assert_eq!(
+        <Wide as Typed>::type_info()
+            .as_tuple()
+            .unwrap()
+            .field_types()
+            .count(),
+        32
+    );
+}
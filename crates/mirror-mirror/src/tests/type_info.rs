@@ -1,5 +1,7 @@
 use core::any::type_name;
 
+use crate::key_path;
+use crate::key_path::GetTypePath;
 use crate::type_info::*;
 use crate::Reflect;
 
@@ -99,3 +101,493 @@ fn type_to_root() {
         .to_type_root();
     assert_eq!(type_info.get_type().type_name(), type_name::<Foo>());
 }
+
+#[test]
+fn kind() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        a: Vec<i32>,
+        b: Bar,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Bar {
+        A(String),
+    }
+
+    let type_info = <Foo as Typed>::type_info();
+    assert_eq!(type_info.get_type().kind(), TypeKind::Struct);
+
+    let kind_at = |key_path| type_info.type_at(&key_path).unwrap().kind();
+    assert_eq!(kind_at(key_path!()), TypeKind::Struct);
+    assert_eq!(kind_at(key_path!(.a)), TypeKind::List);
+    assert_eq!(kind_at(key_path!(.a[0])), TypeKind::Scalar);
+    assert_eq!(kind_at(key_path!(.b)), TypeKind::Enum);
+    assert_eq!(kind_at(key_path!(.b::A)), TypeKind::Variant);
+    assert_eq!(kind_at(key_path!(.b::A.0)), TypeKind::Scalar);
+
+    let list = type_info.type_at(&key_path!(.a)).unwrap();
+    assert!(list.as_list().is_some());
+    assert!(list.as_struct().is_none());
+}
+
+#[test]
+fn fingerprint() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        a: i32,
+        #[reflect(meta(max = 1.0))]
+        b: Vec<String>,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Bar {
+        b: Vec<String>,
+        a: i32,
+    }
+
+    let mut first = graph::TypeGraph::default();
+    <Foo as Typed>::build(&mut first);
+    <Bar as Typed>::build(&mut first);
+
+    let mut second = graph::TypeGraph::default();
+    <Bar as Typed>::build(&mut second);
+    <Foo as Typed>::build(&mut second);
+
+    assert_eq!(first.canonical_bytes(), second.canonical_bytes());
+    assert_eq!(first.fingerprint(), second.fingerprint());
+
+    let foo = <Foo as Typed>::type_info();
+    let bar = <Bar as Typed>::type_info();
+    assert_eq!(foo.fingerprint(), <Foo as Typed>::type_info().fingerprint());
+    assert_ne!(foo.fingerprint(), bar.fingerprint());
+
+    // the fingerprint must not depend on anything specific to this build
+    assert_eq!(
+        <i32 as Typed>::type_info().fingerprint(),
+        0x42981c69c7461b79
+    );
+}
+
+#[test]
+fn opaque_inner_type() {
+    use crate::type_info::graph::*;
+    use alloc::collections::BTreeMap;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Settings {
+        volumes: Vec<f32>,
+    }
+
+    // stands in for something like `Arc<RwLock<Settings>>`
+    struct Shared;
+
+    impl Typed for Shared {
+        fn build(graph: &mut TypeGraph) -> NodeId {
+            graph.get_or_build_node_with::<Self, _>(|graph| {
+                OpaqueNode::new::<Self>(BTreeMap::new(), graph).with_inner_type::<Settings>(graph)
+            })
+        }
+    }
+
+    struct Handle;
+
+    impl Typed for Handle {
+        fn build(graph: &mut TypeGraph) -> NodeId {
+            graph.get_or_build_node_with::<Self, _>(|graph| {
+                OpaqueNode::new::<Self>(BTreeMap::new(), graph)
+            })
+        }
+    }
+
+    let type_info = <Shared as Typed>::type_info();
+
+    let opaque = type_info.get_type().as_opaque().unwrap();
+    assert_eq!(
+        opaque.inner_type().unwrap().type_name(),
+        type_name::<Settings>()
+    );
+
+    assert!(type_info
+        .type_at(&key_path!(.volumes))
+        .unwrap()
+        .as_list()
+        .is_some());
+    assert!(matches!(
+        type_info.type_at(&key_path!(.volumes[0])).unwrap(),
+        TypeAtPath::Scalar(ScalarType::f32)
+    ));
+    assert!(type_info.type_at(&key_path!(.missing)).is_none());
+
+    let type_info = <Handle as Typed>::type_info();
+    assert!(type_info
+        .get_type()
+        .as_opaque()
+        .unwrap()
+        .inner_type()
+        .is_none());
+    assert!(type_info.type_at(&key_path!(.volumes)).is_none());
+}
+
+#[test]
+fn opaque_attributes() {
+    use crate::impl_reflect_opaque;
+    use crate::FromReflect;
+    use crate::ReflectRef;
+    use crate::Value;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Settings {
+        volumes: Vec<f32>,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate), opaque(inner = T))]
+    struct Shared<T>(T)
+    where
+        T: Reflect + Typed + Clone + core::fmt::Debug;
+
+    #[derive(Clone, Debug)]
+    struct Handle;
+
+    impl_reflect_opaque!(Handle, inner = Settings);
+
+    let shared = Shared(Settings {
+        volumes: Vec::from([1.0]),
+    });
+    assert!(matches!(shared.reflect_ref(), ReflectRef::Opaque(_)));
+    assert_eq!(
+        shared.to_value(),
+        Value::from(type_name::<Shared<Settings>>())
+    );
+    let cloned = Shared::<Settings>::from_reflect(&shared).unwrap();
+    assert_eq!(cloned.0.volumes, [1.0]);
+    assert!(Handle::from_reflect(&Handle).is_some());
+
+    for type_info in [
+        <Shared<Settings> as Typed>::type_info(),
+        <Handle as Typed>::type_info(),
+    ] {
+        assert!(matches!(
+            type_info.type_at(&key_path!(.volumes[0])).unwrap(),
+            TypeAtPath::Scalar(ScalarType::f32)
+        ));
+    }
+}
+
+#[test]
+fn opaque_inner_type_cycle() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate), opaque(inner = B))]
+    struct A;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate), opaque(inner = A))]
+    struct B;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate), opaque(inner = Self))]
+    struct C;
+
+    let type_info = <A as Typed>::type_info();
+    assert_eq!(
+        type_info
+            .get_type()
+            .as_opaque()
+            .unwrap()
+            .inner_type()
+            .unwrap()
+            .type_name(),
+        type_name::<B>()
+    );
+    assert!(type_info.type_at(&key_path!(.field)).is_none());
+    assert!(<C as Typed>::type_info().type_at(&key_path!([0])).is_none());
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn versioned_encoding() {
+    use alloc::vec::Vec;
+
+    use crate::type_info::graph::TypeGraph;
+
+    let type_info = <Option<Vec<String>> as Typed>::type_info();
+    let bytes = type_info.write_versioned_to_vec().unwrap();
+    let decoded = TypeRoot::read_versioned_from_buffer(&bytes).unwrap();
+    assert_eq!(decoded.fingerprint(), type_info.fingerprint());
+
+    // type info written before the version was added used an older layout
+    assert!(TypeRoot::read_versioned_from_buffer(&bytes[6..]).is_err());
+    let graph = type_info.graph().write_versioned_to_vec().unwrap();
+    assert!(TypeGraph::read_versioned_from_buffer(&graph).is_ok());
+    assert!(TypeGraph::read_versioned_from_buffer(&graph[6..]).is_err());
+}
+
+// If this fails the layout of the type info changed. Bump the format version in `versioned.rs`
+// and keep a way to read the previous layout, then update the expected bytes.
+#[cfg(feature = "speedy")]
+#[test]
+fn versioned_encoding_layout() {
+    use alloc::vec::Vec;
+
+    use crate::type_info::graph::NodeId;
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Layout {
+        flag: bool,
+        kind: Kind,
+        shared: Shared,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    #[repr(u8)]
+    enum Kind {
+        A = 1,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate), opaque(inner = bool))]
+    struct Shared;
+
+    fn string(s: &str) -> Vec<u8> {
+        [&(s.len() as u32).to_le_bytes()[..], s.as_bytes()].concat()
+    }
+
+    fn id<T: Typed>() -> (NodeId, Vec<u8>) {
+        let id = <T as Typed>::type_info().root_id();
+        (id, speedy::Writable::write_to_vec(&id).unwrap())
+    }
+
+    let (layout_id, layout) = id::<Layout>();
+    let (bool_id, bool_) = id::<bool>();
+    let (kind_id, kind) = id::<Kind>();
+    let (shared_id, shared) = id::<Shared>();
+
+    let field = |name: &str, id: &[u8]| {
+        [
+            string(name),
+            string(name),
+            id.to_vec(),
+            // metadata, docs, default value
+            Vec::from([0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        ]
+        .concat()
+    };
+
+    #[rustfmt::skip]
+    let mut nodes = [
+        (layout_id, [
+            // `TypeNode::Struct`
+            Vec::from([0, 0, 0, 0]),
+            string(type_name::<Layout>()),
+            // fields, by name
+            Vec::from([3, 0, 0, 0]),
+            field("flag", &bool_),
+            field("kind", &kind),
+            field("shared", &shared),
+            // field names, in declaration order
+            Vec::from([3, 0, 0, 0]),
+            string("flag"),
+            string("kind"),
+            string("shared"),
+            // metadata, docs
+            Vec::from([0, 0, 0, 0, 0, 0, 0, 0]),
+        ].concat()),
+        (bool_id, Vec::from([
+            // `TypeNode::Scalar(ScalarNode::bool)`
+            7, 0, 0, 0, 11, 0, 0, 0,
+        ])),
+        (kind_id, [
+            // `TypeNode::Enum`
+            Vec::from([3, 0, 0, 0]),
+            string(type_name::<Kind>()),
+            // one `VariantNode::Unit`
+            Vec::from([1, 0, 0, 0, 2, 0, 0, 0]),
+            string("A"),
+            // discriminant
+            Vec::from([1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            // metadata, docs
+            Vec::from([0, 0, 0, 0, 0, 0, 0, 0]),
+            // repr
+            Vec::from([1]),
+            string("u8"),
+            // metadata, docs
+            Vec::from([0, 0, 0, 0, 0, 0, 0, 0]),
+        ].concat()),
+        (shared_id, [
+            // `TypeNode::Opaque`
+            Vec::from([8, 0, 0, 0]),
+            string(type_name::<Shared>()),
+            // metadata
+            Vec::from([0, 0, 0, 0]),
+            // inner type
+            Vec::from([1]),
+            bool_.clone(),
+        ].concat()),
+    ];
+    nodes.sort_by_key(|(id, _)| *id);
+
+    let mut expected = Vec::from(*b"MIRR\x01\x00");
+    expected.extend(layout);
+    expected.extend((nodes.len() as u32).to_le_bytes());
+    for (id, node) in nodes {
+        expected.extend(speedy::Writable::write_to_vec(&id).unwrap());
+        // `Some`
+        expected.push(1);
+        expected.extend(node);
+    }
+
+    let type_info = <Layout as Typed>::type_info();
+    assert_eq!(type_info.write_versioned_to_vec().unwrap(), expected);
+}
+
+#[test]
+fn graph_traversal() {
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    struct Tree {
+        label: String,
+        children: Vec<Tree>,
+        shape: Shape,
+    }
+
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Shape {
+        Circle { radius: f32 },
+        Rect(f32, f32),
+        Empty,
+    }
+
+    let type_info = <Tree as Typed>::type_info();
+    let graph = type_info.graph();
+    let name_of = |id| graph.get_type(id).unwrap().type_name();
+
+    let mut names = graph
+        .iter()
+        .map(|(_, ty)| ty.type_name())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    let mut expected = [
+        type_name::<Tree>(),
+        type_name::<Vec<Tree>>(),
+        type_name::<Shape>(),
+        "String",
+        type_name::<f32>(),
+    ];
+    expected.sort_unstable();
+    assert_eq!(names, expected);
+
+    let edges = graph
+        .edges_from(type_info.root_id())
+        .into_iter()
+        .map(|edge| (edge.kind().clone(), name_of(edge.to())))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        edges,
+        [
+            (EdgeKind::Field(key_path!(.label)), "String"),
+            (
+                EdgeKind::Field(key_path!(.children)),
+                type_name::<Vec<Tree>>()
+            ),
+            (EdgeKind::Field(key_path!(.shape)), type_name::<Shape>()),
+        ]
+    );
+
+    let shape = graph
+        .edges()
+        .filter(|edge| name_of(edge.from()) == type_name::<Shape>())
+        .map(|edge| edge.kind().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        shape,
+        [
+            EdgeKind::Field(key_path!(::Circle.radius)),
+            EdgeKind::Field(key_path!(::Rect.0)),
+            EdgeKind::Field(key_path!(::Rect.1)),
+        ]
+    );
+    assert_eq!(graph.edges().count(), 7);
+
+    let pre_order = graph
+        .depth_first(type_info.root_id())
+        .into_iter()
+        .map(name_of)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pre_order,
+        [
+            type_name::<Tree>(),
+            "String",
+            type_name::<Vec<Tree>>(),
+            type_name::<Shape>(),
+            type_name::<f32>(),
+        ]
+    );
+
+    let topological = graph
+        .topological_order(type_info.root_id())
+        .into_iter()
+        .map(name_of)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        topological,
+        [
+            "String",
+            type_name::<Vec<Tree>>(),
+            type_name::<f32>(),
+            type_name::<Shape>(),
+            type_name::<Tree>(),
+        ]
+    );
+
+    let shape_id = graph
+        .iter()
+        .find(|(_, ty)| ty.type_name() == type_name::<Shape>())
+        .unwrap()
+        .0;
+    assert_eq!(graph.depth_first(shape_id).len(), 2);
+}
+
+#[test]
+fn to_dot() {
+    /// A "quoted" name.
+    #[derive(Reflect, Clone, Debug)]
+    #[reflect(crate_name(crate))]
+    enum Node {
+        Leaf(u8),
+        Branch { children: Vec<Node> },
+    }
+
+    let type_info = <Node as Typed>::type_info();
+    let graph = type_info.graph();
+
+    assert_eq!(
+        graph.to_dot(type_info.root_id()),
+        "\
+digraph {
+    node [shape=box];
+    n0 [label=\"mirror_mirror::tests::type_info::to_dot::Node\"];
+    n1 [label=\"u8\"];
+    n2 [label=\"alloc::vec::Vec<mirror_mirror::tests::type_info::to_dot::Node>\"];
+    n0 -> n1 [label=\"::Leaf.0\"];
+    n0 -> n2 [label=\"::Branch.children\"];
+    n2 -> n0 [label=\"element\"];
+}
+"
+    );
+
+    let dot = graph.to_dot_with(type_info.root_id(), &DotOptions::new().docs(true));
+    assert!(dot.contains(
+        "n0 [label=\"mirror_mirror::tests::type_info::to_dot::Node\\n\\nA \\\"quoted\\\" name.\"];"
+    ));
+}
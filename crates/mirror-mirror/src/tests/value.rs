@@ -1,5 +1,16 @@
-use crate::{FromReflect, Reflect, Typed};
+use alloc::collections::BTreeMap;
 
+use crate::enum_::EnumValue;
+use crate::map::OrderedMap;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::value::ValueArena;
+use crate::value::ValueRef;
+#[cfg(feature = "type_info")]
+use crate::Typed;
+use crate::{FromReflect, Reflect, Value};
+
+#[cfg(feature = "type_info")]
 #[test]
 fn option_uses_none_as_default() {
     #[derive(Reflect, Clone, Debug, PartialEq, Eq)]
@@ -13,3 +24,880 @@ fn option_uses_none_as_default() {
     let foo = Foo::from_reflect(&default).expect("`from_reflect` failed");
     assert_eq!(foo, Foo { x: None });
 }
+
+#[cfg(feature = "speedy")]
+#[test]
+fn interned_encoding_round_trips() {
+    use crate::enum_::EnumValue;
+    use crate::struct_::StructValue;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        name: String,
+        kind: Kind,
+        scale: Scale,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Kind {
+        A { n: i32 },
+        B(bool),
+        C,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Scale(f32, f32);
+
+    let foos = Vec::from([
+        Foo {
+            name: "a".to_owned(),
+            kind: Kind::A { n: 1 },
+            scale: Scale(1.0, 2.0),
+        },
+        Foo {
+            name: "b".to_owned(),
+            kind: Kind::B(true),
+            scale: Scale(3.0, 4.0),
+        },
+        Foo {
+            name: "c".to_owned(),
+            kind: Kind::C,
+            scale: Scale(5.0, 6.0),
+        },
+    ]);
+    let value = foos.to_value();
+
+    let bytes = value.write_interned_to_vec().unwrap();
+    let decoded = Value::read_interned_from_buffer(&bytes).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(Vec::<Foo>::from_reflect(&decoded).unwrap(), foos);
+
+    let many = Value::List(Box::new(
+        (0..100)
+            .map(|n| {
+                StructValue::new()
+                    .with_field("some_long_field_name", n)
+                    .with_field("variant", EnumValue::new_unit_variant("SomeLongVariant"))
+                    .into()
+            })
+            .collect(),
+    ));
+    let interned = many.write_interned_to_vec().unwrap();
+    let plain = speedy::Writable::write_to_vec(&many).unwrap();
+    assert!(interned.len() < plain.len() / 2);
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn speedy_tags_dont_change() {
+    use speedy::Writable;
+
+    use crate::tuple_struct::TupleStructValue;
+
+    fn tag(bytes: &[u8]) -> u32 {
+        u32::from_le_bytes(bytes[..4].try_into().unwrap())
+    }
+
+    let values = [
+        (0, Value::usize(0)),
+        (1, Value::u8(0)),
+        (2, Value::u16(0)),
+        (3, Value::u32(0)),
+        (4, Value::u64(0)),
+        (5, Value::u128(Box::new(0))),
+        (6, Value::i8(0)),
+        (7, Value::i16(0)),
+        (8, Value::i32(0)),
+        (9, Value::i64(0)),
+        (10, Value::i128(Box::new(0))),
+        (11, Value::bool(false)),
+        (12, Value::char('a')),
+        (13, Value::f32(0.0)),
+        (14, Value::f64(0.0)),
+        (15, Value::String(Box::default())),
+        (16, StructValue::new().into()),
+        (17, EnumValue::new_unit_variant("A").into()),
+        (18, TupleStructValue::new().into()),
+        (19, TupleValue::new().into()),
+        (20, Value::List(Box::default())),
+        (21, Value::Map(Box::default())),
+        (22, Value::OrderedMap(Box::default())),
+    ];
+    for (expected, value) in values {
+        assert_eq!(tag(&value.write_to_vec().unwrap()), expected);
+        // interned values come after the string table, which is empty unless there's an enum
+        let bytes = value.write_interned_to_vec().unwrap();
+        let table_len = if let Value::EnumValue(_) = value {
+            9
+        } else {
+            4
+        };
+        assert_eq!(tag(&bytes[table_len..]), expected);
+    }
+
+    let kinds = [
+        (0, EnumValue::new_struct_variant("A").finish()),
+        (1, EnumValue::new_tuple_variant("A").finish()),
+        (2, EnumValue::new_unit_variant("A")),
+    ];
+    for (expected, variant) in kinds {
+        let value = Value::from(variant);
+        // after the tag and the variant name
+        assert_eq!(tag(&value.write_to_vec().unwrap()[9..]), expected);
+        // after the string table, the tag, and the index of the variant name
+        assert_eq!(tag(&value.write_interned_to_vec().unwrap()[17..]), expected);
+    }
+}
+
+#[test]
+fn arena_round_trips() {
+    use alloc::collections::BTreeMap;
+
+    use crate::value::ValueArena;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Entity {
+        name: String,
+        position: [f32; 2],
+        tags: BTreeMap<String, u32>,
+        shape: Shape,
+        id: Id,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Shape {
+        Circle { radius: f32 },
+        Square(f32),
+        Point,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Id(u128);
+
+    let entities = [
+        Entity {
+            name: "a".to_owned(),
+            position: [0.0, 1.0],
+            tags: BTreeMap::from([("x".to_owned(), 1)]),
+            shape: Shape::Circle { radius: 1.0 },
+            id: Id(1),
+        },
+        Entity {
+            name: "b".to_owned(),
+            position: [2.0, 3.0],
+            tags: BTreeMap::new(),
+            shape: Shape::Square(2.0),
+            id: Id(2),
+        },
+        Entity {
+            name: "c".to_owned(),
+            position: [4.0, 5.0],
+            tags: BTreeMap::from([("y".to_owned(), 2), ("z".to_owned(), 3)]),
+            shape: Shape::Point,
+            id: Id(u128::MAX),
+        },
+    ];
+
+    let mut arena = ValueArena::new();
+    let ids = entities
+        .iter()
+        .map(|entity| arena.alloc(entity))
+        .collect::<Vec<_>>();
+    for (entity, id) in entities.iter().zip(&ids) {
+        let value = arena.to_value(*id).unwrap();
+        assert_eq!(value, entity.to_value());
+        assert_eq!(&Entity::from_reflect(&value).unwrap(), entity);
+    }
+
+    // values can also be stored
+    let value = entities[0].to_value();
+    let id = arena.alloc(&value);
+    assert_eq!(arena.to_value(id).unwrap(), value);
+
+    arena.clear();
+    assert!(arena.is_empty());
+    assert!(arena.to_value(ids[0]).is_none());
+
+    // ids from before clearing stay invalid once the arena is filled again
+    let new_ids = entities
+        .iter()
+        .map(|entity| arena.alloc(entity))
+        .collect::<Vec<_>>();
+    assert!(arena.to_value(ids[0]).is_none());
+    assert_eq!(arena.to_value(new_ids[0]).unwrap(), entities[0].to_value());
+}
+
+#[test]
+fn canonical_text_round_trips() {
+    use crate::enum_::EnumValue;
+    use crate::map::OrderedMap;
+    use crate::struct_::StructValue;
+    use crate::tuple::TupleValue;
+    use crate::tuple_struct::TupleStructValue;
+    use alloc::collections::BTreeMap;
+
+    let value: Value = StructValue::new()
+        .with_field("int", -1_i64)
+        .with_field("float", 1.5_f32)
+        .with_field("nan", f64::NAN)
+        .with_field("inf", f32::NEG_INFINITY)
+        .with_field("text", "a \"quoted\"\n\u{1} string")
+        .with_field("char", '\'')
+        .with_field("not an identifier", true)
+        .with_field(
+            "tuples",
+            Value::List(Box::new(vec![
+                TupleValue::new().into(),
+                TupleValue::new().with_field(1_u8).into(),
+                TupleStructValue::new().with_field(2_u128).into(),
+            ])),
+        )
+        .with_field(
+            "enums",
+            Value::List(Box::new(vec![
+                EnumValue::new_unit_variant("A").into(),
+                EnumValue::new_tuple_variant("B")
+                    .with_tuple_field(1_usize)
+                    .finish()
+                    .into(),
+                EnumValue::new_struct_variant("C")
+                    .with_struct_field("x", 'x')
+                    .finish()
+                    .into(),
+            ])),
+        )
+        .with_field(
+            "map",
+            Value::Map(Box::new(BTreeMap::from([
+                (Value::from(2_i8), Value::from("b")),
+                (Value::from(1_i8), Value::from("a")),
+            ]))),
+        )
+        .with_field(
+            "ordered",
+            OrderedMap::new()
+                .with_entry(TupleValue::new().with_field(2_i8).with_field(1_u8), "b")
+                .with_entry(TupleValue::new().with_field(1_i8).with_field(1_u8), "a"),
+        )
+        .into();
+
+    let text = value.to_canonical_string();
+    assert_eq!(
+        text,
+        concat!(
+            r#"{int: -1_i64, float: 1.5_f32, nan: NaN_f64, inf: -inf_f32, "#,
+            r#"text: "a \"quoted\"\n\u{1} string", char: '\'', "not an identifier": true, "#,
+            r#"tuples: [(), (1_u8,), #(2_u128)], "#,
+            r#"enums: [::A, ::B(1_usize), ::C{x: 'x'}], "#,
+            r#"map: #{1_i8: "a", 2_i8: "b"}, "#,
+            r#"ordered: #ordered{(2_i8, 1_u8): "b", (1_i8, 1_u8): "a"}}"#,
+        )
+    );
+    assert_eq!(Value::parse_canonical(&text).unwrap(), value);
+}
+
+#[test]
+fn parse_canonical_text() {
+    use crate::value::ParseError;
+
+    let value = Value::parse_canonical(
+        "
+        // comments and trailing commas are allowed
+        [
+            1,
+            2.5,
+            1_000_u32,
+            (1),
+            ::A { x: 1e3_f32, },
+        ]
+        ",
+    )
+    .unwrap();
+    assert_eq!(
+        value.to_canonical_string(),
+        "[1_i32, 2.5_f64, 1000_u32, 1_i32, ::A{x: 1000.0_f32}]"
+    );
+
+    assert_eq!(
+        Value::parse_canonical("256_u8"),
+        Err(ParseError::InvalidNumber {
+            position: 0,
+            text: "256_u8".to_owned()
+        })
+    );
+    assert_eq!(
+        Value::parse_canonical("[1, 2"),
+        Err(ParseError::UnexpectedEnd {
+            expected: "`,` or a closing delimiter"
+        })
+    );
+    assert_eq!(
+        Value::parse_canonical("{a 1}"),
+        Err(ParseError::Unexpected {
+            position: 3,
+            found: '1',
+            expected: "`:`"
+        })
+    );
+    assert_eq!(
+        Value::parse_canonical(r#""\q""#),
+        Err(ParseError::InvalidEscape { position: 1 })
+    );
+    assert_eq!(
+        Value::parse_canonical("true false"),
+        Err(ParseError::TrailingInput { position: 5 })
+    );
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn versioned_encoding() {
+    use crate::enum_::EnumValue;
+    use crate::struct_::StructValue;
+
+    let value = Value::from(1_u8);
+    let bytes = value.write_versioned_to_vec().unwrap();
+    assert_eq!(bytes, [b'M', b'I', b'R', b'R', 1, 0, 1, 0, 0, 0, 1]);
+    assert_eq!(Value::read_versioned_from_buffer(&bytes).unwrap(), value);
+
+    let value = Value::from(
+        StructValue::new()
+            .with_field("a", true)
+            .with_field("b", EnumValue::new_unit_variant("C")),
+    );
+    let bytes = value.write_versioned_to_vec().unwrap();
+    #[rustfmt::skip]
+    assert_eq!(
+        bytes,
+        [
+            b'M', b'I', b'R', b'R', 1, 0,
+            // `Value::StructValue`
+            16, 0, 0, 0,
+            // field names
+            2, 0, 0, 0,
+            1, 0, 0, 0, b'a',
+            1, 0, 0, 0, b'b',
+            // fields
+            2, 0, 0, 0,
+            1, 0, 0, 0, b'a', 11, 0, 0, 0, 1,
+            1, 0, 0, 0, b'b', 17, 0, 0, 0,
+            // `EnumValue` variant name and kind
+            1, 0, 0, 0, b'C', 2, 0, 0, 0,
+        ]
+    );
+    assert_eq!(Value::read_versioned_from_buffer(&bytes).unwrap(), value);
+
+    // buffers written before the version was added
+    let legacy = speedy::Writable::write_to_vec(&value).unwrap();
+    assert_eq!(legacy, bytes[6..]);
+    assert_eq!(Value::read_versioned_from_buffer(&legacy).unwrap(), value);
+
+    let mut future = bytes.clone();
+    future[4] = 2;
+    assert!(Value::read_versioned_from_buffer(&future).is_err());
+    assert!(Value::read_versioned_from_buffer(b"MIRR").is_err());
+}
+
+#[test]
+fn value_ref_borrows() {
+    use alloc::collections::BTreeMap;
+
+    use crate::value::ValueRef;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        name: String,
+        position: [f32; 2],
+        tags: BTreeMap<String, u32>,
+        kind: Kind,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Kind {
+        A(f32),
+        B,
+    }
+
+    let foo = Foo {
+        name: "foo".to_owned(),
+        position: [1.0, f32::NAN],
+        tags: BTreeMap::from([("a".to_owned(), 1)]),
+        kind: Kind::A(2.0),
+    };
+
+    let ValueRef::StructValue(inner) = ValueRef::new(&foo) else {
+        panic!("expected a struct")
+    };
+    let ValueRef::String(name) = ValueRef::new(inner.field("name").unwrap()) else {
+        panic!("expected a string")
+    };
+    assert!(core::ptr::eq(name, foo.name.as_str()));
+    assert!(matches!(
+        ValueRef::new(inner.field("position").unwrap()),
+        ValueRef::Array(_)
+    ));
+    assert!(matches!(
+        ValueRef::new(inner.field("tags").unwrap()),
+        ValueRef::Map(_)
+    ));
+    assert!(matches!(
+        ValueRef::new(inner.field("kind").unwrap()),
+        ValueRef::EnumValue(_)
+    ));
+
+    let value = foo.to_value();
+    assert_eq!(ValueRef::new(&foo).to_value(), value);
+    assert_eq!(ValueRef::new(&foo), value);
+    assert_eq!(value, ValueRef::new(&foo));
+    assert_eq!(ValueRef::new(&foo), ValueRef::from(&value));
+
+    let mut other = foo.clone();
+    other.kind = Kind::B;
+    assert_ne!(ValueRef::new(&other), value);
+
+    assert_eq!(ValueRef::u8(1), Value::u8(1));
+    assert_ne!(ValueRef::u8(1), Value::u16(1));
+    assert_eq!(ValueRef::f64(f64::NAN), Value::f64(f64::NAN));
+    assert_eq!(ValueRef::String("a").to_value(), Value::from("a"));
+    assert_ne!(ValueRef::String("a"), Value::List(Box::default()));
+}
+
+#[test]
+fn update_value_reuses_unchanged_parts() {
+    use alloc::collections::BTreeMap;
+
+    use crate::key_path;
+    use crate::key_path::GetPath;
+    use crate::struct_::StructValue;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    struct Foo {
+        name: String,
+        items: Vec<Kind>,
+        tags: BTreeMap<String, u32>,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(crate_name(crate))]
+    enum Kind {
+        A { label: String },
+        B(i32),
+    }
+
+    fn label_ptr(value: &Value) -> *const u8 {
+        value
+            .get_at::<String>(&key_path!(.items[0].label))
+            .unwrap()
+            .as_ptr()
+    }
+
+    let mut foo = Foo {
+        name: "foo".to_owned(),
+        items: Vec::from([
+            Kind::A {
+                label: "a".to_owned(),
+            },
+            Kind::B(1),
+        ]),
+        tags: BTreeMap::from([("a".to_owned(), 1)]),
+    };
+    let mut value = foo.to_value();
+    let label = label_ptr(&value);
+
+    foo.name = "bar".to_owned();
+    foo.items[1] = Kind::B(2);
+    foo.items.push(Kind::B(3));
+    foo.tags.insert("b".to_owned(), 2);
+    foo.update_value(&mut value);
+    assert_eq!(value, foo.to_value());
+    assert_eq!(label_ptr(&value), label);
+
+    foo.items.truncate(1);
+    foo.items[0] = Kind::B(4);
+    foo.update_value(&mut value);
+    assert_eq!(value, foo.to_value());
+
+    let mut value = StructValue::new().with_field("name", "foo").to_value();
+    foo.update_value(&mut value);
+    assert_eq!(value, foo.to_value());
+
+    let mut value = Value::u8(1);
+    foo.update_value(&mut value);
+    assert_eq!(value, foo.to_value());
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn value_is_two_words() {
+    assert_eq!(core::mem::size_of::<Value>(), 16);
+}
+
+#[test]
+fn boxed_variants_downcast_to_their_contents() {
+    let mut value = Value::from(u128::MAX);
+    assert_eq!(value.as_any().downcast_ref::<u128>(), Some(&u128::MAX));
+    *value.as_any_mut().downcast_mut::<u128>().unwrap() = 1;
+    assert_eq!(value, Value::from(1_u128));
+
+    let value = Value::from("foo");
+    assert_eq!(value.as_any().downcast_ref::<String>().unwrap(), "foo");
+    assert_eq!(
+        *Box::new(value).into_any().downcast::<String>().unwrap(),
+        "foo"
+    );
+
+    let value = Value::from(Vec::from([1_i32, 2]));
+    assert_eq!(
+        value.as_any().downcast_ref::<Vec<Value>>().unwrap().len(),
+        2
+    );
+
+    let value = Value::from(StructValue::new().with_field("a", 1));
+    assert!(value.as_any().downcast_ref::<StructValue>().is_some());
+}
+
+#[test]
+fn deeply_nested_values_dont_overflow_the_stack() {
+    fn nested(leaf: i32) -> Value {
+        let mut value = Value::from(leaf);
+        for depth in 0..100_000 {
+            value = match depth % 6 {
+                0 => StructValue::new().with_field("child", value).into(),
+                1 => EnumValue::new_tuple_variant("Some")
+                    .with_tuple_field(value)
+                    .finish()
+                    .into(),
+                2 => TupleValue::new().with_field(1).with_field(value).into(),
+                3 => Value::List(Box::new(Vec::from([value]))),
+                4 => Value::Map(Box::new(BTreeMap::from([(Value::from(depth), value)]))),
+                _ => OrderedMap::new().with_entry("child", value).into(),
+            };
+        }
+        value
+    }
+
+    let value = nested(1);
+    assert_eq!(value.depth(), 100_000);
+
+    let clone = value.clone();
+    assert!(clone == value);
+
+    let other = nested(2);
+    assert!(value < other);
+
+    let mut patched = value.to_value();
+    patched.patch(&other);
+    assert!(patched == other);
+
+    assert_eq!(value.reflect_hash(), clone.reflect_hash());
+    assert_ne!(value.reflect_hash(), other.reflect_hash());
+    assert_eq!(value.reflect_partial_eq(&clone), Some(true));
+    assert_eq!(value.reflect_partial_eq(&other), Some(false));
+
+    assert!(ValueRef::new(&value) == clone);
+    assert!(ValueRef::new(&value).to_value() == value);
+
+    let mut arena = ValueArena::new();
+    let id = arena.alloc(&value);
+    assert!(arena.to_value(id).unwrap() == value);
+
+    let text = value.to_canonical_string();
+    assert!(text.starts_with(r#"[(1_i32, ::Some({child: #ordered{"child": #{99994_i32: [(1_i32, "#));
+
+    #[cfg(feature = "speedy")]
+    {
+        assert!(speedy::Writable::write_to_vec(&value).is_ok());
+        assert!(value.write_interned_to_vec().is_ok());
+    }
+
+    // serializers recurse once per level, so serializing is limited like deserializing is. JSON
+    // doesn't support maps with non-string keys so serialize a chain of structs
+    #[cfg(feature = "serde")]
+    {
+        let structs = (0..100_000).fold(Value::from(1), |value, _| {
+            StructValue::new().with_field("child", value).into()
+        });
+        assert!(serde_json::to_string(&structs)
+            .unwrap_err()
+            .to_string()
+            .starts_with("value is nested more than 128 levels deep"));
+    }
+
+    // `Value` has no `Drop` impl of its own so its data can be moved out by matching on it
+    let list = match Value::List(Box::new(Vec::from([value]))) {
+        Value::List(list) => *list,
+        _ => unreachable!(),
+    };
+    assert_eq!(list[0].depth(), 100_000);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips() {
+    let value = Value::from(
+        StructValue::new()
+            .with_field("a", 1_u128)
+            .with_field(
+                "b",
+                EnumValue::new_struct_variant("B")
+                    .with_struct_field("x", "x")
+                    .finish(),
+            )
+            .with_field(
+                "c",
+                EnumValue::new_tuple_variant("C")
+                    .with_tuple_field('c')
+                    .finish(),
+            )
+            .with_field("d", EnumValue::new_unit_variant("D"))
+            .with_field(
+                "e",
+                TupleValue::new().with_field(1.5_f32).with_field(-2_i128),
+            )
+            .with_field(
+                "f",
+                crate::tuple_struct::TupleStructValue::new().with_field(true),
+            )
+            .with_field("g", Value::List(Box::new(Vec::from([Value::from(1_u8)]))))
+            .with_field("h", OrderedMap::new().with_entry("b", 1).with_entry("a", 2)),
+    );
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), value);
+
+    // structs can also be given as sequences of their fields, and unknown fields are skipped
+    let json = r#"{ "StructValue": [["a"], { "a": { "EnumValue": { "name": "A", "kind": "Unit", "x": 1 } } }] }"#;
+    let expected = StructValue::new().with_field("a", EnumValue::new_unit_variant("A"));
+    assert_eq!(
+        serde_json::from_str::<Value>(json).unwrap(),
+        Value::from(expected)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserializing_deeply_nested_values_fails() {
+    use serde::Deserialize;
+
+    fn deserialize(depth: usize, limit: Option<usize>) -> Result<Value, serde_json::Error> {
+        let mut json = r#"{ "List": ["#.repeat(depth);
+        json.push_str(r#"{ "bool": true }"#);
+        json.push_str(&"] }".repeat(depth));
+
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        // serde_json has a limit of its own which would be hit first otherwise
+        deserializer.disable_recursion_limit();
+        match limit {
+            Some(limit) => Value::deserialize_with_depth_limit(&mut deserializer, limit),
+            None => Value::deserialize(&mut deserializer),
+        }
+    }
+
+    let value = deserialize(Value::DEFAULT_DEPTH_LIMIT, None).unwrap();
+    assert_eq!(value.depth(), 128);
+
+    let error = deserialize(Value::DEFAULT_DEPTH_LIMIT + 1, None).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("value is nested more than 128 levels deep"));
+
+    assert!(deserialize(100_000, None).is_err());
+    assert_eq!(deserialize(200, Some(200)).unwrap().depth(), 200);
+}
+
+#[test]
+fn parsing_deeply_nested_values_fails() {
+    use crate::value::ParseError;
+
+    let nested = |depth| format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+
+    let value = Value::parse_canonical(&nested(Value::DEFAULT_DEPTH_LIMIT)).unwrap();
+    assert_eq!(value.depth(), 128);
+
+    assert_eq!(
+        Value::parse_canonical(&nested(Value::DEFAULT_DEPTH_LIMIT + 1)),
+        Err(ParseError::TooDeep {
+            position: 128,
+            limit: 128
+        })
+    );
+    assert!(Value::parse_canonical(&"[".repeat(200_000)).is_err());
+    assert!(Value::parse_canonical(&"#{".repeat(200_000)).is_err());
+
+    let value = Value::parse_canonical_with_depth_limit(&nested(200), 200).unwrap();
+    assert_eq!(value.depth(), 200);
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn reading_deeply_nested_values() {
+    use speedy::Readable;
+
+    fn nested(depth: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for _ in 0..depth {
+            // a list with one element
+            bytes.extend(20_u32.to_le_bytes());
+            bytes.extend(1_u32.to_le_bytes());
+        }
+        bytes.extend(11_u32.to_le_bytes());
+        bytes.push(1);
+        bytes
+    }
+
+    let value = Value::read_from_buffer(&nested(100_000)).unwrap();
+    assert_eq!(value.depth(), 100_000);
+    assert_eq!(
+        speedy::Writable::write_to_vec(&value).unwrap(),
+        nested(100_000)
+    );
+
+    let value = Value::read_from_buffer_with_depth_limit(&nested(200), 200).unwrap();
+    assert_eq!(value.depth(), 200);
+    let error = Value::read_from_buffer_with_depth_limit(&nested(201), 200).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("value is nested more than 200 levels deep"));
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn reading_deeply_nested_interned_values() {
+    fn nested(depth: usize) -> Vec<u8> {
+        // an empty string table
+        let mut bytes = Vec::from(0_u32.to_le_bytes());
+        for _ in 0..depth {
+            // a list with one element
+            bytes.extend(20_u32.to_le_bytes());
+            bytes.extend(1_u32.to_le_bytes());
+        }
+        bytes.extend(11_u32.to_le_bytes());
+        bytes.push(1);
+        bytes
+    }
+
+    let value = Value::read_interned_from_buffer(&nested(100_000)).unwrap();
+    assert_eq!(value.depth(), 100_000);
+    assert_eq!(value.write_interned_to_vec().unwrap(), nested(100_000));
+
+    let value = Value::read_interned_from_buffer_with_depth_limit(&nested(200), 200).unwrap();
+    assert_eq!(value.depth(), 200);
+    let error = Value::read_interned_from_buffer_with_depth_limit(&nested(201), 200).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("value is nested more than 200 levels deep"));
+}
+
+#[test]
+fn values_at_the_default_depth_limit_round_trip() {
+    let mut value = Value::from(1_i32);
+    for depth in 0..Value::DEFAULT_DEPTH_LIMIT {
+        value = match depth % 7 {
+            0 => Value::List(Box::new(Vec::from([value]))),
+            1 => StructValue::new().with_field("a", value).into(),
+            2 => EnumValue::new_tuple_variant("A")
+                .with_tuple_field(value)
+                .finish()
+                .into(),
+            3 => EnumValue::new_struct_variant("B")
+                .with_struct_field("b", value)
+                .finish()
+                .into(),
+            4 => TupleValue::new().with_field(value).with_field(1_u8).into(),
+            5 => OrderedMap::new().with_entry("c", value).into(),
+            _ => Value::Map(Box::new(BTreeMap::from([(Value::from(depth), value)]))),
+        };
+    }
+    assert_eq!(value.depth(), Value::DEFAULT_DEPTH_LIMIT);
+
+    let text = value.to_canonical_string();
+    assert_eq!(Value::parse_canonical(&text).unwrap(), value);
+
+    #[cfg(feature = "speedy")]
+    {
+        use speedy::Readable;
+        use speedy::Writable;
+
+        let bytes = value.write_to_vec().unwrap();
+        assert_eq!(Value::read_from_buffer(&bytes).unwrap(), value);
+        let bytes = value.write_interned_to_vec().unwrap();
+        assert_eq!(Value::read_interned_from_buffer(&bytes).unwrap(), value);
+    }
+
+    // maps with keys other than strings can't be written as JSON
+    #[cfg(feature = "serde")]
+    {
+        use serde::Deserialize;
+
+        let mut value = Value::from(1_i32);
+        for _ in 0..Value::DEFAULT_DEPTH_LIMIT {
+            value = StructValue::new().with_field("a", value).into();
+        }
+        let json = serde_json::to_string(&value).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        // serde_json has a limit of its own which would be hit first otherwise
+        deserializer.disable_recursion_limit();
+        assert_eq!(Value::deserialize(&mut deserializer).unwrap(), value);
+    }
+}
+
+#[test]
+fn ordering_matches_the_contained_types() {
+    let structs = [
+        StructValue::new(),
+        StructValue::new().with_field("a", 1),
+        StructValue::new().with_field("a", 2),
+        StructValue::new().with_field("a", 1).with_field("b", 1),
+        StructValue::new().with_field("b", 1).with_field("a", 1),
+        StructValue::new().with_field("b", Value::List(Box::new(Vec::from([Value::from(1)])))),
+    ];
+    for a in &structs {
+        for b in &structs {
+            let (a_value, b_value) = (Value::from(a.clone()), Value::from(b.clone()));
+            assert_eq!(a_value.cmp(&b_value), a.cmp(b), "{a:?} {b:?}");
+            assert_eq!(a_value == b_value, a == b, "{a:?} {b:?}");
+        }
+    }
+
+    let enums = [
+        EnumValue::new_unit_variant("A"),
+        EnumValue::new_unit_variant("B"),
+        EnumValue::new_tuple_variant("A")
+            .with_tuple_field(1)
+            .finish(),
+        EnumValue::new_tuple_variant("A")
+            .with_tuple_field(2)
+            .finish(),
+        EnumValue::new_struct_variant("A")
+            .with_struct_field("a", 1)
+            .finish(),
+    ];
+    for a in &enums {
+        for b in &enums {
+            let (a_value, b_value) = (Value::from(a.clone()), Value::from(b.clone()));
+            assert_eq!(a_value.cmp(&b_value), a.cmp(b), "{a:?} {b:?}");
+        }
+    }
+
+    let maps = [
+        OrderedMap::new(),
+        OrderedMap::new().with_entry(1, "a"),
+        OrderedMap::new().with_entry(1, "b"),
+        OrderedMap::new().with_entry(1, "a").with_entry(0, "a"),
+        OrderedMap::new().with_entry(0, "a").with_entry(1, "a"),
+    ];
+    for a in &maps {
+        for b in &maps {
+            let (a_value, b_value) = (Value::from(a.clone()), Value::from(b.clone()));
+            assert_eq!(a_value.cmp(&b_value), a.cmp(b), "{a:?} {b:?}");
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use crate::key_path;
+use crate::key_path::GetPath;
+use crate::key_path::KeyPath;
+use crate::visit::walk;
+use crate::visit::ReflectVisitor;
+use crate::Reflect;
+use crate::ScalarRef;
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+struct Foo {
+    a: i32,
+    b: Vec<Bar>,
+    c: BTreeMap<String, (u8, bool)>,
+}
+
+#[derive(Reflect, Debug, Clone)]
+#[reflect(crate_name(crate))]
+enum Bar {
+    A { a: i32 },
+    B(i32),
+}
+
+#[derive(Default)]
+struct Paths {
+    entered: Vec<KeyPath>,
+    exited: Vec<KeyPath>,
+    scalars: Vec<KeyPath>,
+}
+
+impl ReflectVisitor for Paths {
+    fn enter(&mut self, _value: &dyn Reflect, path: &KeyPath) {
+        self.entered.push(path.clone());
+    }
+
+    fn exit(&mut self, _value: &dyn Reflect, path: &KeyPath) {
+        self.exited.push(path.clone());
+    }
+
+    fn visit_scalar(&mut self, _value: ScalarRef<'_>, path: &KeyPath) {
+        self.scalars.push(path.clone());
+    }
+}
+
+#[test]
+fn visits_everything() {
+    let foo = Foo {
+        a: 1,
+        b: Vec::from([Bar::A { a: 2 }, Bar::B(3)]),
+        c: BTreeMap::from([("x".to_owned(), (4, true))]),
+    };
+
+    let mut paths = Paths::default();
+    walk(&foo, &mut paths);
+
+    assert_eq!(
+        paths.scalars,
+        Vec::from([
+            key_path!(.a),
+            key_path!(.b[0_usize]::A.a),
+            key_path!(.b[1_usize]::B.0),
+            key_path!(.c["x"].0),
+            key_path!(.c["x"].1),
+        ])
+    );
+
+    assert_eq!(paths.entered.first(), Some(&key_path!()));
+    assert_eq!(paths.exited.last(), Some(&key_path!()));
+    assert_eq!(paths.entered.len(), paths.exited.len());
+
+    for path in &paths.entered {
+        assert!(foo.at(path).is_some(), "{path:?}");
+    }
+}
+
+#[test]
+fn callbacks_per_kind() {
+    #[derive(Default)]
+    struct Counts {
+        struct_fields: usize,
+        variant_fields: usize,
+        list_elements: usize,
+        map_entries: usize,
+        tuple_fields: usize,
+    }
+
+    impl ReflectVisitor for Counts {
+        fn visit_struct_field(&mut self, _name: &str, _value: &dyn Reflect, _path: &KeyPath) {
+            self.struct_fields += 1;
+        }
+
+        fn visit_struct_variant_field(
+            &mut self,
+            _name: &str,
+            _value: &dyn Reflect,
+            _path: &KeyPath,
+        ) {
+            self.variant_fields += 1;
+        }
+
+        fn visit_tuple_variant_field(
+            &mut self,
+            _index: usize,
+            _value: &dyn Reflect,
+            _path: &KeyPath,
+        ) {
+            self.variant_fields += 1;
+        }
+
+        fn visit_list_element(&mut self, _index: usize, _value: &dyn Reflect, _path: &KeyPath) {
+            self.list_elements += 1;
+        }
+
+        fn visit_map_entry(&mut self, key: &dyn Reflect, _value: &dyn Reflect, _path: &KeyPath) {
+            assert_eq!(key.downcast_ref::<String>().unwrap(), "x");
+            self.map_entries += 1;
+        }
+
+        fn visit_tuple_field(&mut self, _index: usize, _value: &dyn Reflect, _path: &KeyPath) {
+            self.tuple_fields += 1;
+        }
+    }
+
+    let foo = Foo {
+        a: 1,
+        b: Vec::from([Bar::A { a: 2 }, Bar::B(3)]),
+        c: BTreeMap::from([("x".to_owned(), (4, true))]),
+    };
+
+    let mut counts = Counts::default();
+    walk(&foo, &mut counts);
+
+    assert_eq!(counts.struct_fields, 3);
+    assert_eq!(counts.variant_fields, 2);
+    assert_eq!(counts.list_elements, 2);
+    assert_eq!(counts.map_entries, 1);
+    assert_eq!(counts.tuple_fields, 2);
+
+    // values are visited the same way as the types they came from
+    let mut counts = Counts::default();
+    walk(&foo.to_value(), &mut counts);
+    assert_eq!(counts.struct_fields, 3);
+    assert_eq!(counts.variant_fields, 2);
+}
+
+#[test]
+fn find_all() {
+    let foo = Foo {
+        a: 1,
+        b: Vec::from([Bar::A { a: 2 }, Bar::B(3)]),
+        c: BTreeMap::from([("x".to_owned(), (4, true))]),
+    };
+
+    let found = foo.as_reflect().find_all::<i32>().collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        Vec::from([
+            (key_path!(.a), &1),
+            (key_path!(.b[0_usize]::A.a), &2),
+            (key_path!(.b[1_usize]::B.0), &3),
+        ])
+    );
+
+    let bars = foo.as_reflect().find_all::<Bar>().count();
+    assert_eq!(bars, 2);
+
+    // the root is included
+    assert_eq!(foo.as_reflect().find_all::<Foo>().count(), 1);
+
+    // the order is the same as `walk`
+    let mut paths = Paths::default();
+    walk(&foo, &mut paths);
+    let found = foo
+        .as_reflect()
+        .find_all_by(|_| true)
+        .map(|(path, _)| path)
+        .collect::<Vec<_>>();
+    assert_eq!(found, paths.entered);
+}
+
+#[test]
+fn find_all_by() {
+    let foo = Foo {
+        a: 1,
+        b: Vec::from([Bar::A { a: 2 }, Bar::B(3)]),
+        c: BTreeMap::from([("x".to_owned(), (4, true))]),
+    };
+
+    let found = foo
+        .as_reflect()
+        .find_all_by(|value| value.as_enum().is_some())
+        .map(|(path, _)| path)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        Vec::from([key_path!(.b[0_usize]), key_path!(.b[1_usize])])
+    );
+}
@@ -0,0 +1,285 @@
+//! Track which parts of a value have been changed.
+//!
+//! [`Tracked`] wraps a value and records the [`KeyPath`]s of everything that is mutated through
+//! it, which is useful for replicating changes without diffing the entire value.
+//!
+//! # Example
+//!
+//! ```
+//! use mirror_mirror::key_path;
+//! use mirror_mirror::tracked::Tracked;
+//! use mirror_mirror::Reflect;
+//!
+//! #[derive(Reflect, Clone, Debug, Default)]
+//! struct Player {
+//!     name: String,
+//!     position: [f32; 2],
+//! }
+//!
+//! let mut player = Tracked::new(Player::default());
+//!
+//! player.set_at(&key_path!(.name), "Alice").unwrap();
+//! player.at_mut(&key_path!(.position[0_usize])).unwrap().patch(&1.0_f32);
+//!
+//! assert_eq!(
+//!     player.take_changes(),
+//!     [key_path!(.name), key_path!(.position[0_usize])]
+//! );
+//! assert!(player.take_changes().is_empty());
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::any::TypeId;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::key_path::GetPath;
+use crate::key_path::KeyPath;
+use crate::key_path::SetError;
+use crate::method::Method;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::TypeGraph;
+use crate::visit::walk;
+use crate::visit::ReflectVisitor;
+use crate::Enum;
+use crate::FromReflect;
+use crate::Reflect;
+use crate::ReflectMut;
+use crate::ReflectOwned;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::TypeRoot;
+#[cfg(feature = "type_info")]
+use crate::Typed;
+use crate::Value;
+
+/// A value that records the key paths of everything mutated through it.
+///
+/// `Tracked<T>` implements [`Reflect`] by delegating to `T`, so it can be used anywhere `T` can
+/// and `downcast_ref::<T>` works on it. Changes are recorded as follows:
+///
+/// - [`Tracked::at_mut`] and [`Tracked::set_at`] record the key path they were called with.
+/// - [`Reflect::patch`] records the path of every scalar and opaque value in the patch, as well as
+///   of every enum since patching an enum can change its variant.
+/// - Anything else that gives out mutable access to the whole value, such as
+///   [`Reflect::reflect_mut`], [`Tracked::get_mut`], or `downcast_mut`, records the empty key
+///   path, meaning everything might have changed.
+///
+/// Note that [`Tracked::at_mut`] and [`Tracked::set_at`] shadow the [`GetPath`] methods, which
+/// would go through [`Reflect::reflect_mut`] and so mark the whole value as changed.
+#[derive(Debug, Clone, Default)]
+pub struct Tracked<T> {
+    value: T,
+    changes: BTreeSet<KeyPath>,
+}
+
+impl<T> Tracked<T>
+where
+    T: Reflect,
+{
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            changes: BTreeSet::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Mutable access to the wrapped value. Marks the whole value as changed.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.changes.insert(KeyPath::default());
+        &mut self.value
+    }
+
+    /// Get the value at `key_path` mutably and record `key_path` as changed.
+    ///
+    /// Nothing is recorded if there is no value at `key_path`.
+    pub fn at_mut(&mut self, key_path: &KeyPath) -> Option<&mut dyn Reflect> {
+        let value = self.value.at_mut(key_path)?;
+        self.changes.insert(key_path.clone());
+        Some(value)
+    }
+
+    /// Like [`GetPath::set_at`] but also records `key_path` as changed if setting succeeds.
+    pub fn set_at(&mut self, key_path: &KeyPath, value: impl Into<Value>) -> Result<(), SetError> {
+        self.value.set_at(key_path, value)?;
+        self.changes.insert(key_path.clone());
+        Ok(())
+    }
+
+    /// Whether anything has been changed since the changes were last taken.
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// The key paths changed since the changes were last taken, sorted and without duplicates.
+    ///
+    /// The paths can overlap, for example if both a struct and one of its fields were changed.
+    pub fn changes(&self) -> impl Iterator<Item = &KeyPath> {
+        self.changes.iter()
+    }
+
+    /// Return the changed key paths and start tracking from scratch.
+    pub fn take_changes(&mut self) -> Vec<KeyPath> {
+        core::mem::take(&mut self.changes).into_iter().collect()
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+#[cfg(feature = "type_info")]
+impl<T> Typed for Tracked<T>
+where
+    T: Typed,
+{
+    fn build(graph: &mut TypeGraph) -> NodeId {
+        T::build(graph)
+    }
+}
+
+impl<T> Reflect for Tracked<T>
+where
+    T: Reflect,
+{
+    fn type_info(&self) -> TypeRoot {
+        self.value.type_info()
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        <T as Reflect>::into_any(Box::new(self.value))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self.value.as_any()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self.changes.insert(KeyPath::default());
+        self.value.as_any_mut()
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        Box::new(self.value)
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self.value.as_reflect()
+    }
+
+    // returns `self` rather than the wrapped value so changes made through it are still tracked
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        <T as Reflect>::reflect_owned(Box::new(self.value))
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        self.value.reflect_ref()
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        self.changes.insert(KeyPath::default());
+        self.value.reflect_mut()
+    }
+
+    fn patch(&mut self, value: &dyn Reflect) {
+        walk(value, &mut RecordPatch(&mut self.changes));
+        self.value.patch(value);
+    }
+
+    fn to_value(&self) -> Value {
+        self.value.to_value()
+    }
+
+    fn update_value(&self, value: &mut Value) {
+        self.value.update_value(value)
+    }
+
+    fn clone_reflect(&self) -> Box<dyn Reflect> {
+        self.value.clone_reflect()
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.debug(f)
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        self.value.reflect_hash()
+    }
+
+    fn reflect_partial_eq(&self, other: &dyn Reflect) -> Option<bool> {
+        self.value.reflect_partial_eq(other)
+    }
+
+    fn reflect_partial_cmp(&self, other: &dyn Reflect) -> Option<Ordering> {
+        self.value.reflect_partial_cmp(other)
+    }
+
+    fn reflect_display(&self) -> Option<&dyn fmt::Display> {
+        self.value.reflect_display()
+    }
+
+    fn methods(&self) -> &'static [Method] {
+        self.value.methods()
+    }
+
+    fn type_id(&self) -> TypeId {
+        Reflect::type_id(&self.value)
+    }
+
+    fn type_name(&self) -> &str {
+        self.value.type_name()
+    }
+}
+
+impl<T> FromReflect for Tracked<T>
+where
+    T: FromReflect,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        Some(Self::new(T::from_reflect(reflect)?))
+    }
+}
+
+impl<T> From<Tracked<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(tracked: Tracked<T>) -> Self {
+        tracked.value.into()
+    }
+}
+
+/// Records the paths a patch might change.
+struct RecordPatch<'a>(&'a mut BTreeSet<KeyPath>);
+
+impl ReflectVisitor for RecordPatch<'_> {
+    fn visit_enum(&mut self, _value: &dyn Enum, path: &KeyPath) {
+        self.0.insert(path.clone());
+    }
+
+    fn visit_scalar(&mut self, _value: ScalarRef<'_>, path: &KeyPath) {
+        self.0.insert(path.clone());
+    }
+
+    fn visit_opaque(&mut self, _value: &dyn Reflect, path: &KeyPath) {
+        self.0.insert(path.clone());
+    }
+}
@@ -5,10 +5,15 @@ use core::fmt;
 use core::fmt::Debug;
 
 use crate::iter::ValueIterMut;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::OpaqueNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TupleNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::UnnamedFieldNode;
 use crate::FromReflect;
 use crate::Reflect;
@@ -57,6 +62,32 @@ impl TupleValue {
     pub fn push_field(&mut self, value: impl Into<Value>) {
         self.fields.push(value.into());
     }
+
+    pub(crate) fn values(&self) -> &[Value] {
+        &self.fields
+    }
+
+    pub(crate) fn values_mut(&mut self) -> &mut [Value] {
+        &mut self.fields
+    }
+
+    #[cfg(any(feature = "serde", feature = "speedy"))]
+    pub(crate) fn from_values(fields: Vec<Value>) -> Self {
+        Self { fields }
+    }
+
+    /// Clone the tuple, creating the values of the fields with `f`.
+    pub(crate) fn map_values(&self, f: impl FnMut(&Value) -> Value) -> Self {
+        Self {
+            fields: self.fields.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Drop for TupleValue {
+    fn drop(&mut self) {
+        crate::value::drop_nested(&mut self.fields);
+    }
 }
 
 impl Tuple for TupleValue {
@@ -84,6 +115,7 @@ impl Tuple for TupleValue {
 
 impl Reflect for TupleValue {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl Typed for TupleValue {
             fn build(graph: &mut TypeGraph) -> NodeId {
                 graph.get_or_build_node_with::<Self, _>(|graph| {
@@ -150,6 +182,7 @@ impl FromReflect for TupleValue {
 macro_rules! impl_tuple {
     ($($ident:ident),* $(,)?) => {
         #[allow(non_snake_case, unused_mut, unused_variables)]
+        #[cfg(feature = "type_info")]
         impl<$($ident,)*> Typed for ($($ident,)*)
         where
             $($ident: Reflect + Typed + Clone,)*
@@ -275,11 +308,25 @@ macro_rules! impl_tuple {
             $($ident: FromReflect + Typed + Clone,)*
         {
             fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                let tuple = reflect.reflect_ref().as_tuple()?;
+                let mut len = 0;
+                $(
+                    let _ = stringify!($ident);
+                    len += 1;
+                )*
+                if tuple.fields_len() != len {
+                    return None;
+                }
+                let mut fields = tuple.fields();
                 Some((
-                    $($ident::from_reflect(reflect)?,)*
+                    $($ident::from_reflect(fields.next()?)?,)*
                 ))
             }
         }
+
+        impl<$($ident,)*> crate::__private::ReflectedFields<()> for ($($ident,)*) {
+            type Fields = Self;
+        }
     };
 }
 
@@ -300,6 +347,59 @@ impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
 impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
 impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
 impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26, T27
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26, T27, T28
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26, T27, T28, T29
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26, T27, T28, T29, T30
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26, T27, T28, T29, T30, T31
+);
+impl_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26, T27, T28, T29, T30, T31, T32
+);
 
 impl<V> FromIterator<V> for TupleValue
 where
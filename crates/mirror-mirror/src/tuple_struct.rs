@@ -4,8 +4,11 @@ use core::fmt;
 
 use crate::iter::ValueIterMut;
 use crate::tuple::TupleValue;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::OpaqueNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::Reflect;
@@ -56,10 +59,31 @@ impl TupleStructValue {
     pub fn push_field(&mut self, value: impl Into<Value>) {
         self.tuple.push_field(value);
     }
+
+    pub(crate) fn values(&self) -> &[Value] {
+        self.tuple.values()
+    }
+
+    pub(crate) fn values_mut(&mut self) -> &mut [Value] {
+        self.tuple.values_mut()
+    }
+
+    #[cfg(any(feature = "serde", feature = "speedy"))]
+    pub(crate) fn from_tuple(tuple: TupleValue) -> Self {
+        Self { tuple }
+    }
+
+    /// Clone the tuple struct, creating the values of the fields with `f`.
+    pub(crate) fn map_values(&self, f: impl FnMut(&Value) -> Value) -> Self {
+        Self {
+            tuple: self.tuple.map_values(f),
+        }
+    }
 }
 
 impl Reflect for TupleStructValue {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl Typed for TupleStructValue {
             fn build(graph: &mut TypeGraph) -> NodeId {
                 graph.get_or_build_node_with::<Self, _>(|graph| {
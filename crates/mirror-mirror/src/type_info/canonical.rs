@@ -0,0 +1,381 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::graph::*;
+use crate::enum_::VariantField;
+use crate::hash::StableHasher;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Value;
+
+/// Encode a [`TypeGraph`] such that equal graphs always produce the same bytes, regardless of
+/// the platform or the order in which the nodes were built.
+///
+/// [`NodeId`]s are derived from `TypeId`s which aren't stable across builds, so they are never
+/// written. Nodes are instead sorted by their normalized type name and other nodes are referred
+/// to by name.
+pub(super) fn encode(graph: &TypeGraph) -> Vec<u8> {
+    let mut nodes = graph
+        .map
+        .values()
+        .flatten()
+        .map(|node| {
+            let mut encoder = Encoder {
+                graph,
+                out: Vec::new(),
+            };
+            encoder.node(node);
+            (normalized_name(node), encoder.out)
+        })
+        .collect::<Vec<_>>();
+    nodes.sort();
+
+    let mut encoder = Encoder {
+        graph,
+        out: Vec::new(),
+    };
+    encoder.len(nodes.len());
+    for (name, node) in nodes {
+        encoder.str(&name);
+        encoder.out.extend(node);
+    }
+    encoder.out
+}
+
+/// Hash the canonical bytes with a hasher whose output is the same on every platform.
+pub(super) fn fingerprint(bytes: &[u8]) -> u64 {
+    use core::hash::Hasher;
+
+    let mut hasher = StableHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// The type name with all whitespace removed, since `core::any::type_name` doesn't guarantee
+/// how it formats names.
+pub(super) fn normalized_name(node: &TypeNode) -> String {
+    let name = match node {
+        TypeNode::Struct(node) => &node.type_name,
+        TypeNode::TupleStruct(node) => &node.type_name,
+        TypeNode::Tuple(node) => &node.type_name,
+        TypeNode::Enum(node) => &node.type_name,
+        TypeNode::List(node) => &node.type_name,
+        TypeNode::Array(node) => &node.type_name,
+        TypeNode::Map(node) => &node.type_name,
+        TypeNode::Opaque(node) => &node.type_name,
+        TypeNode::Scalar(node) => scalar_name(node),
+    };
+    normalize(name)
+}
+
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+fn scalar_name(node: &ScalarNode) -> &'static str {
+    match node {
+        ScalarNode::usize => "usize",
+        ScalarNode::u8 => "u8",
+        ScalarNode::u16 => "u16",
+        ScalarNode::u32 => "u32",
+        ScalarNode::u64 => "u64",
+        ScalarNode::u128 => "u128",
+        ScalarNode::i8 => "i8",
+        ScalarNode::i16 => "i16",
+        ScalarNode::i32 => "i32",
+        ScalarNode::i64 => "i64",
+        ScalarNode::i128 => "i128",
+        ScalarNode::bool => "bool",
+        ScalarNode::char => "char",
+        ScalarNode::f32 => "f32",
+        ScalarNode::f64 => "f64",
+        ScalarNode::String => "String",
+    }
+}
+
+struct Encoder<'a> {
+    graph: &'a TypeGraph,
+    out: Vec<u8>,
+}
+
+impl<'a> Encoder<'a> {
+    fn tag(&mut self, tag: u8) {
+        self.out.push(tag);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.out.extend((len as u64).to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.len(s.len());
+        self.out.extend(s.as_bytes());
+    }
+
+    fn id(&mut self, id: NodeId) {
+        let name = normalized_name(self.graph.get(id));
+        self.str(&name);
+    }
+
+    fn node(&mut self, node: &TypeNode) {
+        match node {
+            TypeNode::Struct(node) => {
+                self.tag(0);
+                self.named_fields(&node.field_names, &node.fields);
+                self.metadata(&node.metadata);
+                self.docs(&node.docs);
+            }
+            TypeNode::TupleStruct(node) => {
+                self.tag(1);
+                self.unnamed_fields(&node.fields);
+                self.metadata(&node.metadata);
+                self.docs(&node.docs);
+            }
+            TypeNode::Tuple(node) => {
+                self.tag(2);
+                self.unnamed_fields(&node.fields);
+                self.metadata(&node.metadata);
+                self.docs(&node.docs);
+            }
+            TypeNode::Enum(node) => {
+                self.tag(3);
+                self.len(node.variants.len());
+                for variant in &node.variants {
+                    self.variant(variant);
+                }
+                self.option(node.repr.as_deref(), Self::str);
+                self.metadata(&node.metadata);
+                self.docs(&node.docs);
+            }
+            TypeNode::List(node) => {
+                self.tag(4);
+                self.id(node.field_type_id);
+            }
+            TypeNode::Array(node) => {
+                self.tag(5);
+                self.id(node.field_type_id);
+                self.len(node.len);
+            }
+            TypeNode::Map(node) => {
+                self.tag(6);
+                self.id(node.key_type_id);
+                self.id(node.value_type_id);
+            }
+            TypeNode::Scalar(_) => {
+                // the name, which is written by the caller, is enough to identify the scalar
+                self.tag(7);
+            }
+            TypeNode::Opaque(node) => {
+                self.tag(8);
+                self.metadata(&node.metadata);
+                self.option(node.inner_type_id, Self::id);
+            }
+        }
+    }
+
+    fn variant(&mut self, variant: &VariantNode) {
+        match variant {
+            VariantNode::Struct(variant) => {
+                self.tag(0);
+                self.str(&variant.name);
+                self.named_fields(&variant.field_names, &variant.fields);
+                self.option(variant.discriminant, Self::i128);
+                self.metadata(&variant.metadata);
+                self.docs(&variant.docs);
+            }
+            VariantNode::Tuple(variant) => {
+                self.tag(1);
+                self.str(&variant.name);
+                self.unnamed_fields(&variant.fields);
+                self.option(variant.discriminant, Self::i128);
+                self.metadata(&variant.metadata);
+                self.docs(&variant.docs);
+            }
+            VariantNode::Unit(variant) => {
+                self.tag(2);
+                self.str(&variant.name);
+                self.option(variant.discriminant, Self::i128);
+                self.metadata(&variant.metadata);
+                self.docs(&variant.docs);
+            }
+        }
+    }
+
+    fn named_fields(&mut self, field_names: &[String], fields: &BTreeMap<String, NamedFieldNode>) {
+        // fields are written in declaration order since that is part of the type's shape
+        self.len(field_names.len());
+        for name in field_names {
+            let field = &fields[name];
+            self.str(&field.name);
+            self.id(field.id);
+            self.metadata(&field.metadata);
+            self.docs(&field.docs);
+            self.option(field.default_value.as_ref(), Self::value);
+        }
+    }
+
+    fn unnamed_fields(&mut self, fields: &[UnnamedFieldNode]) {
+        self.len(fields.len());
+        for field in fields {
+            self.id(field.id);
+            self.metadata(&field.metadata);
+            self.docs(&field.docs);
+            self.option(field.default_value.as_ref(), Self::value);
+        }
+    }
+
+    fn metadata(&mut self, metadata: &BTreeMap<String, Value>) {
+        self.len(metadata.len());
+        for (key, value) in metadata {
+            self.str(key);
+            self.value(value);
+        }
+    }
+
+    fn docs(&mut self, docs: &[String]) {
+        self.len(docs.len());
+        for doc in docs {
+            self.str(doc);
+        }
+    }
+
+    fn option<T>(&mut self, value: Option<T>, f: impl FnOnce(&mut Self, T)) {
+        match value {
+            Some(value) => {
+                self.tag(1);
+                f(self, value);
+            }
+            None => self.tag(0),
+        }
+    }
+
+    fn i128(&mut self, n: i128) {
+        self.out.extend(n.to_le_bytes());
+    }
+
+    fn value(&mut self, value: &Value) {
+        self.reflect(value.as_reflect());
+    }
+
+    fn reflect(&mut self, value: &dyn Reflect) {
+        match value.reflect_ref() {
+            ReflectRef::Struct(inner) => {
+                self.tag(0);
+                self.len(inner.fields_len());
+                for (name, value) in inner.fields() {
+                    self.str(name);
+                    self.reflect(value);
+                }
+            }
+            ReflectRef::TupleStruct(inner) => {
+                self.tag(1);
+                self.len(inner.fields_len());
+                for value in inner.fields() {
+                    self.reflect(value);
+                }
+            }
+            ReflectRef::Tuple(inner) => {
+                self.tag(2);
+                self.len(inner.fields_len());
+                for value in inner.fields() {
+                    self.reflect(value);
+                }
+            }
+            ReflectRef::Enum(inner) => {
+                self.tag(3);
+                self.str(inner.variant_name());
+                self.len(inner.fields_len());
+                for field in inner.fields() {
+                    match field {
+                        VariantField::Struct(name, value) => {
+                            self.str(name);
+                            self.reflect(value);
+                        }
+                        VariantField::Tuple(value) => self.reflect(value),
+                    }
+                }
+            }
+            // arrays and lists are encoded the same, just like they have the same `Value`
+            // representation
+            ReflectRef::Array(inner) => {
+                self.tag(4);
+                self.len(inner.len());
+                for value in inner.iter() {
+                    self.reflect(value);
+                }
+            }
+            ReflectRef::List(inner) => {
+                self.tag(4);
+                self.len(inner.len());
+                for value in inner.iter() {
+                    self.reflect(value);
+                }
+            }
+            ReflectRef::Map(inner) => {
+                // map iteration order isn't guaranteed so sort the encoded entries
+                let mut entries = inner
+                    .iter()
+                    .map(|(key, value)| {
+                        let mut encoder = Encoder {
+                            graph: self.graph,
+                            out: Vec::new(),
+                        };
+                        encoder.reflect(key);
+                        encoder.reflect(value);
+                        encoder.out
+                    })
+                    .collect::<Vec<_>>();
+                entries.sort();
+
+                self.tag(5);
+                self.len(entries.len());
+                for entry in entries {
+                    self.out.extend(entry);
+                }
+            }
+            ReflectRef::Scalar(inner) => {
+                self.tag(6);
+                match inner {
+                    ScalarRef::usize(n) => self.scalar(0, &(n as u64).to_le_bytes()),
+                    ScalarRef::u8(n) => self.scalar(1, &n.to_le_bytes()),
+                    ScalarRef::u16(n) => self.scalar(2, &n.to_le_bytes()),
+                    ScalarRef::u32(n) => self.scalar(3, &n.to_le_bytes()),
+                    ScalarRef::u64(n) => self.scalar(4, &n.to_le_bytes()),
+                    ScalarRef::u128(n) => self.scalar(5, &n.to_le_bytes()),
+                    ScalarRef::i8(n) => self.scalar(6, &n.to_le_bytes()),
+                    ScalarRef::i16(n) => self.scalar(7, &n.to_le_bytes()),
+                    ScalarRef::i32(n) => self.scalar(8, &n.to_le_bytes()),
+                    ScalarRef::i64(n) => self.scalar(9, &n.to_le_bytes()),
+                    ScalarRef::i128(n) => self.scalar(10, &n.to_le_bytes()),
+                    ScalarRef::bool(b) => self.scalar(11, &[b as u8]),
+                    ScalarRef::char(c) => self.scalar(12, &u32::from(c).to_le_bytes()),
+                    // all NaNs are encoded the same
+                    ScalarRef::f32(n) => {
+                        let n = if n.is_nan() { f32::NAN } else { n };
+                        self.scalar(13, &n.to_bits().to_le_bytes())
+                    }
+                    ScalarRef::f64(n) => {
+                        let n = if n.is_nan() { f64::NAN } else { n };
+                        self.scalar(14, &n.to_bits().to_le_bytes())
+                    }
+                    ScalarRef::String(s) => {
+                        self.tag(15);
+                        self.str(s);
+                    }
+                }
+            }
+            // opaque values can't be inspected so only their type is encoded
+            ReflectRef::Opaque(inner) => {
+                self.tag(7);
+                self.str(&normalize(inner.type_info().type_name()));
+            }
+        }
+    }
+
+    fn scalar(&mut self, tag: u8, bytes: &[u8]) {
+        self.tag(tag);
+        self.out.extend(bytes);
+    }
+}
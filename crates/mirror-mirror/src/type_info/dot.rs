@@ -0,0 +1,86 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt::Write;
+
+use super::graph::NodeId;
+use super::graph::TypeGraph;
+use super::EdgeKind;
+use super::GetMeta;
+
+/// Options for [`TypeGraph::to_dot_with`].
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions {
+    docs: bool,
+}
+
+impl DotOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the doc comments of structs and enums to their nodes. Defaults to `false`.
+    pub fn docs(mut self, docs: bool) -> Self {
+        self.docs = docs;
+        self
+    }
+}
+
+pub(super) fn to_dot(graph: &TypeGraph, root: NodeId, options: &DotOptions) -> String {
+    let ids = graph.depth_first(root);
+    // `NodeId`s are long and meaningless so number the nodes in the order they're visited instead
+    let names = ids
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (*id, index))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut out = String::from("digraph {\n    node [shape=box];\n");
+
+    for (index, id) in ids.iter().enumerate() {
+        let Some(ty) = graph.get_type(*id) else { continue };
+        let mut label = escape(ty.type_name());
+        if options.docs && !ty.docs().is_empty() {
+            label.push_str("\\n");
+            for line in ty.docs() {
+                label.push_str("\\n");
+                label.push_str(&escape(line.trim()));
+            }
+        }
+        let _ = writeln!(out, "    n{index} [label=\"{label}\"];");
+    }
+
+    for id in &ids {
+        for edge in graph.edges_from(*id) {
+            let label = match edge.kind() {
+                EdgeKind::Field(key_path) => escape(&key_path.to_string()),
+                EdgeKind::Element => String::from("element"),
+                EdgeKind::MapKey => String::from("key"),
+                EdgeKind::MapValue => String::from("value"),
+                EdgeKind::Inner => String::from("inner"),
+            };
+            let _ = writeln!(
+                out,
+                "    n{} -> n{} [label=\"{label}\"];",
+                names[&edge.from()],
+                names[&edge.to()],
+            );
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
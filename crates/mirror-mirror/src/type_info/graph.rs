@@ -7,6 +7,7 @@ use core::any::type_name;
 use core::any::TypeId;
 use core::ops::Deref;
 
+use super::canonical;
 use super::*;
 use crate::Value;
 
@@ -23,7 +24,9 @@ impl NodeId {
         use core::hash::Hash;
         use core::hash::Hasher;
 
-        let mut hasher = ahash::AHasher::default();
+        // a stable hasher so ids don't depend on the hasher's random state. `TypeId`s themselves
+        // can change between compilations, so ids are only stable within one build
+        let mut hasher = crate::hash::StableHasher::default();
         TypeId::of::<T>().hash(&mut hasher);
         Self(hasher.finish())
     }
@@ -82,6 +85,221 @@ impl TypeGraph {
             }
         }
     }
+
+    /// A byte encoding of the graph that is the same for equal graphs, regardless of the
+    /// platform, the build, or the order in which types were added.
+    ///
+    /// Nodes are sorted by type name, with whitespace removed, and refer to each other by name
+    /// rather than by [`NodeId`], since those are derived from `TypeId`s.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        canonical::encode(self)
+    }
+
+    /// A hash of [`TypeGraph::canonical_bytes`] that is reproducible across builds and
+    /// platforms.
+    ///
+    /// Can be used to check that two programs agree on the shape of their types.
+    pub fn fingerprint(&self) -> u64 {
+        canonical::fingerprint(&self.canonical_bytes())
+    }
+
+    /// Find every type, variant, and field in the graph that has the metadata `key`.
+    ///
+    /// Matches are sorted by type name and, within a type, are in declaration order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::key_path;
+    /// use mirror_mirror::Reflect;
+    /// use mirror_mirror::Typed;
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Level {
+    ///     #[reflect(meta(asset_ref = true))]
+    ///     skybox: String,
+    ///     props: Vec<Prop>,
+    /// }
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// enum Prop {
+    ///     Mesh {
+    ///         #[reflect(meta(asset_ref = true))]
+    ///         path: String,
+    ///     },
+    ///     Light,
+    /// }
+    ///
+    /// let type_info = <Level as Typed>::type_info();
+    /// let asset_refs = type_info
+    ///     .find_meta("asset_ref")
+    ///     .into_iter()
+    ///     .map(|found| (found.type_name(), found.key_path().clone()))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     asset_refs,
+    ///     [
+    ///         (<Level as Typed>::type_info().type_name(), key_path!(.skybox)),
+    ///         (<Prop as Typed>::type_info().type_name(), key_path!(::Mesh.path)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn find_meta(&self, key: &str) -> Vec<MetaMatch<'_>> {
+        super::query::find_meta(self, key)
+    }
+
+    /// Get the type with the given id.
+    ///
+    /// Returns `None` if the id isn't in the graph, for example because it comes from a different
+    /// graph or a different build of the program.
+    pub fn get_type(&self, id: NodeId) -> Option<Type<'_>> {
+        match self.map.get(&id) {
+            Some(Some(_)) => Some(Type::new(id, self)),
+            _ => None,
+        }
+    }
+
+    /// Iterate over every type in the graph, with its id.
+    ///
+    /// Every type is yielded exactly once. Types are ordered by [`NodeId`], which is the same
+    /// every time a given build of the program runs but otherwise meaningless. Use
+    /// [`TypeGraph::depth_first`] or [`TypeGraph::topological_order`] for an order that follows
+    /// the references between types.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, Type<'_>)> + '_ {
+        self.map
+            .iter()
+            .filter(|(_, node)| node.is_some())
+            .map(|(id, _)| (*id, Type::new(*id, self)))
+    }
+
+    /// The references from the type with the given id to the types of its fields, elements, keys,
+    /// values, and inner type, in declaration order.
+    ///
+    /// A type referenced by several fields has one edge per field. Returns an empty `Vec` if the
+    /// id isn't in the graph.
+    pub fn edges_from(&self, id: NodeId) -> Vec<Edge> {
+        super::traverse::edges_from(self, id)
+    }
+
+    /// Iterate over the references between all types in the graph.
+    ///
+    /// This is [`TypeGraph::edges_from`] for every type, with the types ordered as in
+    /// [`TypeGraph::iter`]. Recursive types have edges that point back to themselves or to a type
+    /// that refers to them.
+    pub fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.map
+            .keys()
+            .flat_map(|id| super::traverse::edges_from(self, *id))
+    }
+
+    /// The ids of all types reachable from `root`, including `root` itself, in depth-first
+    /// pre-order.
+    ///
+    /// `root` comes first and every other type comes right after the first type found to refer
+    /// to it, with references followed in the order of [`TypeGraph::edges_from`]. Each type is
+    /// only included once, even if it's referenced several times or recursively, so cycles don't
+    /// make the search loop. Returns an empty `Vec` if `root` isn't in the graph.
+    pub fn depth_first(&self, root: NodeId) -> Vec<NodeId> {
+        let mut order = Vec::new();
+        super::traverse::depth_first(self, root, |id| order.push(id), |_| {});
+        order
+    }
+
+    /// The ids of all types reachable from `root`, including `root` itself, in depth-first
+    /// post-order, so children come before their parents.
+    ///
+    /// Every type comes after the types it refers to, which makes this the order to generate
+    /// definitions in. That isn't possible for recursive types. A reference that leads back to a
+    /// type that is still being searched is skipped, so the types in a cycle are ordered by when
+    /// the search finishes them, and the first type of the cycle that the search reached comes
+    /// after the others. Each type is only included once and `root` is always last. Returns an
+    /// empty `Vec` if `root` isn't in the graph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::Reflect;
+    /// use mirror_mirror::Typed;
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Inventory {
+    ///     items: Vec<Item>,
+    /// }
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Item {
+    ///     name: String,
+    /// }
+    ///
+    /// let type_info = <Inventory as Typed>::type_info();
+    /// let graph = type_info.graph();
+    /// let names = graph
+    ///     .topological_order(type_info.root_id())
+    ///     .into_iter()
+    ///     .map(|id| graph.get_type(id).unwrap().type_name())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     names,
+    ///     [
+    ///         <String as Typed>::type_info().type_name(),
+    ///         <Item as Typed>::type_info().type_name(),
+    ///         <Vec<Item> as Typed>::type_info().type_name(),
+    ///         <Inventory as Typed>::type_info().type_name(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn topological_order(&self, root: NodeId) -> Vec<NodeId> {
+        let mut order = Vec::new();
+        super::traverse::depth_first(self, root, |_| {}, |id| order.push(id));
+        order
+    }
+
+    /// Render `root` and every type reachable from it in the [DOT] format used by GraphViz.
+    ///
+    /// Types are nodes labeled with their names, and fields, elements, keys, values, and inner
+    /// types are edges. Field edges are labeled with the key path of the field, such as `.name` or
+    /// `::Variant.0`.
+    ///
+    /// Nodes are named `n0`, `n1`, and so on in the order of [`TypeGraph::depth_first`], so `root`
+    /// is always `n0` and the output is the same for the same types. Each type is one node, even
+    /// if it's referenced several times, and recursive types become cycles in the rendered graph.
+    /// Renders an empty graph if `root` isn't in the graph.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::type_info::DotOptions;
+    /// use mirror_mirror::Reflect;
+    /// use mirror_mirror::Typed;
+    ///
+    /// /// Something to hold.
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Item {
+    ///     weight: f32,
+    /// }
+    ///
+    /// let type_info = <Item as Typed>::type_info();
+    /// let dot = type_info.graph().to_dot(type_info.root_id());
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("n0 -> n1 [label=\".weight\"];"));
+    ///
+    /// let dot = type_info
+    ///     .graph()
+    ///     .to_dot_with(type_info.root_id(), &DotOptions::new().docs(true));
+    /// assert!(dot.contains("\\n\\nSomething to hold."));
+    /// ```
+    pub fn to_dot(&self, root: NodeId) -> String {
+        self.to_dot_with(root, &DotOptions::default())
+    }
+
+    /// Like [`TypeGraph::to_dot`] but with options for what to include.
+    pub fn to_dot_with(&self, root: NodeId, options: &DotOptions) -> String {
+        super::dot::to_dot(self, root, options)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -197,6 +415,7 @@ impl TupleStructNode {
 pub struct EnumNode {
     pub(super) type_name: String,
     pub(super) variants: Vec<VariantNode>,
+    pub(super) repr: Option<String>,
     pub(super) metadata: BTreeMap<String, Value>,
     pub(super) docs: Box<[String]>,
 }
@@ -213,10 +432,17 @@ impl EnumNode {
         Self {
             type_name: type_name::<T>().to_owned(),
             variants: variants.to_vec(),
+            repr: None,
             metadata: map_metadata(metadata),
             docs: map_docs(docs),
         }
     }
+
+    /// Record the integer type from the enum's `#[repr(...)]` attribute, such as `"u8"`.
+    pub fn with_repr(mut self, repr: &'static str) -> Self {
+        self.repr = Some(repr.to_owned());
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -235,6 +461,7 @@ pub struct StructVariantNode {
     pub(super) name: String,
     pub(super) fields: BTreeMap<String, NamedFieldNode>,
     pub(super) field_names: Box<[String]>,
+    pub(super) discriminant: Option<i128>,
     pub(super) metadata: BTreeMap<String, Value>,
     pub(super) docs: Box<[String]>,
 }
@@ -253,10 +480,16 @@ impl StructVariantNode {
                 .map(|field| (field.name.clone(), field.clone()))
                 .collect(),
             field_names: fields.iter().map(|field| field.name.clone()).collect(),
+            discriminant: None,
             metadata: map_metadata(metadata),
             docs: map_docs(docs),
         }
     }
+
+    pub fn with_discriminant(mut self, discriminant: i128) -> Self {
+        self.discriminant = Some(discriminant);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -265,6 +498,7 @@ impl StructVariantNode {
 pub struct TupleVariantNode {
     pub(super) name: String,
     pub(super) fields: Vec<UnnamedFieldNode>,
+    pub(super) discriminant: Option<i128>,
     pub(super) metadata: BTreeMap<String, Value>,
     pub(super) docs: Box<[String]>,
 }
@@ -279,10 +513,16 @@ impl TupleVariantNode {
         Self {
             name: name.to_owned(),
             fields: fields.to_vec(),
+            discriminant: None,
             metadata: map_metadata(metadata),
             docs: map_docs(docs),
         }
     }
+
+    pub fn with_discriminant(mut self, discriminant: i128) -> Self {
+        self.discriminant = Some(discriminant);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -290,6 +530,7 @@ impl TupleVariantNode {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnitVariantNode {
     pub(super) name: String,
+    pub(super) discriminant: Option<i128>,
     pub(super) metadata: BTreeMap<String, Value>,
     pub(super) docs: Box<[String]>,
 }
@@ -302,10 +543,16 @@ impl UnitVariantNode {
     ) -> Self {
         Self {
             name: name.to_owned(),
+            discriminant: None,
             metadata: map_metadata(metadata),
             docs: map_docs(docs),
         }
     }
+
+    pub fn with_discriminant(mut self, discriminant: i128) -> Self {
+        self.discriminant = Some(discriminant);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -344,6 +591,7 @@ pub struct NamedFieldNode {
     pub(super) id: NodeId,
     pub(super) metadata: BTreeMap<String, Value>,
     pub(super) docs: Box<[String]>,
+    pub(super) default_value: Option<Value>,
 }
 
 impl NamedFieldNode {
@@ -361,8 +609,14 @@ impl NamedFieldNode {
             id: T::build(graph),
             metadata: map_metadata(metadata),
             docs: map_docs(docs),
+            default_value: None,
         }
     }
+
+    pub fn with_default_value(mut self, value: Value) -> Self {
+        self.default_value = Some(value);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -372,6 +626,7 @@ pub struct UnnamedFieldNode {
     pub(super) id: NodeId,
     pub(super) metadata: BTreeMap<String, Value>,
     pub(super) docs: Box<[String]>,
+    pub(super) default_value: Option<Value>,
 }
 
 impl UnnamedFieldNode {
@@ -387,8 +642,14 @@ impl UnnamedFieldNode {
             id: T::build(graph),
             metadata: map_metadata(metadata),
             docs: map_docs(docs),
+            default_value: None,
         }
     }
+
+    pub fn with_default_value(mut self, value: Value) -> Self {
+        self.default_value = Some(value);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -507,6 +768,7 @@ scalar_typed! {
 pub struct OpaqueNode {
     pub(super) type_name: String,
     pub(super) metadata: BTreeMap<String, Value>,
+    pub(super) inner_type_id: Option<NodeId>,
 }
 
 impl OpaqueNode {
@@ -517,6 +779,20 @@ impl OpaqueNode {
         Self {
             type_name: type_name::<T>().to_owned(),
             metadata: map_metadata(metadata),
+            inner_type_id: None,
         }
     }
+
+    /// Declare the type that the opaque type logically wraps, such as `T` for a handle to a
+    /// shared `T`.
+    ///
+    /// Type level queries, like [`GetTypePath::type_at`], then continue into `I` rather than
+    /// stopping at the opaque type. Values of the opaque type still can't be inspected.
+    pub fn with_inner_type<I>(mut self, graph: &mut TypeGraph) -> Self
+    where
+        I: Typed,
+    {
+        self.inner_type_id = Some(I::build(graph));
+        self
+    }
 }
@@ -20,7 +20,18 @@ use crate::FromReflect;
 use crate::Reflect;
 use crate::Value;
 
+mod canonical;
+mod dot;
 pub mod graph;
+pub mod overlay;
+mod query;
+mod traverse;
+
+pub use self::dot::DotOptions;
+pub use self::overlay::MetadataOverlay;
+pub use self::query::MetaMatch;
+pub use self::traverse::Edge;
+pub use self::traverse::EdgeKind;
 
 pub trait Typed: 'static {
     fn type_info() -> TypeRoot {
@@ -45,6 +56,16 @@ impl TypeRoot {
         Type::new(self.root, &self.graph)
     }
 
+    /// The id of the root type in [`TypeRoot::graph`].
+    pub fn root_id(&self) -> NodeId {
+        self.root
+    }
+
+    /// The graph containing the root type and every type it refers to.
+    pub fn graph(&self) -> &TypeGraph {
+        &self.graph
+    }
+
     pub fn type_name(&self) -> &str {
         self.get_type().type_name()
     }
@@ -53,6 +74,28 @@ impl TypeRoot {
         self.get_type().default_value()
     }
 
+    /// Like [`TypeGraph::canonical_bytes`] but also encodes which type is the root.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let root = canonical::normalized_name(self.graph.get(self.root));
+        let mut bytes = Vec::new();
+        bytes.extend((root.len() as u64).to_le_bytes());
+        bytes.extend(root.as_bytes());
+        bytes.extend(self.graph.canonical_bytes());
+        bytes
+    }
+
+    /// Like [`TypeGraph::fingerprint`] but also takes into account which type is the root.
+    pub fn fingerprint(&self) -> u64 {
+        canonical::fingerprint(&self.canonical_bytes())
+    }
+
+    /// Find every type, variant, and field in the graph that has the metadata `key`.
+    ///
+    /// See [`TypeGraph::find_meta`].
+    pub fn find_meta(&self, key: &str) -> Vec<MetaMatch<'_>> {
+        self.graph.find_meta(key)
+    }
+
     pub fn as_struct(&self) -> Option<StructType<'_>> {
         self.get_type().as_struct()
     }
@@ -206,6 +249,20 @@ impl<'a> Type<'a> {
         }
     }
 
+    pub fn kind(self) -> TypeKind {
+        match self {
+            Type::Struct(_) => TypeKind::Struct,
+            Type::TupleStruct(_) => TypeKind::TupleStruct,
+            Type::Tuple(_) => TypeKind::Tuple,
+            Type::Enum(_) => TypeKind::Enum,
+            Type::List(_) => TypeKind::List,
+            Type::Array(_) => TypeKind::Array,
+            Type::Map(_) => TypeKind::Map,
+            Type::Scalar(_) => TypeKind::Scalar,
+            Type::Opaque(_) => TypeKind::Opaque,
+        }
+    }
+
     fn into_type_info_at_path(self) -> TypeAtPath<'a> {
         match self {
             Type::Struct(inner) => inner.into_type_info_at_path(),
@@ -225,47 +282,25 @@ impl<'a> Type<'a> {
             Type::Struct(struct_) => {
                 let mut value = StructValue::new();
                 for field in struct_.field_types() {
-                    value.set_field(field.name(), field.get_type().default_value()?);
+                    value.set_field(field.name(), field.default_value()?);
                 }
                 value.to_value()
             }
             Type::TupleStruct(tuple_struct) => {
                 let mut value = TupleStructValue::new();
                 for field in tuple_struct.field_types() {
-                    value.push_field(field.get_type().default_value()?);
+                    value.push_field(field.default_value()?);
                 }
                 value.to_value()
             }
             Type::Tuple(tuple) => {
                 let mut value = TupleValue::new();
                 for field in tuple.field_types() {
-                    value.push_field(field.get_type().default_value()?);
+                    value.push_field(field.default_value()?);
                 }
                 value.to_value()
             }
-            Type::Enum(enum_) => {
-                let mut variants = enum_.variants();
-                let variant = variants.next()?;
-                match variant {
-                    Variant::Struct(variant) => {
-                        let mut value = EnumValue::new_struct_variant(variant.name());
-                        for field in variant.field_types() {
-                            value.set_struct_field(field.name(), field.get_type().default_value()?);
-                        }
-                        value.finish().to_value()
-                    }
-                    Variant::Tuple(variant) => {
-                        let mut value = EnumValue::new_tuple_variant(variant.name());
-                        for field in variant.field_types() {
-                            value.push_tuple_field(field.get_type().default_value()?);
-                        }
-                        value.finish().to_value()
-                    }
-                    Variant::Unit(variant) => {
-                        EnumValue::new_unit_variant(variant.name()).to_value()
-                    }
-                }
-            }
+            Type::Enum(enum_) => enum_.variants().next()?.default_value()?,
             Type::List(_) => Vec::<()>::new().to_value(),
             Type::Array(_) => <[(); 0] as Reflect>::to_value(&[]),
             Type::Map(_) => BTreeMap::<(), ()>::new().to_value(),
@@ -464,6 +499,9 @@ mod private {
 pub trait GetMeta<'a>: private::Sealed {
     fn meta(self, key: &str) -> Option<&'a dyn Reflect>;
 
+    /// Get the metadata at `key` converted to `T`.
+    ///
+    /// Returns `None` if there is no metadata at `key` or if it isn't a `T`.
     fn get_meta<T>(self, key: &str) -> Option<T>
     where
         T: FromReflect,
@@ -472,9 +510,42 @@ pub trait GetMeta<'a>: private::Sealed {
         T::from_reflect(self.meta(key)?)
     }
 
+    fn has_meta(self, key: &str) -> bool
+    where
+        Self: Sized,
+    {
+        self.meta(key).is_some()
+    }
+
+    /// Iterate over all the metadata, sorted by key.
+    fn meta_entries(self) -> MetaEntries<'a>;
+
     fn docs(self) -> &'a [String];
 }
 
+/// Iterator over metadata entries. Returned by [`GetMeta::meta_entries`].
+#[derive(Debug, Clone, Default)]
+pub struct MetaEntries<'a> {
+    iter: Option<alloc::collections::btree_map::Iter<'a, String, Value>>,
+}
+
+impl<'a> MetaEntries<'a> {
+    fn new(metadata: &'a BTreeMap<String, Value>) -> Self {
+        Self {
+            iter: Some(metadata.iter()),
+        }
+    }
+}
+
+impl<'a> Iterator for MetaEntries<'a> {
+    type Item = (&'a str, &'a dyn Reflect);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.as_mut()?.next()?;
+        Some((key, value.as_reflect()))
+    }
+}
+
 impl<'a> GetMeta<'a> for Type<'a> {
     fn meta(self, key: &str) -> Option<&'a dyn Reflect> {
         match self {
@@ -488,6 +559,18 @@ impl<'a> GetMeta<'a> for Type<'a> {
         }
     }
 
+    fn meta_entries(self) -> MetaEntries<'a> {
+        match self {
+            Type::Struct(inner) => inner.meta_entries(),
+            Type::TupleStruct(inner) => inner.meta_entries(),
+            Type::Enum(inner) => inner.meta_entries(),
+            Type::Opaque(inner) => inner.meta_entries(),
+            Type::Tuple(_) | Type::List(_) | Type::Array(_) | Type::Map(_) | Type::Scalar(_) => {
+                MetaEntries::default()
+            }
+        }
+    }
+
     fn docs(self) -> &'a [String] {
         match self {
             Type::Struct(inner) => inner.docs(),
@@ -511,6 +594,10 @@ macro_rules! impl_get_meta {
                     Some(self.node.metadata.get(key)?.as_reflect())
                 }
 
+                fn meta_entries(self) -> MetaEntries<'a> {
+                    MetaEntries::new(&self.node.metadata)
+                }
+
                 fn docs(self) -> &'a [String] {
                     &self.node.docs
                 }
@@ -535,6 +622,10 @@ impl<'a> GetMeta<'a> for OpaqueType<'a> {
         Some(self.node.metadata.get(key)?.as_reflect())
     }
 
+    fn meta_entries(self) -> MetaEntries<'a> {
+        MetaEntries::new(&self.node.metadata)
+    }
+
     fn docs(self) -> &'a [String] {
         &[]
     }
@@ -601,11 +692,12 @@ impl<'a> StructType<'a> {
         &self.node.type_name
     }
 
+    /// The fields in declaration order.
     pub fn field_types(self) -> impl Iterator<Item = NamedField<'a>> {
-        self.node.fields.values().map(|node| NamedField {
-            node,
-            graph: self.graph,
-        })
+        self.node
+            .field_names
+            .iter()
+            .filter_map(move |name| self.field_type(name))
     }
 
     pub fn field_type(self, name: &str) -> Option<NamedField<'a>> {
@@ -744,6 +836,25 @@ impl<'a> EnumType<'a> {
         self.variants().find(|variant| variant.name() == name)
     }
 
+    /// The integer type from the enum's `#[repr(...)]` attribute, such as `"u8"`, if it has one.
+    pub fn repr(self) -> Option<&'a str> {
+        self.node.repr.as_deref()
+    }
+
+    pub fn variant_by_discriminant(self, discriminant: i128) -> Option<Variant<'a>> {
+        self.variants()
+            .find(|variant| variant.discriminant() == Some(discriminant))
+    }
+
+    pub fn variant_name_for_discriminant(self, discriminant: i128) -> Option<&'a str> {
+        self.variant_by_discriminant(discriminant)
+            .map(|variant| variant.name())
+    }
+
+    pub fn discriminant_for_variant_name(self, name: &str) -> Option<i128> {
+        self.variant(name)?.discriminant()
+    }
+
     fn into_type_info_at_path(self) -> TypeAtPath<'a> {
         TypeAtPath::Enum(self)
     }
@@ -772,6 +883,17 @@ impl<'a> Variant<'a> {
         }
     }
 
+    /// The variant's discriminant.
+    ///
+    /// Only available if the enum has an integer `#[repr(...)]` or explicit discriminants.
+    pub fn discriminant(self) -> Option<i128> {
+        match self {
+            Variant::Struct(inner) => inner.discriminant(),
+            Variant::Tuple(inner) => inner.discriminant(),
+            Variant::Unit(inner) => inner.discriminant(),
+        }
+    }
+
     pub fn field_types(self) -> impl Iterator<Item = VariantField<'a>> {
         match self {
             Variant::Struct(inner) => Box::new(inner.field_types().map(VariantField::Named))
@@ -796,6 +918,30 @@ impl<'a> Variant<'a> {
         }
     }
 
+    /// The value of this variant with all fields set to their default values.
+    ///
+    /// Returns `None` if any field doesn't have a default value.
+    pub fn default_value(self) -> Option<Value> {
+        let value = match self {
+            Variant::Struct(variant) => {
+                let mut value = EnumValue::new_struct_variant(variant.name());
+                for field in variant.field_types() {
+                    value.set_struct_field(field.name(), field.default_value()?);
+                }
+                value.finish().to_value()
+            }
+            Variant::Tuple(variant) => {
+                let mut value = EnumValue::new_tuple_variant(variant.name());
+                for field in variant.field_types() {
+                    value.push_tuple_field(field.default_value()?);
+                }
+                value.finish().to_value()
+            }
+            Variant::Unit(variant) => EnumValue::new_unit_variant(variant.name()).to_value(),
+        };
+        Some(value)
+    }
+
     pub fn enum_type(self) -> EnumType<'a> {
         match self {
             Variant::Struct(inner) => inner.enum_type(),
@@ -818,6 +964,14 @@ impl<'a> GetMeta<'a> for Variant<'a> {
         }
     }
 
+    fn meta_entries(self) -> MetaEntries<'a> {
+        match self {
+            Variant::Struct(inner) => inner.meta_entries(),
+            Variant::Tuple(inner) => inner.meta_entries(),
+            Variant::Unit(inner) => inner.meta_entries(),
+        }
+    }
+
     fn docs(self) -> &'a [String] {
         match self {
             Variant::Struct(inner) => inner.docs(),
@@ -847,6 +1001,13 @@ impl<'a> VariantField<'a> {
             VariantField::Unnamed(_) => None,
         }
     }
+
+    pub fn default_value(self) -> Option<Value> {
+        match self {
+            VariantField::Named(inner) => inner.default_value(),
+            VariantField::Unnamed(inner) => inner.default_value(),
+        }
+    }
 }
 
 impl<'a> GetMeta<'a> for VariantField<'a> {
@@ -857,6 +1018,13 @@ impl<'a> GetMeta<'a> for VariantField<'a> {
         }
     }
 
+    fn meta_entries(self) -> MetaEntries<'a> {
+        match self {
+            VariantField::Named(inner) => inner.meta_entries(),
+            VariantField::Unnamed(inner) => inner.meta_entries(),
+        }
+    }
+
     fn docs(self) -> &'a [String] {
         match self {
             VariantField::Named(inner) => inner.docs(),
@@ -877,11 +1045,16 @@ impl<'a> StructVariant<'a> {
         &self.node.name
     }
 
+    pub fn discriminant(self) -> Option<i128> {
+        self.node.discriminant
+    }
+
+    /// The fields in declaration order.
     pub fn field_types(self) -> impl Iterator<Item = NamedField<'a>> {
-        self.node.fields.values().map(|node| NamedField {
-            node,
-            graph: self.graph,
-        })
+        self.node
+            .field_names
+            .iter()
+            .filter_map(move |name| self.field_type(name))
     }
 
     pub fn field_type(self, name: &str) -> Option<NamedField<'a>> {
@@ -917,6 +1090,10 @@ impl<'a> TupleVariant<'a> {
         &self.node.name
     }
 
+    pub fn discriminant(self) -> Option<i128> {
+        self.node.discriminant
+    }
+
     pub fn field_types(self) -> impl Iterator<Item = UnnamedField<'a>> {
         self.node.fields.iter().map(|node| UnnamedField {
             node,
@@ -952,6 +1129,10 @@ impl<'a> UnitVariant<'a> {
         &self.node.name
     }
 
+    pub fn discriminant(self) -> Option<i128> {
+        self.node.discriminant
+    }
+
     pub fn enum_type(self) -> EnumType<'a> {
         EnumType {
             node: self.enum_node,
@@ -971,6 +1152,27 @@ impl<'a> UnnamedField<'a> {
         Type::new(self.node.id, self.graph)
     }
 
+    /// The default value of the field.
+    ///
+    /// This is [`UnnamedField::declared_default_value`], if any, otherwise the default value of
+    /// the field's type.
+    pub fn default_value(self) -> Option<Value> {
+        match &self.node.default_value {
+            Some(value) => Some(value.clone()),
+            None => self.get_type().default_value(),
+        }
+    }
+
+    /// The default value recorded for the field by `#[derive(Reflect)]`, without falling back to
+    /// the default value of the field's type.
+    ///
+    /// Fields record a default with `#[reflect(default)]`, which uses the `Default`
+    /// implementation of the field's type, or `#[reflect(default = ...)]`. Every field of a struct
+    /// with `#[reflect(opt_in(Default))]` records one.
+    pub fn declared_default_value(self) -> Option<&'a Value> {
+        self.node.default_value.as_ref()
+    }
+
     fn into_type_info_at_path(self) -> TypeAtPath<'a> {
         self.get_type().into_type_info_at_path()
     }
@@ -991,6 +1193,27 @@ impl<'a> NamedField<'a> {
         Type::new(self.node.id, self.graph)
     }
 
+    /// The default value of the field.
+    ///
+    /// This is [`NamedField::declared_default_value`], if any, otherwise the default value of
+    /// the field's type.
+    pub fn default_value(self) -> Option<Value> {
+        match &self.node.default_value {
+            Some(value) => Some(value.clone()),
+            None => self.get_type().default_value(),
+        }
+    }
+
+    /// The default value recorded for the field by `#[derive(Reflect)]`, without falling back to
+    /// the default value of the field's type.
+    ///
+    /// Fields record a default with `#[reflect(default)]`, which uses the `Default`
+    /// implementation of the field's type, or `#[reflect(default = ...)]`. Every field of a struct
+    /// with `#[reflect(opt_in(Default))]` records one.
+    pub fn declared_default_value(self) -> Option<&'a Value> {
+        self.node.default_value.as_ref()
+    }
+
     fn into_type_info_at_path(self) -> TypeAtPath<'a> {
         self.get_type().into_type_info_at_path()
     }
@@ -1100,6 +1323,13 @@ impl<'a> OpaqueType<'a> {
         &self.node.type_name
     }
 
+    /// The type this opaque type wraps, if it declared one with
+    /// [`OpaqueNode::with_inner_type`].
+    pub fn inner_type(self) -> Option<Type<'a>> {
+        let id = self.node.inner_type_id?;
+        Some(Type::new(id, self.graph))
+    }
+
     fn into_type_info_at_path(self) -> TypeAtPath<'a> {
         TypeAtPath::Opaque(self)
     }
@@ -1126,15 +1356,31 @@ pub enum TypeAtPath<'a> {
     Opaque(OpaqueType<'a>),
 }
 
+/// The kind of a [`Type`] or [`TypeAtPath`], without any of the associated type information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypeKind {
+    Struct,
+    TupleStruct,
+    Tuple,
+    Enum,
+    /// Only produced by [`TypeAtPath`], when a key path ends at an enum variant.
+    Variant,
+    List,
+    Array,
+    Map,
+    Scalar,
+    Opaque,
+}
+
 impl<'a> GetMeta<'a> for TypeAtPath<'a> {
     fn meta(self, key: &str) -> Option<&'a dyn Reflect> {
         match self {
             TypeAtPath::Struct(inner) => inner.meta(key),
             TypeAtPath::TupleStruct(inner) => inner.meta(key),
             TypeAtPath::Enum(inner) => inner.meta(key),
+            TypeAtPath::Variant(inner) => inner.meta(key),
             TypeAtPath::Opaque(inner) => inner.meta(key),
-            TypeAtPath::Variant(_)
-            | TypeAtPath::Tuple(_)
+            TypeAtPath::Tuple(_)
             | TypeAtPath::List(_)
             | TypeAtPath::Array(_)
             | TypeAtPath::Map(_)
@@ -1142,13 +1388,28 @@ impl<'a> GetMeta<'a> for TypeAtPath<'a> {
         }
     }
 
+    fn meta_entries(self) -> MetaEntries<'a> {
+        match self {
+            TypeAtPath::Struct(inner) => inner.meta_entries(),
+            TypeAtPath::TupleStruct(inner) => inner.meta_entries(),
+            TypeAtPath::Enum(inner) => inner.meta_entries(),
+            TypeAtPath::Variant(inner) => inner.meta_entries(),
+            TypeAtPath::Opaque(inner) => inner.meta_entries(),
+            TypeAtPath::Tuple(_)
+            | TypeAtPath::List(_)
+            | TypeAtPath::Array(_)
+            | TypeAtPath::Map(_)
+            | TypeAtPath::Scalar(_) => MetaEntries::default(),
+        }
+    }
+
     fn docs(self) -> &'a [String] {
         match self {
             TypeAtPath::Struct(inner) => inner.docs(),
             TypeAtPath::TupleStruct(inner) => inner.docs(),
             TypeAtPath::Enum(inner) => inner.docs(),
-            TypeAtPath::Variant(_)
-            | TypeAtPath::Tuple(_)
+            TypeAtPath::Variant(inner) => inner.docs(),
+            TypeAtPath::Tuple(_)
             | TypeAtPath::List(_)
             | TypeAtPath::Array(_)
             | TypeAtPath::Map(_)
@@ -1159,6 +1420,21 @@ impl<'a> GetMeta<'a> for TypeAtPath<'a> {
 }
 
 impl<'a> TypeAtPath<'a> {
+    pub fn kind(self) -> TypeKind {
+        match self {
+            Self::Struct(_) => TypeKind::Struct,
+            Self::TupleStruct(_) => TypeKind::TupleStruct,
+            Self::Tuple(_) => TypeKind::Tuple,
+            Self::Enum(_) => TypeKind::Enum,
+            Self::Variant(_) => TypeKind::Variant,
+            Self::List(_) => TypeKind::List,
+            Self::Array(_) => TypeKind::Array,
+            Self::Map(_) => TypeKind::Map,
+            Self::Scalar(_) => TypeKind::Scalar,
+            Self::Opaque(_) => TypeKind::Opaque,
+        }
+    }
+
     pub fn as_struct(self) -> Option<StructType<'a>> {
         match self {
             Self::Struct(inner) => Some(inner),
@@ -1238,6 +1514,19 @@ impl<'a> GetTypePath<'a> for TypeAtPath<'a> {
         ) -> Option<TypeAtPath<'a>> {
             let head = stack.next()?;
 
+            // opaque types that wrap another type are navigated as that type. Without a cycle
+            // every step reaches a new type, so more steps than there are types means the inner
+            // types loop back on themselves and never reach anything navigable
+            let mut type_info = type_info;
+            let mut steps = 0;
+            while let TypeAtPath::Opaque(opaque) = type_info {
+                if steps > opaque.graph.map.len() {
+                    return None;
+                }
+                steps += 1;
+                type_info = opaque.inner_type()?.into_type_info_at_path();
+            }
+
             let value_at_key: TypeAtPath<'_> = match head {
                 // .foo
                 Key::Field(KeyOrIndex::Key(key)) => match type_info {
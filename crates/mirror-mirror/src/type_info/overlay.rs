@@ -0,0 +1,183 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use super::graph::TypeNode;
+use super::graph::VariantNode;
+use super::TypeRoot;
+use crate::key_path::Key;
+use crate::key_path::KeyOrIndex;
+use crate::key_path::KeyPath;
+use crate::Value;
+
+/// Metadata that overrides the metadata set with `#[reflect(meta(...))]`.
+///
+/// Entries are keyed by a type name and a key path relative to that type. The key path selects
+/// what the metadata is attached to:
+///
+/// - An empty key path selects the type itself.
+/// - `.field` or `.0` selects a field of a struct or tuple struct.
+/// - `::Variant` selects an enum variant.
+/// - `::Variant.field` or `::Variant.0` selects a field of an enum variant.
+///
+/// This allows tools such as editors to customize things like ranges or labels at runtime,
+/// without recompiling the crate that defines the types.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::key_path;
+/// use mirror_mirror::type_info::GetMeta;
+/// use mirror_mirror::type_info::MetadataOverlay;
+/// use mirror_mirror::Reflect;
+/// use mirror_mirror::Typed;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Light {
+///     #[reflect(meta(max = 1.0))]
+///     intensity: f32,
+/// }
+///
+/// let mut overlay = MetadataOverlay::new();
+/// overlay.insert(
+///     <Light as Typed>::type_info().type_name(),
+///     key_path!(.intensity),
+///     "max",
+///     10.0,
+/// );
+///
+/// let mut type_info = <Light as Typed>::type_info();
+/// overlay.apply(&mut type_info);
+///
+/// let intensity = type_info.as_struct().unwrap().field_type("intensity").unwrap();
+/// assert_eq!(intensity.get_meta::<f64>("max").unwrap(), 10.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MetadataOverlay {
+    types: BTreeMap<String, BTreeMap<KeyPath, BTreeMap<String, Value>>>,
+}
+
+impl MetadataOverlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the metadata `key` for the type or field at `key_path` in `type_name`.
+    ///
+    /// Returns the previous value, if any.
+    pub fn insert(
+        &mut self,
+        type_name: impl Into<String>,
+        key_path: KeyPath,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Option<Value> {
+        self.types
+            .entry(type_name.into())
+            .or_default()
+            .entry(key_path)
+            .or_default()
+            .insert(key.into(), value.into())
+    }
+
+    pub fn with(
+        mut self,
+        type_name: impl Into<String>,
+        key_path: KeyPath,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Self {
+        self.insert(type_name, key_path, key, value);
+        self
+    }
+
+    pub fn get(&self, type_name: &str, key_path: &KeyPath, key: &str) -> Option<&Value> {
+        self.types.get(type_name)?.get(key_path)?.get(key)
+    }
+
+    pub fn remove(&mut self, type_name: &str, key_path: &KeyPath, key: &str) -> Option<Value> {
+        self.types
+            .get_mut(type_name)?
+            .get_mut(key_path)?
+            .remove(key)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types
+            .values()
+            .flat_map(|paths| paths.values())
+            .all(|metadata| metadata.is_empty())
+    }
+
+    /// Copy the overlay's metadata into `type_info`, replacing metadata with the same keys.
+    ///
+    /// Entries for types that aren't part of `type_info`, or whose key path doesn't select a type,
+    /// variant, or field, are ignored.
+    pub fn apply(&self, type_info: &mut TypeRoot) {
+        for node in type_info.graph.map.values_mut().flatten() {
+            let Some(type_name) = type_name(node) else { continue };
+            let Some(paths) = self.types.get(type_name) else { continue };
+            for (key_path, metadata) in paths {
+                let Some(target) = metadata_mut(node, &key_path.path) else { continue };
+                target.extend(
+                    metadata
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value.clone())),
+                );
+            }
+        }
+    }
+}
+
+/// The name of the types that can have metadata.
+fn type_name(node: &TypeNode) -> Option<&str> {
+    match node {
+        TypeNode::Struct(node) => Some(&node.type_name),
+        TypeNode::TupleStruct(node) => Some(&node.type_name),
+        TypeNode::Enum(node) => Some(&node.type_name),
+        TypeNode::Opaque(node) => Some(&node.type_name),
+        TypeNode::Tuple(_)
+        | TypeNode::List(_)
+        | TypeNode::Array(_)
+        | TypeNode::Map(_)
+        | TypeNode::Scalar(_) => None,
+    }
+}
+
+fn metadata_mut<'a>(
+    node: &'a mut TypeNode,
+    path: &[Key],
+) -> Option<&'a mut BTreeMap<String, Value>> {
+    let metadata = match (node, path) {
+        (TypeNode::Struct(node), []) => &mut node.metadata,
+        (TypeNode::TupleStruct(node), []) => &mut node.metadata,
+        (TypeNode::Enum(node), []) => &mut node.metadata,
+        (TypeNode::Opaque(node), []) => &mut node.metadata,
+        (TypeNode::Struct(node), [Key::Field(KeyOrIndex::Key(name))]) => {
+            &mut node.fields.get_mut(name)?.metadata
+        }
+        (TypeNode::TupleStruct(node), [Key::Field(KeyOrIndex::Index(index))]) => {
+            &mut node.fields.get_mut(*index)?.metadata
+        }
+        (TypeNode::Enum(node), [Key::Variant(name), rest @ ..]) => {
+            let variant = node.variants.iter_mut().find(|variant| match variant {
+                VariantNode::Struct(variant) => variant.name == *name,
+                VariantNode::Tuple(variant) => variant.name == *name,
+                VariantNode::Unit(variant) => variant.name == *name,
+            })?;
+            match (variant, rest) {
+                (VariantNode::Struct(variant), []) => &mut variant.metadata,
+                (VariantNode::Tuple(variant), []) => &mut variant.metadata,
+                (VariantNode::Unit(variant), []) => &mut variant.metadata,
+                (VariantNode::Struct(variant), [Key::Field(KeyOrIndex::Key(name))]) => {
+                    &mut variant.fields.get_mut(name)?.metadata
+                }
+                (VariantNode::Tuple(variant), [Key::Field(KeyOrIndex::Index(index))]) => {
+                    &mut variant.fields.get_mut(*index)?.metadata
+                }
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some(metadata)
+}
@@ -0,0 +1,121 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::graph::TypeGraph;
+use super::graph::TypeNode;
+use super::graph::VariantNode;
+use super::Type;
+use crate::key_path;
+use crate::key_path::KeyPath;
+use crate::Reflect;
+use crate::Value;
+
+/// A type, variant, or field that has some metadata. Returned by [`TypeGraph::find_meta`].
+///
+/// What has the metadata is given by a key path relative to the type, using the same
+/// conventions as [`MetadataOverlay`](super::MetadataOverlay):
+///
+/// - An empty key path is the type itself.
+/// - `.field` or `.0` is a field of a struct or tuple struct.
+/// - `::Variant` is an enum variant.
+/// - `::Variant.field` or `::Variant.0` is a field of an enum variant.
+#[derive(Debug, Clone)]
+pub struct MetaMatch<'a> {
+    ty: Type<'a>,
+    key_path: KeyPath,
+    value: &'a dyn Reflect,
+}
+
+impl<'a> MetaMatch<'a> {
+    /// The type that owns the type, variant, or field with the metadata.
+    pub fn get_type(&self) -> Type<'a> {
+        self.ty
+    }
+
+    pub fn type_name(&self) -> &'a str {
+        self.ty.type_name()
+    }
+
+    /// The key path, relative to [`MetaMatch::get_type`], of what has the metadata.
+    pub fn key_path(&self) -> &KeyPath {
+        &self.key_path
+    }
+
+    /// The value of the metadata.
+    pub fn value(&self) -> &'a dyn Reflect {
+        self.value
+    }
+}
+
+pub(super) fn find_meta<'a>(graph: &'a TypeGraph, key: &str) -> Vec<MetaMatch<'a>> {
+    let mut matches = Vec::new();
+
+    for (id, node) in &graph.map {
+        let Some(node) = node else { continue };
+        let ty = Type::new(*id, graph);
+        let mut push = |key_path: KeyPath, metadata: &'a BTreeMap<String, Value>| {
+            if let Some(value) = metadata.get(key) {
+                matches.push(MetaMatch {
+                    ty,
+                    key_path,
+                    value: value.as_reflect(),
+                });
+            }
+        };
+
+        match node {
+            TypeNode::Struct(node) => {
+                push(key_path!(), &node.metadata);
+                for name in node.field_names.iter() {
+                    if let Some(field) = node.fields.get(name) {
+                        push(key_path::field(&**name), &field.metadata);
+                    }
+                }
+            }
+            TypeNode::TupleStruct(node) => {
+                push(key_path!(), &node.metadata);
+                for (index, field) in node.fields.iter().enumerate() {
+                    push(key_path::field(index), &field.metadata);
+                }
+            }
+            TypeNode::Enum(node) => {
+                push(key_path!(), &node.metadata);
+                for variant in &node.variants {
+                    match variant {
+                        VariantNode::Struct(variant) => {
+                            let variant_path = key_path::variant(&*variant.name);
+                            push(variant_path.clone(), &variant.metadata);
+                            for name in variant.field_names.iter() {
+                                if let Some(field) = variant.fields.get(name) {
+                                    push(variant_path.clone().field(&**name), &field.metadata);
+                                }
+                            }
+                        }
+                        VariantNode::Tuple(variant) => {
+                            let variant_path = key_path::variant(&*variant.name);
+                            push(variant_path.clone(), &variant.metadata);
+                            for (index, field) in variant.fields.iter().enumerate() {
+                                push(variant_path.clone().field(index), &field.metadata);
+                            }
+                        }
+                        VariantNode::Unit(variant) => {
+                            push(key_path::variant(&*variant.name), &variant.metadata);
+                        }
+                    }
+                }
+            }
+            TypeNode::Opaque(node) => push(key_path!(), &node.metadata),
+            TypeNode::Tuple(_)
+            | TypeNode::List(_)
+            | TypeNode::Array(_)
+            | TypeNode::Map(_)
+            | TypeNode::Scalar(_) => {}
+        }
+    }
+
+    // nodes are keyed by `NodeId`s which aren't stable so sort to get the same order every time.
+    // The sort is stable so matches within a type stay in declaration order
+    matches.sort_by(|a, b| a.type_name().cmp(b.type_name()));
+    matches
+}
@@ -0,0 +1,160 @@
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::graph::NodeId;
+use super::graph::TypeGraph;
+use super::graph::TypeNode;
+use super::graph::VariantNode;
+use crate::key_path;
+use crate::key_path::KeyPath;
+
+/// A reference from one type in a [`TypeGraph`] to another. Returned by [`TypeGraph::edges`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    from: NodeId,
+    to: NodeId,
+    kind: EdgeKind,
+}
+
+impl Edge {
+    /// The type that refers to [`Edge::to`].
+    pub fn from(&self) -> NodeId {
+        self.from
+    }
+
+    /// The type being referred to.
+    pub fn to(&self) -> NodeId {
+        self.to
+    }
+
+    pub fn kind(&self) -> &EdgeKind {
+        &self.kind
+    }
+}
+
+/// How a type refers to another type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// A field of a struct, tuple struct, tuple, or enum variant.
+    ///
+    /// The key path is relative to the type and uses the same conventions as
+    /// [`MetaMatch::key_path`](super::MetaMatch::key_path), so `.field`, `.0`, or
+    /// `::Variant.field`.
+    Field(KeyPath),
+    /// The element type of a list or array.
+    Element,
+    /// The key type of a map.
+    MapKey,
+    /// The value type of a map.
+    MapValue,
+    /// The inner type of an opaque type. See
+    /// [`OpaqueType::inner_type`](super::OpaqueType::inner_type).
+    Inner,
+}
+
+pub(super) fn edges_from(graph: &TypeGraph, id: NodeId) -> Vec<Edge> {
+    let Some(Some(node)) = graph.map.get(&id) else { return Vec::new() };
+
+    let edge = |to: NodeId, kind: EdgeKind| Edge { from: id, to, kind };
+
+    match node {
+        TypeNode::Struct(node) => node
+            .field_names
+            .iter()
+            .filter_map(|name| {
+                let field = node.fields.get(name)?;
+                Some(edge(field.id, EdgeKind::Field(key_path::field(&**name))))
+            })
+            .collect(),
+        TypeNode::TupleStruct(node) => node
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| edge(field.id, EdgeKind::Field(key_path::field(index))))
+            .collect(),
+        TypeNode::Tuple(node) => node
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| edge(field.id, EdgeKind::Field(key_path::field(index))))
+            .collect(),
+        TypeNode::Enum(node) => {
+            let mut edges = Vec::new();
+            for variant in &node.variants {
+                match variant {
+                    VariantNode::Struct(variant) => {
+                        for name in variant.field_names.iter() {
+                            if let Some(field) = variant.fields.get(name) {
+                                let path = key_path::variant(&*variant.name).field(&**name);
+                                edges.push(edge(field.id, EdgeKind::Field(path)));
+                            }
+                        }
+                    }
+                    VariantNode::Tuple(variant) => {
+                        for (index, field) in variant.fields.iter().enumerate() {
+                            let path = key_path::variant(&*variant.name).field(index);
+                            edges.push(edge(field.id, EdgeKind::Field(path)));
+                        }
+                    }
+                    VariantNode::Unit(_) => {}
+                }
+            }
+            edges
+        }
+        TypeNode::List(node) => vec![edge(node.field_type_id, EdgeKind::Element)],
+        TypeNode::Array(node) => vec![edge(node.field_type_id, EdgeKind::Element)],
+        TypeNode::Map(node) => vec![
+            edge(node.key_type_id, EdgeKind::MapKey),
+            edge(node.value_type_id, EdgeKind::MapValue),
+        ],
+        TypeNode::Opaque(node) => node
+            .inner_type_id
+            .map(|inner| edge(inner, EdgeKind::Inner))
+            .into_iter()
+            .collect(),
+        TypeNode::Scalar(_) => Vec::new(),
+    }
+}
+
+/// Depth-first search through the types reachable from `root`, calling `pre` when a type is first
+/// reached and `post` once everything reachable from it has been visited.
+///
+/// Uses an explicit stack so deeply nested types can't overflow the call stack.
+pub(super) fn depth_first(
+    graph: &TypeGraph,
+    root: NodeId,
+    mut pre: impl FnMut(NodeId),
+    mut post: impl FnMut(NodeId),
+) {
+    let targets = |id| {
+        edges_from(graph, id)
+            .into_iter()
+            .map(|edge| edge.to)
+            .collect::<Vec<_>>()
+            .into_iter()
+    };
+
+    if !matches!(graph.map.get(&root), Some(Some(_))) {
+        return;
+    }
+
+    let mut visited = BTreeSet::from([root]);
+    pre(root);
+    let mut stack = vec![(root, targets(root))];
+
+    while let Some((id, children)) = stack.last_mut() {
+        match children.next() {
+            Some(child) => {
+                if visited.insert(child) {
+                    pre(child);
+                    stack.push((child, targets(child)));
+                }
+            }
+            None => {
+                post(*id);
+                stack.pop();
+            }
+        }
+    }
+}
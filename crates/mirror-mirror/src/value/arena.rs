@@ -0,0 +1,583 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::enum_::EnumValue;
+use crate::enum_::VariantField;
+use crate::enum_::VariantKind;
+use crate::map::OrderedMap;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Value;
+
+/// A handle to a value stored in a [`ValueArena`].
+///
+/// Handles remember how many times the arena had been cleared when they were created, so
+/// [`ValueArena::to_value`] can reject handles from before the last [`ValueArena::clear`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ValueId {
+    generation: u32,
+    index: u32,
+}
+
+/// Stores snapshots of reflected values in a few flat buffers rather than one heap allocation per
+/// node, like [`Value`] does.
+///
+/// Strings are stored back to back in a single buffer, and struct field and enum variant names are
+/// interned. Calling [`ValueArena::clear`] keeps all the buffers, and the interned names, around
+/// so an arena that is reused, for example to snapshot a scene every frame, stops allocating once
+/// it has grown large enough.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::value::ValueArena;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Entity {
+///     name: String,
+///     position: [f32; 2],
+/// }
+///
+/// let entities = vec![
+///     Entity {
+///         name: "a".to_owned(),
+///         position: [0.0, 0.0],
+///     };
+///     1000
+/// ];
+///
+/// let mut arena = ValueArena::new();
+/// for _frame in 0..3 {
+///     arena.clear();
+///     let ids = entities
+///         .iter()
+///         .map(|entity| arena.alloc(entity))
+///         .collect::<Vec<_>>();
+///
+///     assert_eq!(arena.to_value(ids[0]).unwrap(), entities[0].to_value());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ValueArena {
+    generation: u32,
+    // nested values refer to each other by their index in `nodes`
+    nodes: Vec<Node>,
+    text: String,
+    children: Vec<u32>,
+    fields: Vec<(u32, u32)>,
+    entries: Vec<(u32, u32)>,
+    names: Vec<String>,
+    name_indices: BTreeMap<String, u32>,
+    // children of the nodes currently being allocated. They are copied into the buffers above once
+    // the node is done so the children of each node end up next to each other.
+    pending_children: Vec<u32>,
+    pending_fields: Vec<(u32, u32)>,
+    pending_entries: Vec<(u32, u32)>,
+    // what's left to do while storing a value, always empty in between calls to `alloc`
+    tasks: Vec<Alloc<'static>>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    usize(usize),
+    u8(u8),
+    u16(u16),
+    u32(u32),
+    u64(u64),
+    u128(u128),
+    i8(i8),
+    i16(i16),
+    i32(i32),
+    i64(i64),
+    i128(i128),
+    bool(bool),
+    char(char),
+    f32(f32),
+    f64(f64),
+    String(Span),
+    Struct(Span),
+    StructVariant(u32, Span),
+    TupleVariant(u32, Span),
+    UnitVariant(u32),
+    TupleStruct(Span),
+    Tuple(Span),
+    List(Span),
+    Map(Span),
+    OrderedMap(Span),
+}
+
+/// A range of one of the arena's buffers.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    start: u32,
+    len: u32,
+}
+
+impl Span {
+    fn get<T>(self, buffer: &[T]) -> &[T] {
+        let start = self.start as usize;
+        &buffer[start..start + self.len as usize]
+    }
+}
+
+impl ValueArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of values in the arena, counting every nested value.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Remove all values while keeping the allocated memory.
+    ///
+    /// All [`ValueId`]s previously returned by the arena are invalidated.
+    pub fn clear(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        self.nodes.clear();
+        self.text.clear();
+        self.children.clear();
+        self.fields.clear();
+        self.entries.clear();
+    }
+
+    /// Store a snapshot of `value` in the arena.
+    ///
+    /// The value is read directly through [`Reflect`] without first converting it into a
+    /// [`Value`]. Opaque values are stored as their [`Value`].
+    pub fn alloc(&mut self, value: &dyn Reflect) -> ValueId {
+        ValueId {
+            generation: self.generation,
+            index: self.alloc_node(value),
+        }
+    }
+
+    /// Store `value` keeping the values that are left to store in a `Vec`, so deeply nested
+    /// values don't overflow the stack.
+    fn alloc_node(&mut self, value: &dyn Reflect) -> u32 {
+        let mut stack = recycle(mem::take(&mut self.tasks));
+        stack.push(Alloc::Value(value, Slot::Root));
+        let mut root = 0;
+        while let Some(task) = stack.pop() {
+            let (index, slot) = match task {
+                Alloc::Value(value, slot) => match self.visit(value, slot, &mut stack) {
+                    Some(index) => (index, slot),
+                    None => continue,
+                },
+                Alloc::Finish(container, start, slot) => {
+                    let node = self.finish(container, start);
+                    (self.push_node(node), slot)
+                }
+            };
+            match slot {
+                Slot::Root => root = index,
+                Slot::Child => self.pending_children.push(index),
+                Slot::Field(name) => self.pending_fields.push((name, index)),
+                Slot::Key => self.pending_entries.push((index, index)),
+                // the entries of maps inside the value have been drained already, so the last
+                // entry is the one for this value's key
+                Slot::Value => {
+                    if let Some((_, value)) = self.pending_entries.last_mut() {
+                        *value = index;
+                    }
+                }
+            }
+        }
+        self.tasks = recycle(stack);
+        root
+    }
+
+    /// Store `value` if it's a scalar, or push its values, followed by finishing it, onto `stack`.
+    fn visit<'a>(
+        &mut self,
+        value: &'a dyn Reflect,
+        slot: Slot,
+        stack: &mut Vec<Alloc<'a>>,
+    ) -> Option<u32> {
+        match value.reflect_ref() {
+            ReflectRef::Struct(inner) => {
+                let start = self.pending_fields.len();
+                stack.push(Alloc::Finish(Container::Struct, start, slot));
+                let fields = inner
+                    .fields()
+                    .map(|(name, value)| Alloc::Value(value, Slot::Field(self.intern(name))));
+                push_in_order(stack, fields);
+                None
+            }
+            ReflectRef::TupleStruct(inner) => {
+                let start = self.pending_children.len();
+                stack.push(Alloc::Finish(Container::TupleStruct, start, slot));
+                push_in_order(
+                    stack,
+                    inner.fields().map(|value| Alloc::Value(value, Slot::Child)),
+                );
+                None
+            }
+            ReflectRef::Tuple(inner) => {
+                let start = self.pending_children.len();
+                stack.push(Alloc::Finish(Container::Tuple, start, slot));
+                push_in_order(
+                    stack,
+                    inner.fields().map(|value| Alloc::Value(value, Slot::Child)),
+                );
+                None
+            }
+            ReflectRef::Enum(inner) => {
+                let variant = self.intern(inner.variant_name());
+                match inner.variant_kind() {
+                    VariantKind::Struct => {
+                        let start = self.pending_fields.len();
+                        stack.push(Alloc::Finish(
+                            Container::StructVariant(variant),
+                            start,
+                            slot,
+                        ));
+                        let fields = inner.fields().filter_map(|field| match field {
+                            VariantField::Struct(name, value) => {
+                                Some(Alloc::Value(value, Slot::Field(self.intern(name))))
+                            }
+                            VariantField::Tuple(_) => None,
+                        });
+                        push_in_order(stack, fields);
+                        None
+                    }
+                    VariantKind::Tuple => {
+                        let start = self.pending_children.len();
+                        stack.push(Alloc::Finish(Container::TupleVariant(variant), start, slot));
+                        let fields = inner.fields().filter_map(|field| match field {
+                            VariantField::Tuple(value) => Some(Alloc::Value(value, Slot::Child)),
+                            VariantField::Struct(..) => None,
+                        });
+                        push_in_order(stack, fields);
+                        None
+                    }
+                    VariantKind::Unit => Some(self.push_node(Node::UnitVariant(variant))),
+                }
+            }
+            // arrays and lists have the same `Value`
+            ReflectRef::Array(inner) => {
+                let start = self.pending_children.len();
+                stack.push(Alloc::Finish(Container::List, start, slot));
+                push_in_order(
+                    stack,
+                    inner.iter().map(|value| Alloc::Value(value, Slot::Child)),
+                );
+                None
+            }
+            ReflectRef::List(inner) => {
+                let start = self.pending_children.len();
+                stack.push(Alloc::Finish(Container::List, start, slot));
+                push_in_order(
+                    stack,
+                    inner.iter().map(|value| Alloc::Value(value, Slot::Child)),
+                );
+                None
+            }
+            ReflectRef::Map(inner) => {
+                let container = if inner.as_reflect().is::<OrderedMap>() {
+                    Container::OrderedMap
+                } else {
+                    Container::Map
+                };
+                let start = self.pending_entries.len();
+                stack.push(Alloc::Finish(container, start, slot));
+                let entries = inner.iter().flat_map(|(key, value)| {
+                    [
+                        Alloc::Value(key, Slot::Key),
+                        Alloc::Value(value, Slot::Value),
+                    ]
+                });
+                push_in_order(stack, entries);
+                None
+            }
+            ReflectRef::Scalar(inner) => {
+                let node = match inner {
+                    ScalarRef::usize(n) => Node::usize(n),
+                    ScalarRef::u8(n) => Node::u8(n),
+                    ScalarRef::u16(n) => Node::u16(n),
+                    ScalarRef::u32(n) => Node::u32(n),
+                    ScalarRef::u64(n) => Node::u64(n),
+                    ScalarRef::u128(n) => Node::u128(n),
+                    ScalarRef::i8(n) => Node::i8(n),
+                    ScalarRef::i16(n) => Node::i16(n),
+                    ScalarRef::i32(n) => Node::i32(n),
+                    ScalarRef::i64(n) => Node::i64(n),
+                    ScalarRef::i128(n) => Node::i128(n),
+                    ScalarRef::bool(b) => Node::bool(b),
+                    ScalarRef::char(c) => Node::char(c),
+                    ScalarRef::f32(n) => Node::f32(n),
+                    ScalarRef::f64(n) => Node::f64(n),
+                    ScalarRef::String(s) => {
+                        let span = Span {
+                            start: self.text.len() as u32,
+                            len: s.len() as u32,
+                        };
+                        self.text.push_str(s);
+                        Node::String(span)
+                    }
+                };
+                Some(self.push_node(node))
+            }
+            // the `Value` of an opaque value doesn't contain opaque values, so this only nests once
+            ReflectRef::Opaque(inner) => Some(self.alloc_node(&inner.to_value())),
+        }
+    }
+
+    /// Create the node for a container whose values have all been stored.
+    fn finish(&mut self, container: Container, start: usize) -> Node {
+        match container {
+            Container::Struct => Node::Struct(self.finish_fields(start)),
+            Container::StructVariant(name) => Node::StructVariant(name, self.finish_fields(start)),
+            Container::TupleVariant(name) => Node::TupleVariant(name, self.finish_children(start)),
+            Container::TupleStruct => Node::TupleStruct(self.finish_children(start)),
+            Container::Tuple => Node::Tuple(self.finish_children(start)),
+            Container::List => Node::List(self.finish_children(start)),
+            Container::Map => Node::Map(self.finish_entries(start)),
+            Container::OrderedMap => Node::OrderedMap(self.finish_entries(start)),
+        }
+    }
+
+    fn push_node(&mut self, node: Node) -> u32 {
+        let index = self.nodes.len() as u32;
+        self.nodes.push(node);
+        index
+    }
+
+    /// Convert a value stored in the arena back into a [`Value`].
+    ///
+    /// Returns `None` if `id` was invalidated by [`ValueArena::clear`]. Ids from other arenas
+    /// aren't detected and can return an unrelated value.
+    pub fn to_value(&self, id: ValueId) -> Option<Value> {
+        if id.generation != self.generation {
+            return None;
+        }
+        self.node_to_value(id.index)
+    }
+
+    /// Build the values inside containers first, keeping them in `values` until the container is
+    /// built, so deeply nested values don't overflow the stack.
+    fn node_to_value(&self, index: u32) -> Option<Value> {
+        let mut stack = Vec::from([Build::Node(index)]);
+        let mut values = Vec::new();
+        while let Some(task) = stack.pop() {
+            let node = match task {
+                Build::Node(index) => *self.nodes.get(index as usize)?,
+                Build::Finish(node) => {
+                    let value = self.build(node, &mut values);
+                    values.push(value);
+                    continue;
+                }
+            };
+            let value = match node {
+                Node::usize(n) => Value::usize(n),
+                Node::u8(n) => Value::u8(n),
+                Node::u16(n) => Value::u16(n),
+                Node::u32(n) => Value::u32(n),
+                Node::u64(n) => Value::u64(n),
+                Node::u128(n) => Value::from(n),
+                Node::i8(n) => Value::i8(n),
+                Node::i16(n) => Value::i16(n),
+                Node::i32(n) => Value::i32(n),
+                Node::i64(n) => Value::i64(n),
+                Node::i128(n) => Value::from(n),
+                Node::bool(b) => Value::bool(b),
+                Node::char(c) => Value::char(c),
+                Node::f32(n) => Value::f32(n),
+                Node::f64(n) => Value::f64(n),
+                Node::String(span) => {
+                    let start = span.start as usize;
+                    Value::from(&self.text[start..start + span.len as usize])
+                }
+                Node::UnitVariant(name) => {
+                    EnumValue::new_unit_variant(&*self.names[name as usize]).into()
+                }
+                Node::Struct(span) | Node::StructVariant(_, span) => {
+                    stack.push(Build::Finish(node));
+                    let fields = span.get(&self.fields).iter();
+                    push_in_order(&mut stack, fields.map(|(_, index)| Build::Node(*index)));
+                    continue;
+                }
+                Node::TupleVariant(_, span)
+                | Node::TupleStruct(span)
+                | Node::Tuple(span)
+                | Node::List(span) => {
+                    stack.push(Build::Finish(node));
+                    let children = span.get(&self.children).iter();
+                    push_in_order(&mut stack, children.map(|index| Build::Node(*index)));
+                    continue;
+                }
+                Node::Map(span) | Node::OrderedMap(span) => {
+                    stack.push(Build::Finish(node));
+                    let entries = span.get(&self.entries).iter();
+                    push_in_order(
+                        &mut stack,
+                        entries.flat_map(|(key, value)| [Build::Node(*key), Build::Node(*value)]),
+                    );
+                    continue;
+                }
+            };
+            values.push(value);
+        }
+        values.pop()
+    }
+
+    /// Build a container out of the values inside it, which are at the end of `values`.
+    fn build(&self, node: Node, values: &mut Vec<Value>) -> Value {
+        match node {
+            Node::Struct(span) => {
+                let mut struct_ = StructValue::new();
+                for ((name, _), value) in span.get(&self.fields).iter().zip(take(values, span.len))
+                {
+                    struct_.set_field(&*self.names[*name as usize], value);
+                }
+                struct_.into()
+            }
+            Node::StructVariant(name, span) => {
+                let mut variant = EnumValue::new_struct_variant(&*self.names[name as usize]);
+                for ((name, _), value) in span.get(&self.fields).iter().zip(take(values, span.len))
+                {
+                    variant.set_struct_field(&*self.names[*name as usize], value);
+                }
+                variant.finish().into()
+            }
+            Node::TupleVariant(name, span) => {
+                let mut variant = EnumValue::new_tuple_variant(&*self.names[name as usize]);
+                for value in take(values, span.len) {
+                    variant.push_tuple_field(value);
+                }
+                variant.finish().into()
+            }
+            Node::TupleStruct(span) => {
+                let mut tuple_struct = TupleStructValue::new();
+                for value in take(values, span.len) {
+                    tuple_struct.push_field(value);
+                }
+                tuple_struct.into()
+            }
+            Node::Tuple(span) => {
+                let mut tuple = TupleValue::new();
+                for value in take(values, span.len) {
+                    tuple.push_field(value);
+                }
+                tuple.into()
+            }
+            Node::List(span) => Value::List(Box::new(take(values, span.len).collect())),
+            Node::Map(span) => Value::Map(Box::new(entries(take(values, span.len * 2)).collect())),
+            Node::OrderedMap(span) => {
+                Value::OrderedMap(Box::new(entries(take(values, span.len * 2)).collect()))
+            }
+            _ => unreachable!("only containers are built"),
+        }
+    }
+
+    fn intern(&mut self, name: &str) -> u32 {
+        if let Some(index) = self.name_indices.get(name) {
+            return *index;
+        }
+        let index = self.names.len() as u32;
+        self.names.push(name.into());
+        self.name_indices.insert(name.into(), index);
+        index
+    }
+
+    fn finish_children(&mut self, start: usize) -> Span {
+        let span = Span {
+            start: self.children.len() as u32,
+            len: (self.pending_children.len() - start) as u32,
+        };
+        self.children.extend(self.pending_children.drain(start..));
+        span
+    }
+
+    fn finish_fields(&mut self, start: usize) -> Span {
+        let span = Span {
+            start: self.fields.len() as u32,
+            len: (self.pending_fields.len() - start) as u32,
+        };
+        self.fields.extend(self.pending_fields.drain(start..));
+        span
+    }
+
+    fn finish_entries(&mut self, start: usize) -> Span {
+        let span = Span {
+            start: self.entries.len() as u32,
+            len: (self.pending_entries.len() - start) as u32,
+        };
+        self.entries.extend(self.pending_entries.drain(start..));
+        span
+    }
+}
+
+/// Something that's left to do while storing a value.
+#[derive(Debug, Clone)]
+enum Alloc<'a> {
+    Value(&'a dyn Reflect, Slot),
+    /// Create the node for a container once the values inside it are stored. The values are in
+    /// the pending buffers from the index on.
+    Finish(Container, usize, Slot),
+}
+
+/// Where the index of a stored value goes.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Root,
+    Child,
+    Field(u32),
+    Key,
+    Value,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Container {
+    Struct,
+    StructVariant(u32),
+    TupleVariant(u32),
+    TupleStruct,
+    Tuple,
+    List,
+    Map,
+    OrderedMap,
+}
+
+enum Build {
+    Node(u32),
+    Finish(Node),
+}
+
+/// Push `tasks` so they're popped in the order they're yielded.
+fn push_in_order<T>(stack: &mut Vec<T>, tasks: impl Iterator<Item = T>) {
+    let start = stack.len();
+    stack.extend(tasks);
+    stack[start..].reverse();
+}
+
+/// Reuse the allocation of a task stack for a value with another lifetime. Collecting a `Vec` into
+/// one whose elements have the same layout happens in place.
+fn recycle<'a, 'b>(mut tasks: Vec<Alloc<'a>>) -> Vec<Alloc<'b>> {
+    tasks.clear();
+    tasks.into_iter().map(|_| unreachable!()).collect()
+}
+
+/// The last `len` values.
+fn take(values: &mut Vec<Value>, len: u32) -> vec::Drain<'_, Value> {
+    values.drain(values.len() - len as usize..)
+}
+
+fn entries(mut values: impl Iterator<Item = Value>) -> impl Iterator<Item = (Value, Value)> {
+    core::iter::from_fn(move || Some((values.next()?, values.next()?)))
+}
@@ -0,0 +1,197 @@
+use crate::scalar_eq;
+use crate::Array;
+use crate::Enum;
+use crate::List;
+use crate::Map;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Struct;
+use crate::Tuple;
+use crate::TupleStruct;
+use crate::Value;
+
+/// A borrowed view of a reflected value, shaped like [`Value`].
+///
+/// Unlike [`Reflect::to_value`], creating a `ValueRef` doesn't clone anything. Scalars are copied
+/// and strings are borrowed, while structs, enums, and collections are borrowed as trait objects
+/// whose fields can themselves be viewed with [`ValueRef::new`]. This makes it a good fit for
+/// code that only reads values, such as serializers or comparisons.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::value::ValueRef;
+/// use mirror_mirror::Reflect;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Player {
+///     name: String,
+///     score: u32,
+/// }
+///
+/// let player = Player {
+///     name: "Alice".to_owned(),
+///     score: 10,
+/// };
+///
+/// let ValueRef::StructValue(player) = ValueRef::new(&player) else { panic!() };
+/// let name = ValueRef::new(player.field("name").unwrap());
+/// assert!(matches!(name, ValueRef::String("Alice")));
+/// assert_eq!(name, "Alice".to_owned().to_value());
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy)]
+pub enum ValueRef<'a> {
+    usize(usize),
+    u8(u8),
+    u16(u16),
+    u32(u32),
+    u64(u64),
+    u128(u128),
+    i8(i8),
+    i16(i16),
+    i32(i32),
+    i64(i64),
+    i128(i128),
+    bool(bool),
+    char(char),
+    f32(f32),
+    f64(f64),
+    String(&'a str),
+    StructValue(&'a dyn Struct),
+    EnumValue(&'a dyn Enum),
+    TupleStructValue(&'a dyn TupleStruct),
+    TupleValue(&'a dyn Tuple),
+    /// Converts to [`Value::List`], like [`ValueRef::Array`].
+    List(&'a dyn List),
+    /// Converts to [`Value::List`], like [`ValueRef::List`].
+    Array(&'a dyn Array),
+    /// Converts to either [`Value::Map`] or [`Value::OrderedMap`], depending on the map.
+    Map(&'a dyn Map),
+    /// A value whose contents aren't accessible through reflection. See [`ReflectRef::Opaque`].
+    Opaque(&'a dyn Reflect),
+}
+
+impl<'a> ValueRef<'a> {
+    pub fn new(reflect: &'a dyn Reflect) -> Self {
+        match reflect.reflect_ref() {
+            ReflectRef::Struct(inner) => Self::StructValue(inner),
+            ReflectRef::TupleStruct(inner) => Self::TupleStructValue(inner),
+            ReflectRef::Tuple(inner) => Self::TupleValue(inner),
+            ReflectRef::Enum(inner) => Self::EnumValue(inner),
+            ReflectRef::Array(inner) => Self::Array(inner),
+            ReflectRef::List(inner) => Self::List(inner),
+            ReflectRef::Map(inner) => Self::Map(inner),
+            ReflectRef::Opaque(inner) => Self::Opaque(inner),
+            ReflectRef::Scalar(scalar) => match scalar {
+                ScalarRef::usize(inner) => Self::usize(inner),
+                ScalarRef::u8(inner) => Self::u8(inner),
+                ScalarRef::u16(inner) => Self::u16(inner),
+                ScalarRef::u32(inner) => Self::u32(inner),
+                ScalarRef::u64(inner) => Self::u64(inner),
+                ScalarRef::u128(inner) => Self::u128(inner),
+                ScalarRef::i8(inner) => Self::i8(inner),
+                ScalarRef::i16(inner) => Self::i16(inner),
+                ScalarRef::i32(inner) => Self::i32(inner),
+                ScalarRef::i64(inner) => Self::i64(inner),
+                ScalarRef::i128(inner) => Self::i128(inner),
+                ScalarRef::bool(inner) => Self::bool(inner),
+                ScalarRef::char(inner) => Self::char(inner),
+                ScalarRef::f32(inner) => Self::f32(inner),
+                ScalarRef::f64(inner) => Self::f64(inner),
+                ScalarRef::String(inner) => Self::String(inner),
+            },
+        }
+    }
+
+    /// Clone the viewed value into a [`Value`].
+    pub fn to_value(self) -> Value {
+        match self.split() {
+            Ok(scalar) => match scalar {
+                ScalarRef::usize(inner) => Value::usize(inner),
+                ScalarRef::u8(inner) => Value::u8(inner),
+                ScalarRef::u16(inner) => Value::u16(inner),
+                ScalarRef::u32(inner) => Value::u32(inner),
+                ScalarRef::u64(inner) => Value::u64(inner),
+                ScalarRef::u128(inner) => Value::from(inner),
+                ScalarRef::i8(inner) => Value::i8(inner),
+                ScalarRef::i16(inner) => Value::i16(inner),
+                ScalarRef::i32(inner) => Value::i32(inner),
+                ScalarRef::i64(inner) => Value::i64(inner),
+                ScalarRef::i128(inner) => Value::from(inner),
+                ScalarRef::bool(inner) => Value::bool(inner),
+                ScalarRef::char(inner) => Value::char(inner),
+                ScalarRef::f32(inner) => Value::f32(inner),
+                ScalarRef::f64(inner) => Value::f64(inner),
+                ScalarRef::String(inner) => Value::from(inner),
+            },
+            Err(reflect) => reflect.to_value(),
+        }
+    }
+
+    fn split(self) -> Result<ScalarRef<'a>, &'a dyn Reflect> {
+        let scalar = match self {
+            ValueRef::usize(inner) => ScalarRef::usize(inner),
+            ValueRef::u8(inner) => ScalarRef::u8(inner),
+            ValueRef::u16(inner) => ScalarRef::u16(inner),
+            ValueRef::u32(inner) => ScalarRef::u32(inner),
+            ValueRef::u64(inner) => ScalarRef::u64(inner),
+            ValueRef::u128(inner) => ScalarRef::u128(inner),
+            ValueRef::i8(inner) => ScalarRef::i8(inner),
+            ValueRef::i16(inner) => ScalarRef::i16(inner),
+            ValueRef::i32(inner) => ScalarRef::i32(inner),
+            ValueRef::i64(inner) => ScalarRef::i64(inner),
+            ValueRef::i128(inner) => ScalarRef::i128(inner),
+            ValueRef::bool(inner) => ScalarRef::bool(inner),
+            ValueRef::char(inner) => ScalarRef::char(inner),
+            ValueRef::f32(inner) => ScalarRef::f32(inner),
+            ValueRef::f64(inner) => ScalarRef::f64(inner),
+            ValueRef::String(inner) => ScalarRef::String(inner),
+            ValueRef::StructValue(inner) => return Err(inner.as_reflect()),
+            ValueRef::EnumValue(inner) => return Err(inner.as_reflect()),
+            ValueRef::TupleStructValue(inner) => return Err(inner.as_reflect()),
+            ValueRef::TupleValue(inner) => return Err(inner.as_reflect()),
+            ValueRef::List(inner) => return Err(inner.as_reflect()),
+            ValueRef::Array(inner) => return Err(inner.as_reflect()),
+            ValueRef::Map(inner) => return Err(inner.as_reflect()),
+            ValueRef::Opaque(inner) => return Err(inner),
+        };
+        Ok(scalar)
+    }
+}
+
+impl<'a> From<&'a dyn Reflect> for ValueRef<'a> {
+    fn from(reflect: &'a dyn Reflect) -> Self {
+        Self::new(reflect)
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    fn from(value: &'a Value) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Values are compared with [`Reflect::reflect_partial_eq`], so opaque values are never equal.
+impl PartialEq for ValueRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.split(), other.split()) {
+            (Ok(a), Ok(b)) => scalar_eq(a, b),
+            (Err(a), Err(b)) => a.reflect_partial_eq(b).unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<Value> for ValueRef<'_> {
+    fn eq(&self, other: &Value) -> bool {
+        *self == ValueRef::new(other)
+    }
+}
+
+impl PartialEq<ValueRef<'_>> for Value {
+    fn eq(&self, other: &ValueRef<'_>) -> bool {
+        ValueRef::new(self) == *other
+    }
+}
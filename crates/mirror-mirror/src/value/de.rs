@@ -0,0 +1,753 @@
+//! Deserializing values with serde, in the format `#[derive(Deserialize)]` would use, but with a
+//! limit on how deeply they can be nested.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::DeserializeSeed;
+use serde::de::EnumAccess;
+use serde::de::Error;
+use serde::de::IgnoredAny;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::VariantAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Deserializer;
+
+use super::DepthLimit;
+use super::Value;
+use crate::enum_::EnumValue;
+use crate::enum_::EnumValueKind;
+use crate::map::OrderedMap;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+
+impl Value {
+    /// Deserialize a value that's nested at most `limit` levels deep, see [`Value::depth`].
+    ///
+    /// Deserializing recurses once per level so this protects against stack overflows caused by
+    /// untrusted input. The [`Deserialize`] impl uses [`Value::DEFAULT_DEPTH_LIMIT`].
+    pub fn deserialize_with_depth_limit<'de, D>(
+        deserializer: D,
+        limit: usize,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ValueSeed(DepthLimit::new(limit)).deserialize(deserializer)
+    }
+
+    /// [`Value::deserialize_with_depth_limit`] with the limit as a const parameter, so fields of
+    /// derived types can raise the limit with `#[serde(deserialize_with = "...")]`.
+    ///
+    /// ```
+    /// use mirror_mirror::Value;
+    /// use serde::Deserialize;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Document {
+    ///     #[serde(
+    ///         serialize_with = "Value::serialize_with_const_depth_limit::<_, 1024>",
+    ///         deserialize_with = "Value::deserialize_with_const_depth_limit::<_, 1024>"
+    ///     )]
+    ///     value: Value,
+    /// }
+    ///
+    /// let mut value = Value::from(1);
+    /// for _ in 0..200 {
+    ///     value = Value::List(Box::new(vec![value]));
+    /// }
+    ///
+    /// let json = serde_json::to_string(&Document { value }).unwrap();
+    /// let mut deserializer = serde_json::Deserializer::from_str(&json);
+    /// // serde_json has a limit of its own
+    /// deserializer.disable_recursion_limit();
+    /// let document = Document::deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(document.value.depth(), 200);
+    /// ```
+    pub fn deserialize_with_const_depth_limit<'de, D, const LIMIT: usize>(
+        deserializer: D,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_with_depth_limit(deserializer, LIMIT)
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_with_depth_limit(deserializer, Self::DEFAULT_DEPTH_LIMIT)
+    }
+}
+
+pub(super) const VARIANTS: &[&str] = &[
+    "usize",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "bool",
+    "char",
+    "f32",
+    "f64",
+    "String",
+    "StructValue",
+    "EnumValue",
+    "TupleStructValue",
+    "TupleValue",
+    "List",
+    "Map",
+    "OrderedMap",
+];
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize)]
+#[serde(variant_identifier)]
+enum Variant {
+    usize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    bool,
+    char,
+    f32,
+    f64,
+    String,
+    StructValue,
+    EnumValue,
+    TupleStructValue,
+    TupleValue,
+    List,
+    Map,
+    OrderedMap,
+}
+
+#[derive(Clone, Copy)]
+struct ValueSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for ValueSeed {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("Value", VARIANTS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for ValueSeed {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("enum Value")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (variant, data) = data.variant()?;
+        let value = match variant {
+            Variant::usize => Value::usize(data.newtype_variant()?),
+            Variant::u8 => Value::u8(data.newtype_variant()?),
+            Variant::u16 => Value::u16(data.newtype_variant()?),
+            Variant::u32 => Value::u32(data.newtype_variant()?),
+            Variant::u64 => Value::u64(data.newtype_variant()?),
+            Variant::u128 => Value::u128(data.newtype_variant()?),
+            Variant::i8 => Value::i8(data.newtype_variant()?),
+            Variant::i16 => Value::i16(data.newtype_variant()?),
+            Variant::i32 => Value::i32(data.newtype_variant()?),
+            Variant::i64 => Value::i64(data.newtype_variant()?),
+            Variant::i128 => Value::i128(data.newtype_variant()?),
+            Variant::bool => Value::bool(data.newtype_variant()?),
+            Variant::char => Value::char(data.newtype_variant()?),
+            Variant::f32 => Value::f32(data.newtype_variant()?),
+            Variant::f64 => Value::f64(data.newtype_variant()?),
+            Variant::String => Value::String(data.newtype_variant()?),
+            Variant::StructValue => {
+                let seed = StructValueSeed(self.nested()?);
+                Value::StructValue(Box::new(data.newtype_variant_seed(seed)?))
+            }
+            Variant::EnumValue => {
+                let seed = EnumValueSeed(self.nested()?);
+                Value::EnumValue(Box::new(data.newtype_variant_seed(seed)?))
+            }
+            Variant::TupleStructValue => {
+                let seed = TupleStructValueSeed(self.nested()?);
+                Value::TupleStructValue(Box::new(data.newtype_variant_seed(seed)?))
+            }
+            Variant::TupleValue => {
+                let seed = TupleValueSeed(self.nested()?);
+                Value::TupleValue(Box::new(data.newtype_variant_seed(seed)?))
+            }
+            Variant::List => {
+                let seed = ListSeed(self.nested()?);
+                Value::List(Box::new(data.newtype_variant_seed(seed)?))
+            }
+            Variant::Map => {
+                let seed = MapSeed(self.nested()?);
+                Value::Map(Box::new(data.newtype_variant_seed(seed)?))
+            }
+            Variant::OrderedMap => {
+                let seed = OrderedMapSeed(self.nested()?);
+                Value::OrderedMap(Box::new(data.newtype_variant_seed(seed)?))
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl ValueSeed {
+    /// The limit for the values inside a container.
+    fn nested<E>(self) -> Result<DepthLimit, E>
+    where
+        E: Error,
+    {
+        self.0.nested().ok_or_else(|| E::custom(self.0))
+    }
+}
+
+/// Deserializes a struct's fields with a seed each, using the field names from `fields`.
+///
+/// Like derived impls, fields can be given by name or index and unknown fields are skipped.
+struct Fields<S> {
+    name: &'static str,
+    fields: &'static [&'static str],
+    seeds: S,
+}
+
+impl<'de, S> DeserializeSeed<'de> for Fields<S>
+where
+    S: FieldSeeds<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(self.name, self.fields, self)
+    }
+}
+
+impl<'de, S> Visitor<'de> for Fields<S>
+where
+    S: FieldSeeds<'de>,
+{
+    type Value = S::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "struct {}", self.name)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.seeds.visit_seq(seq)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.seeds.visit_map(map, self.fields)
+    }
+}
+
+/// The seeds for the fields of a struct, as a tuple with one seed per field.
+trait FieldSeeds<'de> {
+    type Value;
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>;
+
+    fn visit_map<A>(self, map: A, fields: &'static [&'static str]) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>;
+}
+
+impl<'de, S0> FieldSeeds<'de> for (S0,)
+where
+    S0: DeserializeSeed<'de>,
+{
+    type Value = S0::Value;
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let field0 = seq
+            .next_element_seed(self.0)?
+            .ok_or_else(|| A::Error::invalid_length(0, &"struct with 1 element"))?;
+        Ok(field0)
+    }
+
+    fn visit_map<A>(
+        self,
+        mut map: A,
+        fields: &'static [&'static str],
+    ) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut seed0 = Some(self.0);
+        let mut field0 = None;
+        while let Some(index) = map.next_key_seed(FieldIndex(fields))? {
+            match index {
+                Some(0) => {
+                    let seed = seed0
+                        .take()
+                        .ok_or_else(|| A::Error::duplicate_field(fields[0]))?;
+                    field0 = Some(map.next_value_seed(seed)?);
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let field0 = field0.ok_or_else(|| A::Error::missing_field(fields[0]))?;
+        Ok(field0)
+    }
+}
+
+impl<'de, S0, S1> FieldSeeds<'de> for (S0, S1)
+where
+    S0: DeserializeSeed<'de>,
+    S1: DeserializeSeed<'de>,
+{
+    type Value = (S0::Value, S1::Value);
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let field0 = seq
+            .next_element_seed(self.0)?
+            .ok_or_else(|| A::Error::invalid_length(0, &"struct with 2 elements"))?;
+        let field1 = seq
+            .next_element_seed(self.1)?
+            .ok_or_else(|| A::Error::invalid_length(1, &"struct with 2 elements"))?;
+        Ok((field0, field1))
+    }
+
+    fn visit_map<A>(
+        self,
+        mut map: A,
+        fields: &'static [&'static str],
+    ) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let (mut seed0, mut seed1) = (Some(self.0), Some(self.1));
+        let (mut field0, mut field1) = (None, None);
+        while let Some(index) = map.next_key_seed(FieldIndex(fields))? {
+            match index {
+                Some(0) => {
+                    let seed = seed0
+                        .take()
+                        .ok_or_else(|| A::Error::duplicate_field(fields[0]))?;
+                    field0 = Some(map.next_value_seed(seed)?);
+                }
+                Some(1) => {
+                    let seed = seed1
+                        .take()
+                        .ok_or_else(|| A::Error::duplicate_field(fields[1]))?;
+                    field1 = Some(map.next_value_seed(seed)?);
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let field0 = field0.ok_or_else(|| A::Error::missing_field(fields[0]))?;
+        let field1 = field1.ok_or_else(|| A::Error::missing_field(fields[1]))?;
+        Ok((field0, field1))
+    }
+}
+
+/// Deserializes a field identifier into the index of the field, or `None` for unknown fields.
+struct FieldIndex(&'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for FieldIndex {
+    type Value = Option<usize>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldIndex {
+    type Value = Option<usize>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("field identifier")
+    }
+
+    fn visit_u64<E>(self, index: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(usize::try_from(index)
+            .ok()
+            .filter(|index| *index < self.0.len()))
+    }
+
+    fn visit_str<E>(self, name: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(self.0.iter().position(|field| *field == name))
+    }
+
+    fn visit_bytes<E>(self, name: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(self.0.iter().position(|field| field.as_bytes() == name))
+    }
+}
+
+impl<'de> Deserialize<'de> for StructValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        StructValueSeed(DepthLimit::new(Value::DEFAULT_DEPTH_LIMIT)).deserialize(deserializer)
+    }
+}
+
+struct StructValueSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for StructValueSeed {
+    type Value = StructValue;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (field_names, fields) = Fields {
+            name: "StructValue",
+            fields: &["field_names", "fields"],
+            seeds: (PhantomData::<Vec<String>>, StructFieldsSeed(self.0)),
+        }
+        .deserialize(deserializer)?;
+        Ok(StructValue::from_parts(field_names, fields))
+    }
+}
+
+struct StructFieldsSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for StructFieldsSeed {
+    type Value = BTreeMap<String, Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StructFieldsSeed {
+    type Value = BTreeMap<String, Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut fields = BTreeMap::new();
+        while let Some(name) = map.next_key()? {
+            fields.insert(name, map.next_value_seed(ValueSeed(self.0))?);
+        }
+        Ok(fields)
+    }
+}
+
+struct EnumValueSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for EnumValueSeed {
+    type Value = EnumValue;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (name, kind) = Fields {
+            name: "EnumValue",
+            fields: &["name", "kind"],
+            seeds: (PhantomData::<String>, EnumValueKindSeed(self.0)),
+        }
+        .deserialize(deserializer)?;
+        Ok(EnumValue::from_parts(name, kind))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(variant_identifier)]
+enum KindVariant {
+    Struct,
+    Tuple,
+    Unit,
+}
+
+struct EnumValueKindSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for EnumValueKindSeed {
+    type Value = EnumValueKind;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("EnumValueKind", &["Struct", "Tuple", "Unit"], self)
+    }
+}
+
+impl<'de> Visitor<'de> for EnumValueKindSeed {
+    type Value = EnumValueKind;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("enum EnumValueKind")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (variant, data) = data.variant()?;
+        let kind = match variant {
+            KindVariant::Struct => {
+                EnumValueKind::Struct(data.newtype_variant_seed(StructValueSeed(self.0))?)
+            }
+            KindVariant::Tuple => {
+                EnumValueKind::Tuple(data.newtype_variant_seed(TupleValueSeed(self.0))?)
+            }
+            KindVariant::Unit => {
+                data.unit_variant()?;
+                EnumValueKind::Unit
+            }
+        };
+        Ok(kind)
+    }
+}
+
+struct TupleStructValueSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for TupleStructValueSeed {
+    type Value = TupleStructValue;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tuple = Fields {
+            name: "TupleStructValue",
+            fields: &["tuple"],
+            seeds: (TupleValueSeed(self.0),),
+        }
+        .deserialize(deserializer)?;
+        Ok(TupleStructValue::from_tuple(tuple))
+    }
+}
+
+struct TupleValueSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for TupleValueSeed {
+    type Value = TupleValue;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = Fields {
+            name: "TupleValue",
+            fields: &["fields"],
+            seeds: (ListSeed(self.0),),
+        }
+        .deserialize(deserializer)?;
+        Ok(TupleValue::from_values(fields))
+    }
+}
+
+struct ListSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for ListSeed {
+    type Value = Vec<Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ListSeed {
+    type Value = Vec<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element_seed(ValueSeed(self.0))? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+struct MapSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for MapSeed {
+    type Value = BTreeMap<Value, Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for MapSeed {
+    type Value = BTreeMap<Value, Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = BTreeMap::new();
+        while let Some(key) = map.next_key_seed(ValueSeed(self.0))? {
+            entries.insert(key, map.next_value_seed(ValueSeed(self.0))?);
+        }
+        Ok(entries)
+    }
+}
+
+struct OrderedMapSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for OrderedMapSeed {
+    type Value = OrderedMap;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let entries = Fields {
+            name: "OrderedMap",
+            fields: &["entries"],
+            seeds: (EntriesSeed(self.0),),
+        }
+        .deserialize(deserializer)?;
+        Ok(OrderedMap::from_entries(entries))
+    }
+}
+
+struct EntriesSeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for EntriesSeed {
+    type Value = Vec<(Value, Value)>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntriesSeed {
+    type Value = Vec<(Value, Value)>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(entry) = seq.next_element_seed(EntrySeed(self.0))? {
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+struct EntrySeed(DepthLimit);
+
+impl<'de> DeserializeSeed<'de> for EntrySeed {
+    type Value = (Value, Value);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntrySeed {
+    type Value = (Value, Value);
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a tuple of size 2")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let key = seq
+            .next_element_seed(ValueSeed(self.0))?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let value = seq
+            .next_element_seed(ValueSeed(self.0))?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok((key, value))
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use speedy::Context;
+use speedy::Readable;
+use speedy::Reader;
+use speedy::Writable;
+use speedy::Writer;
+
+use super::read::read_value;
+use super::read::LimitContext;
+use super::read::Names as ReadNames;
+use super::write::write_length;
+use super::write::write_value;
+use super::write::Names;
+use super::write::Pending;
+use super::DepthLimit;
+use crate::enum_::EnumValueKind;
+use crate::struct_::StructValue;
+use crate::Enum;
+use crate::Struct;
+use crate::Value;
+
+impl Value {
+    /// Encode the value with [`speedy`], storing struct field names and enum variant names in a
+    /// string table so each distinct name is only written once.
+    ///
+    /// Use [`Value::read_interned_from_buffer`] to decode the result.
+    pub fn write_interned_to_vec(&self) -> Result<Vec<u8>, speedy::Error> {
+        let table = StringTable::new(self);
+        let document = InternedDocument {
+            table: &table,
+            root: self,
+        };
+        document.write_to_vec()
+    }
+
+    /// Decode a value previously encoded with [`Value::write_interned_to_vec`].
+    pub fn read_interned_from_buffer(buffer: &[u8]) -> Result<Self, speedy::Error> {
+        Self::read_interned_from_buffer_with_depth_limit(buffer, usize::MAX)
+    }
+
+    /// Decode a value previously encoded with [`Value::write_interned_to_vec`] that's nested at
+    /// most `limit` levels deep, see [`Value::depth`] and [`Value::read_from_buffer_with_depth_limit`].
+    pub fn read_interned_from_buffer_with_depth_limit(
+        buffer: &[u8],
+        limit: usize,
+    ) -> Result<Self, speedy::Error> {
+        let document = ReadDocument::read_from_buffer_with_ctx(LimitContext(limit), buffer)?;
+        Ok(document.0)
+    }
+}
+
+/// The distinct struct field names and enum variant names in a value, in the order they're first
+/// written in.
+#[derive(Default)]
+struct StringTable<'a> {
+    strings: Vec<&'a str>,
+    indices: BTreeMap<&'a str, u32>,
+}
+
+impl<'a> StringTable<'a> {
+    fn new(value: &'a Value) -> Self {
+        fn push_fields<'a>(
+            struct_: &'a StructValue,
+            stack: &mut Vec<(Option<&'a str>, &'a Value)>,
+        ) {
+            stack.extend(struct_.values().map(|(name, value)| (Some(name), value)));
+        }
+
+        fn push_values<'a>(
+            values: impl IntoIterator<Item = &'a Value>,
+            stack: &mut Vec<(Option<&'a str>, &'a Value)>,
+        ) {
+            stack.extend(values.into_iter().map(|value| (None, value)));
+        }
+
+        let mut table = Self::default();
+        // field values are paired with their name, which is interned right before the value is
+        // visited, like it's written
+        let mut stack = Vec::from([(None, value)]);
+        while let Some((name, value)) = stack.pop() {
+            if let Some(name) = name {
+                table.intern(name);
+            }
+            let start = stack.len();
+            match value {
+                Value::StructValue(inner) => push_fields(inner, &mut stack),
+                Value::EnumValue(inner) => {
+                    table.intern(inner.variant_name());
+                    match inner.kind() {
+                        EnumValueKind::Struct(fields) => push_fields(fields, &mut stack),
+                        EnumValueKind::Tuple(fields) => push_values(fields.values(), &mut stack),
+                        EnumValueKind::Unit => {}
+                    }
+                }
+                Value::TupleStructValue(inner) => push_values(inner.values(), &mut stack),
+                Value::TupleValue(inner) => push_values(inner.values(), &mut stack),
+                Value::List(inner) => push_values(inner.iter(), &mut stack),
+                Value::Map(inner) => {
+                    push_values(
+                        inner.iter().flat_map(|(key, value)| [key, value]),
+                        &mut stack,
+                    );
+                }
+                Value::OrderedMap(inner) => push_values(
+                    inner.entries().flat_map(|(key, value)| [key, value]),
+                    &mut stack,
+                ),
+                _ => {}
+            }
+            // visit the values in the order they're written in
+            stack[start..].reverse();
+        }
+        table
+    }
+
+    fn intern(&mut self, string: &'a str) {
+        if !self.indices.contains_key(string) {
+            self.indices.insert(string, self.strings.len() as u32);
+            self.strings.push(string);
+        }
+    }
+
+    fn index(&self, string: &str) -> Result<u32, speedy::Error> {
+        self.indices
+            .get(string)
+            .copied()
+            .ok_or_else(|| speedy::Error::custom("name missing from the string table"))
+    }
+}
+
+fn lookup(strings: &[String], index: u32) -> Result<&str, speedy::Error> {
+    strings
+        .get(index as usize)
+        .map(|s| &**s)
+        .ok_or_else(|| speedy::Error::custom("string table index out of bounds"))
+}
+
+/// The string table followed by the value, with names written as indices into the table.
+struct InternedDocument<'a> {
+    table: &'a StringTable<'a>,
+    root: &'a Value,
+}
+
+impl<C> Writable<C> for InternedDocument<'_>
+where
+    C: Context,
+{
+    fn write_to<W>(&self, writer: &mut W) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>,
+    {
+        self.table.strings.write_to(writer)?;
+        write_value(self.root, &mut Interned(self.table), writer)
+    }
+}
+
+/// Names are written as indices into the string table, and struct fields in declaration order.
+struct Interned<'a>(&'a StringTable<'a>);
+
+impl<C> Names<C> for Interned<'_>
+where
+    C: Context,
+{
+    fn struct_fields<'a, W>(
+        &mut self,
+        struct_: &'a StructValue,
+        writer: &mut W,
+        pending: &mut Vec<Pending<'a>>,
+    ) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>,
+    {
+        write_length(Struct::fields_len(struct_), writer)?;
+        for (name, value) in struct_.values() {
+            pending.push(Pending::Name(name));
+            pending.push(Pending::Value(value));
+        }
+        Ok(())
+    }
+
+    fn name<W>(&mut self, name: &str, writer: &mut W) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>,
+    {
+        writer.write_u32(self.0.index(name)?)
+    }
+}
+
+/// Reads an [`InternedDocument`].
+struct ReadDocument(Value);
+
+impl<'a> Readable<'a, LimitContext> for ReadDocument {
+    fn read_from<R>(reader: &mut R) -> Result<Self, speedy::Error>
+    where
+        R: Reader<'a, LimitContext>,
+    {
+        let limit = DepthLimit::new(reader.context().0);
+        let strings: Vec<String> = reader.read_value()?;
+        let tag = reader.read_u32()?;
+        read_value(reader, &mut ReadInterned(&strings), tag, limit).map(Self)
+    }
+}
+
+/// Names are read as indices into the string table, and struct fields in declaration order.
+struct ReadInterned<'a>(&'a [String]);
+
+impl<C> ReadNames<C> for ReadInterned<'_>
+where
+    C: Context,
+{
+    fn field_names<'a, R>(&mut self, _reader: &mut R) -> Result<Option<Vec<String>>, C::Error>
+    where
+        R: Reader<'a, C>,
+    {
+        Ok(None)
+    }
+
+    fn name<'a, R>(&mut self, reader: &mut R) -> Result<String, C::Error>
+    where
+        R: Reader<'a, C>,
+    {
+        let index = reader.read_u32()?;
+        Ok(lookup(self.0, index)?.into())
+    }
+}
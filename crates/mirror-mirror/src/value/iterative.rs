@@ -0,0 +1,731 @@
+//! The operations on [`Value`] that have to visit the values nested inside it.
+//!
+//! The straightforward implementations recurse once per level of nesting, so values nested a few
+//! thousand levels deep, such as a long chain of parent and child structs, overflow the stack.
+//! These keep the work that's left in a `Vec` instead.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::mem;
+
+use super::OrdEqValue;
+use super::Value;
+use crate::entry_hash;
+use crate::enum_::EnumValue;
+use crate::enum_::EnumValueKind;
+use crate::named_field_hash;
+use crate::scalar_eq;
+use crate::scalar_hash;
+use crate::struct_::StructValue;
+use crate::Enum;
+use crate::FromReflect;
+use crate::Map;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::Struct;
+
+impl Value {
+    /// How many levels of structs, enums, tuples, lists, and maps the value is made of.
+    ///
+    /// Scalars and strings have a depth of 0, `[1, 2]` a depth of 1, `[[1], [2]]` a depth of 2,
+    /// and so on. Map keys count too.
+    ///
+    /// Serializing and deserializing values with serde, and parsing them, recurses once per level.
+    /// These fail for values nested more than [`Value::DEFAULT_DEPTH_LIMIT`] levels deep so
+    /// untrusted input can't overflow the stack.
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut stack = Vec::from([(self, 1)]);
+        while let Some((value, level)) = stack.pop() {
+            if !is_container(value) {
+                continue;
+            }
+            depth = depth.max(level);
+            for_each_child(value, |child| stack.push((child, level + 1)));
+        }
+        depth
+    }
+}
+
+fn is_container(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::StructValue(_)
+            | Value::EnumValue(_)
+            | Value::TupleStructValue(_)
+            | Value::TupleValue(_)
+            | Value::List(_)
+            | Value::Map(_)
+            | Value::OrderedMap(_)
+    )
+}
+
+/// Cheap to create and drop. Used in place of values that have been moved out or not created yet.
+fn placeholder() -> Value {
+    Value::bool(false)
+}
+
+fn for_each_child<'a>(value: &'a Value, mut f: impl FnMut(&'a Value)) {
+    match value {
+        Value::StructValue(value) => value.values().for_each(|(_, value)| f(value)),
+        Value::EnumValue(value) => match value.kind() {
+            EnumValueKind::Struct(fields) => fields.values().for_each(|(_, value)| f(value)),
+            EnumValueKind::Tuple(fields) => fields.values().iter().for_each(f),
+            EnumValueKind::Unit => {}
+        },
+        Value::TupleStructValue(value) => value.values().iter().for_each(f),
+        Value::TupleValue(value) => value.values().iter().for_each(f),
+        Value::List(list) => list.iter().for_each(f),
+        Value::Map(map) => {
+            for (key, value) in map.iter() {
+                f(key);
+                f(value);
+            }
+        }
+        Value::OrderedMap(map) => {
+            for (key, value) in map.entries() {
+                f(key);
+                f(value);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Drop the values nested inside `values` without recursing.
+///
+/// Called by the `Drop` impls of the types that hold values. Lists and maps are plain `Vec`s and
+/// `BTreeMap`s which can't have their own `Drop` impl, so a chain made only of lists and maps still
+/// recurses when it's dropped. As soon as it contains a struct, enum, tuple, or ordered map the
+/// rest of it is dropped here.
+pub(crate) fn drop_nested<'a>(values: impl IntoIterator<Item = &'a mut Value>) {
+    let mut stack = Vec::new();
+    for value in values {
+        take(value, &mut stack);
+    }
+    while let Some(mut value) = stack.pop() {
+        take_containers(&mut value, &mut stack);
+        // `value` no longer contains any containers so dropping it here doesn't recurse
+    }
+}
+
+fn take(value: &mut Value, stack: &mut Vec<Value>) {
+    if is_container(value) {
+        stack.push(mem::replace(value, placeholder()));
+    }
+}
+
+/// Move the containers directly inside `value` onto `stack`.
+fn take_containers(value: &mut Value, stack: &mut Vec<Value>) {
+    match value {
+        Value::StructValue(value) => {
+            for (_, value) in value.values_mut() {
+                take(value, stack);
+            }
+        }
+        Value::EnumValue(value) => match value.kind_mut() {
+            EnumValueKind::Struct(fields) => {
+                for (_, value) in fields.values_mut() {
+                    take(value, stack);
+                }
+            }
+            EnumValueKind::Tuple(fields) => {
+                for value in fields.values_mut() {
+                    take(value, stack);
+                }
+            }
+            EnumValueKind::Unit => {}
+        },
+        Value::TupleStructValue(value) => {
+            for value in value.values_mut() {
+                take(value, stack);
+            }
+        }
+        Value::TupleValue(value) => {
+            for value in value.values_mut() {
+                take(value, stack);
+            }
+        }
+        Value::List(list) => {
+            for value in list.iter_mut() {
+                take(value, stack);
+            }
+        }
+        // keys can't be replaced in place so the entries have to be moved out of the map
+        Value::Map(map)
+            if map
+                .iter()
+                .any(|(key, value)| is_container(key) || is_container(value)) =>
+        {
+            for (mut key, mut value) in mem::take(&mut **map) {
+                take(&mut key, stack);
+                take(&mut value, stack);
+            }
+        }
+        Value::OrderedMap(map) => {
+            for (key, value) in map.entries_mut() {
+                take(key, stack);
+                take(value, stack);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn clone(value: &Value) -> Value {
+    let mut out = clone_shallow(value);
+    {
+        let mut stack = Vec::new();
+        push_clones(value, &mut out, &mut stack);
+        while let Some((value, out)) = stack.pop() {
+            *out = clone_shallow(value);
+            push_clones(value, out, &mut stack);
+        }
+    }
+    out
+}
+
+/// Clone `value`, except for the containers inside it which are left as placeholders.
+fn clone_shallow(value: &Value) -> Value {
+    fn child(value: &Value) -> Value {
+        if is_container(value) {
+            placeholder()
+        } else {
+            clone_shallow(value)
+        }
+    }
+
+    match value {
+        Value::usize(inner) => Value::usize(*inner),
+        Value::u8(inner) => Value::u8(*inner),
+        Value::u16(inner) => Value::u16(*inner),
+        Value::u32(inner) => Value::u32(*inner),
+        Value::u64(inner) => Value::u64(*inner),
+        Value::u128(inner) => Value::u128(inner.clone()),
+        Value::i8(inner) => Value::i8(*inner),
+        Value::i16(inner) => Value::i16(*inner),
+        Value::i32(inner) => Value::i32(*inner),
+        Value::i64(inner) => Value::i64(*inner),
+        Value::i128(inner) => Value::i128(inner.clone()),
+        Value::bool(inner) => Value::bool(*inner),
+        Value::char(inner) => Value::char(*inner),
+        Value::f32(inner) => Value::f32(*inner),
+        Value::f64(inner) => Value::f64(*inner),
+        Value::String(inner) => Value::String(inner.clone()),
+        Value::StructValue(inner) => Value::StructValue(Box::new(inner.map_values(child))),
+        Value::EnumValue(inner) => Value::EnumValue(Box::new(inner.map_values(child))),
+        Value::TupleStructValue(inner) => {
+            Value::TupleStructValue(Box::new(inner.map_values(child)))
+        }
+        Value::TupleValue(inner) => Value::TupleValue(Box::new(inner.map_values(child))),
+        Value::List(inner) => Value::List(Box::new(inner.iter().map(child).collect())),
+        Value::Map(inner) => Value::Map(Box::new(
+            inner
+                .iter()
+                .map(|(key, value)| (key.clone(), child(value)))
+                .collect(),
+        )),
+        Value::OrderedMap(inner) => Value::OrderedMap(Box::new(inner.map_values(child))),
+    }
+}
+
+/// Push the containers inside `value` onto `stack`, along with the placeholders in `out`, a
+/// shallow clone of `value`, that they should be cloned into.
+fn push_clones<'a>(
+    value: &'a Value,
+    out: &'a mut Value,
+    stack: &mut Vec<(&'a Value, &'a mut Value)>,
+) {
+    let mut push = |pairs: &mut dyn Iterator<Item = (&'a Value, &'a mut Value)>| {
+        stack.extend(pairs.filter(|(value, _)| is_container(value)));
+    };
+
+    match (value, out) {
+        (Value::StructValue(value), Value::StructValue(out)) => {
+            push(&mut struct_pairs(value, out));
+        }
+        (Value::EnumValue(value), Value::EnumValue(out)) => match (value.kind(), out.kind_mut()) {
+            (EnumValueKind::Struct(value), EnumValueKind::Struct(out)) => {
+                push(&mut struct_pairs(value, out));
+            }
+            (EnumValueKind::Tuple(value), EnumValueKind::Tuple(out)) => {
+                push(&mut value.values().iter().zip(out.values_mut()));
+            }
+            _ => {}
+        },
+        (Value::TupleStructValue(value), Value::TupleStructValue(out)) => {
+            push(&mut value.values().iter().zip(out.values_mut()));
+        }
+        (Value::TupleValue(value), Value::TupleValue(out)) => {
+            push(&mut value.values().iter().zip(out.values_mut()));
+        }
+        (Value::List(value), Value::List(out)) => {
+            push(&mut value.iter().zip(out.iter_mut()));
+        }
+        (Value::Map(value), Value::Map(out)) => {
+            push(&mut value.values().zip(out.values_mut()));
+        }
+        (Value::OrderedMap(value), Value::OrderedMap(out)) => {
+            let out = out.entries_mut().iter_mut().map(|(_, value)| value);
+            push(&mut value.entries().map(|(_, value)| value).zip(out));
+        }
+        _ => {}
+    }
+}
+
+fn struct_pairs<'a>(
+    value: &'a StructValue,
+    out: &'a mut StructValue,
+) -> impl Iterator<Item = (&'a Value, &'a mut Value)> {
+    let out = out.values_mut().map(|(_, value)| value);
+    value.values().map(|(_, value)| value).zip(out)
+}
+
+enum Compare<'a> {
+    Values(&'a Value, &'a Value),
+    Done(Ordering),
+}
+
+/// Compares values the same way `#[derive(Ord)]` on `Value`, and the types inside it, would.
+pub(super) fn cmp(a: &Value, b: &Value) -> Ordering {
+    let mut stack = Vec::new();
+    let mut ordering = cmp_shallow(a, b, &mut stack);
+    while ordering == Ordering::Equal {
+        ordering = match stack.pop() {
+            Some(Compare::Values(a, b)) => cmp_shallow(a, b, &mut stack),
+            Some(Compare::Done(ordering)) => ordering,
+            None => break,
+        };
+    }
+    ordering
+}
+
+/// Compare `a` and `b` as far as possible without looking at the values inside them, and push
+/// the comparisons that are left onto `stack`.
+fn cmp_shallow<'a>(a: &'a Value, b: &'a Value, stack: &mut Vec<Compare<'a>>) -> Ordering {
+    match (a, b) {
+        (Value::StructValue(a), Value::StructValue(b)) => cmp_structs(a, b, stack),
+        (Value::EnumValue(a), Value::EnumValue(b)) => {
+            a.variant_name()
+                .cmp(b.variant_name())
+                .then_with(|| match (a.kind(), b.kind()) {
+                    (EnumValueKind::Struct(a), EnumValueKind::Struct(b)) => {
+                        cmp_structs(a, b, stack)
+                    }
+                    (EnumValueKind::Tuple(a), EnumValueKind::Tuple(b)) => {
+                        cmp_slices(a.values(), b.values(), stack)
+                    }
+                    // different kinds, or both units, so there are no values to compare
+                    (a, b) => a.cmp(b),
+                })
+        }
+        (Value::TupleStructValue(a), Value::TupleStructValue(b)) => {
+            cmp_slices(a.values(), b.values(), stack)
+        }
+        (Value::TupleValue(a), Value::TupleValue(b)) => cmp_slices(a.values(), b.values(), stack),
+        (Value::List(a), Value::List(b)) => cmp_slices(a, b, stack),
+        (Value::Map(a), Value::Map(b)) => {
+            stack.push(Compare::Done(a.len().cmp(&b.len())));
+            push_in_order(
+                stack,
+                a.iter().zip(b.iter()).flat_map(|((a_key, a), (b_key, b))| {
+                    [Compare::Values(a_key, b_key), Compare::Values(a, b)]
+                }),
+            );
+            Ordering::Equal
+        }
+        (Value::OrderedMap(a), Value::OrderedMap(b)) => {
+            stack.push(Compare::Done(Map::len(&**a).cmp(&Map::len(&**b))));
+            push_in_order(
+                stack,
+                a.entries()
+                    .zip(b.entries())
+                    .flat_map(|((a_key, a), (b_key, b))| {
+                        [Compare::Values(a_key, b_key), Compare::Values(a, b)]
+                    }),
+            );
+            Ordering::Equal
+        }
+        // scalars, or values of different variants which are ordered by the variant alone
+        _ => OrdEqValue::from(a).cmp(&OrdEqValue::from(b)),
+    }
+}
+
+fn cmp_structs<'a>(
+    a: &'a StructValue,
+    b: &'a StructValue,
+    stack: &mut Vec<Compare<'a>>,
+) -> Ordering {
+    let ordering = a.field_names().cmp(b.field_names());
+    if ordering != Ordering::Equal {
+        return ordering;
+    }
+    let (a, b) = (a.fields_by_name(), b.fields_by_name());
+    stack.push(Compare::Done(a.len().cmp(&b.len())));
+    push_in_order(
+        stack,
+        a.zip(b).flat_map(|((a_name, a), (b_name, b))| {
+            [Compare::Done(a_name.cmp(b_name)), Compare::Values(a, b)]
+        }),
+    );
+    Ordering::Equal
+}
+
+fn cmp_slices<'a>(a: &'a [Value], b: &'a [Value], stack: &mut Vec<Compare<'a>>) -> Ordering {
+    stack.push(Compare::Done(a.len().cmp(&b.len())));
+    push_in_order(stack, a.iter().zip(b).map(|(a, b)| Compare::Values(a, b)));
+    Ordering::Equal
+}
+
+/// Push `comparisons` so they're popped in the order they're yielded.
+fn push_in_order<'a>(stack: &mut Vec<Compare<'a>>, comparisons: impl Iterator<Item = Compare<'a>>) {
+    let start = stack.len();
+    stack.extend(comparisons);
+    stack[start..].reverse();
+}
+
+enum Hash<'a> {
+    Value(&'a Value),
+    /// Combine the hashes of the values inside `value`, which are the hashes from `start` on.
+    Combine {
+        value: &'a Value,
+        start: usize,
+    },
+}
+
+/// Hashes values the same way [`reflect_hash`](crate::reflect_hash) does. Values are never opaque
+/// so there's always a hash.
+pub(super) fn hash(value: &Value) -> u64 {
+    let mut stack = Vec::from([Hash::Value(value)]);
+    let mut hashes = Vec::new();
+    while let Some(task) = stack.pop() {
+        match task {
+            Hash::Value(value) => {
+                if let ReflectRef::Scalar(scalar) = value.reflect_ref() {
+                    hashes.push(scalar_hash(scalar));
+                    continue;
+                }
+                stack.push(Hash::Combine {
+                    value,
+                    start: hashes.len(),
+                });
+                let start = stack.len();
+                for_each_child(value, |child| stack.push(Hash::Value(child)));
+                stack[start..].reverse();
+            }
+            Hash::Combine { value, start } => {
+                let hash = combine(value, &hashes[start..]);
+                hashes.truncate(start);
+                hashes.push(hash);
+            }
+        }
+    }
+    hashes[0]
+}
+
+/// Hash a container given the hashes of the values inside it, in the order `for_each_child` visits
+/// them.
+fn combine(value: &Value, children: &[u64]) -> u64 {
+    use core::hash::Hash;
+    use core::hash::Hasher;
+
+    fn named_fields(struct_: &StructValue, children: &[u64], hasher: &mut impl Hasher) {
+        let fields = struct_
+            .values()
+            .zip(children)
+            .fold(0_u64, |fields, ((name, _), hash)| {
+                fields.wrapping_add(named_field_hash(name, *hash))
+            });
+        struct_.fields_len().hash(hasher);
+        fields.hash(hasher);
+    }
+
+    let mut hasher = crate::hash::DefaultHasher::default();
+    match value {
+        Value::StructValue(inner) => {
+            0_u8.hash(&mut hasher);
+            named_fields(inner, children, &mut hasher);
+        }
+        Value::TupleStructValue(_) => {
+            1_u8.hash(&mut hasher);
+            children.iter().for_each(|hash| hash.hash(&mut hasher));
+        }
+        Value::TupleValue(_) => {
+            2_u8.hash(&mut hasher);
+            children.iter().for_each(|hash| hash.hash(&mut hasher));
+        }
+        Value::EnumValue(inner) => {
+            3_u8.hash(&mut hasher);
+            inner.variant_name().hash(&mut hasher);
+            match inner.kind() {
+                EnumValueKind::Struct(fields) => named_fields(fields, children, &mut hasher),
+                _ => {
+                    children.iter().for_each(|hash| hash.hash(&mut hasher));
+                    inner.fields_len().hash(&mut hasher);
+                    0_u64.hash(&mut hasher);
+                }
+            }
+        }
+        Value::List(_) => {
+            4_u8.hash(&mut hasher);
+            children.iter().for_each(|hash| hash.hash(&mut hasher));
+        }
+        Value::Map(_) | Value::OrderedMap(_) => {
+            5_u8.hash(&mut hasher);
+            let entries = children.chunks(2).fold(0_u64, |entries, entry| {
+                entries.wrapping_add(entry_hash(entry[0], entry[1]))
+            });
+            (children.len() / 2).hash(&mut hasher);
+            entries.hash(&mut hasher);
+        }
+        _ => unreachable!("only containers are combined"),
+    }
+    hasher.finish()
+}
+
+enum Eq<'a> {
+    Values(&'a Value, &'a dyn Reflect),
+    Mismatch,
+}
+
+/// Compares values the same way [`reflect_partial_eq`](crate::reflect_partial_eq) does, going
+/// through the values in the same order so the result is the same when one side is opaque.
+pub(super) fn partial_eq(a: &Value, b: &dyn Reflect) -> Option<bool> {
+    let mut stack = Vec::from([Eq::Values(a, b)]);
+    while let Some(task) = stack.pop() {
+        let eq = match task {
+            Eq::Values(a, b) => eq_shallow(a, b, &mut stack)?,
+            Eq::Mismatch => false,
+        };
+        if !eq {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+/// Compare `a` and `b` as far as possible without looking at the values inside them, and push
+/// the comparisons that are left onto `stack`.
+fn eq_shallow<'a>(a: &'a Value, b: &'a dyn Reflect, stack: &mut Vec<Eq<'a>>) -> Option<bool> {
+    let start = stack.len();
+    match (a, b.reflect_ref()) {
+        (Value::StructValue(a), ReflectRef::Struct(b)) => {
+            if a.fields_len() != b.fields_len() {
+                return Some(false);
+            }
+            push_fields(a, |name| b.field(name), stack);
+        }
+        (Value::TupleStructValue(a), ReflectRef::TupleStruct(b)) => {
+            push_all(a.values(), b.fields(), stack);
+        }
+        (Value::TupleValue(a), ReflectRef::Tuple(b)) => push_all(a.values(), b.fields(), stack),
+        (Value::EnumValue(a), ReflectRef::Enum(b)) => {
+            if a.variant_name() != b.variant_name()
+                || a.variant_kind() != b.variant_kind()
+                || a.fields_len() != b.fields_len()
+            {
+                return Some(false);
+            }
+            match a.kind() {
+                EnumValueKind::Struct(fields) => push_fields(fields, |name| b.field(name), stack),
+                EnumValueKind::Tuple(fields) => {
+                    let b_fields = (0..b.fields_len()).filter_map(|index| b.field_at(index));
+                    push_all(fields.values(), b_fields, stack);
+                }
+                EnumValueKind::Unit => {}
+            }
+        }
+        // lists compare equal to arrays, just like they have the same `Value` representation
+        (Value::List(a), ReflectRef::List(b)) => push_all(a, b.iter(), stack),
+        (Value::List(a), ReflectRef::Array(b)) => push_all(a, b.iter(), stack),
+        (Value::Map(a), ReflectRef::Map(b)) => {
+            if a.len() != b.len() {
+                return Some(false);
+            }
+            push_entries(a.iter(), b, stack);
+        }
+        (Value::OrderedMap(a), ReflectRef::Map(b)) => {
+            if Map::len(&**a) != b.len() {
+                return Some(false);
+            }
+            push_entries(a.entries(), b, stack);
+        }
+        (_, ReflectRef::Opaque(_)) => return None,
+        (a, b) => {
+            let eq = matches!(
+                (a.reflect_ref(), b),
+                (ReflectRef::Scalar(a), ReflectRef::Scalar(b)) if scalar_eq(a, b)
+            );
+            return Some(eq);
+        }
+    }
+    // compare what was just pushed in order
+    stack[start..].reverse();
+    Some(true)
+}
+
+/// Fields are looked up by name. A missing field is a mismatch once the fields before it have
+/// been compared.
+fn push_fields<'a>(
+    a: &'a StructValue,
+    field: impl Fn(&str) -> Option<&'a dyn Reflect>,
+    stack: &mut Vec<Eq<'a>>,
+) {
+    for (name, a) in a.values() {
+        let Some(b) = field(name) else {
+            stack.push(Eq::Mismatch);
+            return;
+        };
+        stack.push(Eq::Values(a, b));
+    }
+}
+
+/// Values are compared pairwise, and a length mismatch is found once the shorter side runs out.
+fn push_all<'a>(
+    a: &'a [Value],
+    mut b: impl Iterator<Item = &'a dyn Reflect>,
+    stack: &mut Vec<Eq<'a>>,
+) {
+    for a in a {
+        let Some(b) = b.next() else {
+            stack.push(Eq::Mismatch);
+            return;
+        };
+        stack.push(Eq::Values(a, b));
+    }
+    if b.next().is_some() {
+        stack.push(Eq::Mismatch);
+    }
+}
+
+fn push_entries<'a>(
+    a: impl Iterator<Item = (&'a Value, &'a Value)>,
+    b: &'a dyn Map,
+    stack: &mut Vec<Eq<'a>>,
+) {
+    for (key, a) in a {
+        let Some(b) = b.get(key) else {
+            stack.push(Eq::Mismatch);
+            return;
+        };
+        stack.push(Eq::Values(a, b));
+    }
+}
+
+pub(super) fn patch(value: &mut Value, patch: &dyn Reflect) {
+    let mut stack = Vec::new();
+    patch_shallow(value, patch, &mut stack);
+    while let Some((value, patch)) = stack.pop() {
+        patch_shallow(value, patch, &mut stack);
+    }
+}
+
+/// Patch `value` if it's a scalar or a string. Otherwise push its fields, elements, or entries
+/// onto `stack` along with what to patch them with.
+fn patch_shallow<'a>(
+    value: &'a mut Value,
+    patch: &'a dyn Reflect,
+    stack: &mut Vec<(&'a mut Value, &'a dyn Reflect)>,
+) {
+    match value {
+        Value::StructValue(value) => {
+            let Some(patch) = patch.reflect_ref().as_struct() else {
+                return;
+            };
+            for (name, value) in value.values_mut() {
+                if let Some(patch) = patch.field(name) {
+                    stack.push((value, patch));
+                }
+            }
+        }
+        Value::EnumValue(value) => {
+            let Some(enum_) = patch.reflect_ref().as_enum() else {
+                return;
+            };
+            if value.variant_name() != enum_.variant_name() {
+                if let Some(new) = EnumValue::from_reflect(patch) {
+                    **value = new;
+                }
+                return;
+            }
+            match value.kind_mut() {
+                EnumValueKind::Struct(fields) => {
+                    for (name, value) in fields.values_mut() {
+                        if let Some(patch) = enum_.field(name) {
+                            stack.push((value, patch));
+                        }
+                    }
+                }
+                EnumValueKind::Tuple(fields) => {
+                    for (index, value) in fields.values_mut().iter_mut().enumerate() {
+                        if let Some(patch) = enum_.field_at(index) {
+                            stack.push((value, patch));
+                        }
+                    }
+                }
+                EnumValueKind::Unit => {}
+            }
+        }
+        Value::TupleStructValue(value) => {
+            let Some(patch) = patch.reflect_ref().as_tuple_struct() else {
+                return;
+            };
+            for (index, value) in value.values_mut().iter_mut().enumerate() {
+                if let Some(patch) = patch.field_at(index) {
+                    stack.push((value, patch));
+                }
+            }
+        }
+        Value::TupleValue(value) => {
+            let Some(patch) = patch.reflect_ref().as_tuple() else {
+                return;
+            };
+            for (index, value) in value.values_mut().iter_mut().enumerate() {
+                if let Some(patch) = patch.field_at(index) {
+                    stack.push((value, patch));
+                }
+            }
+        }
+        Value::List(list) => {
+            let Some(patch) = patch.reflect_ref().as_list() else {
+                return;
+            };
+            stack.extend(list.iter_mut().zip(patch.iter()));
+        }
+        Value::Map(map) => {
+            let Some(patch) = patch.reflect_ref().as_map() else {
+                return;
+            };
+            let patch = index_by_key(patch);
+            for (key, value) in map.iter_mut() {
+                if let Some(patch) = patch.get(key) {
+                    stack.push((value, *patch));
+                }
+            }
+        }
+        Value::OrderedMap(map) => {
+            let Some(patch) = patch.reflect_ref().as_map() else {
+                return;
+            };
+            let patch = index_by_key(patch);
+            for (key, value) in map.entries_mut() {
+                if let Some(patch) = patch.get(key) {
+                    stack.push((value, *patch));
+                }
+            }
+        }
+        scalar => scalar.as_reflect_mut().patch(patch),
+    }
+}
+
+/// The entries of `map` by their keys as values, so the entries of the map being patched can be
+/// looked up without comparing every pair of keys. The first entry wins if keys are repeated.
+fn index_by_key(map: &dyn Map) -> BTreeMap<Value, &dyn Reflect> {
+    let mut index = BTreeMap::new();
+    for (key, value) in map.iter() {
+        index.entry(key.to_value()).or_insert(value);
+    }
+    index
+}
@@ -4,17 +4,22 @@ use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::any::Any;
+use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::fmt;
 
 use ordered_float::OrderedFloat;
 
 use crate::enum_::EnumValue;
+use crate::map::OrderedMap;
 use crate::struct_::StructValue;
 use crate::tuple::TupleValue;
 use crate::tuple_struct::TupleStructValue;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::NodeId;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::OpaqueNode;
+#[cfg(feature = "type_info")]
 use crate::type_info::graph::TypeGraph;
 use crate::FromReflect;
 use crate::Reflect;
@@ -27,33 +32,138 @@ use crate::ScalarRef;
 use crate::TypeRoot;
 use crate::Typed;
 
+mod arena;
+mod borrowed;
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "speedy")]
+mod interned;
+mod iterative;
+#[cfg(feature = "speedy")]
+mod read;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(any(feature = "serde", feature = "speedy"))]
+mod tag;
+mod text;
+mod update;
+#[cfg(feature = "speedy")]
+mod write;
+
+pub use self::arena::ValueArena;
+pub use self::arena::ValueId;
+pub use self::borrowed::ValueRef;
+pub use self::text::ParseError;
+
+pub(crate) use self::iterative::drop_nested;
+pub(crate) use self::update::update_value;
+
+/// An owned, type erased value.
+///
+/// Variants whose data is larger than a pointer are boxed, which keeps `Value` at 16 bytes on 64 bit
+/// targets. That matters because values are mostly stored in lists and maps of other values.
+///
+/// Cloning, comparing, hashing, and patching values doesn't recurse, so values can be nested
+/// arbitrarily deep. Neither does reading and writing them with speedy, writing them as text, or
+/// dropping them, except through chains made only of lists and maps. Serde and parsing do though,
+/// see [`Value::depth`].
 #[allow(non_camel_case_types)]
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
 pub enum Value {
     usize(usize),
     u8(u8),
     u16(u16),
     u32(u32),
     u64(u64),
-    u128(u128),
+    u128(Box<u128>),
     i8(i8),
     i16(i16),
     i32(i32),
     i64(i64),
-    i128(i128),
+    i128(Box<i128>),
     bool(bool),
     char(char),
     f32(f32),
     f64(f64),
-    String(String),
+    String(Box<String>),
     StructValue(Box<StructValue>),
     EnumValue(Box<EnumValue>),
-    TupleStructValue(TupleStructValue),
-    TupleValue(TupleValue),
-    List(Vec<Value>),
-    Map(BTreeMap<Value, Value>),
+    TupleStructValue(Box<TupleStructValue>),
+    TupleValue(Box<TupleValue>),
+    List(Box<Vec<Value>>),
+    Map(Box<BTreeMap<Value, Value>>),
+    OrderedMap(Box<OrderedMap>),
+}
+
+impl Value {
+    /// How deeply values can be nested when they're serialized or deserialized with serde, or
+    /// parsed, see [`Value::depth`].
+    ///
+    /// This is about as deep as a thread with 2 MiB of stack, the default for threads other than
+    /// the main one, can go with `serde_json` in a debug build. Use
+    /// [`Value::deserialize_with_const_depth_limit`] to raise it for fields of derived types.
+    pub const DEFAULT_DEPTH_LIMIT: usize = 128;
+
+    /// The position of the variant, which is how derived impls identify it.
+    #[cfg(any(feature = "serde", feature = "speedy"))]
+    fn variant_index(&self) -> u32 {
+        match self {
+            Value::usize(_) => 0,
+            Value::u8(_) => 1,
+            Value::u16(_) => 2,
+            Value::u32(_) => 3,
+            Value::u64(_) => 4,
+            Value::u128(_) => 5,
+            Value::i8(_) => 6,
+            Value::i16(_) => 7,
+            Value::i32(_) => 8,
+            Value::i64(_) => 9,
+            Value::i128(_) => 10,
+            Value::bool(_) => 11,
+            Value::char(_) => 12,
+            Value::f32(_) => 13,
+            Value::f64(_) => 14,
+            Value::String(_) => 15,
+            Value::StructValue(_) => tag::STRUCT_VALUE,
+            Value::EnumValue(_) => tag::ENUM_VALUE,
+            Value::TupleStructValue(_) => tag::TUPLE_STRUCT_VALUE,
+            Value::TupleValue(_) => tag::TUPLE_VALUE,
+            Value::List(_) => tag::LIST,
+            Value::Map(_) => tag::MAP,
+            Value::OrderedMap(_) => tag::ORDERED_MAP,
+        }
+    }
+}
+
+/// How many more levels of nesting are allowed while serializing, deserializing, reading, or
+/// parsing a value.
+#[derive(Debug, Clone, Copy)]
+struct DepthLimit {
+    limit: usize,
+    remaining: usize,
+}
+
+impl DepthLimit {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            remaining: limit,
+        }
+    }
+
+    /// The limit for the values inside a container, or `None` if there's no room left for them.
+    fn nested(self) -> Option<Self> {
+        Some(Self {
+            remaining: self.remaining.checked_sub(1)?,
+            ..self
+        })
+    }
+}
+
+impl fmt::Display for DepthLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value is nested more than {} levels deep", self.limit)
+    }
 }
 
 impl FromReflect for Value {
@@ -63,7 +173,7 @@ impl FromReflect for Value {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
 enum OrdEqValue<'a> {
     usize(usize),
     u8(u8),
@@ -87,6 +197,7 @@ enum OrdEqValue<'a> {
     TupleValue(&'a TupleValue),
     List(&'a [Value]),
     Map(&'a BTreeMap<Value, Value>),
+    OrderedMap(&'a OrderedMap),
 }
 
 impl<'a> From<&'a Value> for OrdEqValue<'a> {
@@ -97,12 +208,12 @@ impl<'a> From<&'a Value> for OrdEqValue<'a> {
             Value::u16(inner) => OrdEqValue::u16(*inner),
             Value::u32(inner) => OrdEqValue::u32(*inner),
             Value::u64(inner) => OrdEqValue::u64(*inner),
-            Value::u128(inner) => OrdEqValue::u128(*inner),
+            Value::u128(inner) => OrdEqValue::u128(**inner),
             Value::i8(inner) => OrdEqValue::i8(*inner),
             Value::i16(inner) => OrdEqValue::i16(*inner),
             Value::i32(inner) => OrdEqValue::i32(*inner),
             Value::i64(inner) => OrdEqValue::i64(*inner),
-            Value::i128(inner) => OrdEqValue::i128(*inner),
+            Value::i128(inner) => OrdEqValue::i128(**inner),
             Value::bool(inner) => OrdEqValue::bool(*inner),
             Value::char(inner) => OrdEqValue::char(*inner),
             Value::f32(inner) => OrdEqValue::f32(OrderedFloat(*inner)),
@@ -114,13 +225,98 @@ impl<'a> From<&'a Value> for OrdEqValue<'a> {
             Value::TupleValue(inner) => OrdEqValue::TupleValue(inner),
             Value::List(inner) => OrdEqValue::List(inner),
             Value::Map(inner) => OrdEqValue::Map(inner),
+            Value::OrderedMap(inner) => OrdEqValue::OrderedMap(inner),
         }
     }
 }
 
+/// A key that maps keyed by [`Value`] can be searched with, so scalar keys can be looked up
+/// without building a `Value`, which would allocate for strings and 128 bit integers.
+trait BorrowedKey {
+    fn ord_eq(&self) -> OrdEqValue<'_>;
+}
+
+impl BorrowedKey for Value {
+    fn ord_eq(&self) -> OrdEqValue<'_> {
+        self.into()
+    }
+}
+
+impl BorrowedKey for OrdEqValue<'_> {
+    fn ord_eq(&self) -> OrdEqValue<'_> {
+        *self
+    }
+}
+
+impl<'a> Borrow<dyn BorrowedKey + 'a> for Value {
+    fn borrow(&self) -> &(dyn BorrowedKey + 'a) {
+        self
+    }
+}
+
+// maps only ever compare a scalar key with the stored `Value`s, and those compare the same way
+// `Value`s do since values of different variants are ordered by the variant alone
+impl PartialEq for dyn BorrowedKey + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.ord_eq() == other.ord_eq()
+    }
+}
+
+impl Eq for dyn BorrowedKey + '_ {}
+
+impl PartialOrd for dyn BorrowedKey + '_ {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for dyn BorrowedKey + '_ {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ord_eq().cmp(&other.ord_eq())
+    }
+}
+
+/// The keys of every integer type that can hold `key`.
+fn int_keys(key: i128) -> impl Iterator<Item = OrdEqValue<'static>> {
+    [
+        usize::try_from(key).ok().map(OrdEqValue::usize),
+        u8::try_from(key).ok().map(OrdEqValue::u8),
+        u16::try_from(key).ok().map(OrdEqValue::u16),
+        u32::try_from(key).ok().map(OrdEqValue::u32),
+        u64::try_from(key).ok().map(OrdEqValue::u64),
+        u128::try_from(key).ok().map(OrdEqValue::u128),
+        i8::try_from(key).ok().map(OrdEqValue::i8),
+        i16::try_from(key).ok().map(OrdEqValue::i16),
+        i32::try_from(key).ok().map(OrdEqValue::i32),
+        i64::try_from(key).ok().map(OrdEqValue::i64),
+        Some(OrdEqValue::i128(key)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Look up a string key in a map keyed by [`Value`] without allocating.
+pub(crate) fn get_str<'a, V>(map: &'a BTreeMap<Value, V>, key: &str) -> Option<&'a V> {
+    map.get(&OrdEqValue::String(key) as &dyn BorrowedKey)
+}
+
+pub(crate) fn get_str_mut<'a, V>(map: &'a mut BTreeMap<Value, V>, key: &str) -> Option<&'a mut V> {
+    map.get_mut(&OrdEqValue::String(key) as &dyn BorrowedKey)
+}
+
+/// Look up a key of any integer type in a map keyed by [`Value`] without allocating.
+pub(crate) fn get_int<V>(map: &BTreeMap<Value, V>, key: i128) -> Option<&V> {
+    int_keys(key).find_map(|key| map.get(&key as &dyn BorrowedKey))
+}
+
+pub(crate) fn get_int_mut<V>(map: &mut BTreeMap<Value, V>, key: i128) -> Option<&mut V> {
+    let key = int_keys(key).find(|key| map.contains_key(key as &dyn BorrowedKey))?;
+    map.get_mut(&key as &dyn BorrowedKey)
+}
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
-        OrdEqValue::from(self) == OrdEqValue::from(other)
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -134,7 +330,13 @@ impl PartialOrd for Value {
 
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> Ordering {
-        OrdEqValue::from(self).cmp(&OrdEqValue::from(other))
+        iterative::cmp(self, other)
+    }
+}
+
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        iterative::clone(self)
     }
 }
 
@@ -163,12 +365,14 @@ macro_rules! for_each_variant {
             Value::TupleValue($inner) => $expr,
             Value::List($inner) => $expr,
             Value::Map($inner) => $expr,
+            Value::OrderedMap($inner) => $expr,
         }
     };
 }
 
 impl Reflect for Value {
     fn type_info(&self) -> TypeRoot {
+        #[cfg(feature = "type_info")]
         impl Typed for Value {
             fn build(graph: &mut TypeGraph) -> NodeId {
                 graph.get_or_build_node_with::<Self, _>(|graph| {
@@ -181,15 +385,15 @@ impl Reflect for Value {
     }
 
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
-        for_each_variant!(*self, inner => Box::new(inner))
+        for_each_variant!(*self, inner => Box::new(inner).into_any())
     }
 
     fn as_any(&self) -> &dyn Any {
-        for_each_variant!(self, inner => inner)
+        for_each_variant!(self, inner => inner.as_any())
     }
 
     fn as_any_mut(&mut self) -> &mut dyn Any {
-        for_each_variant!(self, inner => inner)
+        for_each_variant!(self, inner => inner.as_any_mut())
     }
 
     fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
@@ -211,23 +415,24 @@ impl Reflect for Value {
             Value::u16(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::u32(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::u64(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
-            Value::u128(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
+            Value::u128(inner) => ReflectOwned::Scalar(ScalarOwned::from(*inner)),
             Value::i8(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::i16(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::i32(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::i64(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
-            Value::i128(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
+            Value::i128(inner) => ReflectOwned::Scalar(ScalarOwned::from(*inner)),
             Value::bool(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::char(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::f32(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
             Value::f64(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
-            Value::String(inner) => ReflectOwned::Scalar(ScalarOwned::from(inner)),
+            Value::String(inner) => ReflectOwned::Scalar(ScalarOwned::from(*inner)),
             Value::StructValue(inner) => ReflectOwned::Struct(inner),
             Value::EnumValue(inner) => ReflectOwned::Enum(inner),
-            Value::TupleStructValue(inner) => ReflectOwned::TupleStruct(Box::new(inner)),
-            Value::TupleValue(inner) => ReflectOwned::Tuple(Box::new(inner)),
-            Value::List(inner) => ReflectOwned::List(Box::new(inner)),
-            Value::Map(inner) => ReflectOwned::Map(Box::new(inner)),
+            Value::TupleStructValue(inner) => ReflectOwned::TupleStruct(inner),
+            Value::TupleValue(inner) => ReflectOwned::Tuple(inner),
+            Value::List(inner) => ReflectOwned::List(inner),
+            Value::Map(inner) => ReflectOwned::Map(inner),
+            Value::OrderedMap(inner) => ReflectOwned::Map(inner),
         }
     }
 
@@ -238,23 +443,24 @@ impl Reflect for Value {
             Value::u16(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::u32(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::u64(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
-            Value::u128(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
+            Value::u128(inner) => ReflectRef::Scalar(ScalarRef::from(**inner)),
             Value::i8(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::i16(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::i32(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::i64(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
-            Value::i128(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
+            Value::i128(inner) => ReflectRef::Scalar(ScalarRef::from(**inner)),
             Value::bool(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::char(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::f32(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
             Value::f64(inner) => ReflectRef::Scalar(ScalarRef::from(*inner)),
-            Value::String(inner) => ReflectRef::Scalar(ScalarRef::from(inner)),
+            Value::String(inner) => ReflectRef::Scalar(ScalarRef::from(&**inner)),
             Value::StructValue(inner) => ReflectRef::Struct(&**inner),
             Value::EnumValue(inner) => ReflectRef::Enum(&**inner),
-            Value::TupleStructValue(inner) => ReflectRef::TupleStruct(inner),
-            Value::TupleValue(inner) => ReflectRef::Tuple(inner),
-            Value::List(inner) => ReflectRef::List(inner),
-            Value::Map(inner) => ReflectRef::Map(inner),
+            Value::TupleStructValue(inner) => ReflectRef::TupleStruct(&**inner),
+            Value::TupleValue(inner) => ReflectRef::Tuple(&**inner),
+            Value::List(inner) => ReflectRef::List(&**inner),
+            Value::Map(inner) => ReflectRef::Map(&**inner),
+            Value::OrderedMap(inner) => ReflectRef::Map(&**inner),
         }
     }
 
@@ -265,28 +471,37 @@ impl Reflect for Value {
             Value::u16(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::u32(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::u64(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
-            Value::u128(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
+            Value::u128(inner) => ReflectMut::Scalar(ScalarMut::from(&mut **inner)),
             Value::i8(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::i16(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::i32(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::i64(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
-            Value::i128(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
+            Value::i128(inner) => ReflectMut::Scalar(ScalarMut::from(&mut **inner)),
             Value::bool(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::char(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::f32(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
             Value::f64(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
-            Value::String(inner) => ReflectMut::Scalar(ScalarMut::from(inner)),
+            Value::String(inner) => ReflectMut::Scalar(ScalarMut::from(&mut **inner)),
             Value::StructValue(inner) => ReflectMut::Struct(&mut **inner),
             Value::EnumValue(inner) => ReflectMut::Enum(&mut **inner),
-            Value::TupleStructValue(inner) => ReflectMut::TupleStruct(inner),
-            Value::TupleValue(inner) => ReflectMut::Tuple(inner),
-            Value::List(inner) => ReflectMut::List(inner),
-            Value::Map(inner) => ReflectMut::Map(inner),
+            Value::TupleStructValue(inner) => ReflectMut::TupleStruct(&mut **inner),
+            Value::TupleValue(inner) => ReflectMut::Tuple(&mut **inner),
+            Value::List(inner) => ReflectMut::List(&mut **inner),
+            Value::Map(inner) => ReflectMut::Map(&mut **inner),
+            Value::OrderedMap(inner) => ReflectMut::Map(&mut **inner),
         }
     }
 
     fn patch(&mut self, value: &dyn Reflect) {
-        for_each_variant!(self, inner => inner.patch(value))
+        iterative::patch(self, value);
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        Some(iterative::hash(self))
+    }
+
+    fn reflect_partial_eq(&self, other: &dyn Reflect) -> Option<bool> {
+        iterative::partial_eq(self, other)
     }
 
     fn to_value(&self) -> Value {
@@ -320,6 +535,20 @@ macro_rules! from_impls {
     };
 }
 
+macro_rules! boxed_from_impls {
+    (
+        $($ident:ident)*
+    ) => {
+        $(
+            impl From<$ident> for Value {
+                fn from(value: $ident) -> Self {
+                    Value::$ident(Box::new(value))
+                }
+            }
+        )*
+    };
+}
+
 impl From<StructValue> for Value {
     fn from(value: StructValue) -> Self {
         Value::StructValue(Box::new(value))
@@ -339,9 +568,13 @@ impl From<&str> for Value {
 }
 
 from_impls! {
-    usize u8 u16 u32 u64 u128
-    i8 i16 i32 i64 i128
+    usize u8 u16 u32 u64
+    i8 i16 i32 i64
     f32 f64
-    bool char String
-    TupleValue TupleStructValue
+    bool char
+}
+
+boxed_from_impls! {
+    u128 i128 String
+    TupleValue TupleStructValue OrderedMap
 }
@@ -0,0 +1,379 @@
+//! Reading values with speedy, in the format `#[derive(Readable)]` would use, without recursing.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter;
+
+use speedy::Context;
+use speedy::Endianness;
+use speedy::Readable;
+use speedy::Reader;
+
+use super::tag;
+use super::DepthLimit;
+use super::Value;
+use crate::enum_::EnumValue;
+use crate::enum_::EnumValueKind;
+use crate::map::OrderedMap;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+
+impl Value {
+    /// Read a value that's nested at most `limit` levels deep, see [`Value::depth`].
+    ///
+    /// Reading doesn't recurse, so the [`Readable`] impl accepts values nested arbitrarily deep.
+    /// This is for callers that want to bound how deeply the values they read are nested, for
+    /// example because they're handed to code that does recurse.
+    pub fn read_from_buffer_with_depth_limit(
+        buffer: &[u8],
+        limit: usize,
+    ) -> Result<Self, speedy::Error> {
+        let limited = Limited::read_from_buffer_with_ctx(LimitContext(limit), buffer)?;
+        Ok(limited.0)
+    }
+}
+
+/// Passes the limit to the [`Readable`] impls of [`Limited`] and of interned documents.
+pub(super) struct LimitContext(pub(super) usize);
+
+impl Context for LimitContext {
+    type Error = speedy::Error;
+
+    fn endianness(&self) -> Endianness {
+        Endianness::LittleEndian
+    }
+}
+
+struct Limited(Value);
+
+impl<'a> Readable<'a, LimitContext> for Limited {
+    fn read_from<R>(reader: &mut R) -> Result<Self, speedy::Error>
+    where
+        R: Reader<'a, LimitContext>,
+    {
+        let limit = DepthLimit::new(reader.context().0);
+        let tag = reader.read_u32()?;
+        read_value(reader, &mut Plain, tag, limit).map(Self)
+    }
+}
+
+impl<'a, C> Readable<'a, C> for Value
+where
+    C: Context,
+{
+    fn read_from<R>(reader: &mut R) -> Result<Self, C::Error>
+    where
+        R: Reader<'a, C>,
+    {
+        let tag = reader.read_u32()?;
+        read_value(reader, &mut Plain, tag, DepthLimit::new(usize::MAX))
+    }
+
+    fn minimum_bytes_needed() -> usize {
+        // the variant tag
+        4
+    }
+}
+
+impl<'a, C> Readable<'a, C> for StructValue
+where
+    C: Context,
+{
+    fn read_from<R>(reader: &mut R) -> Result<Self, C::Error>
+    where
+        R: Reader<'a, C>,
+    {
+        // the fields are read like those of a `Value::StructValue`, without the tag
+        let limit = DepthLimit::new(usize::MAX);
+        match read_value(reader, &mut Plain, tag::STRUCT_VALUE, limit)? {
+            Value::StructValue(struct_) => Ok(*struct_),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// How struct field names and enum variant names are read. This is what the plain and the
+/// interned encodings differ in.
+pub(super) trait Names<C>
+where
+    C: Context,
+{
+    /// Read what comes before the fields of a struct: the names of the fields in order, or `None`
+    /// if that's the order the fields are read in.
+    fn field_names<'a, R>(&mut self, reader: &mut R) -> Result<Option<Vec<String>>, C::Error>
+    where
+        R: Reader<'a, C>;
+
+    fn name<'a, R>(&mut self, reader: &mut R) -> Result<String, C::Error>
+    where
+        R: Reader<'a, C>;
+}
+
+/// Names are read as strings, and struct fields ordered by name after the field names, like
+/// `(Vec<String>, BTreeMap<String, Value>)`.
+struct Plain;
+
+impl<C> Names<C> for Plain
+where
+    C: Context,
+{
+    fn field_names<'a, R>(&mut self, reader: &mut R) -> Result<Option<Vec<String>>, C::Error>
+    where
+        R: Reader<'a, C>,
+    {
+        reader.read_value().map(Some)
+    }
+
+    fn name<'a, R>(&mut self, reader: &mut R) -> Result<String, C::Error>
+    where
+        R: Reader<'a, C>,
+    {
+        reader.read_value()
+    }
+}
+
+/// A container whose values are being read.
+struct Frame {
+    container: Container,
+    /// The names of the fields read so far, for structs and struct variants.
+    names: Vec<String>,
+    values: Vec<Value>,
+    /// How many values the container holds, counting both keys and values of maps.
+    len: usize,
+    /// The limit for the values inside the container.
+    limit: DepthLimit,
+}
+
+enum Container {
+    Struct(Option<Vec<String>>),
+    StructVariant(String, Option<Vec<String>>),
+    TupleVariant(String),
+    TupleStruct,
+    Tuple,
+    List,
+    Map,
+    OrderedMap,
+}
+
+/// What reading the start of a value gave.
+enum Start {
+    Value(Value),
+    Frame(Frame),
+}
+
+/// Read a value whose variant tag `tag` has already been read, keeping the containers that are
+/// being read in a `Vec` so deeply nested values don't overflow the stack.
+pub(super) fn read_value<'a, C, N, R>(
+    reader: &mut R,
+    names: &mut N,
+    tag: u32,
+    limit: DepthLimit,
+) -> Result<Value, C::Error>
+where
+    C: Context,
+    N: Names<C>,
+    R: Reader<'a, C>,
+{
+    let mut stack = Vec::<Frame>::new();
+    let mut first_tag = Some(tag);
+    loop {
+        let limit = match stack.last_mut() {
+            Some(frame) => {
+                if frame.has_names() {
+                    frame.names.push(names.name(reader)?);
+                }
+                frame.limit
+            }
+            None => limit,
+        };
+        let tag = match first_tag.take() {
+            Some(tag) => tag,
+            None => reader.read_u32()?,
+        };
+        let mut value = match read_start(reader, names, tag, limit)? {
+            Start::Value(value) => value,
+            Start::Frame(frame) => {
+                stack.push(frame);
+                continue;
+            }
+        };
+
+        // hand the value to the containers it completes
+        loop {
+            let Some(mut frame) = stack.pop() else {
+                return Ok(value);
+            };
+            frame.values.push(value);
+            if frame.values.len() < frame.len {
+                stack.push(frame);
+                break;
+            }
+            value = frame.finish();
+        }
+    }
+}
+
+/// Read a scalar, or what comes before the values of a container.
+fn read_start<'a, C, N, R>(
+    reader: &mut R,
+    names: &mut N,
+    tag: u32,
+    limit: DepthLimit,
+) -> Result<Start, C::Error>
+where
+    C: Context,
+    N: Names<C>,
+    R: Reader<'a, C>,
+{
+    if tag < tag::STRUCT_VALUE {
+        return read_scalar(reader, tag).map(Start::Value);
+    }
+
+    let limit = limit.nested().ok_or_else(|| speedy::Error::custom(limit))?;
+    let container = match tag {
+        tag::STRUCT_VALUE => Container::Struct(names.field_names(reader)?),
+        tag::ENUM_VALUE => {
+            let name = names.name(reader)?;
+            match reader.read_u32()? {
+                tag::STRUCT_VARIANT => Container::StructVariant(name, names.field_names(reader)?),
+                tag::TUPLE_VARIANT => Container::TupleVariant(name),
+                tag::UNIT_VARIANT => {
+                    let variant = EnumValue::from_parts(name, EnumValueKind::Unit);
+                    return Ok(Start::Value(variant.into()));
+                }
+                tag => return Err(invalid_variant("EnumValueKind", tag).into()),
+            }
+        }
+        tag::TUPLE_STRUCT_VALUE => Container::TupleStruct,
+        tag::TUPLE_VALUE => Container::Tuple,
+        tag::LIST => Container::List,
+        tag::MAP => Container::Map,
+        tag::ORDERED_MAP => Container::OrderedMap,
+        _ => return Err(invalid_variant("Value", tag).into()),
+    };
+
+    let mut len = read_length(reader)?;
+    if matches!(container, Container::Map | Container::OrderedMap) {
+        len = len
+            .checked_mul(2)
+            .ok_or_else(|| speedy::Error::custom("collection is too large"))?;
+    }
+    let frame = Frame {
+        container,
+        names: Vec::new(),
+        values: Vec::new(),
+        len,
+        limit,
+    };
+    if len == 0 {
+        Ok(Start::Value(frame.finish()))
+    } else {
+        Ok(Start::Frame(frame))
+    }
+}
+
+impl Frame {
+    fn has_names(&self) -> bool {
+        matches!(
+            self.container,
+            Container::Struct(_) | Container::StructVariant(..)
+        )
+    }
+
+    fn finish(self) -> Value {
+        let Self {
+            container,
+            names,
+            values,
+            ..
+        } = self;
+        match container {
+            Container::Struct(field_names) => struct_value(field_names, names, values).into(),
+            Container::StructVariant(name, field_names) => {
+                let fields = struct_value(field_names, names, values);
+                EnumValue::from_parts(name, EnumValueKind::Struct(fields)).into()
+            }
+            Container::TupleVariant(name) => {
+                let fields = TupleValue::from_values(values);
+                EnumValue::from_parts(name, EnumValueKind::Tuple(fields)).into()
+            }
+            Container::TupleStruct => {
+                let tuple = TupleValue::from_values(values);
+                Value::TupleStructValue(Box::new(TupleStructValue::from_tuple(tuple)))
+            }
+            Container::Tuple => Value::TupleValue(Box::new(TupleValue::from_values(values))),
+            Container::List => Value::List(Box::new(values)),
+            Container::Map => Value::Map(Box::new(entries(values).collect())),
+            Container::OrderedMap => {
+                let entries = entries(values).collect();
+                Value::OrderedMap(Box::new(OrderedMap::from_entries(entries)))
+            }
+        }
+    }
+}
+
+fn struct_value(
+    field_names: Option<Vec<String>>,
+    names: Vec<String>,
+    values: Vec<Value>,
+) -> StructValue {
+    let fields = names.into_iter().zip(values);
+    match field_names {
+        Some(field_names) => StructValue::from_parts(field_names, fields.collect()),
+        None => {
+            let mut struct_ = StructValue::new();
+            for (name, value) in fields {
+                struct_.set_field(name, value);
+            }
+            struct_
+        }
+    }
+}
+
+/// Pair up keys and values.
+fn entries(values: Vec<Value>) -> impl Iterator<Item = (Value, Value)> {
+    let mut values = values.into_iter();
+    iter::from_fn(move || Some((values.next()?, values.next()?)))
+}
+
+fn read_scalar<'a, C, R>(reader: &mut R, tag: u32) -> Result<Value, C::Error>
+where
+    C: Context,
+    R: Reader<'a, C>,
+{
+    let value = match tag {
+        0 => Value::usize(reader.read_value()?),
+        1 => Value::u8(reader.read_value()?),
+        2 => Value::u16(reader.read_value()?),
+        3 => Value::u32(reader.read_value()?),
+        4 => Value::u64(reader.read_value()?),
+        5 => Value::u128(reader.read_value()?),
+        6 => Value::i8(reader.read_value()?),
+        7 => Value::i16(reader.read_value()?),
+        8 => Value::i32(reader.read_value()?),
+        9 => Value::i64(reader.read_value()?),
+        10 => Value::i128(reader.read_value()?),
+        11 => Value::bool(reader.read_value()?),
+        12 => Value::char(reader.read_value()?),
+        13 => Value::f32(reader.read_value()?),
+        14 => Value::f64(reader.read_value()?),
+        15 => Value::String(reader.read_value()?),
+        _ => return Err(invalid_variant("Value", tag).into()),
+    };
+    Ok(value)
+}
+
+/// Collections are prefixed by their length as a `u32`.
+fn read_length<'a, C, R>(reader: &mut R) -> Result<usize, C::Error>
+where
+    C: Context,
+    R: Reader<'a, C>,
+{
+    Ok(reader.read_u32()? as usize)
+}
+
+fn invalid_variant(name: &str, tag: u32) -> speedy::Error {
+    speedy::Error::custom(format_args!("invalid `{name}` variant {tag}"))
+}
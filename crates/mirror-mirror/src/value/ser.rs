@@ -0,0 +1,276 @@
+//! Serializing values with serde, in the format `#[derive(Serialize)]` would use, but with a
+//! limit on how deeply they can be nested.
+
+use alloc::collections::BTreeMap;
+
+use serde::ser::Error;
+use serde::ser::SerializeStruct;
+use serde::ser::SerializeTuple;
+use serde::Serialize;
+use serde::Serializer;
+
+use super::de::VARIANTS;
+use super::tag;
+use super::DepthLimit;
+use super::Value;
+use crate::enum_::EnumValue;
+use crate::enum_::EnumValueKind;
+use crate::map::OrderedMap;
+use crate::struct_::StructValue;
+use crate::Enum;
+
+impl Value {
+    /// Serialize a value that's nested at most `limit` levels deep, see [`Value::depth`].
+    ///
+    /// Serializing recurses once per level so this protects against stack overflows, and makes
+    /// sure the result can be deserialized with the same limit. The [`Serialize`] impl uses
+    /// [`Value::DEFAULT_DEPTH_LIMIT`].
+    pub fn serialize_with_depth_limit<S>(
+        &self,
+        serializer: S,
+        limit: usize,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Limited(self, DepthLimit::new(limit)).serialize(serializer)
+    }
+
+    /// [`Value::serialize_with_depth_limit`] with the limit as a const parameter, so fields of
+    /// derived types can raise the limit with `#[serde(serialize_with = "...")]`, see
+    /// [`Value::deserialize_with_const_depth_limit`].
+    pub fn serialize_with_const_depth_limit<S, const LIMIT: usize>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_with_depth_limit(serializer, LIMIT)
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_with_depth_limit(serializer, Self::DEFAULT_DEPTH_LIMIT)
+    }
+}
+
+/// Something to serialize along with the limit for the values in it.
+struct Limited<'a, T>(&'a T, DepthLimit)
+where
+    T: ?Sized;
+
+impl Serialize for Limited<'_, Value> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Self(value, limit) = *self;
+        let index = value.variant_index();
+        let variant = VARIANTS[index as usize];
+
+        macro_rules! scalar {
+            ($inner:expr) => {
+                serializer.serialize_newtype_variant("Value", index, variant, $inner)
+            };
+        }
+
+        // the values in containers get one less level
+        macro_rules! nested {
+            ($limit:ident => $inner:expr) => {{
+                let $limit = limit.nested().ok_or_else(|| S::Error::custom(limit))?;
+                serializer.serialize_newtype_variant("Value", index, variant, &$inner)
+            }};
+        }
+
+        match value {
+            Value::usize(inner) => scalar!(inner),
+            Value::u8(inner) => scalar!(inner),
+            Value::u16(inner) => scalar!(inner),
+            Value::u32(inner) => scalar!(inner),
+            Value::u64(inner) => scalar!(inner),
+            Value::u128(inner) => scalar!(inner),
+            Value::i8(inner) => scalar!(inner),
+            Value::i16(inner) => scalar!(inner),
+            Value::i32(inner) => scalar!(inner),
+            Value::i64(inner) => scalar!(inner),
+            Value::i128(inner) => scalar!(inner),
+            Value::bool(inner) => scalar!(inner),
+            Value::char(inner) => scalar!(inner),
+            Value::f32(inner) => scalar!(inner),
+            Value::f64(inner) => scalar!(inner),
+            Value::String(inner) => scalar!(inner),
+            Value::StructValue(inner) => nested!(limit => Limited(&**inner, limit)),
+            Value::EnumValue(inner) => nested!(limit => Limited(&**inner, limit)),
+            Value::TupleStructValue(inner) => nested!(limit => TupleStruct(inner.values(), limit)),
+            Value::TupleValue(inner) => nested!(limit => Tuple(inner.values(), limit)),
+            Value::List(inner) => nested!(limit => Limited(&***inner, limit)),
+            Value::Map(inner) => nested!(limit => Limited(&**inner, limit)),
+            Value::OrderedMap(inner) => nested!(limit => Limited(&**inner, limit)),
+        }
+    }
+}
+
+impl Serialize for Limited<'_, StructValue> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut struct_ = serializer.serialize_struct("StructValue", 2)?;
+        struct_.serialize_field("field_names", self.0.field_names())?;
+        struct_.serialize_field("fields", &FieldsByName(self.0, self.1))?;
+        struct_.end()
+    }
+}
+
+/// The fields ordered by name, like the `Serialize` impl of `StructValue` writes them.
+struct FieldsByName<'a>(&'a StructValue, DepthLimit);
+
+impl Serialize for FieldsByName<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let limit = self.1;
+        serializer.collect_map(
+            self.0
+                .fields_by_name()
+                .map(|(name, value)| (name, Limited(value, limit))),
+        )
+    }
+}
+
+impl Serialize for Limited<'_, EnumValue> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut struct_ = serializer.serialize_struct("EnumValue", 2)?;
+        struct_.serialize_field("name", self.0.variant_name())?;
+        struct_.serialize_field("kind", &Limited(self.0.kind(), self.1))?;
+        struct_.end()
+    }
+}
+
+impl Serialize for Limited<'_, EnumValueKind> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        const NAME: &str = "EnumValueKind";
+        match self.0 {
+            EnumValueKind::Struct(fields) => serializer.serialize_newtype_variant(
+                NAME,
+                tag::STRUCT_VARIANT,
+                "Struct",
+                &Limited(fields, self.1),
+            ),
+            EnumValueKind::Tuple(fields) => serializer.serialize_newtype_variant(
+                NAME,
+                tag::TUPLE_VARIANT,
+                "Tuple",
+                &Tuple(fields.values(), self.1),
+            ),
+            EnumValueKind::Unit => {
+                serializer.serialize_unit_variant(NAME, tag::UNIT_VARIANT, "Unit")
+            }
+        }
+    }
+}
+
+/// The fields of a `TupleStructValue`.
+struct TupleStruct<'a>(&'a [Value], DepthLimit);
+
+impl Serialize for TupleStruct<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut struct_ = serializer.serialize_struct("TupleStructValue", 1)?;
+        struct_.serialize_field("tuple", &Tuple(self.0, self.1))?;
+        struct_.end()
+    }
+}
+
+/// The fields of a `TupleValue`.
+struct Tuple<'a>(&'a [Value], DepthLimit);
+
+impl Serialize for Tuple<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut struct_ = serializer.serialize_struct("TupleValue", 1)?;
+        struct_.serialize_field("fields", &Limited(self.0, self.1))?;
+        struct_.end()
+    }
+}
+
+impl Serialize for Limited<'_, [Value]> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let limit = self.1;
+        serializer.collect_seq(self.0.iter().map(|value| Limited(value, limit)))
+    }
+}
+
+impl Serialize for Limited<'_, BTreeMap<Value, Value>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let limit = self.1;
+        serializer.collect_map(
+            self.0
+                .iter()
+                .map(|(key, value)| (Limited(key, limit), Limited(value, limit))),
+        )
+    }
+}
+
+impl Serialize for Limited<'_, OrderedMap> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut struct_ = serializer.serialize_struct("OrderedMap", 1)?;
+        struct_.serialize_field("entries", &Entries(self.0, self.1))?;
+        struct_.end()
+    }
+}
+
+struct Entries<'a>(&'a OrderedMap, DepthLimit);
+
+impl Serialize for Entries<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let limit = self.1;
+        serializer.collect_seq(
+            self.0
+                .entries()
+                .map(|(key, value)| Entry(key, value, limit)),
+        )
+    }
+}
+
+struct Entry<'a>(&'a Value, &'a Value, DepthLimit);
+
+impl Serialize for Entry<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&Limited(self.0, self.2))?;
+        tuple.serialize_element(&Limited(self.1, self.2))?;
+        tuple.end()
+    }
+}
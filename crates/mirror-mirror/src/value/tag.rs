@@ -0,0 +1,19 @@
+//! The tags that identify the variants of [`Value`](super::Value) and
+//! [`EnumValueKind`](crate::enum_::EnumValueKind) in serde and speedy encodings. They're the
+//! positions of the variants, which is how derived impls number them, so they can't change
+//! without breaking values that have already been encoded.
+//!
+//! The scalar variants of `Value` come first, numbered from 0 in declaration order.
+
+/// The first tag that isn't a scalar's.
+pub(super) const STRUCT_VALUE: u32 = 16;
+pub(super) const ENUM_VALUE: u32 = 17;
+pub(super) const TUPLE_STRUCT_VALUE: u32 = 18;
+pub(super) const TUPLE_VALUE: u32 = 19;
+pub(super) const LIST: u32 = 20;
+pub(super) const MAP: u32 = 21;
+pub(super) const ORDERED_MAP: u32 = 22;
+
+pub(super) const STRUCT_VARIANT: u32 = 0;
+pub(super) const TUPLE_VARIANT: u32 = 1;
+pub(super) const UNIT_VARIANT: u32 = 2;
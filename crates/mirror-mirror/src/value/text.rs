@@ -0,0 +1,699 @@
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+
+use super::DepthLimit;
+use crate::enum_::EnumValue;
+use crate::enum_::EnumValueKind;
+use crate::map::OrderedMap;
+use crate::struct_::StructValue;
+use crate::tuple::TupleValue;
+use crate::tuple_struct::TupleStructValue;
+use crate::Enum;
+use crate::Value;
+
+impl Value {
+    /// Write the value in a stable text format, independent of serde.
+    ///
+    /// Equal values always produce the same string, and [`Value::parse_canonical`] turns it back
+    /// into an equal value.
+    ///
+    /// The syntax is close to Rust's, and to [`key_path!`](crate::key_path!):
+    ///
+    /// | Value | Syntax |
+    /// |---|---|
+    /// | Integers and floats | `1_u8`, `-1_i64`, `1.5_f32`, `inf_f64`, `NaN_f32` |
+    /// | Booleans, chars, and strings | `true`, `'a'`, `"foo\n"` |
+    /// | Structs | `{a: 1_i32, "not an identifier": 2_i32}` |
+    /// | Tuple structs | `#(1_i32, 2_i32)` |
+    /// | Tuples | `()`, `(1_i32,)`, `(1_i32, 2_i32)` |
+    /// | Enums | `::A`, `::B(1_i32)`, `::C{a: 1_i32}` |
+    /// | Lists | `[1_i32, 2_i32]` |
+    /// | Maps | `#{1_i32: "a", 2_i32: "b"}` |
+    /// | Ordered maps | `#ordered{"b": 1_i32, "a": 2_i32}` |
+    ///
+    /// When parsing, whitespace, `//` comments, and trailing commas are allowed, and integers and
+    /// floats without a suffix are `i32` and `f64` respectively, like in Rust. This method always
+    /// writes the suffix so parsing the result gives back the exact same value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mirror_mirror::Reflect;
+    /// use mirror_mirror::Value;
+    ///
+    /// #[derive(Reflect, Clone, Debug)]
+    /// struct Foo {
+    ///     a: u8,
+    ///     b: Option<String>,
+    /// }
+    ///
+    /// let value = Foo {
+    ///     a: 1,
+    ///     b: Some("hi".to_owned()),
+    /// }
+    /// .to_value();
+    ///
+    /// let text = value.to_canonical_string();
+    /// assert_eq!(text, r#"{a: 1_u8, b: ::Some("hi")}"#);
+    /// assert_eq!(Value::parse_canonical(&text).unwrap(), value);
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        let mut out = String::new();
+        write_value(self, &mut out);
+        out
+    }
+
+    /// Parse a value written in the text format described in [`Value::to_canonical_string`].
+    ///
+    /// Values nested more than [`Value::DEFAULT_DEPTH_LIMIT`] levels deep are rejected, see
+    /// [`Value::parse_canonical_with_depth_limit`].
+    pub fn parse_canonical(input: &str) -> Result<Value, ParseError> {
+        Self::parse_canonical_with_depth_limit(input, Self::DEFAULT_DEPTH_LIMIT)
+    }
+
+    /// Parse a value that's nested at most `limit` levels deep, see [`Value::depth`].
+    ///
+    /// Parsing recurses once per level so this protects against stack overflows caused by
+    /// untrusted input.
+    pub fn parse_canonical_with_depth_limit(
+        input: &str,
+        limit: usize,
+    ) -> Result<Value, ParseError> {
+        let mut parser = Parser {
+            input,
+            position: 0,
+            limit: DepthLimit::new(limit),
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < input.len() {
+            return Err(ParseError::TrailingInput {
+                position: parser.position,
+            });
+        }
+        Ok(value)
+    }
+}
+
+/// Something that's left to write.
+enum Pending<'a> {
+    Value(&'a Value),
+    Name(&'a str),
+    Text(&'static str),
+}
+
+/// Write `value` keeping what's left to write in a `Vec`, so deeply nested values don't overflow
+/// the stack.
+fn write_value(value: &Value, out: &mut String) {
+    let mut stack = Vec::from([Pending::Value(value)]);
+    while let Some(pending) = stack.pop() {
+        let value = match pending {
+            Pending::Value(value) => value,
+            Pending::Name(name) => {
+                write_name(name, out);
+                continue;
+            }
+            Pending::Text(text) => {
+                out.push_str(text);
+                continue;
+            }
+        };
+
+        let start = stack.len();
+        // writing to a `String` never fails
+        let _ = match value {
+            Value::usize(n) => write!(out, "{n}_usize"),
+            Value::u8(n) => write!(out, "{n}_u8"),
+            Value::u16(n) => write!(out, "{n}_u16"),
+            Value::u32(n) => write!(out, "{n}_u32"),
+            Value::u64(n) => write!(out, "{n}_u64"),
+            Value::u128(n) => write!(out, "{n}_u128"),
+            Value::i8(n) => write!(out, "{n}_i8"),
+            Value::i16(n) => write!(out, "{n}_i16"),
+            Value::i32(n) => write!(out, "{n}_i32"),
+            Value::i64(n) => write!(out, "{n}_i64"),
+            Value::i128(n) => write!(out, "{n}_i128"),
+            // `Debug` uses the shortest representation that parses back to the same float
+            Value::f32(n) => write!(out, "{n:?}_f32"),
+            Value::f64(n) => write!(out, "{n:?}_f64"),
+            Value::bool(b) => write!(out, "{b}"),
+            Value::char(c) => write!(out, "{c:?}"),
+            Value::String(s) => write!(out, "{s:?}"),
+            Value::StructValue(inner) => {
+                push_fields(inner.values(), out, &mut stack);
+                Ok(())
+            }
+            Value::TupleStructValue(inner) => {
+                out.push('#');
+                push_list(inner.values(), '(', ")", out, &mut stack);
+                Ok(())
+            }
+            Value::TupleValue(inner) => {
+                if let [value] = inner.values() {
+                    out.push('(');
+                    stack.push(Pending::Value(value));
+                    stack.push(Pending::Text(",)"));
+                } else {
+                    push_list(inner.values(), '(', ")", out, &mut stack);
+                }
+                Ok(())
+            }
+            Value::EnumValue(inner) => {
+                out.push_str("::");
+                write_name(inner.variant_name(), out);
+                match inner.kind() {
+                    EnumValueKind::Struct(fields) => push_fields(fields.values(), out, &mut stack),
+                    EnumValueKind::Tuple(fields) => {
+                        push_list(fields.values(), '(', ")", out, &mut stack)
+                    }
+                    EnumValueKind::Unit => {}
+                }
+                Ok(())
+            }
+            Value::List(inner) => {
+                push_list(inner, '[', "]", out, &mut stack);
+                Ok(())
+            }
+            Value::Map(inner) => {
+                out.push('#');
+                push_entries(inner.iter(), out, &mut stack);
+                Ok(())
+            }
+            Value::OrderedMap(inner) => {
+                out.push_str("#ordered");
+                push_entries(inner.entries(), out, &mut stack);
+                Ok(())
+            }
+        };
+        // what was just pushed is written first, and in order
+        stack[start..].reverse();
+    }
+}
+
+fn push_list<'a>(
+    values: &'a [Value],
+    open: char,
+    close: &'static str,
+    out: &mut String,
+    stack: &mut Vec<Pending<'a>>,
+) {
+    out.push(open);
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            stack.push(Pending::Text(", "));
+        }
+        stack.push(Pending::Value(value));
+    }
+    stack.push(Pending::Text(close));
+}
+
+fn push_fields<'a>(
+    fields: impl Iterator<Item = (&'a str, &'a Value)>,
+    out: &mut String,
+    stack: &mut Vec<Pending<'a>>,
+) {
+    out.push('{');
+    for (index, (name, value)) in fields.enumerate() {
+        if index > 0 {
+            stack.push(Pending::Text(", "));
+        }
+        stack.push(Pending::Name(name));
+        stack.push(Pending::Text(": "));
+        stack.push(Pending::Value(value));
+    }
+    stack.push(Pending::Text("}"));
+}
+
+fn push_entries<'a>(
+    entries: impl Iterator<Item = (&'a Value, &'a Value)>,
+    out: &mut String,
+    stack: &mut Vec<Pending<'a>>,
+) {
+    out.push('{');
+    for (index, (key, value)) in entries.enumerate() {
+        if index > 0 {
+            stack.push(Pending::Text(", "));
+        }
+        stack.push(Pending::Value(key));
+        stack.push(Pending::Text(": "));
+        stack.push(Pending::Value(value));
+    }
+    stack.push(Pending::Text("}"));
+}
+
+/// Names that aren't identifiers are quoted.
+fn write_name(name: &str, out: &mut String) {
+    if is_identifier(name) {
+        out.push_str(name);
+    } else {
+        let _ = write!(out, "{name:?}");
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The error returned by [`Value::parse_canonical`].
+///
+/// Positions are byte offsets into the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input ended in the middle of a value.
+    UnexpectedEnd { expected: &'static str },
+    /// Found a character that doesn't fit the syntax.
+    Unexpected {
+        position: usize,
+        found: char,
+        expected: &'static str,
+    },
+    /// A number is malformed, has an unknown suffix, or doesn't fit its type.
+    InvalidNumber { position: usize, text: String },
+    /// A string or char contains an invalid escape sequence.
+    InvalidEscape { position: usize },
+    /// There is more input after the value.
+    TrailingInput { position: usize },
+    /// A value is nested more than `limit` levels deep.
+    TooDeep { position: usize, limit: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd { expected } => {
+                write!(f, "expected {expected} but the input ended")
+            }
+            ParseError::Unexpected {
+                position,
+                found,
+                expected,
+            } => write!(f, "expected {expected} at {position} but found `{found}`"),
+            ParseError::InvalidNumber { position, text } => {
+                write!(f, "invalid number `{text}` at {position}")
+            }
+            ParseError::InvalidEscape { position } => {
+                write!(f, "invalid escape sequence at {position}")
+            }
+            ParseError::TrailingInput { position } => {
+                write!(f, "unexpected input after the value at {position}")
+            }
+            ParseError::TooDeep { position, limit } => {
+                write!(
+                    f,
+                    "value at {position} is nested more than {limit} levels deep"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    limit: DepthLimit,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = &self.input[self.position..];
+            if rest.starts_with("//") {
+                self.position += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+                self.position += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn error(&self, expected: &'static str) -> ParseError {
+        match self.peek() {
+            Some(found) => ParseError::Unexpected {
+                position: self.position,
+                found,
+                expected,
+            },
+            None => ParseError::UnexpectedEnd { expected },
+        }
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    /// Consume `c` if it's the next non whitespace character.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        let opens_container = matches!(self.peek(), Some('{' | '(' | '[' | '#'))
+            || self.input[self.position..].starts_with("::");
+        if !opens_container {
+            return self.value_inner();
+        }
+
+        let outer = self.limit;
+        self.limit = outer.nested().ok_or(ParseError::TooDeep {
+            position: self.position,
+            limit: outer.limit,
+        })?;
+        let value = self.value_inner();
+        self.limit = outer;
+        value
+    }
+
+    /// Parse a value without checking whether it's nested too deep.
+    fn value_inner(&mut self) -> Result<Value, ParseError> {
+        let value = match self.peek() {
+            Some('{') => {
+                let mut struct_ = StructValue::new();
+                self.fields(|name, value| struct_.set_field(name, value))?;
+                struct_.into()
+            }
+            Some('(') => {
+                self.position += 1;
+                let mut tuple = TupleValue::new();
+                let mut trailing_comma = false;
+                while !self.eat(')') {
+                    tuple.push_field(self.value()?);
+                    trailing_comma = self.eat(',');
+                    if !trailing_comma {
+                        self.expect(')', "`,` or `)`")?;
+                        break;
+                    }
+                }
+                // `(x)` is just `x` in parentheses
+                if tuple.values().len() == 1 && !trailing_comma {
+                    tuple.values()[0].clone()
+                } else {
+                    tuple.into()
+                }
+            }
+            Some('[') => {
+                self.position += 1;
+                Value::List(Box::new(self.list(']')?))
+            }
+            Some('#') => {
+                self.position += 1;
+                match self.peek() {
+                    Some('(') => {
+                        self.position += 1;
+                        let mut tuple_struct = TupleStructValue::new();
+                        for value in self.list(')')? {
+                            tuple_struct.push_field(value);
+                        }
+                        tuple_struct.into()
+                    }
+                    Some('{') => {
+                        let mut map = BTreeMap::new();
+                        self.entries(|key, value| {
+                            map.insert(key, value);
+                        })?;
+                        Value::Map(Box::new(map))
+                    }
+                    _ if self.input[self.position..].starts_with("ordered") => {
+                        self.position += "ordered".len();
+                        let mut map = OrderedMap::new();
+                        self.entries(|key, value| {
+                            map.set_entry(key, value);
+                        })?;
+                        map.into()
+                    }
+                    _ => return Err(self.error("`(`, `{`, or `ordered` after `#`")),
+                }
+            }
+            Some(':') if self.input[self.position..].starts_with("::") => {
+                self.position += 2;
+                let name = self.name()?;
+                self.skip_whitespace();
+                match self.peek() {
+                    Some('(') => {
+                        self.position += 1;
+                        let mut variant = EnumValue::new_tuple_variant(name);
+                        for value in self.list(')')? {
+                            variant.push_tuple_field(value);
+                        }
+                        variant.finish().into()
+                    }
+                    Some('{') => {
+                        let mut variant = EnumValue::new_struct_variant(name);
+                        self.fields(|name, value| variant.set_struct_field(name, value))?;
+                        variant.finish().into()
+                    }
+                    _ => EnumValue::new_unit_variant(name).into(),
+                }
+            }
+            Some('"') => Value::from(self.string()?),
+            Some('\'') => {
+                let start = self.position;
+                self.position += 1;
+                let c = self
+                    .char_in_literal('\'')?
+                    .ok_or_else(|| self.error("a char"))?;
+                if self.peek() != Some('\'') {
+                    return Err(ParseError::Unexpected {
+                        position: start,
+                        found: '\'',
+                        expected: "a single char",
+                    });
+                }
+                self.position += 1;
+                Value::char(c)
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' => self.number()?,
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let start = self.position;
+                let ident = self.identifier();
+                match ident {
+                    "true" => Value::bool(true),
+                    "false" => Value::bool(false),
+                    _ if ident.starts_with("inf") || ident.starts_with("NaN") => {
+                        self.position = start;
+                        self.number()?
+                    }
+                    _ => {
+                        self.position = start;
+                        return Err(self.error("a value"));
+                    }
+                }
+            }
+            _ => return Err(self.error("a value")),
+        };
+        Ok(value)
+    }
+
+    /// Parse comma separated values up to `close`. The opening delimiter must already have been
+    /// consumed.
+    fn list(&mut self, close: char) -> Result<Vec<Value>, ParseError> {
+        let mut values = Vec::new();
+        while !self.eat(close) {
+            values.push(self.value()?);
+            if !self.eat(',') {
+                self.expect(close, "`,` or a closing delimiter")?;
+                break;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Parse `{name: value, ...}`.
+    fn fields(&mut self, mut f: impl FnMut(String, Value)) -> Result<(), ParseError> {
+        self.expect('{', "`{`")?;
+        while !self.eat('}') {
+            let name = self.name()?;
+            self.expect(':', "`:`")?;
+            f(name, self.value()?);
+            if !self.eat(',') {
+                self.expect('}', "`,` or `}`")?;
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse `{key: value, ...}`.
+    fn entries(&mut self, mut f: impl FnMut(Value, Value)) -> Result<(), ParseError> {
+        self.expect('{', "`{`")?;
+        while !self.eat('}') {
+            let key = self.value()?;
+            self.expect(':', "`:`")?;
+            f(key, self.value()?);
+            if !self.eat(',') {
+                self.expect('}', "`,` or `}`")?;
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// An identifier or a quoted string.
+    fn name(&mut self) -> Result<String, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => self.string(),
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => Ok(self.identifier().to_owned()),
+            _ => Err(self.error("a name")),
+        }
+    }
+
+    fn identifier(&mut self) -> &'a str {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_') {
+            self.position += 1;
+        }
+        &self.input[start..self.position]
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"', "`\"`")?;
+        let mut out = String::new();
+        while let Some(c) = self.char_in_literal('"')? {
+            out.push(c);
+        }
+        self.position += 1;
+        Ok(out)
+    }
+
+    /// Parse a possibly escaped char inside a string or char literal. Returns `None`, without
+    /// consuming it, when reaching the unescaped `quote`.
+    fn char_in_literal(&mut self, quote: char) -> Result<Option<char>, ParseError> {
+        let start = self.position;
+        let c = match self.bump() {
+            None => {
+                return Err(ParseError::UnexpectedEnd {
+                    expected: "a closing quote",
+                })
+            }
+            Some(c) if c == quote => {
+                self.position = start;
+                return Ok(None);
+            }
+            Some('\\') => match self.bump() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('0') => '\0',
+                Some('\\') => '\\',
+                Some('"') => '"',
+                Some('\'') => '\'',
+                Some('u') => {
+                    let rest = &self.input[self.position..];
+                    let code = rest
+                        .strip_prefix('{')
+                        .and_then(|rest| rest.split_once('}'))
+                        .and_then(|(code, _)| {
+                            u32::from_str_radix(code, 16).ok().map(|n| (code, n))
+                        });
+                    match code.and_then(|(code, n)| Some((code, char::from_u32(n)?))) {
+                        Some((code, c)) => {
+                            self.position += code.len() + 2;
+                            c
+                        }
+                        None => return Err(ParseError::InvalidEscape { position: start }),
+                    }
+                }
+                _ => return Err(ParseError::InvalidEscape { position: start }),
+            },
+            Some(c) => c,
+        };
+        Ok(Some(c))
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.position;
+        let mut prev = None;
+        while let Some(c) = self.peek() {
+            let is_sign = matches!(c, '-' | '+')
+                && (self.position == start || matches!(prev, Some('e' | 'E')));
+            if !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.') || is_sign) {
+                break;
+            }
+            prev = Some(c);
+            self.position += 1;
+        }
+
+        let text = &self.input[start..self.position];
+        parse_number(text).ok_or_else(|| ParseError::InvalidNumber {
+            position: start,
+            text: text.to_owned(),
+        })
+    }
+}
+
+fn parse_number(text: &str) -> Option<Value> {
+    const SUFFIXES: &[&str] = &[
+        "usize", "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+    ];
+
+    let (number, suffix) = match text.rsplit_once('_') {
+        Some((number, suffix)) if SUFFIXES.contains(&suffix) => (number, Some(suffix)),
+        _ => (text, None),
+    };
+    let number = number.replace('_', "");
+    let number = number.strip_prefix('+').unwrap_or(&number);
+
+    let suffix = suffix.unwrap_or_else(|| {
+        let is_float = number.contains(['.', 'e', 'E'])
+            || number
+                .trim_start_matches('-')
+                .starts_with(char::is_alphabetic);
+        if is_float {
+            "f64"
+        } else {
+            "i32"
+        }
+    });
+
+    let value = match suffix {
+        "usize" => Value::usize(number.parse().ok()?),
+        "u8" => Value::u8(number.parse().ok()?),
+        "u16" => Value::u16(number.parse().ok()?),
+        "u32" => Value::u32(number.parse().ok()?),
+        "u64" => Value::u64(number.parse().ok()?),
+        "u128" => Value::from(number.parse::<u128>().ok()?),
+        "i8" => Value::i8(number.parse().ok()?),
+        "i16" => Value::i16(number.parse().ok()?),
+        "i32" => Value::i32(number.parse().ok()?),
+        "i64" => Value::i64(number.parse().ok()?),
+        "i128" => Value::from(number.parse::<i128>().ok()?),
+        "f32" => Value::f32(number.parse().ok()?),
+        "f64" => Value::f64(number.parse().ok()?),
+        _ => return None,
+    };
+    Some(value)
+}
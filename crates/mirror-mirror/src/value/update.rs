@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+
+use crate::enum_::EnumValueKind;
+use crate::struct_::StructValue;
+use crate::Enum;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Struct;
+use crate::Value;
+
+/// The implementation of [`Reflect::update_value`].
+pub(crate) fn update_value(value: &mut Value, reflect: &dyn Reflect) {
+    if !update_in_place(value, reflect) {
+        *value = reflect.to_value();
+    }
+}
+
+/// Returns `false` if `value` doesn't have the same shape as `reflect` and must be replaced. It
+/// might have been partially updated by then.
+fn update_in_place(value: &mut Value, reflect: &dyn Reflect) -> bool {
+    match (reflect.reflect_ref(), value) {
+        (ReflectRef::Struct(reflect), Value::StructValue(value)) => update_struct(
+            value,
+            reflect.fields_len(),
+            |index| reflect.name_at(index),
+            |index| reflect.field_at(index),
+        ),
+        (ReflectRef::TupleStruct(reflect), Value::TupleStructValue(value)) => {
+            update_tuple(value.values_mut(), reflect.fields_len(), |index| {
+                reflect.field_at(index)
+            })
+        }
+        (ReflectRef::Tuple(reflect), Value::TupleValue(value)) => {
+            update_tuple(value.values_mut(), reflect.fields_len(), |index| {
+                reflect.field_at(index)
+            })
+        }
+        (ReflectRef::Enum(reflect), Value::EnumValue(value)) => {
+            if reflect.variant_name() != value.variant_name()
+                || reflect.variant_kind() != value.variant_kind()
+            {
+                return false;
+            }
+            let len = reflect.fields_len();
+            match value.kind_mut() {
+                EnumValueKind::Struct(value) => update_struct(
+                    value,
+                    len,
+                    |index| reflect.name_at(index),
+                    |index| reflect.field_at(index),
+                ),
+                EnumValueKind::Tuple(value) => {
+                    update_tuple(value.values_mut(), len, |index| reflect.field_at(index))
+                }
+                EnumValueKind::Unit => true,
+            }
+        }
+        (ReflectRef::Array(reflect), Value::List(list)) => {
+            update_list(list, reflect.len(), |index| reflect.get(index))
+        }
+        (ReflectRef::List(reflect), Value::List(list)) => {
+            update_list(list, reflect.len(), |index| reflect.get(index))
+        }
+        // keys would have to be converted to `Value`s to find the entries to update, so maps are
+        // only kept if nothing changed
+        (ReflectRef::Map(reflect), value @ (Value::Map(_) | Value::OrderedMap(_))) => {
+            reflect.reflect_partial_eq(value).unwrap_or(false)
+        }
+        (ReflectRef::Scalar(ScalarRef::String(reflect)), Value::String(value)) => {
+            if **value != reflect {
+                value.clear();
+                value.push_str(reflect);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+fn update_struct<'a>(
+    value: &mut StructValue,
+    len: usize,
+    name_at: impl Fn(usize) -> Option<&'a str>,
+    field_at: impl Fn(usize) -> Option<&'a dyn Reflect>,
+) -> bool {
+    if value.fields_len() != len {
+        return false;
+    }
+    for index in 0..len {
+        let (Some(name), Some(field)) = (name_at(index), field_at(index)) else { return false };
+        if value.name_at(index) != Some(name) {
+            return false;
+        }
+        let Some(value) = value.value_mut(name) else { return false };
+        update_value(value, field);
+    }
+    true
+}
+
+fn update_tuple<'a>(
+    values: &mut [Value],
+    len: usize,
+    field_at: impl Fn(usize) -> Option<&'a dyn Reflect>,
+) -> bool {
+    if values.len() != len {
+        return false;
+    }
+    for (index, value) in values.iter_mut().enumerate() {
+        let Some(field) = field_at(index) else { return false };
+        update_value(value, field);
+    }
+    true
+}
+
+fn update_list<'a>(
+    list: &mut Vec<Value>,
+    len: usize,
+    get: impl Fn(usize) -> Option<&'a dyn Reflect>,
+) -> bool {
+    list.truncate(len);
+    for index in 0..len {
+        let Some(element) = get(index) else { return false };
+        match list.as_mut_slice().get_mut(index) {
+            Some(value) => update_value(value, element),
+            None => list.push(element.to_value()),
+        }
+    }
+    true
+}
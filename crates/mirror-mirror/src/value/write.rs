@@ -0,0 +1,193 @@
+//! Writing values with speedy, in the format `#[derive(Writable)]` would use, without recursing.
+
+use alloc::vec::Vec;
+
+use speedy::Context;
+use speedy::Writable;
+use speedy::Writer;
+
+use super::tag;
+use super::Value;
+use crate::enum_::EnumValueKind;
+use crate::struct_::StructValue;
+use crate::Enum;
+use crate::Map;
+
+impl<C> Writable<C> for Value
+where
+    C: Context,
+{
+    fn write_to<W>(&self, writer: &mut W) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>,
+    {
+        write_value(self, &mut Plain, writer)
+    }
+}
+
+/// Something that's left to write.
+pub(super) enum Pending<'a> {
+    Value(&'a Value),
+    Name(&'a str),
+}
+
+/// How struct field names and enum variant names are written. This is what the plain and the
+/// interned encodings differ in.
+pub(super) trait Names<C>
+where
+    C: Context,
+{
+    /// Write what comes before the fields of `struct_` and push the fields onto `pending`, in
+    /// the order they're written in.
+    fn struct_fields<'a, W>(
+        &mut self,
+        struct_: &'a StructValue,
+        writer: &mut W,
+        pending: &mut Vec<Pending<'a>>,
+    ) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>;
+
+    fn name<W>(&mut self, name: &str, writer: &mut W) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>;
+}
+
+/// Names are written as strings, and struct fields ordered by name after the field names, like
+/// `(Vec<String>, BTreeMap<String, Value>)`.
+struct Plain;
+
+impl<C> Names<C> for Plain
+where
+    C: Context,
+{
+    fn struct_fields<'a, W>(
+        &mut self,
+        struct_: &'a StructValue,
+        writer: &mut W,
+        pending: &mut Vec<Pending<'a>>,
+    ) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>,
+    {
+        struct_.field_names().write_to(writer)?;
+        let fields = struct_.fields_by_name();
+        write_length(fields.len(), writer)?;
+        for (name, value) in fields {
+            pending.push(Pending::Name(name));
+            pending.push(Pending::Value(value));
+        }
+        Ok(())
+    }
+
+    fn name<W>(&mut self, name: &str, writer: &mut W) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer<C>,
+    {
+        name.write_to(writer)
+    }
+}
+
+/// Write `value` keeping the values that are left to write in a `Vec`, so deeply nested values
+/// don't overflow the stack.
+pub(super) fn write_value<C, N, W>(
+    value: &Value,
+    names: &mut N,
+    writer: &mut W,
+) -> Result<(), C::Error>
+where
+    C: Context,
+    N: Names<C>,
+    W: ?Sized + Writer<C>,
+{
+    let mut stack = Vec::from([Pending::Value(value)]);
+    while let Some(pending) = stack.pop() {
+        let value = match pending {
+            Pending::Value(value) => value,
+            Pending::Name(name) => {
+                names.name(name, writer)?;
+                continue;
+            }
+        };
+
+        writer.write_u32(value.variant_index())?;
+        let start = stack.len();
+        match value {
+            Value::usize(inner) => inner.write_to(writer)?,
+            Value::u8(inner) => inner.write_to(writer)?,
+            Value::u16(inner) => inner.write_to(writer)?,
+            Value::u32(inner) => inner.write_to(writer)?,
+            Value::u64(inner) => inner.write_to(writer)?,
+            Value::u128(inner) => inner.write_to(writer)?,
+            Value::i8(inner) => inner.write_to(writer)?,
+            Value::i16(inner) => inner.write_to(writer)?,
+            Value::i32(inner) => inner.write_to(writer)?,
+            Value::i64(inner) => inner.write_to(writer)?,
+            Value::i128(inner) => inner.write_to(writer)?,
+            Value::bool(inner) => inner.write_to(writer)?,
+            Value::char(inner) => inner.write_to(writer)?,
+            Value::f32(inner) => inner.write_to(writer)?,
+            Value::f64(inner) => inner.write_to(writer)?,
+            Value::String(inner) => inner.write_to(writer)?,
+            Value::StructValue(inner) => names.struct_fields(inner, writer, &mut stack)?,
+            Value::EnumValue(inner) => {
+                names.name(inner.variant_name(), writer)?;
+                match inner.kind() {
+                    EnumValueKind::Struct(fields) => {
+                        writer.write_u32(tag::STRUCT_VARIANT)?;
+                        names.struct_fields(fields, writer, &mut stack)?;
+                    }
+                    EnumValueKind::Tuple(fields) => {
+                        writer.write_u32(tag::TUPLE_VARIANT)?;
+                        push_list(fields.values(), writer, &mut stack)?;
+                    }
+                    EnumValueKind::Unit => writer.write_u32(tag::UNIT_VARIANT)?,
+                }
+            }
+            Value::TupleStructValue(inner) => push_list(inner.values(), writer, &mut stack)?,
+            Value::TupleValue(inner) => push_list(inner.values(), writer, &mut stack)?,
+            Value::List(inner) => push_list(inner, writer, &mut stack)?,
+            Value::Map(inner) => {
+                write_length(inner.len(), writer)?;
+                for (key, value) in inner.iter() {
+                    stack.push(Pending::Value(key));
+                    stack.push(Pending::Value(value));
+                }
+            }
+            Value::OrderedMap(inner) => {
+                write_length(Map::len(&**inner), writer)?;
+                for (key, value) in inner.entries() {
+                    stack.push(Pending::Value(key));
+                    stack.push(Pending::Value(value));
+                }
+            }
+        }
+        // what was just pushed is written first, and in order
+        stack[start..].reverse();
+    }
+    Ok(())
+}
+
+fn push_list<'a, C, W>(
+    values: &'a [Value],
+    writer: &mut W,
+    stack: &mut Vec<Pending<'a>>,
+) -> Result<(), C::Error>
+where
+    C: Context,
+    W: ?Sized + Writer<C>,
+{
+    write_length(values.len(), writer)?;
+    stack.extend(values.iter().map(Pending::Value));
+    Ok(())
+}
+
+/// Collections are prefixed by their length as a `u32`.
+pub(super) fn write_length<C, W>(len: usize, writer: &mut W) -> Result<(), C::Error>
+where
+    C: Context,
+    W: ?Sized + Writer<C>,
+{
+    let len = u32::try_from(len).map_err(|_| speedy::Error::custom("collection is too large"))?;
+    writer.write_u32(len)
+}
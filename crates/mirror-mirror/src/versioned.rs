@@ -0,0 +1,133 @@
+//! Speedy encodings prefixed with a format version.
+//!
+//! The derived speedy encodings depend on the order of enum variants and fields, so a change to
+//! the layout would otherwise make old data decode into garbage, or fail to decode, without any
+//! indication of why. The versioned encoding starts with [`MAGIC`] followed by the format
+//! version as a little endian `u16`.
+//!
+//! Versions:
+//!
+//! - `0`: no header. This is what the derived encodings wrote before the header was added, and
+//!   is assumed when the buffer doesn't start with [`MAGIC`]. Only accepted for [`Value`], whose
+//!   layout hasn't changed since. The layout of type info changed several times before the
+//!   header was added, so type info without a header is rejected rather than misread.
+//! - `1`: the header followed by the derived encoding.
+//!
+//! When the layout of a type changes, the previous layout should be kept around so the previous
+//! versions can still be read.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use speedy::LittleEndian;
+use speedy::Readable;
+use speedy::Writable;
+
+#[cfg(feature = "type_info")]
+use crate::type_info::graph::TypeGraph;
+#[cfg(feature = "type_info")]
+use crate::TypeRoot;
+use crate::Value;
+
+const MAGIC: [u8; 4] = *b"MIRR";
+
+const FORMAT_VERSION: u16 = 1;
+
+impl Value {
+    /// Encode the value with [`speedy`], prefixed with the version of the format.
+    ///
+    /// Use [`Value::read_versioned_from_buffer`] to decode the result.
+    pub fn write_versioned_to_vec(&self) -> Result<Vec<u8>, speedy::Error> {
+        write(self)
+    }
+
+    /// Decode a value encoded with [`Value::write_versioned_to_vec`], by this or an earlier
+    /// version of the crate.
+    ///
+    /// Buffers without a version, written with the derived [`speedy::Writable`] implementation,
+    /// are also accepted.
+    pub fn read_versioned_from_buffer(buffer: &[u8]) -> Result<Self, speedy::Error> {
+        read(buffer, Headerless::Accept)
+    }
+}
+
+#[cfg(feature = "type_info")]
+impl TypeGraph {
+    /// Encode the graph with [`speedy`], prefixed with the version of the format.
+    ///
+    /// Use [`TypeGraph::read_versioned_from_buffer`] to decode the result.
+    pub fn write_versioned_to_vec(&self) -> Result<Vec<u8>, speedy::Error> {
+        write(self)
+    }
+
+    /// Decode a graph encoded with [`TypeGraph::write_versioned_to_vec`], by this or an earlier
+    /// version of the crate.
+    ///
+    /// Buffers without a version are rejected, since graphs written before the version was added
+    /// used a different layout.
+    pub fn read_versioned_from_buffer(buffer: &[u8]) -> Result<Self, speedy::Error> {
+        read(buffer, Headerless::Reject)
+    }
+}
+
+#[cfg(feature = "type_info")]
+impl TypeRoot {
+    /// Encode the type info with [`speedy`], prefixed with the version of the format.
+    ///
+    /// Use [`TypeRoot::read_versioned_from_buffer`] to decode the result.
+    pub fn write_versioned_to_vec(&self) -> Result<Vec<u8>, speedy::Error> {
+        write(self)
+    }
+
+    /// Decode type info encoded with [`TypeRoot::write_versioned_to_vec`], by this or an
+    /// earlier version of the crate.
+    ///
+    /// Buffers without a version are rejected, since type info written before the version was
+    /// added used a different layout.
+    pub fn read_versioned_from_buffer(buffer: &[u8]) -> Result<Self, speedy::Error> {
+        read(buffer, Headerless::Reject)
+    }
+}
+
+fn write<T>(value: &T) -> Result<Vec<u8>, speedy::Error>
+where
+    T: Writable<LittleEndian>,
+{
+    let mut buffer = Vec::from(MAGIC);
+    buffer.extend(FORMAT_VERSION.to_le_bytes());
+    value.write_to_stream(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Whether buffers without a header, version `0`, can be read.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Headerless {
+    Accept,
+    Reject,
+}
+
+fn read<T>(buffer: &[u8], headerless: Headerless) -> Result<T, speedy::Error>
+where
+    T: for<'a> Readable<'a, LittleEndian>,
+{
+    let (version, body) = match buffer.strip_prefix(&MAGIC) {
+        Some([low, high, body @ ..]) => (u16::from_le_bytes([*low, *high]), body),
+        Some(_) => return Err(speedy::Error::custom("missing format version")),
+        None if headerless == Headerless::Accept => (0, buffer),
+        None => {
+            return Err(speedy::Error::custom(
+                "missing format version, data written before the version was added isn't \
+                 supported",
+            ))
+        }
+    };
+
+    match version {
+        // version 1 only added the header
+        0 | 1 => T::read_from_buffer(body),
+        _ => Err(speedy::Error::custom(format!(
+            "unsupported format version {version}, the latest supported version is \
+             {FORMAT_VERSION}"
+        ))),
+    }
+}
@@ -0,0 +1,295 @@
+use alloc::vec::Vec;
+
+use crate::enum_::VariantField;
+use crate::key_path::KeyPath;
+use crate::Array;
+use crate::Enum;
+use crate::List;
+use crate::Map;
+use crate::Reflect;
+use crate::ReflectRef;
+use crate::ScalarRef;
+use crate::Struct;
+use crate::Tuple;
+use crate::TupleStruct;
+
+/// Callbacks for each kind of value found while [`walk`]ing a reflected value.
+///
+/// All methods do nothing by default so implementors only have to override the ones they care
+/// about. Every callback receives the [`KeyPath`] of the value it is called for, relative to the
+/// value passed to [`walk`].
+///
+/// For each value the callbacks are called in this order:
+///
+/// 1. [`enter`](ReflectVisitor::enter).
+/// 2. The `visit_*` method for the value's kind, such as
+///    [`visit_struct`](ReflectVisitor::visit_struct).
+/// 3. For each field, element, or entry, the matching method such as
+///    [`visit_struct_field`](ReflectVisitor::visit_struct_field), followed by a full walk of the
+///    field itself.
+/// 4. [`exit`](ReflectVisitor::exit).
+///
+/// Opaque values are not descended into.
+///
+/// # Example
+///
+/// ```
+/// use mirror_mirror::key_path::KeyPath;
+/// use mirror_mirror::visit::walk;
+/// use mirror_mirror::visit::ReflectVisitor;
+/// use mirror_mirror::Reflect;
+/// use mirror_mirror::ScalarRef;
+///
+/// #[derive(Reflect, Clone, Debug)]
+/// struct Player {
+///     name: String,
+///     scores: Vec<u32>,
+/// }
+///
+/// #[derive(Default)]
+/// struct Sum(u32);
+///
+/// impl ReflectVisitor for Sum {
+///     fn visit_scalar(&mut self, value: ScalarRef<'_>, _path: &KeyPath) {
+///         if let ScalarRef::u32(n) = value {
+///             self.0 += n;
+///         }
+///     }
+/// }
+///
+/// let player = Player {
+///     name: "Alice".to_owned(),
+///     scores: vec![1, 2, 3],
+/// };
+///
+/// let mut sum = Sum::default();
+/// walk(&player, &mut sum);
+/// assert_eq!(sum.0, 6);
+/// ```
+#[allow(unused_variables)]
+pub trait ReflectVisitor {
+    /// Called before anything else for every value, including the root.
+    fn enter(&mut self, value: &dyn Reflect, path: &KeyPath) {}
+
+    /// Called after a value and all of its fields have been visited.
+    fn exit(&mut self, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_struct(&mut self, value: &dyn Struct, path: &KeyPath) {}
+
+    fn visit_struct_field(&mut self, name: &str, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_tuple_struct(&mut self, value: &dyn TupleStruct, path: &KeyPath) {}
+
+    fn visit_tuple_struct_field(&mut self, index: usize, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_tuple(&mut self, value: &dyn Tuple, path: &KeyPath) {}
+
+    fn visit_tuple_field(&mut self, index: usize, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_enum(&mut self, value: &dyn Enum, path: &KeyPath) {}
+
+    /// Called for fields of struct variants, such as `a` in `Foo::A { a: i32 }`.
+    fn visit_struct_variant_field(&mut self, name: &str, value: &dyn Reflect, path: &KeyPath) {}
+
+    /// Called for fields of tuple variants, such as `0` in `Foo::B(i32)`.
+    fn visit_tuple_variant_field(&mut self, index: usize, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_list(&mut self, value: &dyn List, path: &KeyPath) {}
+
+    fn visit_list_element(&mut self, index: usize, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_array(&mut self, value: &dyn Array, path: &KeyPath) {}
+
+    fn visit_array_element(&mut self, index: usize, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_map(&mut self, value: &dyn Map, path: &KeyPath) {}
+
+    /// Called for each entry of a map. Only `value` is walked afterwards, not `key`.
+    fn visit_map_entry(&mut self, key: &dyn Reflect, value: &dyn Reflect, path: &KeyPath) {}
+
+    fn visit_scalar(&mut self, value: ScalarRef<'_>, path: &KeyPath) {}
+
+    fn visit_opaque(&mut self, value: &dyn Reflect, path: &KeyPath) {}
+}
+
+/// Walk `value` and everything it contains, depth first, calling the methods of `visitor` along
+/// the way.
+///
+/// See [`ReflectVisitor`] for the order in which callbacks are called.
+pub fn walk(value: &dyn Reflect, visitor: &mut dyn ReflectVisitor) {
+    walk_at(value, visitor, &mut KeyPath::default());
+}
+
+fn walk_at(value: &dyn Reflect, visitor: &mut dyn ReflectVisitor, path: &mut KeyPath) {
+    visitor.enter(value, path);
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(inner) => {
+            visitor.visit_struct(inner, path);
+            for (name, value) in inner.fields() {
+                path.push_field(name);
+                visitor.visit_struct_field(name, value, path);
+                walk_at(value, visitor, path);
+                path.pop();
+            }
+        }
+        ReflectRef::TupleStruct(inner) => {
+            visitor.visit_tuple_struct(inner, path);
+            for (index, value) in inner.fields().enumerate() {
+                path.push_field(index);
+                visitor.visit_tuple_struct_field(index, value, path);
+                walk_at(value, visitor, path);
+                path.pop();
+            }
+        }
+        ReflectRef::Tuple(inner) => {
+            visitor.visit_tuple(inner, path);
+            for (index, value) in inner.fields().enumerate() {
+                path.push_field(index);
+                visitor.visit_tuple_field(index, value, path);
+                walk_at(value, visitor, path);
+                path.pop();
+            }
+        }
+        ReflectRef::Enum(inner) => {
+            visitor.visit_enum(inner, path);
+            path.push_variant(inner.variant_name());
+            for (index, field) in inner.fields().enumerate() {
+                match field {
+                    VariantField::Struct(name, value) => {
+                        path.push_field(name);
+                        visitor.visit_struct_variant_field(name, value, path);
+                        walk_at(value, visitor, path);
+                    }
+                    VariantField::Tuple(value) => {
+                        path.push_field(index);
+                        visitor.visit_tuple_variant_field(index, value, path);
+                        walk_at(value, visitor, path);
+                    }
+                }
+                path.pop();
+            }
+            path.pop();
+        }
+        ReflectRef::List(inner) => {
+            visitor.visit_list(inner, path);
+            for (index, value) in inner.iter().enumerate() {
+                path.push_get(index);
+                visitor.visit_list_element(index, value, path);
+                walk_at(value, visitor, path);
+                path.pop();
+            }
+        }
+        ReflectRef::Array(inner) => {
+            visitor.visit_array(inner, path);
+            for (index, value) in inner.iter().enumerate() {
+                path.push_get(index);
+                visitor.visit_array_element(index, value, path);
+                walk_at(value, visitor, path);
+                path.pop();
+            }
+        }
+        ReflectRef::Map(inner) => {
+            visitor.visit_map(inner, path);
+            for (key, value) in inner.iter() {
+                path.push_get(key.to_value());
+                visitor.visit_map_entry(key, value, path);
+                walk_at(value, visitor, path);
+                path.pop();
+            }
+        }
+        ReflectRef::Scalar(inner) => visitor.visit_scalar(inner, path),
+        ReflectRef::Opaque(inner) => visitor.visit_opaque(inner, path),
+    }
+
+    visitor.exit(value, path);
+}
+
+/// A depth first search for values matching a predicate.
+///
+/// Values are yielded in the same order [`walk`] visits them. Unlike [`walk`] it borrows the
+/// values for as long as the root is borrowed, which is what [`find_all`] needs.
+///
+/// [`find_all`]: crate::Reflect#method.find_all
+pub(crate) struct Search<'a, F> {
+    stack: Vec<(KeyPath, &'a dyn Reflect)>,
+    predicate: F,
+}
+
+impl<'a, F> Search<'a, F> {
+    pub(crate) fn new(value: &'a dyn Reflect, predicate: F) -> Self {
+        Self {
+            stack: Vec::from([(KeyPath::default(), value)]),
+            predicate,
+        }
+    }
+
+    fn push_children(&mut self, path: &KeyPath, value: &'a dyn Reflect) {
+        // children are pushed in reverse so they're popped in order
+        let start = self.stack.len();
+        match value.reflect_ref() {
+            ReflectRef::Struct(inner) => {
+                for (name, value) in inner.fields() {
+                    self.stack.push((path.clone().field(name), value));
+                }
+            }
+            ReflectRef::TupleStruct(inner) => {
+                for (index, value) in inner.fields().enumerate() {
+                    self.stack.push((path.clone().field(index), value));
+                }
+            }
+            ReflectRef::Tuple(inner) => {
+                for (index, value) in inner.fields().enumerate() {
+                    self.stack.push((path.clone().field(index), value));
+                }
+            }
+            ReflectRef::Enum(inner) => {
+                let path = path.clone().variant(inner.variant_name());
+                for (index, field) in inner.fields().enumerate() {
+                    match field {
+                        VariantField::Struct(name, value) => {
+                            self.stack.push((path.clone().field(name), value));
+                        }
+                        VariantField::Tuple(value) => {
+                            self.stack.push((path.clone().field(index), value));
+                        }
+                    }
+                }
+            }
+            ReflectRef::List(inner) => {
+                for (index, value) in inner.iter().enumerate() {
+                    self.stack.push((path.clone().get(index), value));
+                }
+            }
+            ReflectRef::Array(inner) => {
+                for (index, value) in inner.iter().enumerate() {
+                    self.stack.push((path.clone().get(index), value));
+                }
+            }
+            ReflectRef::Map(inner) => {
+                for (key, value) in inner.iter() {
+                    self.stack.push((path.clone().get(key.to_value()), value));
+                }
+            }
+            ReflectRef::Scalar(_) | ReflectRef::Opaque(_) => {}
+        }
+        self.stack[start..].reverse();
+    }
+}
+
+impl<'a, F> Iterator for Search<'a, F>
+where
+    F: FnMut(&dyn Reflect) -> bool,
+{
+    type Item = (KeyPath, &'a dyn Reflect);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, value)) = self.stack.pop() {
+            self.push_children(&path, value);
+            if (self.predicate)(value) {
+                return Some((path, value));
+            }
+        }
+        None
+    }
+}